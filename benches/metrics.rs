@@ -26,12 +26,14 @@ fn create_test_backend(id: &str, model_id: &str) -> Backend {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         priority: 1,
         pending_requests: AtomicU32::new(0),
         total_requests: AtomicU64::new(100),
         avg_latency_ms: AtomicU32::new(50),
+        reported_queue_depth: AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
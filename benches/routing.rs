@@ -18,6 +18,7 @@ fn create_backend(id: usize, model_count: usize) -> Backend {
             supports_vision: m % 3 == 0,
             supports_tools: m % 2 == 0,
             supports_json_mode: m % 4 == 0,
+            supports_embeddings: false,
             max_output_tokens: Some(2048),
         })
         .collect();
@@ -35,6 +36,7 @@ fn create_backend(id: usize, model_count: usize) -> Backend {
         pending_requests: AtomicU32::new((id % 10) as u32),
         total_requests: AtomicU64::new(100 + id as u64),
         avg_latency_ms: AtomicU32::new(20 + (id * 5) as u32),
+        reported_queue_depth: AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
@@ -85,8 +87,16 @@ fn bench_smart_routing_by_backend_count(c: &mut Criterion) {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             estimated_tokens: 100,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         group.bench_with_input(BenchmarkId::new("backends", count), &count, |b, _| {
@@ -110,8 +120,16 @@ fn bench_round_robin_routing(c: &mut Criterion) {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             estimated_tokens: 100,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         group.bench_with_input(BenchmarkId::new("backends", count), &count, |b, _| {
@@ -133,8 +151,16 @@ fn bench_capability_filtered_routing(c: &mut Criterion) {
         needs_vision: true,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         estimated_tokens: 100,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     c.bench_function("capability_filtered_25_backends", |b| {
@@ -175,8 +201,16 @@ fn bench_routing_with_fallback(c: &mut Criterion) {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         estimated_tokens: 100,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     c.bench_function("routing_with_fallback_10_backends", |b| {
@@ -213,8 +247,16 @@ fn bench_routing_with_alias(c: &mut Criterion) {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         estimated_tokens: 100,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     c.bench_function("routing_with_alias_10_backends", |b| {
@@ -311,8 +353,16 @@ fn bench_full_pipeline(c: &mut Criterion) {
                         needs_vision: false,
                         needs_tools: false,
                         needs_json_mode: false,
+                        needs_json_schema: false,
+                        needs_embeddings: false,
+                        prompt_tokens_exact: false,
                         estimated_tokens: 100,
                         prefers_streaming: false,
+                        max_tokens: None,
+                        privacy_constraint: None,
+                        min_capability_tier: None,
+                        prompt_text: None,
+                        injection_risk: None,
                     },
                     vec![],
                 );
@@ -348,8 +398,16 @@ fn bench_request_analyzer(c: &mut Criterion) {
                         needs_vision: false,
                         needs_tools: false,
                         needs_json_mode: false,
+                        needs_json_schema: false,
+                        needs_embeddings: false,
+                        prompt_tokens_exact: false,
                         estimated_tokens: 100,
                         prefers_streaming: false,
+                        max_tokens: None,
+                        privacy_constraint: None,
+                        min_capability_tier: None,
+                        prompt_text: None,
+                        injection_risk: None,
                     },
                     vec![],
                 );
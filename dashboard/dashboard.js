@@ -20,6 +20,8 @@ const BASE_RECONNECT_DELAY = 3000; // Start at 3 seconds
 let currentReconnectDelay = BASE_RECONNECT_DELAY;
 /** @type {Map<string, string>} Maps backend UUID → display name for request history */
 const backendNameMap = new Map();
+/** @type {Map<string, Object>} Maps backend UUID → circuit breaker snapshot from /v1/stats */
+const circuitStateMap = new Map();
 
 // Initialize dashboard
 document.addEventListener('DOMContentLoaded', () => {
@@ -50,6 +52,7 @@ function loadInitialData() {
             if (data.stats) {
                 updateSystemSummary(data.stats);
                 if (data.stats.backends && Array.isArray(data.stats.backends)) {
+                    updateCircuitStates(data.stats.backends);
                     renderBackendCardsFromStats(data.stats.backends);
                 }
             }
@@ -74,6 +77,7 @@ async function fetchSystemSummary() {
             updateSystemSummary(stats);
             // Stats include backend data — render cards from it
             if (stats.backends && Array.isArray(stats.backends)) {
+                updateCircuitStates(stats.backends);
                 renderBackendCardsFromStats(stats.backends);
             }
         } else {
@@ -327,6 +331,35 @@ function truncateModelName(name, maxLength = 50) {
     return name.substring(0, maxLength - 3) + '...';
 }
 
+/**
+ * Record circuit breaker snapshots from /v1/stats and refresh badges on rendered cards.
+ * @param {Array<Object>} backends - Array of BackendStats from /v1/stats
+ */
+function updateCircuitStates(backends) {
+    backends.forEach(b => {
+        if (b.circuit) circuitStateMap.set(b.id, b.circuit);
+    });
+    document.querySelectorAll('.backend-card[data-backend-id]').forEach(card => {
+        const badge = card.querySelector('.circuit-badge-slot');
+        if (badge) badge.innerHTML = renderCircuitBadge(card.dataset.backendId);
+    });
+}
+
+/**
+ * Render a circuit breaker badge for a backend (empty when the circuit is closed).
+ * @param {string} backendId - Backend UUID
+ * @returns {string} Badge HTML
+ */
+function renderCircuitBadge(backendId) {
+    const circuit = circuitStateMap.get(backendId);
+    if (!circuit || circuit.state === 'closed') return '';
+    const label = circuit.state === 'open' ? 'circuit open' : 'circuit half-open';
+    const title = circuit.retry_after_secs !== undefined
+        ? `Half-open in ${circuit.retry_after_secs}s`
+        : 'Probing with trial requests';
+    return `<span class="circuit-badge ${circuit.state}" title="${title}">${label}</span>`;
+}
+
 /**
  * Render backend cards from full BackendView data (WebSocket source).
  * Sets hasFullBackendData flag to prevent stats-based rendering from overwriting.
@@ -356,11 +389,13 @@ function renderBackendCards(backends) {
         const statusClass = (backend.status || 'Unknown').toLowerCase();
         const card = document.createElement('div');
         card.className = `backend-card ${statusClass}`;
+        card.dataset.backendId = backend.id;
 
         const modelCount = (backend.models || []).length;
         card.innerHTML = `
             <div class="backend-header">
                 <span class="backend-name">${escapeHtml(backend.name || backend.id)}</span>
+                <span class="circuit-badge-slot">${renderCircuitBadge(backend.id)}</span>
                 <span class="status-badge ${statusClass}">${statusClass}</span>
             </div>
             <div class="backend-url">${escapeHtml(backend.url || '')} · ${escapeHtml(backend.backend_type || 'Unknown')}</div>
@@ -415,10 +450,12 @@ function renderBackendCardsFromStats(backends) {
         const displayName = backend.name || backend.id;
         const card = document.createElement('div');
         card.className = 'backend-card healthy';
+        card.dataset.backendId = backend.id;
 
         card.innerHTML = `
             <div class="backend-header">
                 <span class="backend-name">${escapeHtml(displayName)}</span>
+                <span class="circuit-badge-slot">${renderCircuitBadge(backend.id)}</span>
                 <span class="status-badge healthy">healthy</span>
             </div>
             <div class="backend-metrics">
@@ -159,6 +159,8 @@ impl AnthropicAgent {
                     content: MessageContent::Text { content: text },
                     name: None,
                     function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
                 finish_reason: Some(finish_reason),
             }],
@@ -168,6 +170,7 @@ impl AnthropicAgent {
                 total_tokens: response.usage.input_tokens + response.usage.output_tokens,
             }),
             extra: std::collections::HashMap::new(),
+            timings: None,
         }
     }
 
@@ -343,6 +346,7 @@ impl InferenceAgent for AnthropicAgent {
             privacy_zone: self.privacy_zone,
             capabilities: AgentCapabilities {
                 embeddings: false,
+                rerank: false,
                 model_lifecycle: false,
                 token_counting: false,
                 resource_monitoring: false,
@@ -425,6 +429,7 @@ impl InferenceAgent for AnthropicAgent {
                 supports_vision: true,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: Some(4096),
                 capability_tier: Some(3), // Premium tier
             },
@@ -435,6 +440,7 @@ impl InferenceAgent for AnthropicAgent {
                 supports_vision: true,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: Some(4096),
                 capability_tier: Some(2), // Standard tier
             },
@@ -445,6 +451,7 @@ impl InferenceAgent for AnthropicAgent {
                 supports_vision: true,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: Some(4096),
                 capability_tier: Some(1), // Fast tier
             },
@@ -455,6 +462,7 @@ impl InferenceAgent for AnthropicAgent {
                 supports_vision: true,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: Some(8192),
                 capability_tier: Some(3), // Premium tier
             },
@@ -671,7 +679,10 @@ impl InferenceAgent for AnthropicAgent {
                         text
                     };
 
-                    StreamChunk { data: translated }
+                    StreamChunk {
+                        data: translated,
+                        timings: None,
+                    }
                 })
                 .map_err(|e| AgentError::Network(e.to_string()))
         });
@@ -770,6 +781,8 @@ mod tests {
                     },
                     name: None,
                     function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
                 ChatMessage {
                     role: "user".to_string(),
@@ -778,6 +791,8 @@ mod tests {
                     },
                     name: None,
                     function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
             ],
             stream: false,
@@ -843,6 +858,8 @@ mod tests {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -0,0 +1,520 @@
+//! Azure OpenAI agent implementation.
+
+use super::embeddings::{is_embedding_model, send_embeddings_request};
+use super::openai::OpenAIAgent;
+use super::{
+    AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
+    PrivacyZone, StreamChunk, TokenCount,
+};
+use crate::agent::pricing::PricingTable;
+use crate::api::types::{ChatCompletionRequest, ChatCompletionResponse};
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use futures_util::stream::BoxStream;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// REST API version used when the backend config does not set one.
+pub const DEFAULT_API_VERSION: &str = "2024-10-21";
+
+/// Azure OpenAI agent implementation.
+///
+/// Azure serves each model through a named deployment on the resource:
+/// - Health check via GET /openai/models
+/// - Model listing from the configured model → deployment map
+/// - Chat completion via POST /openai/deployments/{deployment}/chat/completions
+/// - Embeddings via POST /openai/deployments/{deployment}/embeddings
+///
+/// Every request carries the `api-version` query parameter and authenticates
+/// with the `api-key` header.
+pub struct AzureOpenAIAgent {
+    /// Unique agent ID
+    id: String,
+    /// Human-readable name
+    name: String,
+    /// Resource endpoint (e.g., "https://my-resource.openai.azure.com")
+    base_url: String,
+    /// API key sent in the `api-key` header
+    api_key: String,
+    /// REST API version (e.g., "2024-10-21")
+    api_version: String,
+    /// Model name → deployment name
+    deployments: HashMap<String, String>,
+    /// Shared HTTP client for connection pooling
+    client: Arc<Client>,
+    /// Pricing table for cost estimation (F12)
+    #[allow(dead_code)]
+    pricing: Arc<PricingTable>,
+    /// Privacy zone classification from config
+    privacy_zone: PrivacyZone,
+    /// Capability tier from config
+    capability_tier: Option<u8>,
+}
+
+impl AzureOpenAIAgent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        name: String,
+        base_url: String,
+        api_key: String,
+        api_version: String,
+        deployments: HashMap<String, String>,
+        client: Arc<Client>,
+        privacy_zone: PrivacyZone,
+        capability_tier: Option<u8>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            api_version,
+            deployments,
+            client,
+            pricing: Arc::new(PricingTable::new()),
+            privacy_zone,
+            capability_tier,
+        }
+    }
+
+    /// Deployment serving `model`, falling back to the model name itself
+    /// (deployments are often named after their model).
+    fn deployment_for<'a>(&'a self, model: &'a str) -> &'a str {
+        self.deployments
+            .get(model)
+            .map(String::as_str)
+            .unwrap_or(model)
+    }
+
+    /// URL of a deployment-scoped operation, e.g. `chat/completions`.
+    fn deployment_url(&self, model: &str, operation: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}/{}?api-version={}",
+            self.base_url,
+            self.deployment_for(model),
+            operation,
+            self.api_version
+        )
+    }
+
+    async fn send_chat(
+        &self,
+        request: &ChatCompletionRequest,
+    ) -> Result<reqwest::Response, AgentError> {
+        let url = self.deployment_url(&request.model, "chat/completions");
+
+        let response = self
+            .client
+            .post(&url)
+            .header("api-key", &self.api_key)
+            .json(request)
+            .timeout(Duration::from_secs(120))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(120000)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // Content filter rejections arrive here as 400s; the API layer
+            // recognises their body shape
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status: status.as_u16(),
+                message: error_body,
+            });
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl InferenceAgent for AzureOpenAIAgent {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn profile(&self) -> AgentProfile {
+        AgentProfile {
+            backend_type: "azure_openai".to_string(),
+            version: Some(self.api_version.clone()),
+            privacy_zone: self.privacy_zone,
+            capabilities: AgentCapabilities {
+                embeddings: true,
+                rerank: false,
+                model_lifecycle: false,
+                token_counting: true,
+                resource_monitoring: false,
+            },
+            capability_tier: self.capability_tier,
+        }
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, AgentError> {
+        let url = format!(
+            "{}/openai/models?api-version={}",
+            self.base_url, self.api_version
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("api-key", &self.api_key)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(5000)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        if !response.status().is_success() {
+            return Ok(HealthStatus::Unhealthy);
+        }
+
+        Ok(HealthStatus::Healthy {
+            model_count: self.deployments.len(),
+        })
+    }
+
+    /// Azure lists base models, not deployments, so the served models come
+    /// from the configured deployment map.
+    async fn list_models(&self) -> Result<Vec<ModelCapability>, AgentError> {
+        let mut names: Vec<&String> = self.deployments.keys().collect();
+        names.sort();
+
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                let mut model = ModelCapability {
+                    id: name.clone(),
+                    name: name.clone(),
+                    context_length: 4096, // Default
+                    supports_vision: false,
+                    supports_tools: false,
+                    supports_json_mode: false,
+                    supports_embeddings: false,
+                    max_output_tokens: None,
+                    capability_tier: None,
+                };
+                OpenAIAgent::apply_name_heuristics(&mut model);
+                model.supports_embeddings |= is_embedding_model(name);
+                model
+            })
+            .collect())
+    }
+
+    async fn chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+        _headers: Option<&HeaderMap>,
+    ) -> Result<ChatCompletionResponse, AgentError> {
+        let response = self.send_chat(&request).await?;
+
+        let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to parse completion response: {}", e))
+        })?;
+
+        Ok(completion)
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+        _headers: Option<&HeaderMap>,
+    ) -> Result<BoxStream<'static, Result<StreamChunk, AgentError>>, AgentError> {
+        use futures_util::stream::StreamExt;
+
+        let response = self.send_chat(&request).await?;
+
+        // Azure streams OpenAI-format SSE chunks
+        let stream = response.bytes_stream().map(|result| {
+            result
+                .map(|bytes| StreamChunk {
+                    data: String::from_utf8_lossy(&bytes).to_string(),
+                    timings: None,
+                })
+                .map_err(|e| AgentError::Network(e.to_string()))
+        });
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Count tokens with tiktoken o200k_base, as for OpenAI models.
+    async fn count_tokens(&self, _model_id: &str, text: &str) -> TokenCount {
+        use tiktoken_rs::o200k_base;
+
+        match o200k_base() {
+            Ok(bpe) => TokenCount::Exact(bpe.encode_ordinary(text).len() as u32),
+            Err(e) => {
+                tracing::warn!("tiktoken encoding failed: {}, using heuristic", e);
+                TokenCount::Heuristic((text.len() / 4) as u32)
+            }
+        }
+    }
+
+    /// Generate embeddings via the model's deployment.
+    async fn embeddings(
+        &self,
+        model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, AgentError> {
+        let url = self.deployment_url(model, "embeddings");
+        let request = self
+            .client
+            .post(&url)
+            .header("api-key", &self.api_key)
+            .json(&serde_json::json!({ "input": input }));
+
+        send_embeddings_request(request, input.len()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn test_agent(base_url: String) -> AzureOpenAIAgent {
+        let deployments = HashMap::from([
+            ("gpt-4o".to_string(), "prod-gpt4o".to_string()),
+            (
+                "text-embedding-3-small".to_string(),
+                "embed-small".to_string(),
+            ),
+        ]);
+        AzureOpenAIAgent::new(
+            "test-azure".to_string(),
+            "Test Azure".to_string(),
+            base_url,
+            "azure-key".to_string(),
+            DEFAULT_API_VERSION.to_string(),
+            deployments,
+            Arc::new(Client::new()),
+            PrivacyZone::Open,
+            None,
+        )
+    }
+
+    fn chat_request(model: &str) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model.to_string(),
+            messages: vec![],
+            stream: false,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            user: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_uses_api_key_and_version() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/openai/models")
+            .match_query(Matcher::UrlEncoded(
+                "api-version".into(),
+                DEFAULT_API_VERSION.into(),
+            ))
+            .match_header("api-key", "azure-key")
+            .with_status(200)
+            .with_body(r#"{"data":[{"id":"gpt-4o-2024-08-06"}],"object":"list"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let status = agent.health_check().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(status, HealthStatus::Healthy { model_count: 2 });
+    }
+
+    #[tokio::test]
+    async fn test_health_check_unauthorized() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/openai/models")
+            .match_query(Matcher::Any)
+            .with_status(401)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        assert_eq!(agent.health_check().await.unwrap(), HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_list_models_from_deployments() {
+        let agent = test_agent("https://example.openai.azure.com".to_string());
+        let models = agent.list_models().await.unwrap();
+
+        let ids: Vec<&str> = models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["gpt-4o", "text-embedding-3-small"]);
+        assert!(models[0].supports_tools);
+        assert_eq!(models[0].context_length, 128000);
+        assert!(models[1].supports_embeddings);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_targets_deployment() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/openai/deployments/prod-gpt4o/chat/completions")
+            .match_query(Matcher::UrlEncoded(
+                "api-version".into(),
+                DEFAULT_API_VERSION.into(),
+            ))
+            .match_header("api-key", "azure-key")
+            .with_status(200)
+            .with_body(r#"{"id":"chatcmpl-1","object":"chat.completion","created":1234567890,"model":"gpt-4o-2024-08-06","choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop"}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let response = agent
+            .chat_completion(chat_request("gpt-4o"), None)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.choices.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unmapped_model_uses_model_name_as_deployment() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/openai/deployments/gpt-4o-mini/chat/completions")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(r#"{"id":"chatcmpl-1","object":"chat.completion","created":1234567890,"model":"gpt-4o-mini","choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop"}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        agent
+            .chat_completion(chat_request("gpt-4o-mini"), None)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_content_filter_error_is_upstream_error() {
+        let mut server = Server::new_async().await;
+        let body = r#"{"error":{"message":"The response was filtered due to the prompt triggering Azure OpenAI's content management policy.","type":null,"param":"prompt","code":"content_filter","status":400,"innererror":{"code":"ResponsibleAIPolicyViolation","content_filter_result":{"hate":{"filtered":true,"severity":"high"}}}}}"#;
+        server
+            .mock("POST", "/openai/deployments/prod-gpt4o/chat/completions")
+            .match_query(Matcher::Any)
+            .with_status(400)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let err = agent
+            .chat_completion(chat_request("gpt-4o"), None)
+            .await
+            .unwrap_err();
+
+        match err {
+            AgentError::Upstream { status, message } => {
+                assert_eq!(status, 400);
+                assert!(message.contains("ResponsibleAIPolicyViolation"));
+            }
+            other => panic!("expected upstream error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_stream_targets_deployment() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/openai/deployments/prod-gpt4o/chat/completions")
+            .match_query(Matcher::Any)
+            .match_body(Matcher::PartialJson(serde_json::json!({"stream": true})))
+            .with_status(200)
+            .with_body("data: {\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\ndata: [DONE]\n\n")
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let mut request = chat_request("gpt-4o");
+        request.stream = true;
+        let stream = agent.chat_completion_stream(request, None).await.unwrap();
+
+        use futures_util::StreamExt;
+        let chunks: Vec<_> = stream.collect().await;
+        mock.assert_async().await;
+        assert!(chunks[0].as_ref().unwrap().data.contains("Hi"));
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_target_deployment() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/openai/deployments/embed-small/embeddings")
+            .match_query(Matcher::UrlEncoded(
+                "api-version".into(),
+                DEFAULT_API_VERSION.into(),
+            ))
+            .match_header("api-key", "azure-key")
+            .with_status(200)
+            .with_body(r#"{"data":[{"index":0,"embedding":[0.1,0.2]}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let vectors = agent
+            .embeddings("text-embedding-3-small", vec!["hello".to_string()])
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors, vec![vec![0.1, 0.2]]);
+    }
+
+    #[test]
+    fn test_profile() {
+        let agent = test_agent("https://example.openai.azure.com/".to_string());
+        let profile = agent.profile();
+        assert_eq!(profile.backend_type, "azure_openai");
+        assert_eq!(profile.privacy_zone, PrivacyZone::Open);
+        assert!(profile.capabilities.embeddings);
+        assert_eq!(agent.base_url, "https://example.openai.azure.com");
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_exact() {
+        let agent = test_agent("https://example.openai.azure.com".to_string());
+        assert!(matches!(
+            agent.count_tokens("gpt-4o", "Hello, world!").await,
+            TokenCount::Exact(_)
+        ));
+    }
+}
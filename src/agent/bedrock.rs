@@ -0,0 +1,1126 @@
+//! AWS Bedrock agent implementation.
+
+use super::eventstream::{EventMessage, EventStreamDecoder};
+use super::sigv4::{self, AwsCredentials};
+use super::{
+    AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
+    PrivacyZone, StreamChunk,
+};
+use crate::agent::pricing::PricingTable;
+use crate::api::types::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Choice, FunctionCall,
+    MessageContent, ToolCall, Usage,
+};
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use futures_util::stream::BoxStream;
+use reqwest::{Client, Method, Url};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// SigV4 service name for runtime (inference) calls.
+const RUNTIME_SERVICE: &str = "bedrock";
+
+/// AWS Bedrock agent implementation.
+///
+/// Uses the model-agnostic Converse API with SigV4 request signing, translating
+/// to/from OpenAI format:
+/// - Health check via GET /foundation-models on the control plane endpoint
+/// - Model listing from the configured model → Bedrock model ID map
+/// - Chat completion via POST /model/{modelId}/converse
+/// - Streaming via POST /model/{modelId}/converse-stream, decoding the binary
+///   event stream into OpenAI chunks
+pub struct BedrockAgent {
+    /// Unique agent ID
+    id: String,
+    /// Human-readable name
+    name: String,
+    /// Runtime endpoint (e.g., "https://bedrock-runtime.eu-central-1.amazonaws.com")
+    base_url: String,
+    /// AWS region used in request signatures
+    region: String,
+    /// Credentials for SigV4 signing
+    credentials: AwsCredentials,
+    /// Model name → Bedrock model ID or inference profile ID
+    models: HashMap<String, String>,
+    /// Shared HTTP client for connection pooling
+    client: Arc<Client>,
+    /// Pricing table for cost estimation
+    #[allow(dead_code)]
+    pricing: Arc<PricingTable>,
+    /// Privacy zone classification from config
+    privacy_zone: PrivacyZone,
+    /// Capability tier from config
+    capability_tier: Option<u8>,
+}
+
+impl BedrockAgent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        name: String,
+        base_url: String,
+        region: String,
+        credentials: AwsCredentials,
+        models: HashMap<String, String>,
+        client: Arc<Client>,
+        privacy_zone: PrivacyZone,
+        capability_tier: Option<u8>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            region,
+            credentials,
+            models,
+            client,
+            pricing: Arc::new(PricingTable::new()),
+            privacy_zone,
+            capability_tier,
+        }
+    }
+
+    /// Region named in a standard endpoint host such as
+    /// `bedrock-runtime.eu-central-1.amazonaws.com`.
+    pub fn region_from_url(url: &str) -> Option<String> {
+        let host = Url::parse(url).ok()?.host_str()?.to_string();
+        let mut labels = host.split('.');
+        let service = labels.next()?;
+        let region = labels.next()?;
+        (service.starts_with("bedrock") && host.ends_with(".amazonaws.com"))
+            .then(|| region.to_string())
+    }
+
+    /// Bedrock model ID for `model`, falling back to the name itself.
+    fn model_id<'a>(&'a self, model: &'a str) -> &'a str {
+        self.models.get(model).map(String::as_str).unwrap_or(model)
+    }
+
+    /// Control plane endpoint serving model listings. Custom endpoints
+    /// (VPC endpoints, test stubs) serve both planes.
+    fn control_plane_url(&self) -> String {
+        self.base_url
+            .replacen("://bedrock-runtime", "://bedrock", 1)
+    }
+
+    /// Sign and send a request, returning upstream errors as `AgentError`.
+    async fn send_signed(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&Value>,
+        timeout: Duration,
+    ) -> Result<reqwest::Response, AgentError> {
+        let parsed = Url::parse(url)
+            .map_err(|e| AgentError::Configuration(format!("Invalid Bedrock URL: {}", e)))?;
+        let body = body.map(|b| b.to_string().into_bytes()).unwrap_or_default();
+        let signed_headers: &[(&str, &str)] = if body.is_empty() {
+            &[]
+        } else {
+            &[("content-type", "application/json")]
+        };
+        let signature = sigv4::sign(
+            method.as_str(),
+            &parsed,
+            signed_headers,
+            &body,
+            &self.credentials,
+            &self.region,
+            RUNTIME_SERVICE,
+            chrono::Utc::now(),
+        );
+
+        let mut request = self.client.request(method, parsed).timeout(timeout);
+        for (name, value) in signed_headers.iter().copied() {
+            request = request.header(name, value);
+        }
+        for (name, value) in signature {
+            request = request.header(name, value);
+        }
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                AgentError::Timeout(timeout.as_millis() as u64)
+            } else {
+                AgentError::Network(e.to_string())
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status: status.as_u16(),
+                message: error_body,
+            });
+        }
+        Ok(response)
+    }
+
+    /// Translate OpenAI request to a Converse request body.
+    fn translate_request(request: &ChatCompletionRequest) -> Value {
+        let mut system = Vec::new();
+        let mut messages: Vec<Value> = Vec::new();
+
+        for msg in &request.messages {
+            let text = message_text(&msg.content);
+            match msg.role.as_str() {
+                "system" | "developer" => {
+                    if !text.is_empty() {
+                        system.push(json!({ "text": text }));
+                    }
+                }
+                // Tool results go back as user turns
+                "tool" => push_blocks(
+                    &mut messages,
+                    "user",
+                    vec![json!({
+                        "toolResult": {
+                            "toolUseId": msg.tool_call_id.clone().unwrap_or_default(),
+                            "content": [{ "text": text }]
+                        }
+                    })],
+                ),
+                role => {
+                    let mut blocks = Vec::new();
+                    if !text.is_empty() {
+                        blocks.push(json!({ "text": text }));
+                    }
+                    for call in msg.tool_calls.iter().flatten() {
+                        let input: Value =
+                            serde_json::from_str(&call.function.arguments).unwrap_or(json!({}));
+                        blocks.push(json!({
+                            "toolUse": {
+                                "toolUseId": call.id,
+                                "name": call.function.name,
+                                "input": input
+                            }
+                        }));
+                    }
+                    let role = if role == "assistant" {
+                        "assistant"
+                    } else {
+                        "user"
+                    };
+                    push_blocks(&mut messages, role, blocks);
+                }
+            }
+        }
+
+        let mut body = json!({ "messages": messages });
+        if !system.is_empty() {
+            body["system"] = json!(system);
+        }
+
+        let mut inference = serde_json::Map::new();
+        if let Some(max_tokens) = request.max_tokens {
+            inference.insert("maxTokens".to_string(), json!(max_tokens));
+        }
+        if let Some(temperature) = request.temperature {
+            inference.insert("temperature".to_string(), json!(temperature));
+        }
+        if let Some(top_p) = request.top_p {
+            inference.insert("topP".to_string(), json!(top_p));
+        }
+        if let Some(stop) = &request.stop {
+            inference.insert("stopSequences".to_string(), json!(stop));
+        }
+        if !inference.is_empty() {
+            body["inferenceConfig"] = Value::Object(inference);
+        }
+
+        if let Some(tool_config) = translate_tools(&request.extra) {
+            body["toolConfig"] = tool_config;
+        }
+        body
+    }
+
+    /// Translate a Converse response to OpenAI format.
+    fn translate_response(model: &str, body: &Value) -> Result<ChatCompletionResponse, AgentError> {
+        let content = body["output"]["message"]["content"]
+            .as_array()
+            .ok_or_else(|| {
+                AgentError::InvalidResponse("Missing output message in Converse response".into())
+            })?;
+
+        let text: String = content
+            .iter()
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+        let tool_calls: Vec<ToolCall> = content
+            .iter()
+            .filter_map(|block| block.get("toolUse"))
+            .map(|tool_use| ToolCall {
+                id: tool_use["toolUseId"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                call_type: "function".to_string(),
+                function: FunctionCall {
+                    name: tool_use["name"].as_str().unwrap_or_default().to_string(),
+                    arguments: tool_use["input"].to_string(),
+                },
+            })
+            .collect();
+
+        let usage = &body["usage"];
+        let prompt_tokens = usage["inputTokens"].as_u64().unwrap_or(0) as u32;
+        let completion_tokens = usage["outputTokens"].as_u64().unwrap_or(0) as u32;
+
+        Ok(ChatCompletionResponse {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            object: "chat.completion".to_string(),
+            created: chrono::Utc::now().timestamp(),
+            model: model.to_string(),
+            choices: vec![Choice {
+                index: 0,
+                message: ChatMessage {
+                    role: "assistant".to_string(),
+                    content: MessageContent::Text { content: text },
+                    name: None,
+                    function_call: None,
+                    tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                    tool_call_id: None,
+                },
+                finish_reason: Some(
+                    finish_reason(body["stopReason"].as_str().unwrap_or_default()).to_string(),
+                ),
+            }],
+            usage: Some(Usage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+            }),
+            extra: HashMap::new(),
+            timings: None,
+        })
+    }
+
+    /// Apply Bedrock model ID heuristics for capability detection.
+    fn apply_model_heuristics(model: &mut ModelCapability, model_id: &str) {
+        let id = model_id.to_lowercase();
+
+        if id.contains("anthropic.claude") {
+            model.context_length = 200000;
+            model.supports_tools = true;
+            model.supports_vision = !id.contains("claude-v2") && !id.contains("claude-instant");
+        } else if id.contains("meta.llama3-1")
+            || id.contains("meta.llama3-2")
+            || id.contains("meta.llama3-3")
+            || id.contains("meta.llama4")
+        {
+            model.context_length = 128000;
+            model.supports_tools = true;
+        } else if id.contains("meta.llama3") {
+            model.context_length = 8192;
+        } else if id.contains("amazon.nova") {
+            model.context_length = 300000;
+            model.supports_tools = true;
+            model.supports_vision = !id.contains("nova-micro");
+        } else if id.contains("mistral.mistral-large") || id.contains("cohere.command-r") {
+            model.context_length = 128000;
+            model.supports_tools = true;
+        }
+    }
+}
+
+/// Text of a message; image parts are not forwarded.
+fn message_text(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text { content } => content.clone(),
+        MessageContent::Parts { content } => content
+            .iter()
+            .filter_map(|part| part.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Append content blocks, merging consecutive turns of the same role
+/// (Converse requires alternating user/assistant messages).
+fn push_blocks(messages: &mut Vec<Value>, role: &str, blocks: Vec<Value>) {
+    if blocks.is_empty() {
+        return;
+    }
+    if let Some(last) = messages.last_mut() {
+        if last["role"] == role {
+            if let Some(content) = last["content"].as_array_mut() {
+                content.extend(blocks);
+                return;
+            }
+        }
+    }
+    messages.push(json!({ "role": role, "content": blocks }));
+}
+
+/// Translate OpenAI `tools`/`tool_choice` into a Converse `toolConfig`.
+fn translate_tools(extra: &HashMap<String, Value>) -> Option<Value> {
+    let tools: Vec<Value> = extra
+        .get("tools")?
+        .as_array()?
+        .iter()
+        .filter_map(|tool| {
+            let function = tool.get("function")?;
+            Some(json!({
+                "toolSpec": {
+                    "name": function["name"],
+                    "description": function["description"].as_str().unwrap_or_default(),
+                    "inputSchema": {
+                        "json": function.get("parameters").cloned()
+                            .unwrap_or(json!({"type": "object", "properties": {}}))
+                    }
+                }
+            }))
+        })
+        .collect();
+    if tools.is_empty() {
+        return None;
+    }
+
+    let mut config = json!({ "tools": tools });
+    let choice = match extra.get("tool_choice") {
+        Some(Value::String(choice)) if choice == "required" => Some(json!({ "any": {} })),
+        Some(Value::String(choice)) if choice == "auto" => Some(json!({ "auto": {} })),
+        Some(choice @ Value::Object(_)) => choice["function"]["name"]
+            .as_str()
+            .map(|name| json!({ "tool": { "name": name } })),
+        _ => None,
+    };
+    if let Some(choice) = choice {
+        config["toolChoice"] = choice;
+    }
+    Some(config)
+}
+
+/// Map a Converse stop reason to an OpenAI finish reason.
+fn finish_reason(stop_reason: &str) -> &'static str {
+    match stop_reason {
+        "max_tokens" => "length",
+        "tool_use" => "tool_calls",
+        "content_filtered" | "guardrail_intervened" => "content_filter",
+        _ => "stop",
+    }
+}
+
+/// HTTP status equivalent of a streamed exception.
+fn exception_status(exception_type: &str) -> u16 {
+    match exception_type {
+        "throttlingException" => 429,
+        "validationException" => 400,
+        "modelTimeoutException" => 504,
+        "serviceUnavailableException" => 503,
+        _ => 500,
+    }
+}
+
+/// Translates ConverseStream events into OpenAI chunks.
+struct StreamTranslator {
+    id: String,
+    model: String,
+    created: i64,
+    /// Converse content block index → OpenAI tool call index
+    tool_indices: HashMap<u64, usize>,
+}
+
+impl StreamTranslator {
+    fn new(model: String) -> Self {
+        Self {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            model,
+            created: chrono::Utc::now().timestamp(),
+            tool_indices: HashMap::new(),
+        }
+    }
+
+    /// Chunk JSON as forwarded by the SSE handler (without the `data:` framing).
+    fn chunk(&self, delta: Value, finish_reason: Option<&str>) -> String {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": [{
+                "index": 0,
+                "delta": delta,
+                "finish_reason": finish_reason
+            }]
+        })
+        .to_string()
+    }
+
+    fn translate(&mut self, message: &EventMessage) -> Result<Option<String>, AgentError> {
+        let payload: Value = serde_json::from_slice(&message.payload).unwrap_or(Value::Null);
+
+        if message.header(":message-type") == Some("exception") {
+            let exception_type = message.header(":exception-type").unwrap_or_default();
+            return Err(AgentError::Upstream {
+                status: exception_status(exception_type),
+                message: format!(
+                    "{}: {}",
+                    exception_type,
+                    payload["message"].as_str().unwrap_or_default()
+                ),
+            });
+        }
+
+        let chunk = match message.header(":event-type") {
+            Some("messageStart") => Some(self.chunk(json!({ "role": "assistant" }), None)),
+            Some("contentBlockStart") => {
+                let tool_use = &payload["start"]["toolUse"];
+                if tool_use.is_null() {
+                    return Ok(None);
+                }
+                let block = payload["contentBlockIndex"].as_u64().unwrap_or_default();
+                let index = self.tool_indices.len();
+                self.tool_indices.insert(block, index);
+                Some(self.chunk(
+                    json!({
+                        "tool_calls": [{
+                            "index": index,
+                            "id": tool_use["toolUseId"],
+                            "type": "function",
+                            "function": { "name": tool_use["name"], "arguments": "" }
+                        }]
+                    }),
+                    None,
+                ))
+            }
+            Some("contentBlockDelta") => {
+                let delta = &payload["delta"];
+                if let Some(text) = delta["text"].as_str() {
+                    Some(self.chunk(json!({ "content": text }), None))
+                } else if let Some(input) = delta["toolUse"]["input"].as_str() {
+                    let block = payload["contentBlockIndex"].as_u64().unwrap_or_default();
+                    let index = self.tool_indices.get(&block).copied().unwrap_or_default();
+                    Some(self.chunk(
+                        json!({
+                            "tool_calls": [{
+                                "index": index,
+                                "function": { "arguments": input }
+                            }]
+                        }),
+                        None,
+                    ))
+                } else {
+                    None
+                }
+            }
+            Some("messageStop") => Some(self.chunk(
+                json!({}),
+                Some(finish_reason(
+                    payload["stopReason"].as_str().unwrap_or_default(),
+                )),
+            )),
+            _ => None,
+        };
+        Ok(chunk)
+    }
+}
+
+#[async_trait]
+impl InferenceAgent for BedrockAgent {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn profile(&self) -> AgentProfile {
+        AgentProfile {
+            backend_type: "bedrock".to_string(),
+            version: None,
+            privacy_zone: self.privacy_zone,
+            capabilities: AgentCapabilities {
+                embeddings: false,
+                rerank: false,
+                model_lifecycle: false,
+                token_counting: false,
+                resource_monitoring: false,
+            },
+            capability_tier: self.capability_tier,
+        }
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, AgentError> {
+        let url = format!("{}/foundation-models", self.control_plane_url());
+
+        match self
+            .send_signed(Method::GET, &url, None, Duration::from_secs(5))
+            .await
+        {
+            Ok(_) => Ok(HealthStatus::Healthy {
+                model_count: self.models.len(),
+            }),
+            // Credentials scoped to inference may not list models; the
+            // signature was still accepted
+            Err(AgentError::Upstream {
+                status: 403,
+                message,
+            }) if message.contains("AccessDeniedException")
+                || message.contains("not authorized to perform") =>
+            {
+                Ok(HealthStatus::Healthy {
+                    model_count: self.models.len(),
+                })
+            }
+            Err(AgentError::Upstream { .. }) => Ok(HealthStatus::Unhealthy),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Served models come from the configured model → model ID map.
+    async fn list_models(&self) -> Result<Vec<ModelCapability>, AgentError> {
+        let mut names: Vec<&String> = self.models.keys().collect();
+        names.sort();
+
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                let mut model = ModelCapability {
+                    id: name.clone(),
+                    name: name.clone(),
+                    context_length: 4096, // Default
+                    supports_vision: false,
+                    supports_tools: false,
+                    supports_json_mode: false,
+                    supports_embeddings: false,
+                    max_output_tokens: None,
+                    capability_tier: None,
+                };
+                Self::apply_model_heuristics(&mut model, self.model_id(name));
+                model
+            })
+            .collect())
+    }
+
+    async fn chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+        _headers: Option<&HeaderMap>,
+    ) -> Result<ChatCompletionResponse, AgentError> {
+        let url = format!(
+            "{}/model/{}/converse",
+            self.base_url,
+            sigv4::uri_encode(self.model_id(&request.model))
+        );
+        let body = Self::translate_request(&request);
+
+        let response = self
+            .send_signed(Method::POST, &url, Some(&body), Duration::from_secs(120))
+            .await?;
+
+        let converse: Value = response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to parse Converse response: {}", e))
+        })?;
+
+        Self::translate_response(&request.model, &converse)
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+        _headers: Option<&HeaderMap>,
+    ) -> Result<BoxStream<'static, Result<StreamChunk, AgentError>>, AgentError> {
+        use futures_util::stream::{self, StreamExt};
+
+        let url = format!(
+            "{}/model/{}/converse-stream",
+            self.base_url,
+            sigv4::uri_encode(self.model_id(&request.model))
+        );
+        let body = Self::translate_request(&request);
+
+        let response = self
+            .send_signed(Method::POST, &url, Some(&body), Duration::from_secs(120))
+            .await?;
+
+        let state = (
+            EventStreamDecoder::new(),
+            StreamTranslator::new(request.model),
+        );
+        let stream = response
+            .bytes_stream()
+            .scan(state, |(decoder, translator), result| {
+                let chunks: Vec<Result<StreamChunk, AgentError>> = match result {
+                    Err(e) => vec![Err(AgentError::Network(e.to_string()))],
+                    Ok(bytes) => match decoder.push(&bytes) {
+                        Err(e) => vec![Err(e)],
+                        Ok(messages) => messages
+                            .iter()
+                            .filter_map(|message| translator.translate(message).transpose())
+                            .map(|chunk| {
+                                chunk.map(|data| StreamChunk {
+                                    data,
+                                    timings: None,
+                                })
+                            })
+                            .collect(),
+                    },
+                };
+                futures_util::future::ready(Some(stream::iter(chunks)))
+            })
+            .flatten()
+            .chain(stream::once(async {
+                Ok(StreamChunk {
+                    data: "[DONE]".to_string(),
+                    timings: None,
+                })
+            }));
+
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::eventstream::encode_message;
+    use mockito::{Matcher, Server};
+
+    const HAIKU: &str = "anthropic.claude-3-haiku-20240307-v1:0";
+
+    fn test_agent(base_url: String) -> BedrockAgent {
+        BedrockAgent::new(
+            "test-bedrock".to_string(),
+            "Test Bedrock".to_string(),
+            base_url,
+            "us-east-1".to_string(),
+            AwsCredentials {
+                access_key_id: "AKIDEXAMPLE".to_string(),
+                secret_access_key: "secret".to_string(),
+                session_token: None,
+            },
+            HashMap::from([
+                ("claude-3-haiku".to_string(), HAIKU.to_string()),
+                (
+                    "llama3-70b".to_string(),
+                    "meta.llama3-70b-instruct-v1:0".to_string(),
+                ),
+            ]),
+            Arc::new(Client::new()),
+            PrivacyZone::Open,
+            None,
+        )
+    }
+
+    fn msg(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: MessageContent::Text {
+                content: content.to_string(),
+            },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+
+    fn make_request(messages: Vec<ChatMessage>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: "claude-3-haiku".to_string(),
+            messages,
+            stream: false,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            user: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    fn event(event_type: &str, payload: Value) -> Vec<u8> {
+        encode_message(
+            &[
+                (":event-type", event_type),
+                (":content-type", "application/json"),
+                (":message-type", "event"),
+            ],
+            payload.to_string().as_bytes(),
+        )
+    }
+
+    #[test]
+    fn test_region_from_url() {
+        assert_eq!(
+            BedrockAgent::region_from_url("https://bedrock-runtime.eu-central-1.amazonaws.com"),
+            Some("eu-central-1".to_string())
+        );
+        assert_eq!(BedrockAgent::region_from_url("http://127.0.0.1:8080"), None);
+    }
+
+    #[test]
+    fn test_translate_request_system_and_inference_config() {
+        let mut request = make_request(vec![
+            msg("system", "Be brief."),
+            msg("user", "Hello"),
+            msg("assistant", "Hi"),
+            msg("user", "Bye"),
+        ]);
+        request.max_tokens = Some(100);
+        request.temperature = Some(0.5);
+        request.stop = Some(vec!["END".to_string()]);
+
+        let body = BedrockAgent::translate_request(&request);
+
+        assert_eq!(body["system"], json!([{ "text": "Be brief." }]));
+        assert_eq!(body["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(body["messages"][0]["content"], json!([{ "text": "Hello" }]));
+        assert_eq!(body["inferenceConfig"]["maxTokens"], 100);
+        assert_eq!(body["inferenceConfig"]["temperature"], 0.5);
+        assert_eq!(body["inferenceConfig"]["stopSequences"], json!(["END"]));
+        assert!(body.get("toolConfig").is_none());
+    }
+
+    #[test]
+    fn test_translate_request_tool_round_trip() {
+        let mut assistant = msg("assistant", "");
+        assistant.tool_calls = Some(vec![
+            ToolCall {
+                id: "call_1".to_string(),
+                call_type: "function".to_string(),
+                function: FunctionCall {
+                    name: "get_weather".to_string(),
+                    arguments: r#"{"city":"Paris"}"#.to_string(),
+                },
+            },
+            ToolCall {
+                id: "call_2".to_string(),
+                call_type: "function".to_string(),
+                function: FunctionCall {
+                    name: "get_weather".to_string(),
+                    arguments: r#"{"city":"Rome"}"#.to_string(),
+                },
+            },
+        ]);
+        let mut result_1 = msg("tool", "18C");
+        result_1.tool_call_id = Some("call_1".to_string());
+        let mut result_2 = msg("tool", "24C");
+        result_2.tool_call_id = Some("call_2".to_string());
+
+        let mut request =
+            make_request(vec![msg("user", "Weather?"), assistant, result_1, result_2]);
+        request.extra.insert(
+            "tools".to_string(),
+            json!([{
+                "type": "function",
+                "function": {
+                    "name": "get_weather",
+                    "description": "Current weather",
+                    "parameters": {"type": "object", "properties": {"city": {"type": "string"}}}
+                }
+            }]),
+        );
+        request
+            .extra
+            .insert("tool_choice".to_string(), json!("required"));
+
+        let body = BedrockAgent::translate_request(&request);
+        let messages = body["messages"].as_array().unwrap();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(
+            messages[1]["content"][0]["toolUse"],
+            json!({"toolUseId": "call_1", "name": "get_weather", "input": {"city": "Paris"}})
+        );
+        // Both results merged into one user turn
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(messages[2]["content"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            messages[2]["content"][1]["toolResult"]["toolUseId"],
+            "call_2"
+        );
+        assert_eq!(
+            body["toolConfig"]["tools"][0]["toolSpec"]["inputSchema"]["json"]["type"],
+            "object"
+        );
+        assert_eq!(body["toolConfig"]["toolChoice"], json!({ "any": {} }));
+    }
+
+    #[test]
+    fn test_translate_response_text_and_tool_use() {
+        let body = json!({
+            "output": {"message": {"role": "assistant", "content": [
+                {"text": "Checking."},
+                {"toolUse": {"toolUseId": "tu_1", "name": "get_weather", "input": {"city": "Paris"}}}
+            ]}},
+            "stopReason": "tool_use",
+            "usage": {"inputTokens": 12, "outputTokens": 8, "totalTokens": 20}
+        });
+
+        let response = BedrockAgent::translate_response("claude-3-haiku", &body).unwrap();
+        let choice = &response.choices[0];
+
+        assert_eq!(response.model, "claude-3-haiku");
+        assert_eq!(choice.finish_reason.as_deref(), Some("tool_calls"));
+        assert!(
+            matches!(&choice.message.content, MessageContent::Text { content } if content == "Checking.")
+        );
+        let calls = choice.message.tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].id, "tu_1");
+        assert_eq!(calls[0].function.arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(response.usage.unwrap().total_tokens, 20);
+    }
+
+    #[test]
+    fn test_finish_reason_mapping() {
+        assert_eq!(finish_reason("end_turn"), "stop");
+        assert_eq!(finish_reason("stop_sequence"), "stop");
+        assert_eq!(finish_reason("max_tokens"), "length");
+        assert_eq!(finish_reason("guardrail_intervened"), "content_filter");
+    }
+
+    #[tokio::test]
+    async fn test_list_models_uses_model_id_heuristics() {
+        let agent = test_agent("https://bedrock-runtime.us-east-1.amazonaws.com".to_string());
+        let models = agent.list_models().await.unwrap();
+
+        assert_eq!(models[0].id, "claude-3-haiku");
+        assert_eq!(models[0].context_length, 200000);
+        assert!(models[0].supports_vision);
+        assert_eq!(models[1].id, "llama3-70b");
+        assert_eq!(models[1].context_length, 8192);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_is_signed() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/model/anthropic.claude-3-haiku-20240307-v1%3A0/converse",
+            )
+            .match_header(
+                "authorization",
+                Matcher::Regex(
+                    r"^AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/\d{8}/us-east-1/bedrock/aws4_request, SignedHeaders=content-type;host;x-amz-date, Signature=[0-9a-f]{64}$"
+                        .to_string(),
+                ),
+            )
+            .match_header("x-amz-date", Matcher::Regex(r"^\d{8}T\d{6}Z$".to_string()))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "output": {"message": {"role": "assistant", "content": [{"text": "Hi"}]}},
+                    "stopReason": "end_turn",
+                    "usage": {"inputTokens": 3, "outputTokens": 1, "totalTokens": 4}
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let response = agent
+            .chat_completion(make_request(vec![msg("user", "Hello")]), None)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_upstream_error() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", Matcher::Any)
+            .with_status(400)
+            .with_body(r#"{"message":"Malformed input request"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let result = agent
+            .chat_completion(make_request(vec![msg("user", "Hello")]), None)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AgentError::Upstream { status: 400, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_translates_events() {
+        let mut body = event("messageStart", json!({"role": "assistant"}));
+        body.extend(event(
+            "contentBlockDelta",
+            json!({"contentBlockIndex": 0, "delta": {"text": "Hel"}}),
+        ));
+        body.extend(event(
+            "contentBlockDelta",
+            json!({"contentBlockIndex": 0, "delta": {"text": "lo"}}),
+        ));
+        body.extend(event(
+            "contentBlockStart",
+            json!({"contentBlockIndex": 1, "start": {"toolUse": {"toolUseId": "tu_1", "name": "lookup"}}}),
+        ));
+        body.extend(event(
+            "contentBlockDelta",
+            json!({"contentBlockIndex": 1, "delta": {"toolUse": {"input": "{\"q\":1}"}}}),
+        ));
+        body.extend(event("messageStop", json!({"stopReason": "tool_use"})));
+        body.extend(event(
+            "metadata",
+            json!({"usage": {"inputTokens": 3, "outputTokens": 2, "totalTokens": 5}}),
+        ));
+
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/model/anthropic.claude-3-haiku-20240307-v1%3A0/converse-stream",
+            )
+            .with_status(200)
+            .with_header("content-type", "application/vnd.amazon.eventstream")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let mut request = make_request(vec![msg("user", "Hello")]);
+        request.stream = true;
+        let stream = agent.chat_completion_stream(request, None).await.unwrap();
+
+        use futures_util::StreamExt;
+        let chunks: Vec<String> = stream.map(|chunk| chunk.unwrap().data).collect().await;
+        mock.assert_async().await;
+
+        let events: Vec<Value> = chunks[..chunks.len() - 1]
+            .iter()
+            .map(|c| serde_json::from_str(c).unwrap())
+            .collect();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[0]["choices"][0]["delta"]["role"], "assistant");
+        assert_eq!(events[1]["choices"][0]["delta"]["content"], "Hel");
+        assert_eq!(events[2]["choices"][0]["delta"]["content"], "lo");
+        assert_eq!(
+            events[3]["choices"][0]["delta"]["tool_calls"][0]["id"],
+            "tu_1"
+        );
+        assert_eq!(
+            events[4]["choices"][0]["delta"]["tool_calls"][0]["function"]["arguments"],
+            "{\"q\":1}"
+        );
+        assert_eq!(events[5]["choices"][0]["finish_reason"], "tool_calls");
+        assert_eq!(events[0]["id"], events[5]["id"]);
+        assert_eq!(chunks.last().unwrap(), "[DONE]");
+    }
+
+    #[tokio::test]
+    async fn test_stream_exception_becomes_error() {
+        let mut body = event("messageStart", json!({"role": "assistant"}));
+        body.extend(encode_message(
+            &[
+                (":exception-type", "throttlingException"),
+                (":content-type", "application/json"),
+                (":message-type", "exception"),
+            ],
+            br#"{"message":"Too many requests"}"#,
+        ));
+
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", Matcher::Any)
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let stream = agent
+            .chat_completion_stream(make_request(vec![msg("user", "Hello")]), None)
+            .await
+            .unwrap();
+
+        use futures_util::StreamExt;
+        let chunks: Vec<_> = stream.collect().await;
+        assert!(chunks[0].is_ok());
+        assert!(matches!(
+            &chunks[1],
+            Err(AgentError::Upstream { status: 429, message }) if message.contains("Too many requests")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/foundation-models")
+            .match_header(
+                "authorization",
+                Matcher::Regex(
+                    r"/bedrock/aws4_request, SignedHeaders=host;x-amz-date,".to_string(),
+                ),
+            )
+            .with_status(200)
+            .with_body(r#"{"modelSummaries":[]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let status = agent.health_check().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(status, HealthStatus::Healthy { model_count: 2 });
+    }
+
+    #[tokio::test]
+    async fn test_health_check_access_denied_is_healthy() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/foundation-models")
+            .with_status(403)
+            .with_header("x-amzn-errortype", "AccessDeniedException")
+            .with_body(r#"{"message":"User: arn:aws:iam::123:user/x is not authorized to perform: bedrock:ListFoundationModels"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        assert!(matches!(
+            agent.health_check().await.unwrap(),
+            HealthStatus::Healthy { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_bad_signature_is_unhealthy() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/foundation-models")
+            .with_status(403)
+            .with_body(r#"{"message":"The request signature we calculated does not match the signature you provided."}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        assert_eq!(agent.health_check().await.unwrap(), HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_profile() {
+        let agent = test_agent("https://bedrock-runtime.us-east-1.amazonaws.com/".to_string());
+        let profile = agent.profile();
+        assert_eq!(profile.backend_type, "bedrock");
+        assert_eq!(profile.privacy_zone, PrivacyZone::Open);
+        assert_eq!(
+            agent.control_plane_url(),
+            "https://bedrock.us-east-1.amazonaws.com"
+        );
+    }
+}
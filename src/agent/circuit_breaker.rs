@@ -0,0 +1,423 @@
+//! Per-agent circuit breakers for fast failure isolation.
+//!
+//! Complements the QualityMetricsStore: quality metrics react over a 1-hour
+//! window, while a circuit breaker trips on consecutive failures and recovers
+//! through a limited number of half-open trial requests.
+
+use crate::config::CircuitBreakerConfig;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Circuit state for a single agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Normal operation, all requests admitted
+    Closed,
+    /// Tripped, requests rejected until the open duration elapses
+    Open,
+    /// Probing, a limited number of trial requests admitted
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Lowercase name used in stats, logs and metric labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Admission decision for a routing candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitAdmission {
+    /// Agent may receive the request
+    Allowed,
+    /// Circuit is open; retry after the given number of seconds
+    Open { retry_after_secs: u64 },
+    /// Circuit is half-open and all trial slots are in use
+    HalfOpenSaturated,
+}
+
+/// Point-in-time view of an agent's circuit.
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitSnapshot {
+    /// Current state
+    pub state: CircuitState,
+    /// Consecutive failures observed while closed
+    pub consecutive_failures: u32,
+    /// Seconds until an open circuit moves to half-open
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+struct CircuitBreaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    half_open_in_flight: u32,
+    half_open_successes: u32,
+    /// Incremented on every transition so stale permits release nothing
+    epoch: u64,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+            half_open_in_flight: 0,
+            half_open_successes: 0,
+            epoch: 0,
+        }
+    }
+}
+
+/// Half-open trial slot reserved by [`CircuitBreakerStore::on_dispatch`].
+///
+/// Releases the slot on drop, so a request that is cancelled or a stream the
+/// client abandons cannot keep a half-open circuit saturated.
+#[derive(Debug)]
+#[must_use = "dropping the permit releases the half-open slot"]
+pub struct DispatchPermit {
+    slot: Option<(Arc<CircuitBreakerStore>, String, u64)>,
+}
+
+impl Drop for DispatchPermit {
+    fn drop(&mut self) {
+        if let Some((store, agent_id, epoch)) = self.slot.take() {
+            store.release(&agent_id, epoch);
+        }
+    }
+}
+
+/// Thread-safe store of circuit breakers, indexed by agent_id.
+#[derive(Debug)]
+pub struct CircuitBreakerStore {
+    breakers: DashMap<String, CircuitBreaker>,
+    config: CircuitBreakerConfig,
+}
+
+impl CircuitBreakerStore {
+    /// Create a new store with the given configuration.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            breakers: DashMap::new(),
+            config,
+        }
+    }
+
+    /// Get the circuit breaker configuration.
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    fn open_duration(&self) -> Duration {
+        Duration::from_secs(self.config.open_duration_seconds)
+    }
+
+    /// Move an open circuit to half-open once its open duration has elapsed.
+    fn refresh(&self, agent_id: &str, breaker: &mut CircuitBreaker) {
+        if breaker.state != CircuitState::Open {
+            return;
+        }
+        let elapsed = breaker.opened_at.map(|t| t.elapsed()).unwrap_or_default();
+        if elapsed >= self.open_duration() {
+            self.transition(agent_id, breaker, CircuitState::HalfOpen);
+        }
+    }
+
+    fn transition(&self, agent_id: &str, breaker: &mut CircuitBreaker, to: CircuitState) {
+        let from = breaker.state;
+        breaker.state = to;
+        breaker.epoch += 1;
+        breaker.half_open_in_flight = 0;
+        breaker.half_open_successes = 0;
+        match to {
+            CircuitState::Open => breaker.opened_at = Some(Instant::now()),
+            CircuitState::Closed => {
+                breaker.opened_at = None;
+                breaker.consecutive_failures = 0;
+            }
+            CircuitState::HalfOpen => {}
+        }
+
+        tracing::info!(
+            agent_id,
+            from = %from,
+            to = %to,
+            "Circuit breaker state changed"
+        );
+        metrics::counter!(
+            "nexus_circuit_breaker_transitions_total",
+            "agent_id" => agent_id.to_string(),
+            "state" => to.as_str(),
+        )
+        .increment(1);
+    }
+
+    /// Check whether an agent may receive a request, without reserving a slot.
+    pub fn admission(&self, agent_id: &str) -> CircuitAdmission {
+        if !self.config.enabled {
+            return CircuitAdmission::Allowed;
+        }
+        let Some(mut breaker) = self.breakers.get_mut(agent_id) else {
+            return CircuitAdmission::Allowed;
+        };
+        self.refresh(agent_id, &mut breaker);
+
+        match breaker.state {
+            CircuitState::Closed => CircuitAdmission::Allowed,
+            CircuitState::Open => {
+                let remaining = breaker
+                    .opened_at
+                    .map(|t| self.open_duration().saturating_sub(t.elapsed()))
+                    .unwrap_or_default();
+                CircuitAdmission::Open {
+                    retry_after_secs: remaining.as_secs().max(1),
+                }
+            }
+            CircuitState::HalfOpen => {
+                if breaker.half_open_in_flight < self.config.half_open_max_requests {
+                    CircuitAdmission::Allowed
+                } else {
+                    CircuitAdmission::HalfOpenSaturated
+                }
+            }
+        }
+    }
+
+    /// Mark a request as dispatched to an agent.
+    ///
+    /// Reserves a trial slot when the circuit is half-open. Called once per
+    /// attempt, immediately before the request is sent to the backend. The
+    /// slot is held until the returned permit is dropped, so keep it alive
+    /// until the request (or its stream) has finished.
+    pub fn on_dispatch(self: &Arc<Self>, agent_id: &str) -> DispatchPermit {
+        let mut permit = DispatchPermit { slot: None };
+        if !self.config.enabled {
+            return permit;
+        }
+        if let Some(mut breaker) = self.breakers.get_mut(agent_id) {
+            self.refresh(agent_id, &mut breaker);
+            if breaker.state == CircuitState::HalfOpen {
+                breaker.half_open_in_flight += 1;
+                permit.slot = Some((Arc::clone(self), agent_id.to_string(), breaker.epoch));
+            }
+        }
+        permit
+    }
+
+    /// Free a half-open trial slot reserved in the given epoch.
+    fn release(&self, agent_id: &str, epoch: u64) {
+        if let Some(mut breaker) = self.breakers.get_mut(agent_id) {
+            if breaker.epoch == epoch && breaker.state == CircuitState::HalfOpen {
+                breaker.half_open_in_flight = breaker.half_open_in_flight.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Record a successful request.
+    pub fn record_success(&self, agent_id: &str) {
+        if !self.config.enabled {
+            return;
+        }
+        let Some(mut breaker) = self.breakers.get_mut(agent_id) else {
+            return;
+        };
+        match breaker.state {
+            CircuitState::Closed => breaker.consecutive_failures = 0,
+            CircuitState::HalfOpen => {
+                breaker.half_open_successes += 1;
+                if breaker.half_open_successes >= self.config.success_threshold {
+                    self.transition(agent_id, &mut breaker, CircuitState::Closed);
+                }
+            }
+            // A request admitted before the circuit opened; ignore it.
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Record a failed request (error or timeout).
+    pub fn record_failure(&self, agent_id: &str) {
+        if !self.config.enabled {
+            return;
+        }
+        let mut breaker = self.breakers.entry(agent_id.to_string()).or_default();
+        match breaker.state {
+            CircuitState::Closed => {
+                breaker.consecutive_failures += 1;
+                if breaker.consecutive_failures >= self.config.failure_threshold {
+                    self.transition(agent_id, &mut breaker, CircuitState::Open);
+                }
+            }
+            CircuitState::HalfOpen => {
+                self.transition(agent_id, &mut breaker, CircuitState::Open);
+            }
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Get the current state of an agent's circuit.
+    pub fn state(&self, agent_id: &str) -> CircuitState {
+        self.snapshot(agent_id).state
+    }
+
+    /// Get a snapshot of an agent's circuit.
+    pub fn snapshot(&self, agent_id: &str) -> CircuitSnapshot {
+        let closed = CircuitSnapshot {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            retry_after_secs: None,
+        };
+        if !self.config.enabled {
+            return closed;
+        }
+        let Some(mut breaker) = self.breakers.get_mut(agent_id) else {
+            return closed;
+        };
+        self.refresh(agent_id, &mut breaker);
+        let retry_after_secs = match breaker.state {
+            CircuitState::Open => breaker
+                .opened_at
+                .map(|t| self.open_duration().saturating_sub(t.elapsed()).as_secs()),
+            _ => None,
+        };
+        CircuitSnapshot {
+            state: breaker.state,
+            consecutive_failures: breaker.consecutive_failures,
+            retry_after_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(failure_threshold: u32, open_duration_seconds: u64) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold,
+            open_duration_seconds,
+            ..CircuitBreakerConfig::default()
+        }
+    }
+
+    #[test]
+    fn unknown_agent_is_closed() {
+        let store = CircuitBreakerStore::new(CircuitBreakerConfig::default());
+        assert_eq!(store.state("a"), CircuitState::Closed);
+        assert_eq!(store.admission("a"), CircuitAdmission::Allowed);
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let store = CircuitBreakerStore::new(config(3, 30));
+        store.record_failure("a");
+        store.record_failure("a");
+        assert_eq!(store.state("a"), CircuitState::Closed);
+        store.record_failure("a");
+        assert_eq!(store.state("a"), CircuitState::Open);
+        assert!(matches!(
+            store.admission("a"),
+            CircuitAdmission::Open { retry_after_secs } if retry_after_secs > 0
+        ));
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let store = CircuitBreakerStore::new(config(3, 30));
+        store.record_failure("a");
+        store.record_failure("a");
+        store.record_success("a");
+        store.record_failure("a");
+        store.record_failure("a");
+        assert_eq!(store.state("a"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn half_open_limits_trial_requests() {
+        let store = Arc::new(CircuitBreakerStore::new(config(1, 0)));
+        store.record_failure("a");
+        assert_eq!(store.state("a"), CircuitState::HalfOpen);
+        assert_eq!(store.admission("a"), CircuitAdmission::Allowed);
+
+        let _permit = store.on_dispatch("a");
+        assert_eq!(store.admission("a"), CircuitAdmission::HalfOpenSaturated);
+    }
+
+    #[test]
+    fn dropped_permit_releases_half_open_slot() {
+        let store = Arc::new(CircuitBreakerStore::new(config(1, 0)));
+        store.record_failure("a");
+
+        let permit = store.on_dispatch("a");
+        assert_eq!(store.admission("a"), CircuitAdmission::HalfOpenSaturated);
+        // The request is cancelled before any outcome is recorded
+        drop(permit);
+        assert_eq!(store.admission("a"), CircuitAdmission::Allowed);
+    }
+
+    #[test]
+    fn stale_permit_does_not_release_new_slot() {
+        let store = Arc::new(CircuitBreakerStore::new(config(1, 0)));
+        store.record_failure("a");
+        let stale = store.on_dispatch("a");
+        // Trial fails; the circuit reopens and immediately goes half-open again
+        store.record_failure("a");
+        let _current = store.on_dispatch("a");
+
+        drop(stale);
+        assert_eq!(store.admission("a"), CircuitAdmission::HalfOpenSaturated);
+    }
+
+    #[test]
+    fn half_open_success_closes_circuit() {
+        let store = Arc::new(CircuitBreakerStore::new(config(1, 0)));
+        store.record_failure("a");
+        let _permit = store.on_dispatch("a");
+        store.record_success("a");
+        assert_eq!(store.state("a"), CircuitState::Closed);
+        assert_eq!(store.snapshot("a").consecutive_failures, 0);
+    }
+
+    #[test]
+    fn half_open_failure_reopens_circuit() {
+        let store = Arc::new(CircuitBreakerStore::new(config(1, 1)));
+        store.record_failure("a");
+        {
+            // Simulate the open duration elapsing
+            let mut breaker = store.breakers.get_mut("a").unwrap();
+            breaker.opened_at = Some(Instant::now() - Duration::from_secs(2));
+        }
+        assert_eq!(store.state("a"), CircuitState::HalfOpen);
+        let _permit = store.on_dispatch("a");
+        store.record_failure("a");
+        assert_eq!(store.state("a"), CircuitState::Open);
+    }
+
+    #[test]
+    fn disabled_store_always_allows() {
+        let store = CircuitBreakerStore::new(CircuitBreakerConfig {
+            enabled: false,
+            failure_threshold: 1,
+            ..CircuitBreakerConfig::default()
+        });
+        store.record_failure("a");
+        assert_eq!(store.admission("a"), CircuitAdmission::Allowed);
+        assert_eq!(store.state("a"), CircuitState::Closed);
+    }
+}
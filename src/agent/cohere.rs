@@ -0,0 +1,924 @@
+//! Cohere agent implementation.
+
+use super::tei::scores_in_input_order;
+use super::{
+    AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
+    PrivacyZone, StreamChunk,
+};
+use crate::api::types::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Choice, MessageContent, ToolCall,
+    Usage,
+};
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use futures_util::stream::BoxStream;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Timeout for chat, embed and rerank requests.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Maximum texts per /v2/embed call.
+const MAX_EMBED_BATCH: usize = 96;
+
+/// Cohere agent implementation.
+///
+/// Speaks Cohere's v2 API natively, translating to/from OpenAI format:
+/// - Health check and model listing via GET /v1/models, with capabilities
+///   taken from each model's endpoints and features
+/// - Chat completion via POST /v2/chat
+/// - Streaming via POST /v2/chat with `stream: true`, translating Cohere's
+///   typed events into OpenAI chunks
+/// - Embeddings via POST /v2/embed
+/// - Reranking via POST /v2/rerank
+pub struct CohereAgent {
+    /// Unique agent ID
+    id: String,
+    /// Human-readable name
+    name: String,
+    /// Base URL (e.g., "https://api.cohere.com")
+    base_url: String,
+    /// API key for Bearer authentication
+    api_key: String,
+    /// Shared HTTP client for connection pooling
+    client: Arc<Client>,
+    /// Privacy zone classification from config
+    privacy_zone: PrivacyZone,
+    /// Capability tier from config
+    capability_tier: Option<u8>,
+}
+
+impl CohereAgent {
+    pub fn new(
+        id: String,
+        name: String,
+        base_url: String,
+        api_key: String,
+        client: Arc<Client>,
+        privacy_zone: PrivacyZone,
+        capability_tier: Option<u8>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            base_url,
+            api_key,
+            client,
+            privacy_zone,
+            capability_tier,
+        }
+    }
+
+    /// POST a JSON body to `path`, returning upstream errors as `AgentError`.
+    ///
+    /// A client `Authorization` header overrides the configured key.
+    async fn post(
+        &self,
+        path: &str,
+        body: &Value,
+        headers: Option<&HeaderMap>,
+    ) -> Result<reqwest::Response, AgentError> {
+        let url = format!("{}{}", self.base_url, path);
+        let auth = headers
+            .and_then(|h| h.get("authorization"))
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+            .unwrap_or_else(|| format!("Bearer {}", self.api_key));
+
+        let response = self
+            .client
+            .post(&url)
+            .header("authorization", auth)
+            .json(body)
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(REQUEST_TIMEOUT.as_millis() as u64)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status: status.as_u16(),
+                message: error_body,
+            });
+        }
+        Ok(response)
+    }
+
+    /// GET /v1/models.
+    async fn get_models(&self) -> Result<reqwest::Response, AgentError> {
+        let url = format!("{}/v1/models?page_size=1000", self.base_url);
+
+        self.client
+            .get(&url)
+            .header("authorization", format!("Bearer {}", self.api_key))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(5000)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })
+    }
+
+    /// Translate an OpenAI chat request to a Cohere v2 chat request.
+    ///
+    /// v2 messages and tools are close to OpenAI's; sampling parameters and
+    /// `response_format` are renamed.
+    fn translate_request(request: &ChatCompletionRequest) -> Value {
+        let messages: Vec<Value> = request.messages.iter().map(translate_message).collect();
+
+        let mut body = json!({
+            "model": request.model,
+            "messages": messages,
+        });
+        if let Some(max_tokens) = request.max_tokens {
+            body["max_tokens"] = json!(max_tokens);
+        }
+        if let Some(temperature) = request.temperature {
+            body["temperature"] = json!(temperature);
+        }
+        if let Some(top_p) = request.top_p {
+            body["p"] = json!(top_p);
+        }
+        if let Some(stop) = &request.stop {
+            body["stop_sequences"] = json!(stop);
+        }
+        if let Some(penalty) = request.presence_penalty {
+            body["presence_penalty"] = json!(penalty);
+        }
+        if let Some(penalty) = request.frequency_penalty {
+            body["frequency_penalty"] = json!(penalty);
+        }
+        if let Some(seed) = request.extra.get("seed") {
+            body["seed"] = seed.clone();
+        }
+        if let Some(tools) = request.extra.get("tools") {
+            body["tools"] = tools.clone();
+        }
+        match request.extra.get("tool_choice").and_then(Value::as_str) {
+            Some("required") => body["tool_choice"] = json!("REQUIRED"),
+            Some("none") => body["tool_choice"] = json!("NONE"),
+            _ => {}
+        }
+        if let Some(format) = request.extra.get("response_format") {
+            match format["type"].as_str() {
+                Some("json_object") => body["response_format"] = json!({ "type": "json_object" }),
+                Some("json_schema") => {
+                    body["response_format"] = json!({
+                        "type": "json_object",
+                        "json_schema": format["json_schema"]["schema"]
+                    })
+                }
+                _ => {}
+            }
+        }
+        body
+    }
+
+    /// Translate a Cohere v2 chat response to OpenAI format.
+    fn translate_response(model: &str, body: &Value) -> Result<ChatCompletionResponse, AgentError> {
+        let message = body.get("message").ok_or_else(|| {
+            AgentError::InvalidResponse("Missing message in Cohere chat response".into())
+        })?;
+
+        let text: String = message["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+        let tool_calls: Vec<ToolCall> = match message.get("tool_calls") {
+            Some(calls) if !calls.is_null() => serde_json::from_value(calls.clone())
+                .map_err(|e| AgentError::InvalidResponse(format!("Invalid tool calls: {}", e)))?,
+            _ => Vec::new(),
+        };
+
+        let tokens = &body["usage"]["tokens"];
+        let prompt_tokens = tokens["input_tokens"].as_f64().unwrap_or(0.0) as u32;
+        let completion_tokens = tokens["output_tokens"].as_f64().unwrap_or(0.0) as u32;
+
+        Ok(ChatCompletionResponse {
+            id: body["id"]
+                .as_str()
+                .map(|id| format!("chatcmpl-{}", id))
+                .unwrap_or_else(|| format!("chatcmpl-{}", uuid::Uuid::new_v4())),
+            object: "chat.completion".to_string(),
+            created: chrono::Utc::now().timestamp(),
+            model: model.to_string(),
+            choices: vec![Choice {
+                index: 0,
+                message: ChatMessage {
+                    role: "assistant".to_string(),
+                    content: MessageContent::Text { content: text },
+                    name: None,
+                    function_call: None,
+                    tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                    tool_call_id: None,
+                },
+                finish_reason: Some(
+                    finish_reason(body["finish_reason"].as_str().unwrap_or_default()).to_string(),
+                ),
+            }],
+            usage: Some(Usage {
+                prompt_tokens,
+                completion_tokens,
+                total_tokens: prompt_tokens + completion_tokens,
+            }),
+            extra: HashMap::new(),
+            timings: None,
+        })
+    }
+}
+
+/// Translate one OpenAI message to a Cohere v2 message.
+fn translate_message(msg: &ChatMessage) -> Value {
+    let content = match &msg.content {
+        MessageContent::Text { content } => json!(content),
+        // v2 accepts OpenAI-style text and image_url parts
+        MessageContent::Parts { content } => json!(content),
+    };
+
+    match msg.role.as_str() {
+        "system" | "developer" => json!({ "role": "system", "content": content }),
+        "tool" => json!({
+            "role": "tool",
+            "tool_call_id": msg.tool_call_id.clone().unwrap_or_default(),
+            "content": content
+        }),
+        "assistant" => {
+            let mut message = json!({ "role": "assistant" });
+            if content.as_str().is_some_and(|text| !text.is_empty()) || content.is_array() {
+                message["content"] = content;
+            }
+            if let Some(calls) = &msg.tool_calls {
+                message["tool_calls"] = json!(calls);
+            }
+            message
+        }
+        _ => json!({ "role": "user", "content": content }),
+    }
+}
+
+/// Map a Cohere finish reason to an OpenAI finish reason.
+fn finish_reason(reason: &str) -> &'static str {
+    match reason {
+        "MAX_TOKENS" => "length",
+        "TOOL_CALL" => "tool_calls",
+        _ => "stop",
+    }
+}
+
+/// Translates Cohere v2 stream events into OpenAI chunks.
+struct StreamTranslator {
+    id: String,
+    model: String,
+    created: i64,
+    /// Bytes after the last complete line
+    buffer: String,
+    /// Tool calls started so far
+    tool_calls: usize,
+}
+
+impl StreamTranslator {
+    fn new(model: String) -> Self {
+        Self {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            model,
+            created: chrono::Utc::now().timestamp(),
+            buffer: String::new(),
+            tool_calls: 0,
+        }
+    }
+
+    /// Chunk JSON as forwarded by the SSE handler (without the `data:` framing).
+    fn chunk(&self, delta: Value, finish_reason: Option<&str>) -> String {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": [{
+                "index": 0,
+                "delta": delta,
+                "finish_reason": finish_reason
+            }]
+        })
+        .to_string()
+    }
+
+    /// Feed raw SSE bytes, returning chunks for every complete event line.
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.push_str(&String::from_utf8_lossy(bytes));
+        let mut chunks = Vec::new();
+        while let Some(end) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=end).collect();
+            if let Some(data) = line.trim_end().strip_prefix("data:") {
+                if let Ok(event) = serde_json::from_str::<Value>(data.trim()) {
+                    chunks.extend(self.translate(&event));
+                }
+            }
+        }
+        chunks
+    }
+
+    /// Translate one event; each carries its kind in `type`.
+    fn translate(&mut self, event: &Value) -> Option<String> {
+        let message = &event["delta"]["message"];
+        match event["type"].as_str()? {
+            "message-start" => Some(self.chunk(json!({ "role": "assistant" }), None)),
+            "content-delta" => {
+                let text = message["content"]["text"].as_str()?;
+                Some(self.chunk(json!({ "content": text }), None))
+            }
+            "tool-call-start" => {
+                let call = &message["tool_calls"];
+                let index = self.tool_calls;
+                self.tool_calls += 1;
+                Some(self.chunk(
+                    json!({
+                        "tool_calls": [{
+                            "index": index,
+                            "id": call["id"],
+                            "type": "function",
+                            "function": {
+                                "name": call["function"]["name"],
+                                "arguments": call["function"]["arguments"].as_str().unwrap_or_default()
+                            }
+                        }]
+                    }),
+                    None,
+                ))
+            }
+            "tool-call-delta" => {
+                let arguments = message["tool_calls"]["function"]["arguments"].as_str()?;
+                Some(self.chunk(
+                    json!({
+                        "tool_calls": [{
+                            "index": self.tool_calls.saturating_sub(1),
+                            "function": { "arguments": arguments }
+                        }]
+                    }),
+                    None,
+                ))
+            }
+            "message-end" => Some(self.chunk(
+                json!({}),
+                Some(finish_reason(
+                    event["delta"]["finish_reason"].as_str().unwrap_or_default(),
+                )),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+impl InferenceAgent for CohereAgent {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn profile(&self) -> AgentProfile {
+        AgentProfile {
+            backend_type: "cohere".to_string(),
+            version: None,
+            privacy_zone: self.privacy_zone,
+            capabilities: AgentCapabilities {
+                embeddings: true,
+                rerank: true,
+                model_lifecycle: false,
+                token_counting: false,
+                resource_monitoring: false,
+            },
+            capability_tier: self.capability_tier,
+        }
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, AgentError> {
+        let response = self.get_models().await?;
+        if !response.status().is_success() {
+            return Ok(HealthStatus::Unhealthy);
+        }
+
+        let body = response.text().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to read response body: {}", e))
+        })?;
+        let models = crate::health::parser::parse_cohere_models_response(&body)
+            .map_err(|e| AgentError::InvalidResponse(e.to_string()))?;
+
+        Ok(HealthStatus::Healthy {
+            model_count: models.len(),
+        })
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelCapability>, AgentError> {
+        let response = self.get_models().await?;
+        if !response.status().is_success() {
+            return Err(AgentError::Upstream {
+                status: response.status().as_u16(),
+                message: format!("Failed to list models: {}", response.status()),
+            });
+        }
+
+        let body = response.text().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to read response body: {}", e))
+        })?;
+        let models = crate::health::parser::parse_cohere_models_response(&body)
+            .map_err(|e| AgentError::InvalidResponse(e.to_string()))?;
+
+        Ok(models.into_iter().map(ModelCapability::from).collect())
+    }
+
+    async fn chat_completion(
+        &self,
+        request: ChatCompletionRequest,
+        headers: Option<&HeaderMap>,
+    ) -> Result<ChatCompletionResponse, AgentError> {
+        let body = Self::translate_request(&request);
+        let response = self.post("/v2/chat", &body, headers).await?;
+
+        let chat: Value = response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to parse chat response: {}", e))
+        })?;
+
+        Self::translate_response(&request.model, &chat)
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+        headers: Option<&HeaderMap>,
+    ) -> Result<BoxStream<'static, Result<StreamChunk, AgentError>>, AgentError> {
+        use futures_util::stream::{self, StreamExt};
+
+        let mut body = Self::translate_request(&request);
+        body["stream"] = json!(true);
+        let response = self.post("/v2/chat", &body, headers).await?;
+
+        let stream = response
+            .bytes_stream()
+            .scan(
+                StreamTranslator::new(request.model),
+                |translator, result| {
+                    let chunks: Vec<Result<StreamChunk, AgentError>> = match result {
+                        Err(e) => vec![Err(AgentError::Network(e.to_string()))],
+                        Ok(bytes) => translator
+                            .push(&bytes)
+                            .into_iter()
+                            .map(|data| {
+                                Ok(StreamChunk {
+                                    data,
+                                    timings: None,
+                                })
+                            })
+                            .collect(),
+                    };
+                    futures_util::future::ready(Some(stream::iter(chunks)))
+                },
+            )
+            .flatten()
+            .chain(stream::once(async {
+                Ok(StreamChunk {
+                    data: "[DONE]".to_string(),
+                    timings: None,
+                })
+            }));
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Generate embeddings via POST /v2/embed, in batches of up to 96 texts.
+    async fn embeddings(
+        &self,
+        model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, AgentError> {
+        let mut vectors = Vec::with_capacity(input.len());
+        for batch in input.chunks(MAX_EMBED_BATCH) {
+            let body = json!({
+                "model": model,
+                "texts": batch,
+                "input_type": "search_document",
+                "embedding_types": ["float"],
+            });
+            let response = self.post("/v2/embed", &body, None).await?;
+            let embed: Value = response.json().await.map_err(|e| {
+                AgentError::InvalidResponse(format!("Failed to parse embed response: {}", e))
+            })?;
+            let batch_vectors: Vec<Vec<f32>> =
+                serde_json::from_value(embed["embeddings"]["float"].clone()).map_err(|e| {
+                    AgentError::InvalidResponse(format!("Missing float embeddings: {}", e))
+                })?;
+            vectors.extend(batch_vectors);
+        }
+        Ok(vectors)
+    }
+
+    /// Score documents via POST /v2/rerank, restoring input order.
+    async fn rerank(
+        &self,
+        model: &str,
+        query: &str,
+        documents: Vec<String>,
+    ) -> Result<Vec<f32>, AgentError> {
+        let count = documents.len();
+        let body = json!({ "model": model, "query": query, "documents": documents });
+        let response = self.post("/v2/rerank", &body, None).await?;
+
+        let rerank: Value = response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to parse rerank response: {}", e))
+        })?;
+        let results = rerank["results"].as_array().ok_or_else(|| {
+            AgentError::InvalidResponse("Missing results in rerank response".into())
+        })?;
+
+        scores_in_input_order(
+            count,
+            results.iter().filter_map(|result| {
+                Some((
+                    result["index"].as_u64()? as usize,
+                    result["relevance_score"].as_f64()? as f32,
+                ))
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn test_agent(base_url: String) -> CohereAgent {
+        CohereAgent::new(
+            "test-cohere".to_string(),
+            "Test Cohere".to_string(),
+            base_url,
+            "co-test".to_string(),
+            Arc::new(Client::new()),
+            PrivacyZone::Open,
+            Some(4),
+        )
+    }
+
+    fn msg(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: MessageContent::Text {
+                content: content.to_string(),
+            },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+
+    fn make_request(messages: Vec<ChatMessage>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: "command-r-plus".to_string(),
+            messages,
+            stream: false,
+            temperature: Some(0.3),
+            max_tokens: Some(100),
+            top_p: Some(0.9),
+            stop: Some(vec!["END".to_string()]),
+            presence_penalty: None,
+            frequency_penalty: None,
+            user: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_translate_request_renames_parameters() {
+        let mut request = make_request(vec![msg("system", "Be brief."), msg("user", "Hi")]);
+        request.extra.insert(
+            "response_format".to_string(),
+            json!({"type": "json_schema", "json_schema": {"name": "r", "schema": {"type": "object"}}}),
+        );
+        request
+            .extra
+            .insert("tool_choice".to_string(), json!("required"));
+
+        let body = CohereAgent::translate_request(&request);
+
+        assert_eq!(
+            body["messages"][0],
+            json!({"role": "system", "content": "Be brief."})
+        );
+        assert_eq!(
+            body["messages"][1],
+            json!({"role": "user", "content": "Hi"})
+        );
+        assert_eq!(body["p"], json!(0.9_f32));
+        assert_eq!(body["stop_sequences"], json!(["END"]));
+        assert_eq!(body["max_tokens"], 100);
+        assert_eq!(body["tool_choice"], "REQUIRED");
+        assert_eq!(
+            body["response_format"],
+            json!({"type": "json_object", "json_schema": {"type": "object"}})
+        );
+    }
+
+    #[test]
+    fn test_translate_request_tool_round_trip() {
+        let mut assistant = msg("assistant", "");
+        assistant.tool_calls = Some(vec![ToolCall {
+            id: "call_1".to_string(),
+            call_type: "function".to_string(),
+            function: crate::api::types::FunctionCall {
+                name: "get_weather".to_string(),
+                arguments: r#"{"city":"Paris"}"#.to_string(),
+            },
+        }]);
+        let mut tool = msg("tool", "22C");
+        tool.tool_call_id = Some("call_1".to_string());
+
+        let body = CohereAgent::translate_request(&make_request(vec![
+            msg("user", "Weather?"),
+            assistant,
+            tool,
+        ]));
+
+        assert!(body["messages"][1].get("content").is_none());
+        assert_eq!(body["messages"][1]["tool_calls"][0]["id"], "call_1");
+        assert_eq!(
+            body["messages"][2],
+            json!({"role": "tool", "tool_call_id": "call_1", "content": "22C"})
+        );
+    }
+
+    #[test]
+    fn test_translate_response_text_and_tool_calls() {
+        let body = json!({
+            "id": "abc",
+            "finish_reason": "TOOL_CALL",
+            "message": {
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Checking."}],
+                "tool_calls": [{"id": "call_1", "type": "function",
+                    "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}}]
+            },
+            "usage": {"billed_units": {"input_tokens": 5, "output_tokens": 3},
+                      "tokens": {"input_tokens": 12, "output_tokens": 3}}
+        });
+
+        let response = CohereAgent::translate_response("command-r-plus", &body).unwrap();
+        let choice = &response.choices[0];
+        assert_eq!(choice.finish_reason.as_deref(), Some("tool_calls"));
+        assert!(
+            matches!(&choice.message.content, MessageContent::Text { content } if content == "Checking.")
+        );
+        assert_eq!(
+            choice.message.tool_calls.as_ref().unwrap()[0].function.name,
+            "get_weather"
+        );
+        assert_eq!(response.usage.unwrap().total_tokens, 15);
+    }
+
+    #[test]
+    fn test_finish_reason_mapping() {
+        assert_eq!(finish_reason("COMPLETE"), "stop");
+        assert_eq!(finish_reason("STOP_SEQUENCE"), "stop");
+        assert_eq!(finish_reason("MAX_TOKENS"), "length");
+        assert_eq!(finish_reason("TOOL_CALL"), "tool_calls");
+    }
+
+    #[test]
+    fn test_stream_translator_handles_split_events() {
+        let mut translator = StreamTranslator::new("command-r".to_string());
+        let events = concat!(
+            "event: message-start\n",
+            "data: {\"type\":\"message-start\",\"id\":\"x\",\"delta\":{\"message\":{\"role\":\"assistant\"}}}\n\n",
+            "event: content-delta\n",
+            "data: {\"type\":\"content-delta\",\"index\":0,\"delta\":{\"message\":{\"content\":{\"text\":\"Hel\"}}}}\n\n",
+            "event: content-delta\n",
+            "data: {\"type\":\"content-delta\",\"index\":0,\"delta\":{\"message\":{\"content\":{\"text\":\"lo\"}}}}\n\n",
+            "event: message-end\n",
+            "data: {\"type\":\"message-end\",\"delta\":{\"finish_reason\":\"MAX_TOKENS\"}}\n\n",
+        );
+        let (first, second) = events.split_at(120);
+
+        let mut chunks = translator.push(first.as_bytes());
+        chunks.extend(translator.push(second.as_bytes()));
+
+        let chunks: Vec<Value> = chunks
+            .iter()
+            .map(|c| serde_json::from_str(c).unwrap())
+            .collect();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[0]["choices"][0]["delta"]["role"], "assistant");
+        assert_eq!(chunks[1]["choices"][0]["delta"]["content"], "Hel");
+        assert_eq!(chunks[2]["choices"][0]["delta"]["content"], "lo");
+        assert_eq!(chunks[3]["choices"][0]["finish_reason"], "length");
+    }
+
+    #[test]
+    fn test_stream_translator_tool_calls() {
+        let mut translator = StreamTranslator::new("command-r".to_string());
+        let start = translator
+            .translate(&json!({"type": "tool-call-start", "index": 1, "delta": {"message": {"tool_calls":
+                {"id": "call_1", "type": "function", "function": {"name": "get_weather", "arguments": ""}}}}}))
+            .unwrap();
+        let delta = translator
+            .translate(
+                &json!({"type": "tool-call-delta", "index": 1, "delta": {"message": {"tool_calls":
+                {"function": {"arguments": "{\"city\""}}}}}),
+            )
+            .unwrap();
+
+        let start: Value = serde_json::from_str(&start).unwrap();
+        let delta: Value = serde_json::from_str(&delta).unwrap();
+        assert_eq!(start["choices"][0]["delta"]["tool_calls"][0]["index"], 0);
+        assert_eq!(
+            start["choices"][0]["delta"]["tool_calls"][0]["id"],
+            "call_1"
+        );
+        assert_eq!(
+            delta["choices"][0]["delta"]["tool_calls"][0]["function"]["arguments"],
+            "{\"city\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_models_uses_endpoints_and_features() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/v1/models")
+            .match_query(Matcher::Any)
+            .match_header("authorization", "Bearer co-test")
+            .with_status(200)
+            .with_body(
+                r#"{"models":[
+                    {"name":"command-r-plus","endpoints":["chat"],"context_length":128000,"features":["tools","json_mode"]},
+                    {"name":"embed-english-v3.0","endpoints":["embed"],"context_length":512,"features":null}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        assert_eq!(
+            agent.health_check().await.unwrap(),
+            HealthStatus::Healthy { model_count: 2 }
+        );
+        let models = agent.list_models().await.unwrap();
+        assert!(models[0].supports_tools);
+        assert_eq!(models[0].context_length, 128000);
+        assert!(models[1].supports_embeddings);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_unauthorized() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/v1/models")
+            .match_query(Matcher::Any)
+            .with_status(401)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        assert_eq!(agent.health_check().await.unwrap(), HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v2/chat")
+            .match_header("authorization", "Bearer co-test")
+            .match_body(Matcher::PartialJson(json!({
+                "model": "command-r-plus",
+                "messages": [{"role": "user", "content": "Hi"}]
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"id":"abc","finish_reason":"COMPLETE","message":{"role":"assistant","content":[{"type":"text","text":"Hello!"}]},"usage":{"tokens":{"input_tokens":3,"output_tokens":2}}}"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let response = agent
+            .chat_completion(make_request(vec![msg("user", "Hi")]), None)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(response.model, "command-r-plus");
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_upstream_error() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/v2/chat")
+            .with_status(429)
+            .with_body(r#"{"message":"You are using a Trial key, which is limited to 10 API calls / minute."}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let result = agent
+            .chat_completion(make_request(vec![msg("user", "Hi")]), None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(AgentError::Upstream { status: 429, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_batches_requests() {
+        let mut server = Server::new_async().await;
+        let vector = json!([0.5, 0.25]);
+        let full_batch =
+            json!({ "embeddings": { "float": vec![vector.clone(); MAX_EMBED_BATCH] } });
+        let mock = server
+            .mock("POST", "/v2/embed")
+            .match_body(Matcher::PartialJson(json!({
+                "model": "embed-english-v3.0",
+                "input_type": "search_document",
+                "embedding_types": ["float"]
+            })))
+            .with_status(200)
+            .with_body(full_batch.to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let input: Vec<String> = (0..MAX_EMBED_BATCH + 1).map(|i| i.to_string()).collect();
+        let vectors = agent.embeddings("embed-english-v3.0", input).await.unwrap();
+
+        mock.assert_async().await;
+        // Both calls answer with a full batch; all vectors are kept in order
+        assert_eq!(vectors.len(), 2 * MAX_EMBED_BATCH);
+        assert_eq!(vectors[0], vec![0.5, 0.25]);
+    }
+
+    #[tokio::test]
+    async fn test_rerank_restores_input_order() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/v2/rerank")
+            .match_body(Matcher::Json(json!({
+                "model": "rerank-v3.5",
+                "query": "capital of France",
+                "documents": ["Berlin is in Germany.", "Paris is the capital of France."]
+            })))
+            .with_status(200)
+            .with_body(r#"{"id":"r","results":[{"index":1,"relevance_score":0.9},{"index":0,"relevance_score":0.1}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let scores = agent
+            .rerank(
+                "rerank-v3.5",
+                "capital of France",
+                vec![
+                    "Berlin is in Germany.".to_string(),
+                    "Paris is the capital of France.".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(scores, vec![0.1, 0.9]);
+    }
+
+    #[test]
+    fn test_profile() {
+        let profile = test_agent("https://api.cohere.com".to_string()).profile();
+        assert_eq!(profile.backend_type, "cohere");
+        assert_eq!(profile.privacy_zone, PrivacyZone::Open);
+        assert!(profile.capabilities.embeddings);
+        assert!(profile.capabilities.rerank);
+        assert_eq!(profile.capability_tier, Some(4));
+    }
+}
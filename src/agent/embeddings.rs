@@ -0,0 +1,203 @@
+//! Shared embedding support for OpenAI-compatible backends.
+//!
+//! OpenAI, vLLM, llama.cpp, LM Studio and most generic servers expose the same
+//! `POST /v1/embeddings` endpoint. None of them report which models produce
+//! embeddings, so model discovery falls back to [`is_embedding_model`].
+
+use super::AgentError;
+use reqwest::{Client, RequestBuilder};
+use std::time::Duration;
+
+/// Timeout for embedding requests.
+const EMBEDDINGS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Name fragments of common embedding model families.
+const EMBEDDING_MODEL_PATTERNS: &[&str] = &[
+    "embed",
+    "bge-",
+    "bge_",
+    "e5-",
+    "gte-",
+    "minilm",
+    "mpnet",
+    "arctic-embed",
+];
+
+/// Whether a model id looks like an embedding model.
+pub fn is_embedding_model(id: &str) -> bool {
+    let name = id.to_lowercase();
+    EMBEDDING_MODEL_PATTERNS
+        .iter()
+        .any(|pattern| name.contains(pattern))
+}
+
+/// POST `{base_url}/v1/embeddings` and return one vector per input, in order.
+pub(crate) async fn openai_embeddings(
+    client: &Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    input: Vec<String>,
+) -> Result<Vec<Vec<f32>>, AgentError> {
+    let url = format!("{}/v1/embeddings", base_url);
+    let body = serde_json::json!({
+        "model": model,
+        "input": input,
+    });
+
+    let mut request = client.post(&url).json(&body);
+    if let Some(key) = api_key {
+        request = request.header("authorization", format!("Bearer {}", key));
+    }
+
+    send_embeddings_request(request, input.len()).await
+}
+
+/// Send a prepared OpenAI-format embeddings request for `inputs` texts and
+/// return one vector per input, in order.
+pub(crate) async fn send_embeddings_request(
+    request: RequestBuilder,
+    inputs: usize,
+) -> Result<Vec<Vec<f32>>, AgentError> {
+    let response = request
+        .timeout(EMBEDDINGS_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| {
+            if e.is_timeout() {
+                AgentError::Timeout(EMBEDDINGS_TIMEOUT.as_millis() as u64)
+            } else {
+                AgentError::Network(e.to_string())
+            }
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(AgentError::Upstream {
+            status: status.as_u16(),
+            message: error_body,
+        });
+    }
+
+    let body: serde_json::Value = response.json().await.map_err(|e| {
+        AgentError::InvalidResponse(format!("Failed to parse embeddings response: {}", e))
+    })?;
+
+    let data = body["data"].as_array().ok_or_else(|| {
+        AgentError::InvalidResponse("Missing data array in embeddings response".to_string())
+    })?;
+    if data.len() != inputs {
+        return Err(AgentError::InvalidResponse(format!(
+            "Embeddings response has {} vectors for {} inputs",
+            data.len(),
+            inputs
+        )));
+    }
+
+    // Servers may return items out of order; `index` restores input order
+    let mut items: Vec<(usize, &serde_json::Value)> = data
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let index = item["index"].as_u64().map_or(position, |i| i as usize);
+            (index, item)
+        })
+        .collect();
+    items.sort_by_key(|(index, _)| *index);
+
+    items
+        .into_iter()
+        .map(|(_, item)| {
+            let embedding = item["embedding"].as_array().ok_or_else(|| {
+                AgentError::InvalidResponse("Missing embedding array in response item".to_string())
+            })?;
+            Ok(embedding
+                .iter()
+                .filter_map(|v| v.as_f64().map(|f| f as f32))
+                .collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[test]
+    fn detects_embedding_model_names() {
+        for id in [
+            "nomic-embed-text",
+            "text-embedding-3-small",
+            "BAAI/bge-large-en-v1.5",
+            "intfloat/e5-mistral-7b-instruct",
+            "thenlper/gte-base",
+            "sentence-transformers/all-MiniLM-L6-v2",
+            "mxbai-embed-large",
+        ] {
+            assert!(is_embedding_model(id), "{id} should be an embedding model");
+        }
+        for id in [
+            "llama3:8b",
+            "mistral-7b-instruct",
+            "qwen2.5-coder",
+            "gpt-4o",
+        ] {
+            assert!(
+                !is_embedding_model(id),
+                "{id} should not be an embedding model"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn restores_input_order_from_index() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/embeddings")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_body(r#"{"data":[{"index":1,"embedding":[2.0]},{"index":0,"embedding":[1.0]}]}"#)
+            .create_async()
+            .await;
+
+        let vectors = openai_embeddings(
+            &Client::new(),
+            &server.url(),
+            None,
+            "bge-small",
+            vec!["a".to_string(), "b".to_string()],
+        )
+        .await
+        .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors, vec![vec![1.0], vec![2.0]]);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_vectors() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/v1/embeddings")
+            .with_status(200)
+            .with_body(r#"{"data":[{"index":0,"embedding":[1.0]}]}"#)
+            .create_async()
+            .await;
+
+        let result = openai_embeddings(
+            &Client::new(),
+            &server.url(),
+            None,
+            "bge-small",
+            vec!["a".to_string(), "b".to_string()],
+        )
+        .await;
+
+        assert!(matches!(result, Err(AgentError::InvalidResponse(_))));
+    }
+}
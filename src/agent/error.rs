@@ -29,3 +29,11 @@ pub enum AgentError {
     #[error("Configuration error: {0}")]
     Configuration(String),
 }
+
+impl AgentError {
+    /// Whether the backend rejected the request itself (4xx), as opposed to
+    /// failing to serve it.
+    pub fn is_client_error(&self) -> bool {
+        matches!(self, AgentError::Upstream { status, .. } if (400..500).contains(status))
+    }
+}
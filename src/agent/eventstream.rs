@@ -0,0 +1,240 @@
+//! AWS event-stream decoding.
+//!
+//! Bedrock's streaming APIs answer with `application/vnd.amazon.eventstream`,
+//! a binary framing of messages:
+//!
+//! ```text
+//! total length (u32) | headers length (u32) | prelude CRC32 (u32)
+//! headers | payload | message CRC32 (u32)
+//! ```
+//!
+//! Each header is a name, a type tag and a typed value. Frames can be split
+//! across network chunks, so [`EventStreamDecoder`] buffers partial input.
+
+use super::AgentError;
+use std::collections::HashMap;
+
+/// Prelude (12 bytes) plus trailing message CRC (4 bytes).
+const FRAME_OVERHEAD: usize = 16;
+
+/// Frames larger than this are treated as corrupt input.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// A decoded event-stream message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventMessage {
+    /// String-valued headers (`:event-type`, `:message-type`, ...)
+    pub headers: HashMap<String, String>,
+    pub payload: Vec<u8>,
+}
+
+impl EventMessage {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+}
+
+/// Incremental decoder for a stream of event-stream frames.
+#[derive(Debug, Default)]
+pub struct EventStreamDecoder {
+    buffer: Vec<u8>,
+}
+
+impl EventStreamDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append bytes from the network and return every message completed by
+    /// them. Incomplete trailing data is kept for the next call.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Vec<EventMessage>, AgentError> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut messages = Vec::new();
+        while self.buffer.len() >= 4 {
+            let total_len = read_u32(&self.buffer, 0) as usize;
+            if !(FRAME_OVERHEAD..=MAX_FRAME_LEN).contains(&total_len) {
+                return Err(invalid(format!("invalid frame length {}", total_len)));
+            }
+            if self.buffer.len() < total_len {
+                break;
+            }
+            let frame: Vec<u8> = self.buffer.drain(..total_len).collect();
+            messages.push(decode_frame(&frame)?);
+        }
+        Ok(messages)
+    }
+}
+
+fn decode_frame(frame: &[u8]) -> Result<EventMessage, AgentError> {
+    let total_len = frame.len();
+    let headers_len = read_u32(frame, 4) as usize;
+    if crc32fast::hash(&frame[..8]) != read_u32(frame, 8) {
+        return Err(invalid("prelude checksum mismatch".to_string()));
+    }
+    if crc32fast::hash(&frame[..total_len - 4]) != read_u32(frame, total_len - 4) {
+        return Err(invalid("message checksum mismatch".to_string()));
+    }
+    if 12 + headers_len > total_len - 4 {
+        return Err(invalid(format!("invalid headers length {}", headers_len)));
+    }
+
+    let headers = decode_headers(&frame[12..12 + headers_len])?;
+    let payload = frame[12 + headers_len..total_len - 4].to_vec();
+    Ok(EventMessage { headers, payload })
+}
+
+fn decode_headers(mut data: &[u8]) -> Result<HashMap<String, String>, AgentError> {
+    let truncated = || invalid("truncated header".to_string());
+    let mut headers = HashMap::new();
+
+    while !data.is_empty() {
+        let name_len = data[0] as usize;
+        let name = data.get(1..1 + name_len).ok_or_else(truncated)?;
+        let name = String::from_utf8_lossy(name).to_string();
+        let value_type = *data.get(1 + name_len).ok_or_else(truncated)?;
+        data = &data[2 + name_len..];
+
+        // Fixed-size values are skipped; only strings are kept
+        let value_len = match value_type {
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            9 => 16,
+            6 | 7 => {
+                let len = data.get(..2).ok_or_else(truncated)?;
+                data = &data[2..];
+                u16::from_be_bytes([len[0], len[1]]) as usize
+            }
+            other => return Err(invalid(format!("unknown header type {}", other))),
+        };
+        let value = data.get(..value_len).ok_or_else(truncated)?;
+        if value_type == 7 {
+            headers.insert(name, String::from_utf8_lossy(value).to_string());
+        }
+        data = &data[value_len..];
+    }
+    Ok(headers)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+fn invalid(message: String) -> AgentError {
+    AgentError::InvalidResponse(format!("Malformed event stream: {}", message))
+}
+
+/// Encode a message with string headers (the inverse of the decoder).
+#[cfg(test)]
+pub(crate) fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut encoded_headers = Vec::new();
+    for (name, value) in headers {
+        encoded_headers.push(name.len() as u8);
+        encoded_headers.extend_from_slice(name.as_bytes());
+        encoded_headers.push(7);
+        encoded_headers.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        encoded_headers.extend_from_slice(value.as_bytes());
+    }
+
+    let total_len = FRAME_OVERHEAD + encoded_headers.len() + payload.len();
+    let mut frame = Vec::with_capacity(total_len);
+    frame.extend_from_slice(&(total_len as u32).to_be_bytes());
+    frame.extend_from_slice(&(encoded_headers.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&crc32fast::hash(&frame).to_be_bytes());
+    frame.extend_from_slice(&encoded_headers);
+    frame.extend_from_slice(payload);
+    frame.extend_from_slice(&crc32fast::hash(&frame).to_be_bytes());
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str, payload: &str) -> Vec<u8> {
+        encode_message(
+            &[
+                (":event-type", event_type),
+                (":content-type", "application/json"),
+                (":message-type", "event"),
+            ],
+            payload.as_bytes(),
+        )
+    }
+
+    #[test]
+    fn decodes_complete_frames() {
+        let mut bytes = event("messageStart", r#"{"role":"assistant"}"#);
+        bytes.extend(event("messageStop", r#"{"stopReason":"end_turn"}"#));
+
+        let messages = EventStreamDecoder::new().push(&bytes).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].header(":event-type"), Some("messageStart"));
+        assert_eq!(messages[0].header(":message-type"), Some("event"));
+        assert_eq!(messages[1].payload, br#"{"stopReason":"end_turn"}"#);
+    }
+
+    #[test]
+    fn buffers_frames_split_across_chunks() {
+        let bytes = event("contentBlockDelta", r#"{"delta":{"text":"Hi"}}"#);
+        let mut decoder = EventStreamDecoder::new();
+
+        for byte in &bytes[..bytes.len() - 1] {
+            assert!(decoder.push(std::slice::from_ref(byte)).unwrap().is_empty());
+        }
+        let messages = decoder.push(&bytes[bytes.len() - 1..]).unwrap();
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn skips_non_string_headers() {
+        // :event-type (string), then a 4-byte int header
+        let mut frame = encode_message(&[(":event-type", "metadata")], b"{}");
+        let mut headers = Vec::new();
+        headers.push(5u8);
+        headers.extend_from_slice(b"count");
+        headers.push(4);
+        headers.extend_from_slice(&7u32.to_be_bytes());
+        let headers_len = read_u32(&frame, 4) as usize;
+        frame.splice(12 + headers_len..12 + headers_len, headers.clone());
+        // Rebuild lengths and checksums around the inserted header
+        let total_len = frame.len() as u32;
+        frame[0..4].copy_from_slice(&total_len.to_be_bytes());
+        frame[4..8].copy_from_slice(&((headers_len + headers.len()) as u32).to_be_bytes());
+        let prelude_crc = crc32fast::hash(&frame[..8]);
+        frame[8..12].copy_from_slice(&prelude_crc.to_be_bytes());
+        let end = frame.len() - 4;
+        let message_crc = crc32fast::hash(&frame[..end]);
+        frame[end..].copy_from_slice(&message_crc.to_be_bytes());
+
+        let messages = EventStreamDecoder::new().push(&frame).unwrap();
+        assert_eq!(messages[0].header(":event-type"), Some("metadata"));
+        assert_eq!(messages[0].headers.len(), 1);
+        assert_eq!(messages[0].payload, b"{}");
+    }
+
+    #[test]
+    fn rejects_corrupted_frames() {
+        let mut bytes = event("messageStop", "{}");
+        let last = bytes.len() - 5;
+        bytes[last] ^= 0xff;
+
+        let result = EventStreamDecoder::new().push(&bytes);
+        assert!(matches!(result, Err(AgentError::InvalidResponse(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_length() {
+        let result = EventStreamDecoder::new().push(&[0, 0, 0, 1]);
+        assert!(matches!(result, Err(AgentError::InvalidResponse(_))));
+    }
+}
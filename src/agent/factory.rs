@@ -1,9 +1,10 @@
 //! Agent factory for creating InferenceAgent trait objects from configuration.
 
 use super::{
-    anthropic::AnthropicAgent, generic::GenericOpenAIAgent, google::GoogleAIAgent,
-    lmstudio::LMStudioAgent, ollama::OllamaAgent, openai::OpenAIAgent, AgentError, InferenceAgent,
-    PrivacyZone,
+    anthropic::AnthropicAgent, azure, azure::AzureOpenAIAgent, bedrock::BedrockAgent,
+    cohere::CohereAgent, generic::GenericOpenAIAgent, google::GoogleAIAgent,
+    lmstudio::LMStudioAgent, ollama::OllamaAgent, openai::OpenAIAgent, openai::ProviderProfile,
+    sigv4::AwsCredentials, tei::TEIAgent, AgentError, InferenceAgent, PrivacyZone,
 };
 use crate::registry::BackendType;
 use reqwest::Client;
@@ -90,7 +91,7 @@ pub fn create_agent(
                 ));
             };
 
-            Ok(Arc::new(OpenAIAgent::new(
+            let agent = OpenAIAgent::new(
                 id,
                 name,
                 url,
@@ -98,7 +99,11 @@ pub fn create_agent(
                 client,
                 privacy_zone,
                 capability_tier,
-            )))
+            );
+            Ok(Arc::new(match provider_profile(&metadata) {
+                Some(provider) => agent.with_provider(provider),
+                None => agent,
+            }))
         }
         BackendType::LMStudio => Ok(Arc::new(LMStudioAgent::new(
             id,
@@ -108,17 +113,27 @@ pub fn create_agent(
             privacy_zone,
             capability_tier,
         ))),
-        BackendType::VLLM | BackendType::LlamaCpp | BackendType::Exo | BackendType::Generic => {
-            Ok(Arc::new(GenericOpenAIAgent::new(
-                id,
-                name,
-                backend_type,
-                url,
-                client,
-                privacy_zone,
-                capability_tier,
-            )))
-        }
+        BackendType::VLLM
+        | BackendType::LlamaCpp
+        | BackendType::Exo
+        | BackendType::TGI
+        | BackendType::Generic => Ok(Arc::new(GenericOpenAIAgent::new(
+            id,
+            name,
+            backend_type,
+            url,
+            client,
+            privacy_zone,
+            capability_tier,
+        ))),
+        BackendType::TEI => Ok(Arc::new(TEIAgent::new(
+            id,
+            name,
+            url,
+            client,
+            privacy_zone,
+            capability_tier,
+        ))),
         BackendType::Anthropic => {
             // Extract API key from metadata (T093)
             let api_key = if let Some(key) = metadata.get("api_key") {
@@ -173,9 +188,138 @@ pub fn create_agent(
                 capability_tier,
             )))
         }
+        BackendType::AzureOpenAI => {
+            let api_key = if let Some(key) = metadata.get("api_key") {
+                key.clone()
+            } else if let Some(env_var) = metadata.get("api_key_env") {
+                std::env::var(env_var).map_err(|e| {
+                    AgentError::Configuration(format!(
+                        "Failed to read API key from env var '{}': {}",
+                        env_var, e
+                    ))
+                })?
+            } else {
+                return Err(AgentError::Configuration(
+                    "Azure OpenAI backend requires 'api_key' or 'api_key_env' in metadata"
+                        .to_string(),
+                ));
+            };
+
+            let api_version = metadata
+                .get("api_version")
+                .cloned()
+                .unwrap_or_else(|| azure::DEFAULT_API_VERSION.to_string());
+
+            Ok(Arc::new(AzureOpenAIAgent::new(
+                id,
+                name,
+                url,
+                api_key,
+                api_version,
+                deployments(&metadata),
+                client,
+                privacy_zone,
+                capability_tier,
+            )))
+        }
+        BackendType::Bedrock => {
+            let region = metadata
+                .get("region")
+                .cloned()
+                .or_else(|| BedrockAgent::region_from_url(&url))
+                .or_else(|| std::env::var("AWS_REGION").ok())
+                .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok())
+                .ok_or_else(|| {
+                    AgentError::Configuration(
+                        "Bedrock backend requires 'region' in metadata or AWS_REGION".to_string(),
+                    )
+                })?;
+
+            let env_var = |key: &str, default: &'static str| {
+                metadata
+                    .get(key)
+                    .map(String::as_str)
+                    .unwrap_or(default)
+                    .to_string()
+            };
+            let credentials = AwsCredentials::from_env(
+                &env_var("aws_access_key_id_env", "AWS_ACCESS_KEY_ID"),
+                &env_var("aws_secret_access_key_env", "AWS_SECRET_ACCESS_KEY"),
+                &env_var("aws_session_token_env", "AWS_SESSION_TOKEN"),
+            )
+            .map_err(AgentError::Configuration)?;
+
+            Ok(Arc::new(BedrockAgent::new(
+                id,
+                name,
+                url,
+                region,
+                credentials,
+                deployments(&metadata),
+                client,
+                privacy_zone,
+                capability_tier,
+            )))
+        }
+        BackendType::Cohere => {
+            let api_key = if let Some(key) = metadata.get("api_key") {
+                key.clone()
+            } else if let Some(env_var) = metadata.get("api_key_env") {
+                std::env::var(env_var).map_err(|e| {
+                    AgentError::Configuration(format!(
+                        "Failed to read API key from env var '{}': {}",
+                        env_var, e
+                    ))
+                })?
+            } else {
+                return Err(AgentError::Configuration(
+                    "Cohere backend requires 'api_key' or 'api_key_env' in metadata".to_string(),
+                ));
+            };
+
+            Ok(Arc::new(CohereAgent::new(
+                id,
+                name,
+                url,
+                api_key,
+                client,
+                privacy_zone,
+                capability_tier,
+            )))
+        }
     }
 }
 
+/// Provider profile for OpenAI-compatible clouds, from the "provider",
+/// "auth_header", "auth_scheme" and "models_path" metadata keys.
+fn provider_profile(metadata: &HashMap<String, String>) -> Option<ProviderProfile> {
+    let name = metadata.get("provider")?;
+    let get = |key: &str, default: &str| {
+        metadata
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| default.to_string())
+    };
+    Some(ProviderProfile {
+        name: name.clone(),
+        auth_header: get("auth_header", "authorization"),
+        auth_scheme: get("auth_scheme", "Bearer"),
+        models_path: get("models_path", "/v1/models"),
+    })
+}
+
+/// Model → deployment (or Bedrock model ID) entries, which arrive as
+/// "deployment:<model>" metadata keys.
+fn deployments(metadata: &HashMap<String, String>) -> HashMap<String, String> {
+    metadata
+        .iter()
+        .filter_map(|(key, deployment)| {
+            key.strip_prefix("deployment:")
+                .map(|model| (model.to_string(), deployment.clone()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,6 +483,86 @@ mod tests {
         assert_eq!(agent.profile().backend_type, "exo");
     }
 
+    #[test]
+    fn test_create_tgi_and_tei_agents() {
+        for (backend_type, expected) in [(BackendType::TGI, "tgi"), (BackendType::TEI, "tei")] {
+            let agent = create_agent(
+                "test-22".to_string(),
+                "Test Hugging Face".to_string(),
+                "http://localhost:8080".to_string(),
+                backend_type,
+                test_client(),
+                HashMap::new(),
+                PrivacyZone::Restricted,
+                None,
+            )
+            .unwrap();
+
+            assert_eq!(agent.profile().backend_type, expected);
+            assert_eq!(
+                agent.profile().capabilities.rerank,
+                backend_type == BackendType::TEI
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_openai_agent_with_provider() {
+        let metadata = HashMap::from([
+            ("api_key".to_string(), "gsk-test".to_string()),
+            ("provider".to_string(), "groq".to_string()),
+        ]);
+
+        let agent = create_agent(
+            "test-groq".to_string(),
+            "Test Groq".to_string(),
+            "https://api.groq.com/openai".to_string(),
+            BackendType::OpenAI,
+            test_client(),
+            metadata,
+            PrivacyZone::Open,
+            Some(3),
+        )
+        .unwrap();
+
+        assert_eq!(agent.profile().backend_type, "groq");
+        assert!(!agent.profile().capabilities.token_counting);
+    }
+
+    #[test]
+    fn test_create_cohere_agent() {
+        let metadata = HashMap::from([("api_key".to_string(), "co-test".to_string())]);
+
+        let agent = create_agent(
+            "test-cohere".to_string(),
+            "Test Cohere".to_string(),
+            "https://api.cohere.com".to_string(),
+            BackendType::Cohere,
+            test_client(),
+            metadata,
+            PrivacyZone::Open,
+            Some(4),
+        )
+        .unwrap();
+
+        assert_eq!(agent.profile().backend_type, "cohere");
+        assert!(agent.profile().capabilities.rerank);
+
+        let result = create_agent(
+            "test-cohere".to_string(),
+            "Test Cohere".to_string(),
+            "https://api.cohere.com".to_string(),
+            BackendType::Cohere,
+            test_client(),
+            HashMap::new(),
+            PrivacyZone::Open,
+            None,
+        );
+        assert!(
+            matches!(result, Err(AgentError::Configuration(ref msg)) if msg.contains("api_key"))
+        );
+    }
+
     #[test]
     fn test_create_generic_agent() {
         let agent = create_agent(
@@ -517,4 +741,112 @@ mod tests {
             matches!(result, Err(AgentError::Configuration(ref msg)) if msg.contains("api_key"))
         );
     }
+    #[tokio::test]
+    async fn test_create_azure_openai_agent_with_deployments() {
+        let mut metadata = HashMap::new();
+        metadata.insert("api_key".to_string(), "azure-key".to_string());
+        metadata.insert("api_version".to_string(), "2024-06-01".to_string());
+        metadata.insert("deployment:gpt-4o".to_string(), "prod-gpt4o".to_string());
+
+        let agent = create_agent(
+            "test-18".to_string(),
+            "Test Azure".to_string(),
+            "https://example.openai.azure.com".to_string(),
+            BackendType::AzureOpenAI,
+            test_client(),
+            metadata,
+            PrivacyZone::Open,
+            Some(4),
+        )
+        .unwrap();
+
+        let profile = agent.profile();
+        assert_eq!(profile.backend_type, "azure_openai");
+        assert_eq!(profile.version.as_deref(), Some("2024-06-01"));
+
+        let models = agent.list_models().await.unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "gpt-4o");
+    }
+
+    #[test]
+    fn test_create_azure_openai_agent_missing_key() {
+        let result = create_agent(
+            "test-19".to_string(),
+            "Test Azure No Key".to_string(),
+            "https://example.openai.azure.com".to_string(),
+            BackendType::AzureOpenAI,
+            test_client(),
+            HashMap::new(),
+            PrivacyZone::Open,
+            None,
+        );
+
+        assert!(
+            matches!(result, Err(AgentError::Configuration(ref msg)) if msg.contains("api_key"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_bedrock_agent_from_env_credentials() {
+        std::env::set_var("TEST_BEDROCK_AKID", "AKIDEXAMPLE");
+        std::env::set_var("TEST_BEDROCK_SECRET", "secret");
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "aws_access_key_id_env".to_string(),
+            "TEST_BEDROCK_AKID".to_string(),
+        );
+        metadata.insert(
+            "aws_secret_access_key_env".to_string(),
+            "TEST_BEDROCK_SECRET".to_string(),
+        );
+        metadata.insert(
+            "deployment:claude-3-haiku".to_string(),
+            "anthropic.claude-3-haiku-20240307-v1:0".to_string(),
+        );
+
+        let agent = create_agent(
+            "test-20".to_string(),
+            "Test Bedrock".to_string(),
+            "https://bedrock-runtime.eu-central-1.amazonaws.com".to_string(),
+            BackendType::Bedrock,
+            test_client(),
+            metadata,
+            PrivacyZone::Open,
+            Some(4),
+        )
+        .unwrap();
+
+        assert_eq!(agent.profile().backend_type, "bedrock");
+        let models = agent.list_models().await.unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "claude-3-haiku");
+        assert_eq!(models[0].context_length, 200000);
+    }
+
+    #[test]
+    fn test_create_bedrock_agent_missing_credentials() {
+        let mut metadata = HashMap::new();
+        metadata.insert("region".to_string(), "us-east-1".to_string());
+        metadata.insert(
+            "aws_access_key_id_env".to_string(),
+            "NONEXISTENT_BEDROCK_AKID".to_string(),
+        );
+
+        let result = create_agent(
+            "test-21".to_string(),
+            "Test Bedrock No Creds".to_string(),
+            "https://bedrock-runtime.us-east-1.amazonaws.com".to_string(),
+            BackendType::Bedrock,
+            test_client(),
+            metadata,
+            PrivacyZone::Open,
+            None,
+        );
+
+        assert!(
+            matches!(result, Err(AgentError::Configuration(ref msg)) if msg.contains("NONEXISTENT_BEDROCK_AKID"))
+        );
+    }
 }
@@ -1,11 +1,13 @@
 //! Generic OpenAI-compatible agent implementation.
 //!
-//! Handles VLLM, LlamaCpp, Exo, and Generic backend types that provide
-//! OpenAI-compatible APIs at /v1/models and /v1/chat/completions.
+//! Handles VLLM, LlamaCpp, Exo, TGI and Generic backend types that provide
+//! OpenAI-compatible APIs at /v1/chat/completions.
 
+use super::embeddings::is_embedding_model;
+use super::tokenize::{BackendTokenCounter, TokenizeApi};
 use super::{
     AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
-    PrivacyZone, StreamChunk,
+    PrivacyZone, ResourceUsage, StreamChunk, TokenCount,
 };
 use crate::api::types::{ChatCompletionRequest, ChatCompletionResponse};
 use crate::registry::BackendType;
@@ -14,6 +16,7 @@ use axum::http::HeaderMap;
 use futures_util::stream::BoxStream;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -23,6 +26,7 @@ use std::time::Duration;
 /// - VLLM
 /// - LlamaCpp
 /// - Exo
+/// - TGI (health via /health, model metadata via /info)
 /// - Generic
 pub struct GenericOpenAIAgent {
     /// Unique agent ID
@@ -39,6 +43,8 @@ pub struct GenericOpenAIAgent {
     privacy_zone: PrivacyZone,
     /// Capability tier from config
     capability_tier: Option<u8>,
+    /// Exact token counting via the backend's tokenize endpoint, if it has one
+    token_counter: Option<BackendTokenCounter>,
 }
 
 impl GenericOpenAIAgent {
@@ -59,6 +65,12 @@ impl GenericOpenAIAgent {
             client,
             privacy_zone,
             capability_tier,
+            token_counter: match backend_type {
+                BackendType::VLLM => Some(BackendTokenCounter::new(TokenizeApi::Vllm)),
+                BackendType::LlamaCpp => Some(BackendTokenCounter::new(TokenizeApi::LlamaCpp)),
+                BackendType::TGI => Some(BackendTokenCounter::new(TokenizeApi::Tgi)),
+                _ => None,
+            },
         }
     }
 }
@@ -89,6 +101,7 @@ impl InferenceAgent for GenericOpenAIAgent {
             BackendType::VLLM => "vllm",
             BackendType::LlamaCpp => "llamacpp",
             BackendType::Exo => "exo",
+            BackendType::TGI => "tgi",
             BackendType::Generic => "generic",
             _ => "generic", // Fallback
         };
@@ -98,16 +111,25 @@ impl InferenceAgent for GenericOpenAIAgent {
             version: None,
             privacy_zone: self.privacy_zone,
             capabilities: AgentCapabilities {
-                embeddings: false,
+                // TGI only serves generation models
+                embeddings: self.backend_type != BackendType::TGI,
+                rerank: false,
                 model_lifecycle: false,
-                token_counting: false,
-                resource_monitoring: false,
+                token_counting: self.token_counter.is_some(),
+                resource_monitoring: matches!(
+                    self.backend_type,
+                    BackendType::VLLM | BackendType::LlamaCpp | BackendType::TGI
+                ),
             },
             capability_tier: self.capability_tier,
         }
     }
 
     async fn health_check(&self) -> Result<HealthStatus, AgentError> {
+        if self.backend_type == BackendType::TGI {
+            return self.tgi_health_check().await;
+        }
+
         let url = format!("{}/v1/models", self.base_url);
 
         let response = self
@@ -142,6 +164,13 @@ impl InferenceAgent for GenericOpenAIAgent {
     }
 
     async fn list_models(&self) -> Result<Vec<ModelCapability>, AgentError> {
+        if self.backend_type == BackendType::TGI {
+            let body = get_info(&self.client, &self.base_url).await?;
+            let models = crate::health::parser::parse_tgi_info_response(&body)
+                .map_err(|e| AgentError::InvalidResponse(e.to_string()))?;
+            return Ok(models.into_iter().map(ModelCapability::from).collect());
+        }
+
         let url = format!("{}/v1/models", self.base_url);
 
         let response = self
@@ -184,6 +213,7 @@ impl InferenceAgent for GenericOpenAIAgent {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                     capability_tier: None,
                 };
@@ -289,19 +319,166 @@ impl InferenceAgent for GenericOpenAIAgent {
             result
                 .map(|bytes| StreamChunk {
                     data: String::from_utf8_lossy(&bytes).to_string(),
+                    timings: None,
                 })
                 .map_err(|e| AgentError::Network(e.to_string()))
         });
 
         Ok(Box::pin(stream))
     }
+
+    /// Count tokens with the backend's tokenizer (vLLM and llama.cpp), falling
+    /// back to the chars/4 heuristic.
+    async fn count_tokens(&self, model_id: &str, text: &str) -> TokenCount {
+        match &self.token_counter {
+            Some(counter) => {
+                counter
+                    .count(&self.client, &self.base_url, model_id, text)
+                    .await
+            }
+            None => TokenCount::Heuristic((text.len() / 4) as u32),
+        }
+    }
+
+    /// Generate embeddings via the OpenAI-compatible POST /v1/embeddings endpoint.
+    async fn embeddings(
+        &self,
+        model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, AgentError> {
+        super::embeddings::openai_embeddings(&self.client, &self.base_url, None, model, input).await
+    }
+
+    /// Scrape queue depth and KV cache usage from vLLM's, llama.cpp's or
+    /// TGI's Prometheus `/metrics` endpoint, or llama.cpp's `/slots` when
+    /// metrics are disabled (`--metrics` not set).
+    async fn resource_usage(&self) -> ResourceUsage {
+        match self.backend_type {
+            BackendType::VLLM => {
+                let Some(metrics) = self.scrape_metrics().await else {
+                    return ResourceUsage::default();
+                };
+                let running = metrics.get("vllm:num_requests_running");
+                let waiting = metrics.get("vllm:num_requests_waiting");
+                ResourceUsage {
+                    pending_requests: queue_depth(running, waiting),
+                    // Renamed to kv_cache_usage_perc in vLLM V1
+                    kv_cache_usage: metrics
+                        .get("vllm:gpu_cache_usage_perc")
+                        .or_else(|| metrics.get("vllm:kv_cache_usage_perc"))
+                        .copied(),
+                    ..Default::default()
+                }
+            }
+            BackendType::LlamaCpp => {
+                if let Some(metrics) = self.scrape_metrics().await {
+                    let processing = metrics.get("llamacpp:requests_processing");
+                    let deferred = metrics.get("llamacpp:requests_deferred");
+                    return ResourceUsage {
+                        pending_requests: queue_depth(processing, deferred),
+                        kv_cache_usage: metrics.get("llamacpp:kv_cache_usage_ratio").copied(),
+                        ..Default::default()
+                    };
+                }
+                ResourceUsage {
+                    pending_requests: self.busy_slots().await,
+                    ..Default::default()
+                }
+            }
+            BackendType::TGI => {
+                let Some(metrics) = self.scrape_metrics().await else {
+                    return ResourceUsage::default();
+                };
+                let batched = metrics.get("tgi_batch_current_size");
+                let queued = metrics.get("tgi_queue_size");
+                ResourceUsage {
+                    pending_requests: queue_depth(batched, queued),
+                    ..Default::default()
+                }
+            }
+            _ => ResourceUsage::default(),
+        }
+    }
 }
 
 impl GenericOpenAIAgent {
+    /// TGI answers GET /health with an empty 200 once the model is loaded
+    /// and 503 while it is still starting.
+    async fn tgi_health_check(&self) -> Result<HealthStatus, AgentError> {
+        let url = format!("{}/health", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(5000)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        if !response.status().is_success() {
+            return Ok(HealthStatus::Unhealthy);
+        }
+
+        // A TGI server runs exactly one model
+        Ok(HealthStatus::Healthy { model_count: 1 })
+    }
+
+    /// Fetch and parse the Prometheus `/metrics` endpoint, if exposed.
+    async fn scrape_metrics(&self) -> Option<HashMap<String, f64>> {
+        let url = format!("{}/metrics", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let body = response.text().await.ok()?;
+        Some(parse_prometheus(&body))
+    }
+
+    /// Count busy slots from llama.cpp's `/slots` endpoint.
+    async fn busy_slots(&self) -> Option<u32> {
+        let url = format!("{}/slots", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let slots: Vec<serde_json::Value> = response.json().await.ok()?;
+        // Newer servers report `is_processing`, older ones `state` (0 = idle)
+        let busy = slots
+            .iter()
+            .filter(|slot| {
+                slot["is_processing"]
+                    .as_bool()
+                    .unwrap_or_else(|| slot["state"].as_u64().is_some_and(|state| state != 0))
+            })
+            .count();
+        Some(busy as u32)
+    }
+
     /// Apply name-based heuristics for capability detection.
     fn apply_name_heuristics(model: &mut ModelCapability) {
         let name = model.id.to_lowercase();
 
+        // Embedding model heuristics
+        model.supports_embeddings |= is_embedding_model(&name);
+
         // Vision support heuristics
         if name.contains("vision") || name.contains("llava") || name.contains("bakllava") {
             model.supports_vision = true;
@@ -332,6 +509,76 @@ impl GenericOpenAIAgent {
     }
 }
 
+/// Sum Prometheus text-format samples by metric name, across label sets
+/// (vLLM labels each series with `model_name`).
+fn parse_prometheus(body: &str) -> HashMap<String, f64> {
+    let mut metrics = HashMap::new();
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (series, rest) = match line.find('{') {
+            Some(brace) => match line[brace..].find('}') {
+                Some(end) => (&line[..brace], &line[brace + end + 1..]),
+                None => continue,
+            },
+            None => match line.split_once(char::is_whitespace) {
+                Some((name, rest)) => (name, rest),
+                None => continue,
+            },
+        };
+        let Some(value) = rest
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<f64>().ok())
+        else {
+            continue;
+        };
+        *metrics.entry(series.to_string()).or_insert(0.0) += value;
+    }
+    metrics
+}
+
+/// Fetch the `/info` document served by TGI and TEI.
+pub(crate) async fn get_info(client: &Client, base_url: &str) -> Result<String, AgentError> {
+    let url = format!("{}/info", base_url);
+
+    let response = client
+        .get(&url)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .map_err(|e| {
+            if e.is_timeout() {
+                AgentError::Timeout(5000)
+            } else {
+                AgentError::Network(e.to_string())
+            }
+        })?;
+
+    if !response.status().is_success() {
+        return Err(AgentError::Upstream {
+            status: response.status().as_u16(),
+            message: format!("Failed to fetch model info: {}", response.status()),
+        });
+    }
+
+    response
+        .text()
+        .await
+        .map_err(|e| AgentError::InvalidResponse(format!("Failed to read response body: {}", e)))
+}
+
+/// Running + waiting requests, if the backend reported either.
+fn queue_depth(running: Option<&f64>, waiting: Option<&f64>) -> Option<u32> {
+    if running.is_none() && waiting.is_none() {
+        return None;
+    }
+    let total = running.copied().unwrap_or(0.0) + waiting.copied().unwrap_or(0.0);
+    Some(total.max(0.0).round() as u32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,6 +604,8 @@ mod tests {
 
         assert_eq!(profile.backend_type, "vllm");
         assert_eq!(profile.privacy_zone, PrivacyZone::Restricted);
+        assert!(profile.capabilities.token_counting);
+        assert!(profile.capabilities.resource_monitoring);
     }
 
     #[tokio::test]
@@ -365,6 +614,61 @@ mod tests {
         let profile = agent.profile();
 
         assert_eq!(profile.backend_type, "llamacpp");
+        assert!(profile.capabilities.token_counting);
+        assert!(profile.capabilities.resource_monitoring);
+    }
+
+    #[tokio::test]
+    async fn test_tgi_profile() {
+        let agent = test_agent("http://localhost:8080".to_string(), BackendType::TGI);
+        let profile = agent.profile();
+
+        assert_eq!(profile.backend_type, "tgi");
+        assert!(!profile.capabilities.embeddings);
+        assert!(profile.capabilities.token_counting);
+        assert!(profile.capabilities.resource_monitoring);
+    }
+
+    #[tokio::test]
+    async fn test_tgi_health_and_info() {
+        let mut server = Server::new_async().await;
+        let health = server
+            .mock("GET", "/health")
+            .with_status(200)
+            .create_async()
+            .await;
+        let info = server
+            .mock("GET", "/info")
+            .with_status(200)
+            .with_body(
+                r#"{"model_id":"meta-llama/Llama-3.1-8B-Instruct","max_input_tokens":8191,"max_total_tokens":8192,"version":"2.4.0"}"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::TGI);
+        let status = agent.health_check().await.unwrap();
+        let models = agent.list_models().await.unwrap();
+
+        health.assert_async().await;
+        info.assert_async().await;
+        assert_eq!(status, HealthStatus::Healthy { model_count: 1 });
+        assert_eq!(models[0].id, "meta-llama/Llama-3.1-8B-Instruct");
+        assert_eq!(models[0].context_length, 8191);
+        assert!(models[0].supports_tools);
+    }
+
+    #[tokio::test]
+    async fn test_tgi_health_while_loading() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/health")
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::TGI);
+        assert_eq!(agent.health_check().await.unwrap(), HealthStatus::Unhealthy);
     }
 
     #[tokio::test]
@@ -373,6 +677,7 @@ mod tests {
         let profile = agent.profile();
 
         assert_eq!(profile.backend_type, "exo");
+        assert!(!profile.capabilities.token_counting);
     }
 
     #[tokio::test]
@@ -475,6 +780,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -491,6 +797,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -508,6 +815,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -524,6 +832,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -540,6 +849,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -558,6 +868,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -574,6 +885,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -592,6 +904,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -608,6 +921,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -626,6 +940,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -965,6 +1280,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -981,6 +1297,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -997,6 +1314,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1013,6 +1331,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1029,6 +1348,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1046,6 +1366,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1062,6 +1383,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1093,6 +1415,96 @@ mod tests {
         assert_eq!(profile.privacy_zone, PrivacyZone::Open);
     }
 
+    #[tokio::test]
+    async fn test_list_models_detects_embedding_models() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/models")
+            .with_status(200)
+            .with_body(r#"{"data":[{"id":"BAAI/bge-large-en-v1.5"},{"id":"llama-2-7b-chat"}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::VLLM);
+        let models = agent.list_models().await.unwrap();
+
+        mock.assert_async().await;
+        assert!(models[0].supports_embeddings);
+        assert!(!models[1].supports_embeddings);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/embeddings")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "model": "bge-small",
+                "input": ["hello", "world"]
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"data":[{"index":0,"embedding":[0.1,0.2]},{"index":1,"embedding":[0.3,0.4]}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::LlamaCpp);
+        assert!(agent.profile().capabilities.embeddings);
+        let vectors = agent
+            .embeddings("bge-small", vec!["hello".to_string(), "world".to_string()])
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors.len(), 2);
+        assert!((vectors[1][0] - 0.3).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_via_llamacpp_tokenize() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/tokenize")
+            .with_status(200)
+            .with_body(r#"{"tokens":[9906,1917,0]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::LlamaCpp);
+        let count = agent.count_tokens("llama3", "Hello world!").await;
+
+        mock.assert_async().await;
+        assert_eq!(count, TokenCount::Exact(3));
+    }
+
+    #[tokio::test]
+    async fn test_count_tokens_heuristic_without_tokenizer() {
+        let agent = test_agent("http://127.0.0.1:1".to_string(), BackendType::Generic);
+        let count = agent.count_tokens("model", "12345678").await;
+        assert_eq!(count, TokenCount::Heuristic(2));
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_upstream_error() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/embeddings")
+            .with_status(400)
+            .with_body("model does not support embeddings")
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::VLLM);
+        let err = agent
+            .embeddings("llama-2-7b-chat", vec!["hello".to_string()])
+            .await
+            .unwrap_err();
+
+        mock.assert_async().await;
+        assert!(matches!(err, AgentError::Upstream { status: 400, .. }));
+    }
+
     #[tokio::test]
     async fn test_list_models_upstream_error() {
         let mut server = Server::new_async().await;
@@ -1285,4 +1697,120 @@ mod tests {
         mock.assert_async().await;
         assert!(matches!(result, Err(AgentError::InvalidResponse(_))));
     }
+
+    #[test]
+    fn test_parse_prometheus_sums_label_sets() {
+        let body = "\
+# HELP vllm:num_requests_running Number of requests currently running on GPU.
+# TYPE vllm:num_requests_running gauge
+vllm:num_requests_running{model_name=\"a\"} 2.0
+vllm:num_requests_running{model_name=\"b\"} 1.0
+vllm:gpu_cache_usage_perc{model_name=\"a\"} 0.25
+llamacpp:requests_deferred 3 1712345678
+";
+        let metrics = parse_prometheus(body);
+        assert_eq!(metrics["vllm:num_requests_running"], 3.0);
+        assert_eq!(metrics["vllm:gpu_cache_usage_perc"], 0.25);
+        assert_eq!(metrics["llamacpp:requests_deferred"], 3.0);
+        assert!(!metrics.contains_key("# HELP"));
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_vllm_metrics() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/metrics")
+            .with_status(200)
+            .with_body(
+                "vllm:num_requests_running{model_name=\"llama3\"} 4.0\n\
+                 vllm:num_requests_waiting{model_name=\"llama3\"} 6.0\n\
+                 vllm:gpu_cache_usage_perc{model_name=\"llama3\"} 0.82\n",
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::VLLM);
+        let usage = agent.resource_usage().await;
+
+        mock.assert_async().await;
+        assert_eq!(usage.pending_requests, Some(10));
+        assert_eq!(usage.kv_cache_usage, Some(0.82));
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_tgi_metrics() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/metrics")
+            .with_status(200)
+            .with_body(
+                "# TYPE tgi_batch_current_size gauge\n\
+                 tgi_batch_current_size 3\n\
+                 tgi_queue_size 5\n",
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::TGI);
+        let usage = agent.resource_usage().await;
+
+        assert_eq!(usage.pending_requests, Some(8));
+        assert_eq!(usage.kv_cache_usage, None);
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_llamacpp_metrics() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/metrics")
+            .with_status(200)
+            .with_body(
+                "llamacpp:requests_processing 1\n\
+                 llamacpp:requests_deferred 2\n\
+                 llamacpp:kv_cache_usage_ratio 0.5\n",
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::LlamaCpp);
+        let usage = agent.resource_usage().await;
+
+        assert_eq!(usage.pending_requests, Some(3));
+        assert_eq!(usage.kv_cache_usage, Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_llamacpp_slots_fallback() {
+        let mut server = Server::new_async().await;
+        let _metrics = server
+            .mock("GET", "/metrics")
+            .with_status(501)
+            .create_async()
+            .await;
+        let _slots = server
+            .mock("GET", "/slots")
+            .with_status(200)
+            .with_body(
+                r#"[{"id":0,"is_processing":true},{"id":1,"is_processing":false},{"id":2,"state":1}]"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), BackendType::LlamaCpp);
+        let usage = agent.resource_usage().await;
+
+        assert_eq!(usage.pending_requests, Some(2));
+        assert_eq!(usage.kv_cache_usage, None);
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_unavailable() {
+        let server = Server::new_async().await;
+        let agent = test_agent(server.url(), BackendType::LlamaCpp);
+        assert_eq!(agent.resource_usage().await, ResourceUsage::default());
+
+        let agent = test_agent(server.url(), BackendType::Generic);
+        assert!(!agent.profile().capabilities.resource_monitoring);
+        assert_eq!(agent.resource_usage().await, ResourceUsage::default());
+    }
 }
@@ -1,5 +1,6 @@
 //! Google AI agent implementation.
 
+use super::embeddings::is_embedding_model;
 use super::{
     AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
     PrivacyZone, StreamChunk, TokenCount,
@@ -188,11 +189,14 @@ impl GoogleAIAgent {
                     content: MessageContent::Text { content },
                     name: None,
                     function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
                 finish_reason: Some(finish_reason),
             }],
             usage,
             extra: std::collections::HashMap::new(),
+            timings: None,
         }
     }
 
@@ -353,6 +357,7 @@ impl InferenceAgent for GoogleAIAgent {
             privacy_zone: self.privacy_zone,
             capabilities: AgentCapabilities {
                 embeddings: true, // Google supports embeddings
+                rerank: false,
                 model_lifecycle: false,
                 token_counting: false,
                 resource_monitoring: false,
@@ -456,7 +461,8 @@ impl InferenceAgent for GoogleAIAgent {
             .into_iter()
             .filter(|m| {
                 m.supported_generation_methods
-                    .contains(&"generateContent".to_string())
+                    .iter()
+                    .any(|method| method == "generateContent" || method == "embedContent")
             })
             .map(|m| {
                 // Extract model ID from name (e.g., "models/gemini-1.5-pro" -> "gemini-1.5-pro")
@@ -473,6 +479,10 @@ impl InferenceAgent for GoogleAIAgent {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: m
+                        .supported_generation_methods
+                        .iter()
+                        .any(|method| method == "embedContent"),
                     max_output_tokens: None,
                     capability_tier: None,
                 };
@@ -670,6 +680,7 @@ impl InferenceAgent for GoogleAIAgent {
                     if data == "[DONE]" {
                         return StreamChunk {
                             data: "data: [DONE]\n\n".to_string(),
+                            timings: None,
                         };
                     }
 
@@ -690,7 +701,10 @@ impl InferenceAgent for GoogleAIAgent {
                         .translate_stream_chunk(data, &model_clone)
                         .unwrap_or_else(|| text.clone());
 
-                    StreamChunk { data: translated }
+                    StreamChunk {
+                        data: translated,
+                        timings: None,
+                    }
                 })
                 .map_err(|e| AgentError::Network(e.to_string()))
         });
@@ -702,6 +716,105 @@ impl InferenceAgent for GoogleAIAgent {
         // Google doesn't provide a public tokenizer, use heuristic
         TokenCount::Heuristic((text.len() / 4) as u32)
     }
+
+    /// Generate embeddings via `embedContent` for a single input or
+    /// `batchEmbedContents` for several.
+    async fn embeddings(
+        &self,
+        model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, AgentError> {
+        let content = |text: &str| serde_json::json!({"parts": [{"text": text}]});
+        let single = input.len() == 1;
+        let (method, body) = if single {
+            (
+                "embedContent",
+                serde_json::json!({"content": content(&input[0])}),
+            )
+        } else {
+            let requests: Vec<_> = input
+                .iter()
+                .map(|text| {
+                    serde_json::json!({
+                        "model": format!("models/{}", model),
+                        "content": content(text),
+                    })
+                })
+                .collect();
+            (
+                "batchEmbedContents",
+                serde_json::json!({"requests": requests}),
+            )
+        };
+        let url = format!(
+            "{}/v1beta/models/{}:{}?key={}",
+            self.base_url, model, method, self.api_key
+        );
+
+        let response = self
+            .client
+            .post(&url)
+            .header("content-type", "application/json")
+            .json(&body)
+            .timeout(Duration::from_secs(60))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(60000)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status: status.as_u16(),
+                message: error_body,
+            });
+        }
+
+        let body: serde_json::Value = response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!(
+                "Failed to parse Google embeddings response: {}",
+                e
+            ))
+        })?;
+
+        let embeddings = if single {
+            vec![&body["embedding"]]
+        } else {
+            body["embeddings"]
+                .as_array()
+                .ok_or_else(|| {
+                    AgentError::InvalidResponse(
+                        "Missing embeddings array in Google embeddings response".to_string(),
+                    )
+                })?
+                .iter()
+                .collect()
+        };
+
+        embeddings
+            .into_iter()
+            .map(|embedding| {
+                let values = embedding["values"].as_array().ok_or_else(|| {
+                    AgentError::InvalidResponse(
+                        "Missing values array in Google embedding".to_string(),
+                    )
+                })?;
+                Ok(values
+                    .iter()
+                    .filter_map(|v| v.as_f64().map(|f| f as f32))
+                    .collect())
+            })
+            .collect()
+    }
 }
 
 impl GoogleAIAgent {
@@ -709,6 +822,9 @@ impl GoogleAIAgent {
     fn apply_name_heuristics(model: &mut ModelCapability) {
         let name = model.id.to_lowercase();
 
+        // Embedding model heuristics
+        model.supports_embeddings |= is_embedding_model(&name);
+
         // Gemini 1.5 models
         if name.contains("gemini-1.5-pro") || name.contains("gemini-1.5-flash") {
             model.supports_vision = true;
@@ -815,6 +931,8 @@ mod tests {
                     },
                     name: None,
                     function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
                 ChatMessage {
                     role: "user".to_string(),
@@ -823,6 +941,8 @@ mod tests {
                     },
                     name: None,
                     function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
                 },
             ],
             stream: false,
@@ -890,6 +1010,8 @@ mod tests {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }
     }
 
@@ -1523,6 +1645,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1544,6 +1667,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1565,6 +1689,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1587,6 +1712,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1607,6 +1733,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1726,6 +1853,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -1765,6 +1894,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -1813,6 +1944,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -2048,4 +2181,103 @@ mod tests {
             other => panic!("Expected Upstream error, got: {:?}", other),
         }
     }
+
+    #[tokio::test]
+    async fn test_list_models_includes_embedding_models() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1beta/models?key=test-key")
+            .with_status(200)
+            .with_body(r#"{"models":[{"name":"models/gemini-1.5-flash","supportedGenerationMethods":["generateContent"]},{"name":"models/text-embedding-004","supportedGenerationMethods":["embedContent"]},{"name":"models/aqa","supportedGenerationMethods":["generateAnswer"]}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), "test-key".to_string());
+        let models = agent.list_models().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(models.len(), 2);
+        assert!(!models[0].supports_embeddings);
+        assert_eq!(models[1].id, "text-embedding-004");
+        assert!(models[1].supports_embeddings);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_single_uses_embed_content() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/v1beta/models/text-embedding-004:embedContent?key=test-key",
+            )
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "content": {"parts": [{"text": "hello"}]}
+            })))
+            .with_status(200)
+            .with_body(r#"{"embedding":{"values":[0.1,0.2,0.3]}}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), "test-key".to_string());
+        let vectors = agent
+            .embeddings("text-embedding-004", vec!["hello".to_string()])
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors.len(), 1);
+        assert_eq!(vectors[0].len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_batch_uses_batch_embed_contents() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/v1beta/models/text-embedding-004:batchEmbedContents?key=test-key",
+            )
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "requests": [
+                    {"model": "models/text-embedding-004", "content": {"parts": [{"text": "a"}]}},
+                    {"model": "models/text-embedding-004", "content": {"parts": [{"text": "b"}]}}
+                ]
+            })))
+            .with_status(200)
+            .with_body(r#"{"embeddings":[{"values":[1.0]},{"values":[2.0]}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), "test-key".to_string());
+        let vectors = agent
+            .embeddings("text-embedding-004", vec!["a".to_string(), "b".to_string()])
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors, vec![vec![1.0], vec![2.0]]);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_upstream_error() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/v1beta/models/gemini-1.5-pro:embedContent?key=test-key",
+            )
+            .with_status(400)
+            .with_body(r#"{"error":{"message":"not supported"}}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url(), "test-key".to_string());
+        let err = agent
+            .embeddings("gemini-1.5-pro", vec!["hello".to_string()])
+            .await
+            .unwrap_err();
+
+        mock.assert_async().await;
+        assert!(matches!(err, AgentError::Upstream { status: 400, .. }));
+    }
 }
@@ -1,8 +1,13 @@
 //! LM Studio agent implementation.
+//!
+//! Inference goes through the OpenAI-compatible `/v1` API. Model lifecycle
+//! uses LM Studio's native REST API: `/api/v1/models/load` and `/unload` to
+//! manage models, and `/api/v0/models` to see which ones are loaded.
 
+use super::embeddings::is_embedding_model;
 use super::{
     AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
-    PrivacyZone, StreamChunk,
+    PrivacyZone, ResourceUsage, StreamChunk,
 };
 use crate::api::types::{ChatCompletionRequest, ChatCompletionResponse};
 use async_trait::async_trait;
@@ -79,10 +84,11 @@ impl InferenceAgent for LMStudioAgent {
             version: None,
             privacy_zone: self.privacy_zone,
             capabilities: AgentCapabilities {
-                embeddings: false,
-                model_lifecycle: false,
+                embeddings: true,
+                rerank: false,
+                model_lifecycle: true,
                 token_counting: false,
-                resource_monitoring: false,
+                resource_monitoring: true,
             },
             capability_tier: self.capability_tier,
         }
@@ -165,6 +171,7 @@ impl InferenceAgent for LMStudioAgent {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                     capability_tier: None,
                 };
@@ -270,19 +277,136 @@ impl InferenceAgent for LMStudioAgent {
             result
                 .map(|bytes| StreamChunk {
                     data: String::from_utf8_lossy(&bytes).to_string(),
+                    timings: None,
                 })
                 .map_err(|e| AgentError::Network(e.to_string()))
         });
 
         Ok(Box::pin(stream))
     }
+
+    /// Generate embeddings via the OpenAI-compatible POST /v1/embeddings endpoint.
+    async fn embeddings(
+        &self,
+        model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, AgentError> {
+        super::embeddings::openai_embeddings(&self.client, &self.base_url, None, model, input).await
+    }
+
+    /// Load a model via LM Studio's POST /api/v1/models/load endpoint.
+    ///
+    /// LM Studio answers once the model is in memory, so this waits for the
+    /// load to finish (up to two minutes).
+    async fn load_model(&self, model_id: &str) -> Result<(), AgentError> {
+        self.post_lifecycle(
+            "/api/v1/models/load",
+            serde_json::json!({ "model": model_id }),
+            Duration::from_secs(120),
+        )
+        .await
+    }
+
+    /// Unload a model via LM Studio's POST /api/v1/models/unload endpoint.
+    ///
+    /// Models loaded without an explicit identifier use the model key as
+    /// their instance ID.
+    async fn unload_model(&self, model_id: &str) -> Result<(), AgentError> {
+        self.post_lifecycle(
+            "/api/v1/models/unload",
+            serde_json::json!({ "instance_id": model_id }),
+            Duration::from_secs(10),
+        )
+        .await
+    }
+
+    /// Query loaded models via LM Studio's GET /api/v0/models endpoint.
+    ///
+    /// LM Studio doesn't report VRAM, so only `loaded_models` is populated.
+    async fn resource_usage(&self) -> ResourceUsage {
+        let url = format!("{}/api/v0/models", self.base_url);
+
+        let response = match self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+        {
+            Ok(resp) if resp.status().is_success() => resp,
+            _ => return ResourceUsage::default(),
+        };
+
+        let body: serde_json::Value = match response.json().await {
+            Ok(b) => b,
+            Err(_) => return ResourceUsage::default(),
+        };
+
+        let loaded_models = body["data"]
+            .as_array()
+            .map(|models| {
+                models
+                    .iter()
+                    .filter(|m| m["state"].as_str() == Some("loaded"))
+                    .filter_map(|m| m["id"].as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ResourceUsage {
+            loaded_models,
+            ..Default::default()
+        }
+    }
 }
 
 impl LMStudioAgent {
+    /// POST a lifecycle request, mapping transport and HTTP errors.
+    async fn post_lifecycle(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), AgentError> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&body)
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(timeout.as_millis() as u64)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status,
+                message: error_body,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Apply name-based heuristics for capability detection.
     fn apply_name_heuristics(model: &mut ModelCapability) {
         let name = model.id.to_lowercase();
 
+        // Embedding model heuristics
+        model.supports_embeddings |= is_embedding_model(&name);
+
         // Vision support heuristics
         if name.contains("vision") || name.contains("llava") || name.contains("bakllava") {
             model.supports_vision = true;
@@ -424,6 +548,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -440,6 +565,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -457,6 +583,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -469,8 +596,9 @@ mod tests {
         // Sync test for profile
         let agent = test_agent("http://localhost:1234".to_string());
         let profile = agent.profile();
-        assert!(!profile.capabilities.embeddings);
-        assert!(!profile.capabilities.model_lifecycle);
+        assert!(profile.capabilities.embeddings);
+        assert!(profile.capabilities.model_lifecycle);
+        assert!(profile.capabilities.resource_monitoring);
     }
 
     #[test]
@@ -482,6 +610,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -498,6 +627,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -514,6 +644,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -530,6 +661,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -548,6 +680,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -818,6 +951,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -834,6 +968,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -850,6 +985,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -866,6 +1002,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -883,6 +1020,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -962,4 +1100,128 @@ mod tests {
         mock.assert_async().await;
         assert_eq!(status, HealthStatus::Unhealthy);
     }
+
+    #[tokio::test]
+    async fn test_embeddings() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/embeddings")
+            .with_status(200)
+            .with_body(r#"{"object":"list","data":[{"object":"embedding","embedding":[0.5,0.25],"index":0}]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let vectors = agent
+            .embeddings(
+                "text-embedding-nomic-embed-text-v1.5",
+                vec!["hello".to_string()],
+            )
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors, vec![vec![0.5, 0.25]]);
+    }
+
+    #[test]
+    fn test_name_heuristics_embedding_model() {
+        let mut model = ModelCapability {
+            id: "text-embedding-nomic-embed-text-v1.5".to_string(),
+            name: "nomic".to_string(),
+            context_length: 4096,
+            supports_vision: false,
+            supports_tools: false,
+            supports_json_mode: false,
+            supports_embeddings: false,
+            max_output_tokens: None,
+            capability_tier: None,
+        };
+        LMStudioAgent::apply_name_heuristics(&mut model);
+        assert!(model.supports_embeddings);
+        assert!(!model.supports_tools);
+    }
+
+    #[tokio::test]
+    async fn test_load_model() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/models/load")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"model": "qwen2.5-7b-instruct"}),
+            ))
+            .with_status(200)
+            .with_body(r#"{"type":"llm","instance_id":"qwen2.5-7b-instruct","status":"loaded"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        agent.load_model("qwen2.5-7b-instruct").await.unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_load_model_upstream_error() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/api/v1/models/load")
+            .with_status(404)
+            .with_body(r#"{"error":"Model not found"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let err = agent.load_model("missing").await.unwrap_err();
+        assert!(matches!(err, AgentError::Upstream { status: 404, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_unload_model() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/models/unload")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"instance_id": "qwen2.5-7b-instruct"}),
+            ))
+            .with_status(200)
+            .with_body(r#"{"instance_id":"qwen2.5-7b-instruct"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        agent.unload_model("qwen2.5-7b-instruct").await.unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_lists_loaded_models() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/v0/models")
+            .with_status(200)
+            .with_body(
+                r#"{"object":"list","data":[
+                    {"id":"qwen2.5-7b-instruct","type":"llm","state":"loaded"},
+                    {"id":"llama-3.2-1b","type":"llm","state":"not-loaded"},
+                    {"id":"nomic-embed-text-v1.5","type":"embeddings","state":"loaded"}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let usage = agent.resource_usage().await;
+        assert_eq!(
+            usage.loaded_models,
+            vec!["qwen2.5-7b-instruct", "nomic-embed-text-v1.5"]
+        );
+        assert_eq!(usage.vram_used_bytes, None);
+    }
+
+    #[tokio::test]
+    async fn test_resource_usage_unavailable() {
+        let server = Server::new_async().await;
+        let agent = test_agent(server.url());
+        assert_eq!(agent.resource_usage().await, ResourceUsage::default());
+    }
 }
@@ -8,7 +8,13 @@ use axum::http::HeaderMap;
 use futures_util::stream::BoxStream;
 
 pub mod anthropic;
+pub mod azure;
+pub mod bedrock;
+pub mod circuit_breaker;
+pub mod cohere;
+pub mod embeddings;
 pub mod error;
+pub mod eventstream;
 pub mod factory;
 pub mod generic;
 pub mod google;
@@ -17,6 +23,9 @@ pub mod ollama;
 pub mod openai;
 pub mod pricing;
 pub mod quality;
+pub mod sigv4;
+pub mod tei;
+pub mod tokenize;
 pub mod tokenizer;
 pub mod translation;
 pub mod types;
@@ -24,8 +33,8 @@ pub mod types;
 // Re-export key types for convenience
 pub use error::AgentError;
 pub use types::{
-    AgentCapabilities, AgentProfile, HealthStatus, ModelCapability, PrivacyZone, ResourceUsage,
-    StreamChunk, TokenCount,
+    AgentCapabilities, AgentProfile, GenerationTimings, HealthStatus, ModelCapability, PrivacyZone,
+    ResourceUsage, StreamChunk, TokenCount,
 };
 
 use crate::api::types::{ChatCompletionRequest, ChatCompletionResponse};
@@ -218,10 +227,24 @@ pub trait InferenceAgent: Send + Sync + 'static {
         Err(AgentError::Unsupported("embeddings"))
     }
 
+    /// Score documents by relevance to a query with a rerank model.
+    ///
+    /// Returns one score per document, in input order. Default implementation
+    /// returns `Unsupported`. Override in TEIAgent (POST /rerank).
+    async fn rerank(
+        &self,
+        _model: &str,
+        _query: &str,
+        _documents: Vec<String>,
+    ) -> Result<Vec<f32>, AgentError> {
+        Err(AgentError::Unsupported("rerank"))
+    }
+
     /// Load a model into backend memory (F20: Model Lifecycle, v0.5).
     ///
     /// Default implementation returns `Unsupported`. Override in OllamaAgent
-    /// (POST /api/pull) and vLLM (if lifecycle API available).
+    /// (POST /api/pull), LMStudioAgent (POST /api/v1/models/load) and vLLM
+    /// (if lifecycle API available).
     async fn load_model(&self, _model_id: &str) -> Result<(), AgentError> {
         Err(AgentError::Unsupported("load_model"))
     }
@@ -249,7 +272,8 @@ pub trait InferenceAgent: Send + Sync + 'static {
     ///
     /// Default implementation returns empty ResourceUsage. Override in:
     /// - OllamaAgent: Parse /api/ps for VRAM usage
-    /// - vLLM: Query metrics endpoint
+    /// - LMStudioAgent: Parse /api/v0/models for loaded models
+    /// - GenericOpenAIAgent (vLLM, llama.cpp): Scrape /metrics (llama.cpp falls back to /slots)
     async fn resource_usage(&self) -> ResourceUsage {
         ResourceUsage::default()
     }
@@ -409,6 +433,13 @@ mod tests {
         assert!(matches!(err, AgentError::Unsupported("embeddings")));
     }
 
+    #[tokio::test]
+    async fn rerank_returns_unsupported() {
+        let agent = MockAgent;
+        let err = agent.rerank("m", "q", vec![]).await.unwrap_err();
+        assert!(matches!(err, AgentError::Unsupported("rerank")));
+    }
+
     #[tokio::test]
     async fn load_model_returns_unsupported() {
         let agent = MockAgent;
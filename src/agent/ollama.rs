@@ -1,24 +1,53 @@
 //! Ollama agent implementation.
 
+use super::embeddings::is_embedding_model;
+use super::tokenize::{BackendTokenCounter, TokenizeApi};
 use super::{
-    AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
-    PrivacyZone, StreamChunk,
+    AgentCapabilities, AgentError, AgentProfile, GenerationTimings, HealthStatus, InferenceAgent,
+    ModelCapability, PrivacyZone, StreamChunk, TokenCount,
+};
+use crate::api::types::{
+    ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Choice, FunctionCall,
+    MessageContent, ToolCall, Usage,
 };
-use crate::api::types::{ChatCompletionRequest, ChatCompletionResponse};
 use async_trait::async_trait;
 use axum::http::HeaderMap;
 use futures_util::stream::BoxStream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Model options that may be passed at the top level of a request and are
+/// moved into the native `options` object.
+const NATIVE_OPTIONS: &[&str] = &[
+    "num_ctx",
+    "num_keep",
+    "num_batch",
+    "num_gpu",
+    "num_thread",
+    "top_k",
+    "min_p",
+    "typical_p",
+    "repeat_penalty",
+    "repeat_last_n",
+    "mirostat",
+    "mirostat_eta",
+    "mirostat_tau",
+    "penalize_newline",
+    "seed",
+];
+
 /// Ollama agent implementation.
 ///
 /// Handles Ollama-specific API calls:
 /// - Health check via GET /api/tags
 /// - Model listing via GET /api/tags + POST /api/show enrichment
-/// - Chat completion via POST /v1/chat/completions (OpenAI-compatible)
+/// - Chat completion and streaming via the native POST /api/chat, which
+///   keeps Ollama-only parameters (`keep_alive`, `format`, `options`) and
+///   reports server-side prompt and generation timings
 pub struct OllamaAgent {
     /// Unique agent ID
     id: String,
@@ -32,6 +61,8 @@ pub struct OllamaAgent {
     privacy_zone: PrivacyZone,
     /// Capability tier from config
     capability_tier: Option<u8>,
+    /// Exact token counting via POST /api/tokenize
+    token_counter: BackendTokenCounter,
 }
 
 impl OllamaAgent {
@@ -50,6 +81,7 @@ impl OllamaAgent {
             client,
             privacy_zone,
             capability_tier,
+            token_counter: BackendTokenCounter::new(TokenizeApi::Ollama),
         }
     }
 }
@@ -74,6 +106,92 @@ struct OllamaShowResponse {
     model_info: serde_json::Value,
 }
 
+/// Ollama /api/chat response, or one line of its NDJSON stream.
+#[derive(Deserialize)]
+struct OllamaChatResponse {
+    #[serde(default)]
+    message: Option<OllamaMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    done_reason: Option<String>,
+    #[serde(default)]
+    load_duration: u64,
+    #[serde(default)]
+    prompt_eval_count: u32,
+    #[serde(default)]
+    prompt_eval_duration: u64,
+    #[serde(default)]
+    eval_count: u32,
+    #[serde(default)]
+    eval_duration: u64,
+}
+
+#[derive(Deserialize)]
+struct OllamaMessage {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    tool_calls: Vec<OllamaToolCall>,
+}
+
+#[derive(Deserialize)]
+struct OllamaToolCall {
+    function: OllamaFunction,
+}
+
+#[derive(Deserialize)]
+struct OllamaFunction {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+impl OllamaChatResponse {
+    fn usage(&self) -> Usage {
+        Usage {
+            prompt_tokens: self.prompt_eval_count,
+            completion_tokens: self.eval_count,
+            total_tokens: self.prompt_eval_count + self.eval_count,
+        }
+    }
+
+    /// Timings from the nanosecond durations Ollama reports on the final message.
+    fn timings(&self) -> GenerationTimings {
+        GenerationTimings {
+            ttft_ms: ((self.load_duration + self.prompt_eval_duration) / 1_000_000) as u32,
+            generation_ms: (self.eval_duration / 1_000_000) as u32,
+            completion_tokens: self.eval_count,
+        }
+    }
+}
+
+/// OpenAI finish reason: tool calls win, then Ollama's `done_reason`.
+fn finish_reason(done_reason: Option<&str>, called_tools: bool) -> &'static str {
+    if called_tools {
+        "tool_calls"
+    } else if done_reason == Some("length") {
+        "length"
+    } else {
+        "stop"
+    }
+}
+
+/// Convert native tool calls, serializing argument objects as JSON strings.
+fn openai_tool_calls(calls: Vec<OllamaToolCall>) -> Vec<ToolCall> {
+    calls
+        .into_iter()
+        .map(|call| ToolCall {
+            id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: call.function.name,
+                arguments: call.function.arguments.to_string(),
+            },
+        })
+        .collect()
+}
+
 #[async_trait]
 impl InferenceAgent for OllamaAgent {
     fn id(&self) -> &str {
@@ -91,8 +209,9 @@ impl InferenceAgent for OllamaAgent {
             privacy_zone: self.privacy_zone,
             capabilities: AgentCapabilities {
                 embeddings: true,
+                rerank: false,
                 model_lifecycle: true, // T027: Enable lifecycle support
-                token_counting: false,
+                token_counting: true,
                 resource_monitoring: true, // T027: Enable resource monitoring
             },
             capability_tier: self.capability_tier,
@@ -176,6 +295,7 @@ impl InferenceAgent for OllamaAgent {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
                 capability_tier: None,
             })
@@ -192,46 +312,43 @@ impl InferenceAgent for OllamaAgent {
         request: ChatCompletionRequest,
         headers: Option<&HeaderMap>,
     ) -> Result<ChatCompletionResponse, AgentError> {
-        let url = format!("{}/v1/chat/completions", self.base_url);
-
-        let mut req = self
-            .client
-            .post(&url)
-            .json(&request)
-            .timeout(Duration::from_secs(120));
-
-        // Forward Authorization header if present
-        if let Some(headers) = headers {
-            if let Some(auth) = headers.get("authorization") {
-                req = req.header("authorization", auth);
-            }
-        }
-
-        let response = req.send().await.map_err(|e| {
-            if e.is_timeout() {
-                AgentError::Timeout(120000)
-            } else {
-                AgentError::Network(e.to_string())
-            }
-        })?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(AgentError::Upstream {
-                status: status.as_u16(),
-                message: error_body,
-            });
-        }
+        let body = Self::translate_request(&request, false)?;
+        let response = self.send_chat(&body, headers).await?;
 
-        let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
+        let chat: OllamaChatResponse = response.json().await.map_err(|e| {
             AgentError::InvalidResponse(format!("Failed to parse completion response: {}", e))
         })?;
-
-        Ok(completion)
+        let usage = chat.usage();
+        let timings = chat.timings();
+        let message = chat.message.ok_or_else(|| {
+            AgentError::InvalidResponse("Missing message in chat response".to_string())
+        })?;
+        let finish_reason =
+            finish_reason(chat.done_reason.as_deref(), !message.tool_calls.is_empty());
+        let content = message.content;
+        let tool_calls = openai_tool_calls(message.tool_calls);
+
+        Ok(ChatCompletionResponse {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            object: "chat.completion".to_string(),
+            created: chrono::Utc::now().timestamp(),
+            model: request.model,
+            choices: vec![Choice {
+                index: 0,
+                finish_reason: Some(finish_reason.to_string()),
+                message: ChatMessage {
+                    role: "assistant".to_string(),
+                    content: MessageContent::Text { content },
+                    name: None,
+                    function_call: None,
+                    tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                    tool_call_id: None,
+                },
+            }],
+            usage: Some(usage),
+            extra: HashMap::new(),
+            timings: Some(timings),
+        })
     }
 
     async fn chat_completion_stream(
@@ -239,55 +356,57 @@ impl InferenceAgent for OllamaAgent {
         request: ChatCompletionRequest,
         headers: Option<&HeaderMap>,
     ) -> Result<BoxStream<'static, Result<StreamChunk, AgentError>>, AgentError> {
-        use futures_util::stream::StreamExt;
-
-        let url = format!("{}/v1/chat/completions", self.base_url);
-
-        let mut req = self
-            .client
-            .post(&url)
-            .json(&request)
-            .timeout(Duration::from_secs(120));
-
-        // Forward Authorization header if present
-        if let Some(headers) = headers {
-            if let Some(auth) = headers.get("authorization") {
-                req = req.header("authorization", auth);
-            }
-        }
-
-        let response = req.send().await.map_err(|e| {
-            if e.is_timeout() {
-                AgentError::Timeout(120000)
-            } else {
-                AgentError::Network(e.to_string())
-            }
-        })?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(AgentError::Upstream {
-                status: status.as_u16(),
-                message: error_body,
-            });
-        }
-
-        // Convert byte stream to SSE chunks
-        let stream = response.bytes_stream().map(|result| {
-            result
-                .map(|bytes| StreamChunk {
-                    data: String::from_utf8_lossy(&bytes).to_string(),
+        use futures_util::stream::{self, StreamExt};
+
+        let body = Self::translate_request(&request, true)?;
+        let response = self.send_chat(&body, headers).await?;
+
+        // NDJSON lines may be split across reads
+        let stream = response
+            .bytes_stream()
+            .scan(
+                StreamTranslator::new(request.model),
+                |translator, result| {
+                    let chunks: Vec<Result<StreamChunk, AgentError>> = match result {
+                        Err(e) => vec![Err(AgentError::Network(e.to_string()))],
+                        Ok(bytes) => {
+                            let mut chunks: Vec<StreamChunk> = translator
+                                .push(&bytes)
+                                .into_iter()
+                                .map(|data| StreamChunk {
+                                    data,
+                                    timings: None,
+                                })
+                                .collect();
+                            // The final line carries the server timings
+                            if let Some(last) = chunks.last_mut() {
+                                last.timings = translator.timings.take();
+                            }
+                            chunks.into_iter().map(Ok).collect()
+                        }
+                    };
+                    futures_util::future::ready(Some(stream::iter(chunks)))
+                },
+            )
+            .flatten()
+            .chain(stream::once(async {
+                Ok(StreamChunk {
+                    data: "[DONE]".to_string(),
+                    timings: None,
                 })
-                .map_err(|e| AgentError::Network(e.to_string()))
-        });
+            }));
 
         Ok(Box::pin(stream))
     }
 
+    /// Count tokens via Ollama's POST /api/tokenize, falling back to the
+    /// chars/4 heuristic on Ollama versions without it.
+    async fn count_tokens(&self, model_id: &str, text: &str) -> TokenCount {
+        self.token_counter
+            .count(&self.client, &self.base_url, model_id, text)
+            .await
+    }
+
     /// Generate embeddings via Ollama's POST /api/embed endpoint.
     async fn embeddings(
         &self,
@@ -490,6 +609,7 @@ impl InferenceAgent for OllamaAgent {
             vram_used_bytes: Some(vram_used),
             vram_total_bytes: None, // Ollama doesn't expose total VRAM
             pending_requests: None,
+            kv_cache_usage: None,
             avg_latency_ms: None,
             loaded_models,
         }
@@ -497,6 +617,114 @@ impl InferenceAgent for OllamaAgent {
 }
 
 impl OllamaAgent {
+    /// POST a native chat request to /api/chat.
+    async fn send_chat(
+        &self,
+        body: &Value,
+        headers: Option<&HeaderMap>,
+    ) -> Result<reqwest::Response, AgentError> {
+        let url = format!("{}/api/chat", self.base_url);
+
+        let mut req = self
+            .client
+            .post(&url)
+            .json(body)
+            .timeout(Duration::from_secs(120));
+
+        // Forward Authorization header if present
+        if let Some(headers) = headers {
+            if let Some(auth) = headers.get("authorization") {
+                req = req.header("authorization", auth);
+            }
+        }
+
+        let response = req.send().await.map_err(|e| {
+            if e.is_timeout() {
+                AgentError::Timeout(120000)
+            } else {
+                AgentError::Network(e.to_string())
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status: status.as_u16(),
+                message: error_body,
+            });
+        }
+        Ok(response)
+    }
+
+    /// Translate an OpenAI chat request to a native /api/chat request.
+    ///
+    /// Sampling parameters move into `options`. Ollama-only parameters come
+    /// from `extra`: `keep_alive` and `format` are passed through, `options`
+    /// is merged last, and known option names such as `num_ctx` or
+    /// `mirostat` may also be given at the top level. `response_format` maps
+    /// to `format`.
+    fn translate_request(
+        request: &ChatCompletionRequest,
+        stream: bool,
+    ) -> Result<Value, AgentError> {
+        let mut options = serde_json::Map::new();
+        if let Some(temperature) = request.temperature {
+            options.insert("temperature".to_string(), json!(temperature));
+        }
+        if let Some(top_p) = request.top_p {
+            options.insert("top_p".to_string(), json!(top_p));
+        }
+        if let Some(max_tokens) = request.max_tokens {
+            options.insert("num_predict".to_string(), json!(max_tokens));
+        }
+        if let Some(stop) = &request.stop {
+            options.insert("stop".to_string(), json!(stop));
+        }
+        if let Some(penalty) = request.presence_penalty {
+            options.insert("presence_penalty".to_string(), json!(penalty));
+        }
+        if let Some(penalty) = request.frequency_penalty {
+            options.insert("frequency_penalty".to_string(), json!(penalty));
+        }
+        for name in NATIVE_OPTIONS {
+            if let Some(value) = request.extra.get(*name) {
+                options.insert(name.to_string(), value.clone());
+            }
+        }
+        if let Some(Value::Object(native)) = request.extra.get("options") {
+            options.extend(native.clone());
+        }
+
+        let mut body = json!({
+            "model": request.model,
+            "messages": translate_messages(&request.messages)?,
+            "stream": stream,
+        });
+        if !options.is_empty() {
+            body["options"] = Value::Object(options);
+        }
+        if let Some(tools) = request.extra.get("tools") {
+            body["tools"] = tools.clone();
+        }
+        if let Some(keep_alive) = request.extra.get("keep_alive") {
+            body["keep_alive"] = keep_alive.clone();
+        }
+        if let Some(format) = request.extra.get("format") {
+            body["format"] = format.clone();
+        } else if let Some(format) = request.extra.get("response_format") {
+            match format["type"].as_str() {
+                Some("json_object") => body["format"] = json!("json"),
+                Some("json_schema") => body["format"] = format["json_schema"]["schema"].clone(),
+                _ => {}
+            }
+        }
+        Ok(body)
+    }
+
     /// Enrich models with real capabilities from /api/show endpoint.
     async fn enrich_models(&self, models: &mut [ModelCapability]) {
         for model in models.iter_mut() {
@@ -516,6 +744,8 @@ impl OllamaAgent {
                         if let Ok(show) = serde_json::from_str::<OllamaShowResponse>(&text) {
                             model.supports_vision = show.capabilities.iter().any(|c| c == "vision");
                             model.supports_tools = show.capabilities.iter().any(|c| c == "tools");
+                            model.supports_embeddings =
+                                show.capabilities.iter().any(|c| c == "embedding");
 
                             // Extract context_length from model_info
                             if let Some(obj) = show.model_info.as_object() {
@@ -545,6 +775,9 @@ impl OllamaAgent {
     fn apply_name_heuristics(model: &mut ModelCapability) {
         let name = model.id.to_lowercase();
 
+        // Embedding model heuristics
+        model.supports_embeddings |= is_embedding_model(&name);
+
         // Vision support heuristics
         if name.contains("vision")
             || name.contains("llava")
@@ -579,6 +812,172 @@ impl OllamaAgent {
     }
 }
 
+/// Translate OpenAI messages to native messages.
+///
+/// Images must be base64 data URLs; tool results carry the name of the tool
+/// that was called instead of the call ID.
+fn translate_messages(messages: &[ChatMessage]) -> Result<Vec<Value>, AgentError> {
+    let mut tool_names: HashMap<&str, &str> = HashMap::new();
+    let mut translated = Vec::with_capacity(messages.len());
+
+    for msg in messages {
+        let mut message = json!({ "role": msg.role });
+        match &msg.content {
+            MessageContent::Text { content } => message["content"] = json!(content),
+            MessageContent::Parts { content } => {
+                let mut text = Vec::new();
+                let mut images = Vec::new();
+                for part in content {
+                    if let Some(part_text) = &part.text {
+                        text.push(part_text.as_str());
+                    }
+                    if let Some(image) = &part.image_url {
+                        let data = image
+                            .url
+                            .strip_prefix("data:")
+                            .and_then(|url| url.split_once(";base64,"))
+                            .map(|(_, data)| data)
+                            .ok_or(AgentError::Unsupported(
+                                "Ollama only accepts images as base64 data URLs",
+                            ))?;
+                        images.push(data);
+                    }
+                }
+                message["content"] = json!(text.join("\n"));
+                if !images.is_empty() {
+                    message["images"] = json!(images);
+                }
+            }
+        }
+        if let Some(calls) = &msg.tool_calls {
+            let calls: Vec<Value> = calls
+                .iter()
+                .map(|call| {
+                    tool_names.insert(&call.id, &call.function.name);
+                    // Native arguments are an object, not a JSON string
+                    let arguments = serde_json::from_str::<Value>(&call.function.arguments)
+                        .unwrap_or_else(|_| json!({}));
+                    json!({ "function": { "name": call.function.name, "arguments": arguments } })
+                })
+                .collect();
+            message["tool_calls"] = json!(calls);
+        }
+        if let Some(name) = msg
+            .tool_call_id
+            .as_deref()
+            .and_then(|id| tool_names.get(id))
+        {
+            message["tool_name"] = json!(name);
+        }
+        translated.push(message);
+    }
+    Ok(translated)
+}
+
+/// Translates the native NDJSON chat stream into OpenAI chunks.
+struct StreamTranslator {
+    id: String,
+    model: String,
+    created: i64,
+    /// Bytes after the last complete line
+    buffer: String,
+    /// Whether the assistant role has been sent
+    started: bool,
+    /// Tool calls emitted so far
+    tool_calls: usize,
+    /// Timings from the final line, until attached to its chunk
+    timings: Option<GenerationTimings>,
+}
+
+impl StreamTranslator {
+    fn new(model: String) -> Self {
+        Self {
+            id: format!("chatcmpl-{}", uuid::Uuid::new_v4()),
+            model,
+            created: chrono::Utc::now().timestamp(),
+            buffer: String::new(),
+            started: false,
+            tool_calls: 0,
+            timings: None,
+        }
+    }
+
+    /// Chunk JSON as forwarded by the SSE handler (without the `data:` framing).
+    fn chunk(&self, delta: Value, finish_reason: Option<&str>, usage: Option<Usage>) -> String {
+        let mut chunk = json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": [{
+                "index": 0,
+                "delta": delta,
+                "finish_reason": finish_reason
+            }]
+        });
+        if let Some(usage) = usage {
+            chunk["usage"] = json!(usage);
+        }
+        chunk.to_string()
+    }
+
+    /// Feed raw stream bytes, returning chunks for every complete line.
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.push_str(&String::from_utf8_lossy(bytes));
+        let mut chunks = Vec::new();
+        while let Some(end) = self.buffer.find('\n') {
+            let line: String = self.buffer.drain(..=end).collect();
+            if let Ok(response) = serde_json::from_str::<OllamaChatResponse>(line.trim()) {
+                self.translate(response, &mut chunks);
+            }
+        }
+        chunks
+    }
+
+    /// Add the assistant role to the first delta of the stream.
+    fn take_role(&mut self, mut delta: Value) -> Value {
+        if !self.started {
+            self.started = true;
+            delta["role"] = json!("assistant");
+        }
+        delta
+    }
+
+    fn translate(&mut self, response: OllamaChatResponse, chunks: &mut Vec<String>) {
+        let usage = response.done.then(|| response.usage());
+        let timings = response.done.then(|| response.timings());
+        if let Some(message) = response.message {
+            if !message.content.is_empty() {
+                let delta = self.take_role(json!({ "content": message.content }));
+                chunks.push(self.chunk(delta, None, None));
+            }
+            // Ollama sends each tool call complete in a single line
+            for call in openai_tool_calls(message.tool_calls) {
+                let delta = self.take_role(json!({
+                    "tool_calls": [{
+                        "index": self.tool_calls,
+                        "id": call.id,
+                        "type": "function",
+                        "function": {
+                            "name": call.function.name,
+                            "arguments": call.function.arguments
+                        }
+                    }]
+                }));
+                self.tool_calls += 1;
+                chunks.push(self.chunk(delta, None, None));
+            }
+        }
+
+        if response.done {
+            self.timings = timings;
+            let delta = self.take_role(json!({}));
+            let finish_reason = finish_reason(response.done_reason.as_deref(), self.tool_calls > 0);
+            chunks.push(self.chunk(delta, Some(finish_reason), usage));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -719,6 +1118,7 @@ mod tests {
         assert!(profile.capabilities.embeddings);
         assert!(profile.capabilities.model_lifecycle); // T027: Now true
         assert!(profile.capabilities.resource_monitoring); // T027: Now true
+        assert!(profile.capabilities.token_counting);
     }
 
     #[tokio::test]
@@ -726,26 +1126,23 @@ mod tests {
         let mut server = Server::new_async().await;
 
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .match_body(Matcher::JsonString(
                 r#"{"model":"llama3:70b","messages":[{"role":"user","content":"Hello"}],"stream":false}"#.to_string(),
             ))
             .with_status(200)
             .with_body(r#"{
-                "id": "chatcmpl-123",
-                "object": "chat.completion",
-                "created": 1234567890,
                 "model": "llama3:70b",
-                "choices": [
-                    {
-                        "index": 0,
-                        "message": {
-                            "role": "assistant",
-                            "content": "Hi there!"
-                        },
-                        "finish_reason": "stop"
-                    }
-                ]
+                "created_at": "2024-05-01T12:00:00Z",
+                "message": {"role": "assistant", "content": "Hi there!"},
+                "done": true,
+                "done_reason": "stop",
+                "total_duration": 900000000,
+                "load_duration": 100000000,
+                "prompt_eval_count": 12,
+                "prompt_eval_duration": 150000000,
+                "eval_count": 40,
+                "eval_duration": 500000000
             }"#)
             .create_async()
             .await;
@@ -760,6 +1157,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -775,9 +1174,17 @@ mod tests {
         let response = agent.chat_completion(request, None).await.unwrap();
 
         mock.assert_async().await;
-        assert_eq!(response.id, "chatcmpl-123");
+        assert!(response.id.starts_with("chatcmpl-"));
         assert_eq!(response.model, "llama3:70b");
         assert_eq!(response.choices.len(), 1);
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+        assert_eq!(response.usage.unwrap().total_tokens, 52);
+
+        // Load plus prompt evaluation, then 40 tokens in 500ms
+        let timings = response.timings.unwrap();
+        assert_eq!(timings.ttft_ms, 250);
+        assert_eq!(timings.generation_ms, 500);
+        assert_eq!(timings.tokens_per_second(), Some(80.0));
     }
 
     #[tokio::test]
@@ -785,7 +1192,7 @@ mod tests {
         let mut server = Server::new_async().await;
 
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(500)
             .with_body("Internal server error")
             .create_async()
@@ -824,6 +1231,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -840,6 +1248,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -856,6 +1265,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -872,6 +1282,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -890,6 +1301,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -906,6 +1318,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -922,6 +1335,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -939,6 +1353,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -955,6 +1370,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -973,6 +1389,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -987,11 +1404,11 @@ mod tests {
         use futures_util::stream::StreamExt;
 
         let mut server = Server::new_async().await;
-        let sse_body = "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"},\"finish_reason\":null}]}\n\n";
+        let sse_body = "{\"model\":\"llama3\",\"message\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"done\":false}\n";
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(200)
-            .with_header("content-type", "text/event-stream")
+            .with_header("content-type", "application/x-ndjson")
             .with_body(sse_body)
             .create_async()
             .await;
@@ -1006,6 +1423,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: true,
             temperature: None,
@@ -1031,7 +1450,7 @@ mod tests {
     async fn test_chat_completion_stream_error() {
         let mut server = Server::new_async().await;
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(500)
             .with_body("Internal Server Error")
             .create_async()
@@ -1146,7 +1565,7 @@ mod tests {
     async fn test_chat_completion_non_streaming_invalid_json() {
         let mut server = Server::new_async().await;
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body("not valid json")
@@ -1313,6 +1732,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1329,6 +1749,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1345,6 +1766,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1361,6 +1783,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1377,6 +1800,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1393,6 +1817,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1409,6 +1834,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1425,6 +1851,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1441,6 +1868,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1454,11 +1882,11 @@ mod tests {
 
         let mut server = Server::new_async().await;
         let sse_body =
-            "data: {\"id\":\"chatcmpl-123\",\"choices\":[{\"delta\":{\"content\":\"Hi\"}}]}\n\n";
+            "{\"model\":\"llama3:8b\",\"message\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"done\":false}\n";
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(200)
-            .with_header("content-type", "text/event-stream")
+            .with_header("content-type", "application/x-ndjson")
             .with_body(sse_body)
             .create_async()
             .await;
@@ -1490,7 +1918,7 @@ mod tests {
     async fn test_chat_completion_stream_upstream_error() {
         let mut server = Server::new_async().await;
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(500)
             .with_body("error")
             .create_async()
@@ -1519,6 +1947,27 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn test_count_tokens_via_api_tokenize() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/tokenize")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "model": "llama3",
+                "content": "Hello world"
+            })))
+            .with_status(200)
+            .with_body(r#"{"model":"llama3","tokens":[9906,1917]}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let count = agent.count_tokens("llama3", "Hello world").await;
+
+        mock.assert_async().await;
+        assert_eq!(count, TokenCount::Exact(2));
+    }
+
     #[test]
     fn test_profile_with_capability_tier() {
         let client = Arc::new(Client::new());
@@ -1559,10 +2008,10 @@ mod tests {
     async fn test_chat_completion_with_auth_header() {
         let mut server = Server::new_async().await;
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .match_header("authorization", "Bearer ollama-key")
             .with_status(200)
-            .with_body(r#"{"id":"1","object":"chat.completion","created":123,"model":"test","choices":[{"index":0,"message":{"role":"assistant","content":"ok"},"finish_reason":"stop"}]}"#)
+            .with_body(r#"{"model":"test","message":{"role":"assistant","content":"ok"},"done":true,"done_reason":"stop"}"#)
             .create_async()
             .await;
 
@@ -1587,7 +2036,7 @@ mod tests {
             .chat_completion(request, Some(&headers))
             .await
             .unwrap();
-        assert_eq!(response.id, "1");
+        assert!(response.id.starts_with("chatcmpl-"));
         mock.assert_async().await;
     }
 
@@ -1654,11 +2103,11 @@ mod tests {
         use futures_util::stream::StreamExt;
 
         let mut server = Server::new_async().await;
-        let sse_body = "data: {\"id\":\"chatcmpl-123\",\"object\":\"chat.completion.chunk\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"},\"finish_reason\":null}]}\n\n";
+        let sse_body = "{\"model\":\"llama3\",\"message\":{\"role\":\"assistant\",\"content\":\"Hello\"},\"done\":false}\n";
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(200)
-            .with_header("content-type", "text/event-stream")
+            .with_header("content-type", "application/x-ndjson")
             .with_body(sse_body)
             .create_async()
             .await;
@@ -1683,11 +2132,53 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn test_chat_completion_stream_reports_timings_on_final_chunk() {
+        use futures_util::stream::StreamExt;
+
+        let mut server = Server::new_async().await;
+        let body = concat!(
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Hi\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,",
+            "\"done_reason\":\"stop\",\"eval_count\":40,\"load_duration\":50000000,",
+            "\"prompt_eval_duration\":200000000,\"eval_duration\":500000000}\n",
+        );
+        let _mock = server
+            .mock("POST", "/api/chat")
+            .with_status(200)
+            .with_header("content-type", "application/x-ndjson")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let request: ChatCompletionRequest = serde_json::from_value(json!({
+            "model": "llama3",
+            "messages": [{"role": "user", "content": "Hello"}],
+            "stream": true
+        }))
+        .unwrap();
+        let chunks: Vec<StreamChunk> = agent
+            .chat_completion_stream(request, None)
+            .await
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].timings.is_none());
+        let timings = chunks[1].timings.unwrap();
+        assert_eq!(timings.ttft_ms, 250);
+        assert_eq!(timings.tokens_per_second(), Some(80.0));
+        assert_eq!(chunks[2].data, "[DONE]");
+    }
+
     #[tokio::test]
     async fn test_chat_completion_invalid_json_response() {
         let mut server = Server::new_async().await;
         let mock = server
-            .mock("POST", "/v1/chat/completions")
+            .mock("POST", "/api/chat")
             .with_status(200)
             .with_body("not json")
             .create_async()
@@ -1711,4 +2202,148 @@ mod tests {
         assert!(matches!(result, Err(AgentError::InvalidResponse(_))));
         mock.assert_async().await;
     }
+
+    fn native_request(extra: serde_json::Value) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "llama3",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "temperature": 0.2,
+            "max_tokens": 64,
+            "stop": ["END"]
+        }))
+        .map(|mut request: ChatCompletionRequest| {
+            request.extra = serde_json::from_value(extra).unwrap();
+            request
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_translate_request_maps_native_options() {
+        let request = native_request(json!({
+            "keep_alive": "10m",
+            "num_ctx": 8192,
+            "options": {"mirostat": 2, "repeat_penalty": 1.1, "temperature": 0.9},
+            "format": {"type": "object", "properties": {"a": {"type": "string"}}}
+        }));
+
+        let body = OllamaAgent::translate_request(&request, false).unwrap();
+
+        assert_eq!(body["keep_alive"], "10m");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["format"]["type"], "object");
+        let options = &body["options"];
+        assert_eq!(options["num_predict"], 64);
+        assert_eq!(options["stop"], json!(["END"]));
+        assert_eq!(options["num_ctx"], 8192);
+        assert_eq!(options["mirostat"], 2);
+        assert_eq!(options["repeat_penalty"], 1.1);
+        // Native options win over the OpenAI parameters
+        assert_eq!(options["temperature"], 0.9);
+    }
+
+    #[test]
+    fn test_translate_request_maps_response_format() {
+        let json_mode = native_request(json!({"response_format": {"type": "json_object"}}));
+        let body = OllamaAgent::translate_request(&json_mode, true).unwrap();
+        assert_eq!(body["format"], "json");
+        assert_eq!(body["stream"], true);
+
+        let schema = native_request(json!({"response_format": {
+            "type": "json_schema",
+            "json_schema": {"name": "r", "schema": {"type": "object"}}
+        }}));
+        let body = OllamaAgent::translate_request(&schema, false).unwrap();
+        assert_eq!(body["format"], json!({"type": "object"}));
+    }
+
+    #[test]
+    fn test_translate_messages_images_and_tools() {
+        let messages: Vec<ChatMessage> = serde_json::from_value(json!([
+            {"role": "user", "content": [
+                {"type": "text", "text": "What is this?"},
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw0KGgo="}}
+            ]},
+            {"role": "assistant", "content": null, "tool_calls": [
+                {"id": "call_1", "type": "function",
+                 "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}}
+            ]},
+            {"role": "tool", "tool_call_id": "call_1", "content": "22C"}
+        ]))
+        .unwrap();
+
+        let translated = translate_messages(&messages).unwrap();
+
+        assert_eq!(translated[0]["content"], "What is this?");
+        assert_eq!(translated[0]["images"], json!(["iVBORw0KGgo="]));
+        assert_eq!(
+            translated[1]["tool_calls"][0]["function"]["arguments"],
+            json!({"city": "Paris"})
+        );
+        assert_eq!(translated[2]["tool_name"], "get_weather");
+    }
+
+    #[test]
+    fn test_translate_messages_rejects_remote_images() {
+        let messages: Vec<ChatMessage> = serde_json::from_value(json!([
+            {"role": "user", "content": [
+                {"type": "image_url", "image_url": {"url": "https://example.com/cat.png"}}
+            ]}
+        ]))
+        .unwrap();
+
+        assert!(matches!(
+            translate_messages(&messages),
+            Err(AgentError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_stream_translator_handles_split_lines() {
+        let mut translator = StreamTranslator::new("llama3".to_string());
+        let lines = concat!(
+            "{\"model\":\"llama3\",\"message\":{\"role\":\"assistant\",\"content\":\"Hel\"},\"done\":false}\n",
+            "{\"model\":\"llama3\",\"message\":{\"role\":\"assistant\",\"content\":\"lo\"},\"done\":false}\n",
+            "{\"model\":\"llama3\",\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,",
+            "\"done_reason\":\"length\",\"prompt_eval_count\":5,\"eval_count\":2}\n",
+        );
+        let (first, second) = lines.split_at(50);
+
+        let mut chunks = translator.push(first.as_bytes());
+        chunks.extend(translator.push(second.as_bytes()));
+
+        let chunks: Vec<Value> = chunks
+            .iter()
+            .map(|c| serde_json::from_str(c).unwrap())
+            .collect();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0]["choices"][0]["delta"]["role"], "assistant");
+        assert_eq!(chunks[0]["choices"][0]["delta"]["content"], "Hel");
+        assert!(chunks[1]["choices"][0]["delta"].get("role").is_none());
+        assert_eq!(chunks[2]["choices"][0]["finish_reason"], "length");
+        assert_eq!(chunks[2]["usage"]["total_tokens"], 7);
+    }
+
+    #[test]
+    fn test_stream_translator_tool_calls() {
+        let mut translator = StreamTranslator::new("llama3".to_string());
+        let chunks = translator.push(
+            concat!(
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"\",\"tool_calls\":[",
+                "{\"function\":{\"name\":\"get_weather\",\"arguments\":{\"city\":\"Paris\"}}}]},\"done\":false}\n",
+                "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"done_reason\":\"stop\"}\n",
+            )
+            .as_bytes(),
+        );
+
+        let chunks: Vec<Value> = chunks
+            .iter()
+            .map(|c| serde_json::from_str(c).unwrap())
+            .collect();
+        let call = &chunks[0]["choices"][0]["delta"]["tool_calls"][0];
+        assert_eq!(call["index"], 0);
+        assert_eq!(call["function"]["name"], "get_weather");
+        assert_eq!(call["function"]["arguments"], r#"{"city":"Paris"}"#);
+        assert_eq!(chunks[1]["choices"][0]["finish_reason"], "tool_calls");
+    }
 }
@@ -1,5 +1,6 @@
 //! OpenAI agent implementation.
 
+use super::embeddings::is_embedding_model;
 use super::{
     AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
     PrivacyZone, StreamChunk, TokenCount,
@@ -14,6 +15,20 @@ use serde::Deserialize;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// How an OpenAI-compatible cloud from the provider catalogue differs from
+/// api.openai.com.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderProfile {
+    /// Catalogue name, reported as the agent's backend type
+    pub name: String,
+    /// Header carrying the API key
+    pub auth_header: String,
+    /// Scheme prefixed to the API key; empty sends the bare key
+    pub auth_scheme: String,
+    /// Path of the model list endpoint
+    pub models_path: String,
+}
+
 /// OpenAI agent implementation.
 ///
 /// Handles OpenAI cloud API calls with API key authentication:
@@ -21,6 +36,11 @@ use std::time::Duration;
 /// - Model listing via GET /v1/models
 /// - Chat completion via POST /v1/chat/completions with Bearer token
 /// - Token counting using tiktoken-rs (F12: Cloud Backend Support)
+///
+/// With a [`ProviderProfile`] it serves other OpenAI-compatible clouds
+/// (Mistral, Groq, ...), using the provider's auth header and model list
+/// endpoint. Their models don't use OpenAI's tokenizer, so token counts fall
+/// back to the heuristic.
 pub struct OpenAIAgent {
     /// Unique agent ID
     id: String,
@@ -40,6 +60,8 @@ pub struct OpenAIAgent {
     privacy_zone: PrivacyZone,
     /// Capability tier from config
     capability_tier: Option<u8>,
+    /// Provider catalogue entry, if this is not OpenAI itself
+    provider: Option<ProviderProfile>,
 }
 
 impl OpenAIAgent {
@@ -61,6 +83,49 @@ impl OpenAIAgent {
             pricing: Arc::new(PricingTable::new()),
             privacy_zone,
             capability_tier,
+            provider: None,
+        }
+    }
+
+    /// Serve an OpenAI-compatible cloud from the provider catalogue.
+    pub fn with_provider(mut self, provider: ProviderProfile) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    fn models_url(&self) -> String {
+        let path = self
+            .provider
+            .as_ref()
+            .map_or("/v1/models", |p| p.models_path.as_str());
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Auth header name and value. A client `Authorization` header overrides
+    /// the configured key.
+    fn auth(&self, headers: Option<&HeaderMap>) -> (&str, String) {
+        let client_auth = headers
+            .and_then(|h| h.get("authorization"))
+            .and_then(|v| v.to_str().ok());
+
+        match &self.provider {
+            None => (
+                "authorization",
+                client_auth
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("Bearer {}", self.api_key)),
+            ),
+            Some(provider) => {
+                let key = client_auth
+                    .map(|auth| auth.strip_prefix("Bearer ").unwrap_or(auth))
+                    .unwrap_or(&self.api_key);
+                let value = if provider.auth_scheme.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{} {}", provider.auth_scheme, key)
+                };
+                (&provider.auth_header, value)
+            }
         }
     }
 
@@ -116,13 +181,18 @@ impl InferenceAgent for OpenAIAgent {
 
     fn profile(&self) -> AgentProfile {
         AgentProfile {
-            backend_type: "openai".to_string(),
+            backend_type: self
+                .provider
+                .as_ref()
+                .map_or("openai", |p| p.name.as_str())
+                .to_string(),
             version: None,
             privacy_zone: self.privacy_zone,
             capabilities: AgentCapabilities {
                 embeddings: true,
+                rerank: false,
                 model_lifecycle: false,
-                token_counting: true,
+                token_counting: self.provider.is_none(),
                 resource_monitoring: false,
             },
             capability_tier: self.capability_tier,
@@ -130,12 +200,13 @@ impl InferenceAgent for OpenAIAgent {
     }
 
     async fn health_check(&self) -> Result<HealthStatus, AgentError> {
-        let url = format!("{}/v1/models", self.base_url);
+        let url = self.models_url();
+        let (auth_header, auth) = self.auth(None);
 
         let response = self
             .client
             .get(&url)
-            .header("authorization", format!("Bearer {}", self.api_key))
+            .header(auth_header, auth)
             .timeout(Duration::from_secs(5))
             .send()
             .await
@@ -165,12 +236,13 @@ impl InferenceAgent for OpenAIAgent {
     }
 
     async fn list_models(&self) -> Result<Vec<ModelCapability>, AgentError> {
-        let url = format!("{}/v1/models", self.base_url);
+        let url = self.models_url();
+        let (auth_header, auth) = self.auth(None);
 
         let response = self
             .client
             .get(&url)
-            .header("authorization", format!("Bearer {}", self.api_key))
+            .header(auth_header, auth)
             .timeout(Duration::from_secs(5))
             .send()
             .await
@@ -193,6 +265,13 @@ impl InferenceAgent for OpenAIAgent {
             AgentError::InvalidResponse(format!("Failed to read response body: {}", e))
         })?;
 
+        if self.provider.is_some() {
+            // Other clouds host open model families; use the shared heuristics
+            let models = crate::health::parser::parse_openai_response(&body)
+                .map_err(|e| AgentError::InvalidResponse(e.to_string()))?;
+            return Ok(models.into_iter().map(ModelCapability::from).collect());
+        }
+
         let models: OpenAIModelsResponse = serde_json::from_str(&body).map_err(|e| {
             AgentError::InvalidResponse(format!("Failed to parse OpenAI models response: {}", e))
         })?;
@@ -208,6 +287,7 @@ impl InferenceAgent for OpenAIAgent {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                     capability_tier: None,
                 };
@@ -227,21 +307,12 @@ impl InferenceAgent for OpenAIAgent {
         let url = format!("{}/v1/chat/completions", self.base_url);
 
         // Prefer config API key, but allow header override
-        let auth_header = if let Some(headers) = headers {
-            headers
-                .get("authorization")
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string())
-        } else {
-            None
-        };
-
-        let auth = auth_header.unwrap_or_else(|| format!("Bearer {}", self.api_key));
+        let (auth_header, auth) = self.auth(headers);
 
         let response = self
             .client
             .post(&url)
-            .header("authorization", auth)
+            .header(auth_header, auth)
             .json(&request)
             .timeout(Duration::from_secs(120))
             .send()
@@ -283,21 +354,12 @@ impl InferenceAgent for OpenAIAgent {
         let url = format!("{}/v1/chat/completions", self.base_url);
 
         // Prefer config API key, but allow header override
-        let auth_header = if let Some(headers) = headers {
-            headers
-                .get("authorization")
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string())
-        } else {
-            None
-        };
-
-        let auth = auth_header.unwrap_or_else(|| format!("Bearer {}", self.api_key));
+        let (auth_header, auth) = self.auth(headers);
 
         let response = self
             .client
             .post(&url)
-            .header("authorization", auth)
+            .header(auth_header, auth)
             .json(&request)
             .timeout(Duration::from_secs(120))
             .send()
@@ -327,6 +389,7 @@ impl InferenceAgent for OpenAIAgent {
             result
                 .map(|bytes| StreamChunk {
                     data: String::from_utf8_lossy(&bytes).to_string(),
+                    timings: None,
                 })
                 .map_err(|e| AgentError::Network(e.to_string()))
         });
@@ -341,6 +404,10 @@ impl InferenceAgent for OpenAIAgent {
     async fn count_tokens(&self, _model_id: &str, text: &str) -> TokenCount {
         use tiktoken_rs::o200k_base;
 
+        if self.provider.is_some() {
+            return TokenCount::Heuristic((text.len() / 4) as u32);
+        }
+
         match o200k_base() {
             Ok(bpe) => {
                 let tokens = bpe.encode_ordinary(text);
@@ -360,77 +427,24 @@ impl InferenceAgent for OpenAIAgent {
         model: &str,
         input: Vec<String>,
     ) -> Result<Vec<Vec<f32>>, AgentError> {
-        let url = format!("{}/v1/embeddings", self.base_url);
-
-        let body = serde_json::json!({
-            "model": model,
-            "input": input,
-        });
-
-        let response = self
+        let (auth_header, auth) = self.auth(None);
+        let request = self
             .client
-            .post(&url)
-            .header("authorization", format!("Bearer {}", self.api_key))
-            .json(&body)
-            .timeout(Duration::from_secs(60))
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    AgentError::Timeout(60000)
-                } else {
-                    AgentError::Network(e.to_string())
-                }
-            })?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(AgentError::Upstream {
-                status: status.as_u16(),
-                message: error_body,
-            });
-        }
-
-        let body: serde_json::Value = response.json().await.map_err(|e| {
-            AgentError::InvalidResponse(format!(
-                "Failed to parse OpenAI embeddings response: {}",
-                e
-            ))
-        })?;
-
-        let data = body["data"].as_array().ok_or_else(|| {
-            AgentError::InvalidResponse(
-                "Missing data array in OpenAI embeddings response".to_string(),
-            )
-        })?;
-
-        let mut results = Vec::with_capacity(data.len());
-        for item in data {
-            let embedding = item["embedding"].as_array().ok_or_else(|| {
-                AgentError::InvalidResponse("Missing embedding array in response item".to_string())
-            })?;
-
-            let vector: Vec<f32> = embedding
-                .iter()
-                .filter_map(|v| v.as_f64().map(|f| f as f32))
-                .collect();
-
-            results.push(vector);
-        }
-
-        Ok(results)
+            .post(format!("{}/v1/embeddings", self.base_url))
+            .header(auth_header, auth)
+            .json(&serde_json::json!({ "model": model, "input": input }));
+        super::embeddings::send_embeddings_request(request, input.len()).await
     }
 }
 
 impl OpenAIAgent {
     /// Apply OpenAI-specific name heuristics for capability detection.
-    fn apply_name_heuristics(model: &mut ModelCapability) {
+    pub(crate) fn apply_name_heuristics(model: &mut ModelCapability) {
         let name = model.id.to_lowercase();
 
+        // Embedding model heuristics
+        model.supports_embeddings |= is_embedding_model(&name);
+
         // Vision support
         if name.contains("vision") || name.contains("gpt-4o") {
             model.supports_vision = true;
@@ -589,6 +603,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -607,6 +622,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -624,6 +640,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -640,6 +657,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -656,6 +674,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -674,6 +693,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -754,6 +774,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -773,6 +794,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -791,6 +813,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -819,6 +842,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -846,6 +870,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -869,6 +894,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -886,6 +912,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1593,6 +1620,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         };
@@ -1601,4 +1629,66 @@ mod tests {
         assert!(model.supports_json_mode);
         assert_eq!(model.context_length, 8192);
     }
+
+    fn provider_agent(base_url: String) -> OpenAIAgent {
+        test_agent(base_url, "acme-key".to_string()).with_provider(ProviderProfile {
+            name: "acme".to_string(),
+            auth_header: "x-api-key".to_string(),
+            auth_scheme: String::new(),
+            models_path: "/api/models".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_provider_list_models_uses_profile() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/models")
+            .match_header("x-api-key", "acme-key")
+            .with_status(200)
+            .with_body(r#"{"object":"list","data":[{"id":"acme-large","object":"model"}]}"#)
+            .create_async()
+            .await;
+
+        let agent = provider_agent(server.url());
+        let models = agent.list_models().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(models[0].id, "acme-large");
+        assert_eq!(agent.profile().backend_type, "acme");
+    }
+
+    #[tokio::test]
+    async fn test_provider_reapplies_scheme_to_client_key() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("x-api-key", "client-key")
+            .with_status(200)
+            .with_body(r#"{"id":"cmpl-1","object":"chat.completion","created":1234567890,"model":"acme-large","choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop"}]}"#)
+            .create_async()
+            .await;
+
+        let agent = provider_agent(server.url());
+        let request: ChatCompletionRequest =
+            serde_json::from_str(r#"{"model":"acme-large","messages":[]}"#).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer client-key".parse().unwrap());
+
+        agent
+            .chat_completion(request, Some(&headers))
+            .await
+            .unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_provider_count_tokens_is_heuristic() {
+        let agent = provider_agent("http://localhost".to_string());
+        let agent_ref: &dyn InferenceAgent = &agent;
+        assert_eq!(
+            agent_ref.count_tokens("acme-large", "abcdefgh").await,
+            TokenCount::Heuristic(2)
+        );
+    }
 }
@@ -1,8 +1,9 @@
 //! Cost estimation for cloud LLM providers.
 //!
 //! This module provides token-based cost estimation for cloud inference backends
-//! (OpenAI, Anthropic, Google AI). Pricing data is hardcoded and must be manually
-//! updated when providers change their pricing.
+//! (OpenAI, Azure OpenAI, Anthropic, Google AI). Pricing data is hardcoded and must be manually
+//! updated when providers change their pricing. Models served through the provider catalogue
+//! (`[providers.*]`) are added with [`PricingTable::with_models`].
 //!
 //! ## Pricing Strategy
 //!
@@ -27,11 +28,12 @@
 //! assert_eq!(cost, Some(0.025)); // $0.01/1K input + $0.03/1K output = $0.025
 //! ```
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Pricing for a specific model (input and output rates).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ModelPricing {
     /// Input (prompt) cost in USD per 1K tokens.
     pub input_price_per_1k: f64,
@@ -83,6 +85,30 @@ impl PricingTable {
                 output_price_per_1k: 0.0015,
             },
         );
+        prices.insert(
+            "gpt-4o".to_string(),
+            ModelPricing {
+                input_price_per_1k: 0.0025,
+                output_price_per_1k: 0.01,
+            },
+        );
+        prices.insert(
+            "gpt-4o-mini".to_string(),
+            ModelPricing {
+                input_price_per_1k: 0.00015,
+                output_price_per_1k: 0.0006,
+            },
+        );
+
+        // Azure OpenAI bills the same per-token rates as OpenAI, keyed by the
+        // model names in `deployments`; it spells GPT-3.5 without the dot
+        prices.insert(
+            "gpt-35-turbo".to_string(),
+            ModelPricing {
+                input_price_per_1k: 0.0005,
+                output_price_per_1k: 0.0015,
+            },
+        );
 
         // Anthropic Pricing (https://www.anthropic.com/pricing)
         prices.insert(
@@ -135,6 +161,12 @@ impl PricingTable {
         }
     }
 
+    /// Add or replace pricing for the given models.
+    pub fn with_models(mut self, models: impl IntoIterator<Item = (String, ModelPricing)>) -> Self {
+        Arc::make_mut(&mut self.prices).extend(models);
+        self
+    }
+
     /// Estimate cost for a model based on token counts.
     ///
     /// Returns `None` if the model is not in the pricing table.
@@ -282,6 +314,46 @@ mod tests {
         assert_eq!(cost, Some(0.06));
     }
 
+    #[test]
+    fn test_azure_model_names_priced() {
+        let pricing = PricingTable::new();
+        assert_eq!(
+            pricing.estimate_cost("gpt-35-turbo", 1000, 1000),
+            pricing.estimate_cost("gpt-3.5-turbo", 1000, 1000)
+        );
+        // (1000/1000)*0.0025 + (1000/1000)*0.01 = 0.0125
+        assert_eq!(pricing.estimate_cost("gpt-4o", 1000, 1000), Some(0.0125));
+    }
+
+    #[test]
+    fn test_with_models_adds_and_overrides() {
+        let pricing = PricingTable::new().with_models([
+            (
+                "mistral-large-latest".to_string(),
+                ModelPricing {
+                    input_price_per_1k: 0.002,
+                    output_price_per_1k: 0.006,
+                },
+            ),
+            (
+                "gpt-4".to_string(),
+                ModelPricing {
+                    input_price_per_1k: 0.0,
+                    output_price_per_1k: 0.0,
+                },
+            ),
+        ]);
+        assert_eq!(
+            pricing.estimate_cost("mistral-large-latest", 1000, 1000),
+            Some(0.008)
+        );
+        assert_eq!(pricing.estimate_cost("gpt-4", 1000, 1000), Some(0.0));
+        assert_eq!(
+            PricingTable::new().estimate_cost("gpt-4", 1000, 0),
+            Some(0.03)
+        );
+    }
+
     #[test]
     fn test_pricing_default() {
         let pricing = PricingTable::default();
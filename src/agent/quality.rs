@@ -419,7 +419,15 @@ mod tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         }
     }
 
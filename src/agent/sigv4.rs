@@ -0,0 +1,300 @@
+//! AWS Signature Version 4 request signing.
+//!
+//! Bedrock authenticates every call with SigV4: a canonical form of the
+//! request is hashed and signed with a key derived from the secret access
+//! key, date, region and service. Only header-based signing is implemented.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// AWS access key credentials.
+#[derive(Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Present for temporary (STS) credentials
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Read credentials from the named environment variables.
+    pub fn from_env(
+        access_key_id_var: &str,
+        secret_access_key_var: &str,
+        session_token_var: &str,
+    ) -> Result<Self, String> {
+        let read = |var: &str| {
+            std::env::var(var).map_err(|e| {
+                format!(
+                    "Failed to read AWS credential from env var '{}': {}",
+                    var, e
+                )
+            })
+        };
+        Ok(Self {
+            access_key_id: read(access_key_id_var)?,
+            secret_access_key: read(secret_access_key_var)?,
+            session_token: std::env::var(session_token_var).ok(),
+        })
+    }
+}
+
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compute the headers that sign a request.
+///
+/// `headers` are additional headers sent with the request that should be
+/// covered by the signature (e.g. `content-type`); `host` and `x-amz-date`
+/// are always signed. Returns `x-amz-date`, `x-amz-security-token` (for
+/// temporary credentials) and `authorization`, to be added to the request.
+#[allow(clippy::too_many_arguments)]
+pub fn sign(
+    method: &str,
+    url: &Url,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    time: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    let amz_date = time.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = time.format("%Y%m%d").to_string();
+
+    let mut signed: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value.trim().to_string()))
+        .collect();
+    signed.push(("host".to_string(), host(url)));
+    signed.push(("x-amz-date".to_string(), amz_date.clone()));
+    if let Some(token) = &credentials.session_token {
+        signed.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    signed.sort();
+
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method,
+        canonical_uri(url),
+        canonical_query(url),
+        canonical_headers,
+        signed_headers,
+        hex_sha256(body)
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        ALGORITHM,
+        amz_date,
+        scope,
+        hex_sha256(canonical_request.as_bytes())
+    );
+
+    let key = [date.as_str(), region, service, "aws4_request"]
+        .iter()
+        .fold(
+            format!("AWS4{}", credentials.secret_access_key).into_bytes(),
+            |key, part| hmac_sha256(&key, part.as_bytes()),
+        );
+    let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    let mut result = vec![("x-amz-date".to_string(), amz_date)];
+    if let Some(token) = &credentials.session_token {
+        result.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    result.push((
+        "authorization".to_string(),
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            ALGORITHM, credentials.access_key_id, scope, signed_headers, signature
+        ),
+    ));
+    result
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+pub fn uri_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Host header value as reqwest sends it (port only when non-default).
+fn host(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
+/// Path with each segment encoded again: services other than S3 sign the
+/// already-encoded path encoded a second time.
+fn canonical_uri(url: &Url) -> String {
+    let path = url.path();
+    if path.is_empty() {
+        return "/".to_string();
+    }
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| (uri_encode(&key), uri_encode(&value)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn example_credentials() -> AwsCredentials {
+        AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        }
+    }
+
+    fn example_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap()
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn matches_aws_get_vanilla_test_vector() {
+        let url = Url::parse("https://example.amazonaws.com/").unwrap();
+        let headers = sign(
+            "GET",
+            &url,
+            &[],
+            b"",
+            &example_credentials(),
+            "us-east-1",
+            "service",
+            example_time(),
+        );
+
+        assert_eq!(header(&headers, "x-amz-date"), Some("20150830T123600Z"));
+        assert_eq!(
+            header(&headers, "authorization"),
+            Some(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+                 SignedHeaders=host;x-amz-date, \
+                 Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+            )
+        );
+    }
+
+    #[test]
+    fn session_token_is_signed_and_returned() {
+        let mut credentials = example_credentials();
+        credentials.session_token = Some("session".to_string());
+        let url =
+            Url::parse("https://bedrock-runtime.us-east-1.amazonaws.com/model/m/converse").unwrap();
+        let headers = sign(
+            "POST",
+            &url,
+            &[("content-type", "application/json")],
+            b"{}",
+            &credentials,
+            "us-east-1",
+            "bedrock",
+            example_time(),
+        );
+
+        assert_eq!(header(&headers, "x-amz-security-token"), Some("session"));
+        assert!(header(&headers, "authorization")
+            .unwrap()
+            .contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token,"));
+    }
+
+    #[test]
+    fn path_segments_are_encoded_twice() {
+        let url = Url::parse(&format!(
+            "https://bedrock-runtime.us-east-1.amazonaws.com/model/{}/converse",
+            uri_encode("anthropic.claude-3-haiku-20240307-v1:0")
+        ))
+        .unwrap();
+        assert_eq!(
+            canonical_uri(&url),
+            "/model/anthropic.claude-3-haiku-20240307-v1%253A0/converse"
+        );
+    }
+
+    #[test]
+    fn query_is_sorted_and_encoded() {
+        let url = Url::parse("https://example.com/?b=2&a=x y").unwrap();
+        assert_eq!(canonical_query(&url), "a=x%20y&b=2");
+    }
+
+    #[test]
+    fn host_includes_non_default_port() {
+        assert_eq!(
+            host(&Url::parse("http://127.0.0.1:8080/").unwrap()),
+            "127.0.0.1:8080"
+        );
+        assert_eq!(
+            host(&Url::parse("https://example.com/").unwrap()),
+            "example.com"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! Hugging Face Text Embeddings Inference agent implementation.
+
+use super::generic::get_info;
+use super::tokenize::{BackendTokenCounter, TokenizeApi};
+use super::{
+    AgentCapabilities, AgentError, AgentProfile, HealthStatus, InferenceAgent, ModelCapability,
+    PrivacyZone, StreamChunk, TokenCount,
+};
+use crate::api::types::{ChatCompletionRequest, ChatCompletionResponse};
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use futures_util::stream::BoxStream;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Timeout for embed and rerank requests.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Text Embeddings Inference agent implementation.
+///
+/// A TEI server runs a single embedding, reranker or classifier model:
+/// - Health check via GET /health
+/// - Model listing from GET /info
+/// - Embeddings via POST /embed
+/// - Reranking via POST /rerank
+///
+/// TEI does not generate text, so chat completions are unsupported.
+pub struct TEIAgent {
+    /// Unique agent ID
+    id: String,
+    /// Human-readable name
+    name: String,
+    /// Base URL (e.g., "http://localhost:8080")
+    base_url: String,
+    /// Shared HTTP client for connection pooling
+    client: Arc<Client>,
+    /// Privacy zone classification from config
+    privacy_zone: PrivacyZone,
+    /// Capability tier from config
+    capability_tier: Option<u8>,
+    /// Exact token counting via POST /tokenize
+    token_counter: BackendTokenCounter,
+}
+
+impl TEIAgent {
+    pub fn new(
+        id: String,
+        name: String,
+        base_url: String,
+        client: Arc<Client>,
+        privacy_zone: PrivacyZone,
+        capability_tier: Option<u8>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            base_url,
+            client,
+            privacy_zone,
+            capability_tier,
+            token_counter: BackendTokenCounter::new(TokenizeApi::Tei),
+        }
+    }
+
+    /// POST a JSON body to `path`, returning upstream errors as `AgentError`.
+    async fn post(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<reqwest::Response, AgentError> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(body)
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(REQUEST_TIMEOUT.as_millis() as u64)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AgentError::Upstream {
+                status: status.as_u16(),
+                message: error_body,
+            });
+        }
+        Ok(response)
+    }
+}
+
+/// One entry of a TEI /rerank response
+#[derive(Deserialize)]
+struct RerankResult {
+    index: usize,
+    score: f32,
+}
+
+#[async_trait]
+impl InferenceAgent for TEIAgent {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn profile(&self) -> AgentProfile {
+        AgentProfile {
+            backend_type: "tei".to_string(),
+            version: None,
+            privacy_zone: self.privacy_zone,
+            capabilities: AgentCapabilities {
+                embeddings: true,
+                rerank: true,
+                model_lifecycle: false,
+                token_counting: true,
+                resource_monitoring: false,
+            },
+            capability_tier: self.capability_tier,
+        }
+    }
+
+    /// TEI answers GET /health with an empty 200 once the model is loaded.
+    async fn health_check(&self) -> Result<HealthStatus, AgentError> {
+        let url = format!("{}/health", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AgentError::Timeout(5000)
+                } else {
+                    AgentError::Network(e.to_string())
+                }
+            })?;
+
+        if !response.status().is_success() {
+            return Ok(HealthStatus::Unhealthy);
+        }
+
+        Ok(HealthStatus::Healthy { model_count: 1 })
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelCapability>, AgentError> {
+        let body = get_info(&self.client, &self.base_url).await?;
+        let models = crate::health::parser::parse_tei_info_response(&body)
+            .map_err(|e| AgentError::InvalidResponse(e.to_string()))?;
+        Ok(models.into_iter().map(ModelCapability::from).collect())
+    }
+
+    async fn chat_completion(
+        &self,
+        _request: ChatCompletionRequest,
+        _headers: Option<&HeaderMap>,
+    ) -> Result<ChatCompletionResponse, AgentError> {
+        Err(AgentError::Unsupported("chat_completion"))
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        _request: ChatCompletionRequest,
+        _headers: Option<&HeaderMap>,
+    ) -> Result<BoxStream<'static, Result<StreamChunk, AgentError>>, AgentError> {
+        Err(AgentError::Unsupported("chat_completion_stream"))
+    }
+
+    async fn count_tokens(&self, model_id: &str, text: &str) -> TokenCount {
+        self.token_counter
+            .count(&self.client, &self.base_url, model_id, text)
+            .await
+    }
+
+    /// Generate embeddings via POST /embed, which returns one vector per input.
+    async fn embeddings(
+        &self,
+        _model: &str,
+        input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, AgentError> {
+        let response = self
+            .post("/embed", &serde_json::json!({ "inputs": input }))
+            .await?;
+
+        response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to parse embed response: {}", e))
+        })
+    }
+
+    /// Score documents via POST /rerank. TEI sorts results by score, so they
+    /// are put back in input order.
+    async fn rerank(
+        &self,
+        _model: &str,
+        query: &str,
+        documents: Vec<String>,
+    ) -> Result<Vec<f32>, AgentError> {
+        let count = documents.len();
+        let response = self
+            .post(
+                "/rerank",
+                &serde_json::json!({ "query": query, "texts": documents }),
+            )
+            .await?;
+
+        let results: Vec<RerankResult> = response.json().await.map_err(|e| {
+            AgentError::InvalidResponse(format!("Failed to parse rerank response: {}", e))
+        })?;
+
+        scores_in_input_order(count, results.into_iter().map(|r| (r.index, r.score)))
+    }
+}
+
+/// Put `(index, score)` pairs from a relevance-sorted rerank response back in
+/// document order, failing if any document is missing.
+pub(crate) fn scores_in_input_order(
+    count: usize,
+    results: impl IntoIterator<Item = (usize, f32)>,
+) -> Result<Vec<f32>, AgentError> {
+    let mut scores = vec![None; count];
+    for (index, score) in results {
+        if let Some(slot) = scores.get_mut(index) {
+            *slot = Some(score);
+        }
+    }
+    scores
+        .into_iter()
+        .collect::<Option<Vec<f32>>>()
+        .ok_or_else(|| AgentError::InvalidResponse("Rerank response is missing documents".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn test_agent(base_url: String) -> TEIAgent {
+        TEIAgent::new(
+            "test-tei".to_string(),
+            "Test TEI".to_string(),
+            base_url,
+            Arc::new(Client::new()),
+            PrivacyZone::Restricted,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_profile() {
+        let profile = test_agent("http://localhost:8080".to_string()).profile();
+        assert_eq!(profile.backend_type, "tei");
+        assert!(profile.capabilities.embeddings);
+        assert!(profile.capabilities.rerank);
+    }
+
+    #[tokio::test]
+    async fn test_health_and_list_models() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/health")
+            .with_status(200)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/info")
+            .with_status(200)
+            .with_body(
+                r#"{"model_id":"BAAI/bge-base-en-v1.5","model_type":{"embedding":{"pooling":"cls"}},"max_input_length":512,"version":"1.5.0"}"#,
+            )
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        assert_eq!(
+            agent.health_check().await.unwrap(),
+            HealthStatus::Healthy { model_count: 1 }
+        );
+        let models = agent.list_models().await.unwrap();
+        assert_eq!(models[0].id, "BAAI/bge-base-en-v1.5");
+        assert_eq!(models[0].context_length, 512);
+        assert!(models[0].supports_embeddings);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_use_embed_endpoint() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/embed")
+            .match_body(Matcher::Json(serde_json::json!({"inputs": ["a", "b"]})))
+            .with_status(200)
+            .with_body("[[0.1,0.2],[0.3,0.4]]")
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let vectors = agent
+            .embeddings("bge", vec!["a".to_string(), "b".to_string()])
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(vectors, vec![vec![0.1, 0.2], vec![0.3, 0.4]]);
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_input_too_long() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/embed")
+            .with_status(413)
+            .with_body(r#"{"error":"Input validation error: inputs must have less than 512 tokens","error_type":"Validation"}"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let result = agent.embeddings("bge", vec!["long".to_string()]).await;
+        assert!(matches!(
+            result,
+            Err(AgentError::Upstream { status: 413, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rerank_restores_input_order() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/rerank")
+            .match_body(Matcher::Json(serde_json::json!({
+                "query": "What is Deep Learning?",
+                "texts": ["Cheese is tasty.", "Deep Learning is a subset of ML."]
+            })))
+            .with_status(200)
+            .with_body(r#"[{"index":1,"score":0.98},{"index":0,"score":0.02}]"#)
+            .create_async()
+            .await;
+
+        let agent = test_agent(server.url());
+        let scores = agent
+            .rerank(
+                "bge-reranker",
+                "What is Deep Learning?",
+                vec![
+                    "Cheese is tasty.".to_string(),
+                    "Deep Learning is a subset of ML.".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(scores, vec![0.02, 0.98]);
+    }
+
+    #[tokio::test]
+    async fn test_chat_is_unsupported() {
+        let agent = test_agent("http://localhost:8080".to_string());
+        let request: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "bge",
+            "messages": [{"role": "user", "content": "Hi"}]
+        }))
+        .unwrap();
+        assert!(matches!(
+            agent.chat_completion(request, None).await,
+            Err(AgentError::Unsupported("chat_completion"))
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+//! Exact token counting through backend tokenize endpoints.
+//!
+//! llama.cpp, vLLM, Ollama, TGI and TEI tokenize text with the loaded model's own
+//! tokenizer. Counts are cached per model, so repeated prompt fragments
+//! (system prompts, earlier conversation turns) cost one backend call. When
+//! the endpoint is missing or fails, counting falls back to chars/4.
+
+use super::TokenCount;
+use lru::LruCache;
+use parking_lot::Mutex;
+use reqwest::{Client, StatusCode};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Cached counts kept per model.
+const CACHE_ENTRIES_PER_MODEL: usize = 1024;
+
+/// Tokenize calls sit on the request path, so they get a short timeout.
+const TOKENIZE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Tokenize API flavour exposed by a backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizeApi {
+    /// llama.cpp server: `POST /tokenize {"content"}` → `{"tokens": [...]}`
+    LlamaCpp,
+    /// vLLM: `POST /tokenize {"model", "prompt"}` → `{"count", "tokens"}`
+    Vllm,
+    /// Ollama: `POST /api/tokenize {"model", "content"}` → `{"tokens": [...]}`
+    Ollama,
+    /// TGI: `POST /tokenize {"inputs"}` → `[{"id", "text", ...}]`
+    Tgi,
+    /// TEI: `POST /tokenize {"inputs"}` → `[[{"id", "text", ...}]]`
+    Tei,
+}
+
+impl TokenizeApi {
+    fn request(self, base_url: &str, model: &str, text: &str) -> (String, serde_json::Value) {
+        match self {
+            TokenizeApi::LlamaCpp => (
+                format!("{}/tokenize", base_url),
+                serde_json::json!({"content": text}),
+            ),
+            TokenizeApi::Vllm => (
+                format!("{}/tokenize", base_url),
+                serde_json::json!({"model": model, "prompt": text, "add_special_tokens": false}),
+            ),
+            TokenizeApi::Ollama => (
+                format!("{}/api/tokenize", base_url),
+                serde_json::json!({"model": model, "content": text}),
+            ),
+            TokenizeApi::Tgi => (
+                format!("{}/tokenize", base_url),
+                serde_json::json!({"inputs": text}),
+            ),
+            TokenizeApi::Tei => (
+                format!("{}/tokenize", base_url),
+                serde_json::json!({"inputs": text, "add_special_tokens": false}),
+            ),
+        }
+    }
+}
+
+/// Token counter backed by a backend's tokenize endpoint, with a per-model
+/// LRU of results.
+pub struct BackendTokenCounter {
+    api: TokenizeApi,
+    /// model → (text hash → token count)
+    cache: Mutex<HashMap<String, LruCache<u64, u32>>>,
+    /// Set once the backend answers 405/501, to stop probing it. A 404 may
+    /// only mean the model is unknown, so it does not latch.
+    unsupported: AtomicBool,
+}
+
+impl BackendTokenCounter {
+    pub fn new(api: TokenizeApi) -> Self {
+        Self {
+            api,
+            cache: Mutex::new(HashMap::new()),
+            unsupported: AtomicBool::new(false),
+        }
+    }
+
+    /// Count tokens in `text` with `model`'s tokenizer on the backend.
+    ///
+    /// Returns `Exact` from the cache or backend, `Heuristic` otherwise.
+    pub async fn count(
+        &self,
+        client: &Client,
+        base_url: &str,
+        model: &str,
+        text: &str,
+    ) -> TokenCount {
+        let key = text_key(text);
+        if let Some(count) = self.cached(model, key) {
+            return TokenCount::Exact(count);
+        }
+        if self.unsupported.load(Ordering::Relaxed) {
+            return heuristic(text);
+        }
+
+        match self.tokenize(client, base_url, model, text).await {
+            Some(count) => {
+                self.store(model, key, count);
+                TokenCount::Exact(count)
+            }
+            None => heuristic(text),
+        }
+    }
+
+    async fn tokenize(
+        &self,
+        client: &Client,
+        base_url: &str,
+        model: &str,
+        text: &str,
+    ) -> Option<u32> {
+        let (url, body) = self.api.request(base_url, model, text);
+        let response = match client
+            .post(&url)
+            .json(&body)
+            .timeout(TOKENIZE_TIMEOUT)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::debug!(url = %url, error = %e, "Tokenize request failed");
+                return None;
+            }
+        };
+
+        let status = response.status();
+        if matches!(
+            status,
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        ) {
+            tracing::info!(url = %url, status = %status, "Backend has no tokenize endpoint");
+            self.unsupported.store(true, Ordering::Relaxed);
+            return None;
+        }
+        if !status.is_success() {
+            tracing::debug!(url = %url, status = %status, "Tokenize request rejected");
+            return None;
+        }
+
+        let body: serde_json::Value = response.json().await.ok()?;
+        body["count"]
+            .as_u64()
+            .or_else(|| body["tokens"].as_array().map(|tokens| tokens.len() as u64))
+            .or_else(|| token_list_len(&body))
+            .map(|count| count as u32)
+    }
+
+    fn cached(&self, model: &str, key: u64) -> Option<u32> {
+        let mut cache = self.cache.lock();
+        cache.get_mut(model)?.get(&key).copied()
+    }
+
+    fn store(&self, model: &str, key: u64, count: u32) {
+        let capacity = NonZeroUsize::new(CACHE_ENTRIES_PER_MODEL).unwrap_or(NonZeroUsize::MIN);
+        self.cache
+            .lock()
+            .entry(model.to_string())
+            .or_insert_with(|| LruCache::new(capacity))
+            .put(key, count);
+    }
+}
+
+/// Length of a bare token list, or of the first list in a batch (TEI).
+fn token_list_len(body: &serde_json::Value) -> Option<u64> {
+    let tokens = body.as_array()?;
+    match tokens.first() {
+        Some(serde_json::Value::Array(first)) => Some(first.len() as u64),
+        _ => Some(tokens.len() as u64),
+    }
+}
+
+fn text_key(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn heuristic(text: &str) -> TokenCount {
+    TokenCount::Heuristic((text.len() / 4) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn llamacpp_counts_are_exact_and_cached() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/tokenize")
+            .match_body(Matcher::Json(serde_json::json!({"content": "Hello world"})))
+            .with_status(200)
+            .with_body(r#"{"tokens":[9906,1917]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let counter = BackendTokenCounter::new(TokenizeApi::LlamaCpp);
+        let client = Client::new();
+        for _ in 0..2 {
+            let count = counter
+                .count(&client, &server.url(), "llama3", "Hello world")
+                .await;
+            assert_eq!(count, TokenCount::Exact(2));
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn vllm_uses_reported_count() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/tokenize")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"model": "qwen", "prompt": "Hi"}),
+            ))
+            .with_status(200)
+            .with_body(r#"{"count":3,"max_model_len":32768,"tokens":[1,2,3]}"#)
+            .create_async()
+            .await;
+
+        let counter = BackendTokenCounter::new(TokenizeApi::Vllm);
+        let count = counter
+            .count(&Client::new(), &server.url(), "qwen", "Hi")
+            .await;
+
+        mock.assert_async().await;
+        assert_eq!(count, TokenCount::Exact(3));
+    }
+
+    #[tokio::test]
+    async fn tgi_and_tei_count_token_lists() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/tokenize")
+            .match_body(Matcher::Json(serde_json::json!({"inputs": "Hi there"})))
+            .with_status(200)
+            .with_body(r#"[{"id":1,"text":"Hi","start":0,"stop":2},{"id":2,"text":" there","start":2,"stop":8}]"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/tokenize")
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"inputs": "Hello", "add_special_tokens": false}),
+            ))
+            .with_status(200)
+            .with_body(r#"[[{"id":7592,"text":"hello","special":false,"start":0,"stop":5}]]"#)
+            .create_async()
+            .await;
+
+        let client = Client::new();
+        let tgi = BackendTokenCounter::new(TokenizeApi::Tgi);
+        assert_eq!(
+            tgi.count(&client, &server.url(), "llama", "Hi there").await,
+            TokenCount::Exact(2)
+        );
+        let tei = BackendTokenCounter::new(TokenizeApi::Tei);
+        assert_eq!(
+            tei.count(&client, &server.url(), "bge", "Hello").await,
+            TokenCount::Exact(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_is_per_model() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/tokenize")
+            .with_status(200)
+            .with_body(r#"{"tokens":[1,2,3,4]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let counter = BackendTokenCounter::new(TokenizeApi::Ollama);
+        let client = Client::new();
+        counter
+            .count(&client, &server.url(), "llama3", "same")
+            .await;
+        counter
+            .count(&client, &server.url(), "mistral", "same")
+            .await;
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn missing_endpoint_falls_back_and_stops_probing() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/tokenize")
+            .with_status(501)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let counter = BackendTokenCounter::new(TokenizeApi::Ollama);
+        let client = Client::new();
+        for text in ["first text", "second text"] {
+            let count = counter.count(&client, &server.url(), "llama3", text).await;
+            assert!(matches!(count, TokenCount::Heuristic(_)));
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn not_found_falls_back_without_disabling() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/tokenize")
+            .with_status(404)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let counter = BackendTokenCounter::new(TokenizeApi::Ollama);
+        let client = Client::new();
+        for model in ["pulled-later", "llama3"] {
+            let count = counter.count(&client, &server.url(), model, "text").await;
+            assert!(matches!(count, TokenCount::Heuristic(_)));
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn server_errors_fall_back_without_disabling() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/tokenize")
+            .with_status(500)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let counter = BackendTokenCounter::new(TokenizeApi::LlamaCpp);
+        let client = Client::new();
+        for _ in 0..2 {
+            let count = counter.count(&client, &server.url(), "m", "12345678").await;
+            assert_eq!(count, TokenCount::Heuristic(2));
+        }
+        mock.assert_async().await;
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module provides provider-specific tokenizers for audit-grade token counting
 //! across OpenAI, Anthropic, and other LLM providers. It supports three tiers:
-//! - **Exact**: Uses provider's official tokenizer (e.g., tiktoken for OpenAI)
+//! - **Exact**: Uses provider's official tokenizer (e.g., tiktoken for OpenAI, or a
+//!   model's Hugging Face `tokenizer.json` configured under `[[routing.tokenizers]]`)
 //! - **Approximation**: Uses similar tokenizer as proxy (e.g., cl100k_base for Anthropic)
 //! - **Heuristic**: Falls back to conservative character-based estimation
 //!
@@ -18,6 +19,7 @@
 //! # }
 //! ```
 
+use crate::config::TokenizerFile;
 use globset::{Glob, GlobMatcher};
 use std::sync::Arc;
 use thiserror::Error;
@@ -42,6 +44,10 @@ pub enum TokenizerError {
     /// Failed to compile glob pattern
     #[error("Invalid glob pattern: {0}")]
     GlobPattern(#[from] globset::Error),
+
+    /// Failed to load a tokenizer file
+    #[error("Failed to load tokenizer {path}: {message}")]
+    Load { path: String, message: String },
 }
 
 /// Trait for token counting implementations
@@ -142,6 +148,45 @@ impl Tokenizer for TiktokenApproximationTokenizer {
     }
 }
 
+/// Exact tokenizer loaded from a Hugging Face `tokenizer.json`
+pub struct HuggingFaceTokenizer {
+    tokenizer: tokenizers::Tokenizer,
+}
+
+impl HuggingFaceTokenizer {
+    /// Load a tokenizer from a `tokenizer.json` file
+    pub fn from_file(path: &std::path::Path) -> Result<Self, TokenizerError> {
+        let tokenizer =
+            tokenizers::Tokenizer::from_file(path).map_err(|e| TokenizerError::Load {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(Self { tokenizer })
+    }
+}
+
+impl Tokenizer for HuggingFaceTokenizer {
+    fn count_tokens(&self, text: &str) -> Result<u32, TokenizerError> {
+        // Special tokens (BOS etc.) are left out, matching the tiktoken counts
+        let encoding = self
+            .tokenizer
+            .encode(text, false)
+            .map_err(|e| TokenizerError::Encoding(e.to_string()))?;
+        encoding
+            .len()
+            .try_into()
+            .map_err(|e| TokenizerError::Encoding(format!("Token count overflow: {}", e)))
+    }
+
+    fn tier(&self) -> u8 {
+        TIER_EXACT
+    }
+
+    fn name(&self) -> &str {
+        "huggingface"
+    }
+}
+
 /// Heuristic tokenizer using character-based estimation
 pub struct HeuristicTokenizer {
     multiplier: f64, // Conservative multiplier for character-based estimation
@@ -190,7 +235,22 @@ pub struct TokenizerRegistry {
 impl TokenizerRegistry {
     /// Create registry with default OpenAI/Anthropic/fallback configuration
     pub fn new() -> Result<Self, TokenizerError> {
-        let mut matchers = Vec::new();
+        Self::with_files(&[])
+    }
+
+    /// Create registry with the default configuration plus Hugging Face
+    /// tokenizer files, which take precedence over the built-in patterns
+    pub fn with_files(files: &[TokenizerFile]) -> Result<Self, TokenizerError> {
+        let mut matchers: Vec<(GlobMatcher, Arc<dyn Tokenizer>)> = Vec::new();
+
+        // Configured tokenizer.json files → exact, first match wins
+        for file in files {
+            let glob = Glob::new(&file.model_pattern)?;
+            matchers.push((
+                glob.compile_matcher(),
+                Arc::new(HuggingFaceTokenizer::from_file(&file.path)?),
+            ));
+        }
 
         // OpenAI GPT-4 Turbo, GPT-4o → o200k_base (exact)
         let o200k_patterns = vec!["gpt-4-turbo*", "gpt-4o*"];
@@ -512,6 +572,78 @@ mod tests {
         );
     }
 
+    // === HuggingFaceTokenizer ===
+
+    /// Minimal word-level tokenizer.json: whitespace split, 3-word vocabulary
+    fn write_tokenizer_json(dir: &std::path::Path) -> std::path::PathBuf {
+        let path = dir.join("tokenizer.json");
+        let json = serde_json::json!({
+            "version": "1.0",
+            "truncation": null,
+            "padding": null,
+            "added_tokens": [],
+            "normalizer": null,
+            "pre_tokenizer": {"type": "Whitespace"},
+            "post_processor": null,
+            "decoder": null,
+            "model": {
+                "type": "WordLevel",
+                "vocab": {"hello": 0, "world": 1, "[UNK]": 2},
+                "unk_token": "[UNK]"
+            }
+        });
+        std::fs::write(&path, json.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn huggingface_tokenizer_counts_exactly() {
+        let dir = tempfile::tempdir().unwrap();
+        let t = HuggingFaceTokenizer::from_file(&write_tokenizer_json(dir.path())).unwrap();
+        assert_eq!(t.tier(), TIER_EXACT);
+        assert_eq!(t.name(), "huggingface");
+        assert_eq!(t.count_tokens("hello world hello unknown").unwrap(), 4);
+        assert_eq!(t.count_tokens("").unwrap(), 0);
+    }
+
+    #[test]
+    fn huggingface_tokenizer_missing_file_errors() {
+        let result = HuggingFaceTokenizer::from_file(std::path::Path::new("/nonexistent.json"));
+        assert!(matches!(result, Err(TokenizerError::Load { .. })));
+    }
+
+    #[test]
+    fn registry_configured_files_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_tokenizer_json(dir.path());
+        let r = TokenizerRegistry::with_files(&[
+            TokenizerFile {
+                model_pattern: "llama3*".to_string(),
+                path: path.clone(),
+            },
+            TokenizerFile {
+                model_pattern: "gpt-4o*".to_string(),
+                path,
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(r.get_tokenizer("llama3:8b").name(), "huggingface");
+        assert_eq!(r.get_tokenizer("gpt-4o-mini").name(), "huggingface");
+        assert_eq!(r.get_tokenizer("gpt-4").name(), "tiktoken_cl100k_base");
+        assert_eq!(r.get_tokenizer("mistral:7b").tier(), TIER_HEURISTIC);
+        assert_eq!(r.count_tokens("llama3:8b", "hello world").unwrap(), 2);
+    }
+
+    #[test]
+    fn registry_with_unreadable_file_errors() {
+        let result = TokenizerRegistry::with_files(&[TokenizerFile {
+            model_pattern: "llama3*".to_string(),
+            path: "/nonexistent/tokenizer.json".into(),
+        }]);
+        assert!(matches!(result, Err(TokenizerError::Load { .. })));
+    }
+
     // === SC-001: Exact tokenizer accuracy within 5% variance ===
 
     #[test]
@@ -39,6 +39,10 @@ pub struct AgentCapabilities {
     /// Supports /v1/embeddings endpoint.
     pub embeddings: bool,
 
+    /// Supports /v1/rerank endpoint.
+    #[serde(default)]
+    pub rerank: bool,
+
     /// Supports model load/unload lifecycle operations.
     pub model_lifecycle: bool,
 
@@ -101,6 +105,10 @@ pub struct ModelCapability {
     /// Supports JSON mode.
     pub supports_json_mode: bool,
 
+    /// Produces embeddings.
+    #[serde(default)]
+    pub supports_embeddings: bool,
+
     /// Maximum output tokens (if limited).
     pub max_output_tokens: Option<u32>,
 
@@ -118,6 +126,7 @@ impl From<Model> for ModelCapability {
             supports_vision: model.supports_vision,
             supports_tools: model.supports_tools,
             supports_json_mode: model.supports_json_mode,
+            supports_embeddings: model.supports_embeddings,
             max_output_tokens: model.max_output_tokens,
             capability_tier: None, // Phase 1: Always None
         }
@@ -133,6 +142,7 @@ impl From<ModelCapability> for Model {
             supports_vision: cap.supports_vision,
             supports_tools: cap.supports_tools,
             supports_json_mode: cap.supports_json_mode,
+            supports_embeddings: cap.supports_embeddings,
             max_output_tokens: cap.max_output_tokens,
         }
     }
@@ -161,6 +171,28 @@ impl TokenCount {
     }
 }
 
+/// Server-side timings reported by backends that measure generation
+/// themselves (e.g., Ollama's native API).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenerationTimings {
+    /// Time until the first output token: model load plus prompt evaluation.
+    pub ttft_ms: u32,
+
+    /// Time spent generating output tokens.
+    pub generation_ms: u32,
+
+    /// Number of output tokens generated.
+    pub completion_tokens: u32,
+}
+
+impl GenerationTimings {
+    /// Output tokens per second, or `None` if no generation time was reported.
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        (self.generation_ms > 0)
+            .then(|| self.completion_tokens as f64 * 1000.0 / self.generation_ms as f64)
+    }
+}
+
 /// Backend resource usage for fleet intelligence (F19, v0.5).
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ResourceUsage {
@@ -173,6 +205,10 @@ pub struct ResourceUsage {
     /// Number of pending inference requests.
     pub pending_requests: Option<u32>,
 
+    /// Fraction of the KV cache in use (0.0-1.0).
+    #[serde(default)]
+    pub kv_cache_usage: Option<f64>,
+
     /// Average request latency in milliseconds.
     pub avg_latency_ms: Option<u32>,
 
@@ -277,6 +313,11 @@ pub struct PrewarmingRecommendation {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamChunk {
     pub data: String,
+
+    /// Backend-measured timings, set on the final chunk of a stream by
+    /// backends that report them; never sent to clients
+    #[serde(skip)]
+    pub timings: Option<GenerationTimings>,
 }
 
 #[cfg(test)]
@@ -293,6 +334,7 @@ mod tests {
             supports_vision: true,
             supports_tools: false,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         };
 
@@ -315,6 +357,7 @@ mod tests {
             supports_vision: true,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
             capability_tier: Some(3),
         };
@@ -374,6 +417,7 @@ mod tests {
             privacy_zone: PrivacyZone::Restricted,
             capabilities: AgentCapabilities {
                 embeddings: false,
+                rerank: false,
                 model_lifecycle: true,
                 token_counting: false,
                 resource_monitoring: true,
@@ -409,6 +453,7 @@ mod tests {
     fn stream_chunk_serialization() {
         let chunk = StreamChunk {
             data: "hello world".to_string(),
+            timings: None,
         };
         let json = serde_json::to_string(&chunk).unwrap();
         let deserialized: StreamChunk = serde_json::from_str(&json).unwrap();
@@ -0,0 +1,49 @@
+//! Response cache management API endpoints.
+//!
+//! Lets clients drop their own semantic cache entries, e.g. after updating the
+//! knowledge base a support bot answers from.
+
+use axum::http::HeaderMap;
+use axum::{extract::Query, extract::State, Json};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::api::types::ApiError;
+use crate::api::AppState;
+
+#[derive(serde::Deserialize)]
+pub struct InvalidateQuery {
+    /// Only drop entries for this model (all models when omitted)
+    pub model: Option<String>,
+}
+
+/// DELETE /v1/cache/semantic
+///
+/// Invalidate the caller's semantic cache entries. The tenant is identified
+/// by the Authorization header, so callers can only clear their own entries.
+///
+/// # Errors
+///
+/// - 404 Not Found: Semantic cache is not enabled
+pub async fn handle_invalidate(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<InvalidateQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let semantic = state
+        .response_cache
+        .semantic()
+        .ok_or_else(|| ApiError::not_found("Semantic cache is not enabled"))?;
+
+    let invalidated = semantic.invalidate(&headers, query.model.as_deref());
+    info!(
+        model = ?query.model,
+        invalidated,
+        "Semantic cache entries invalidated"
+    );
+
+    Ok(Json(serde_json::json!({
+        "invalidated": invalidated,
+        "model": query.model,
+    })))
+}
@@ -1,14 +1,24 @@
 //! Chat completions endpoint handler.
 
+use crate::agent::circuit_breaker::DispatchPermit;
+use crate::agent::PrivacyZone;
 use crate::api::{
     headers::{NexusTransparentHeaders, RouteReason},
     ApiError, AppState, ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse,
-    ChunkChoice, ChunkDelta,
+    ChunkChoice, ChunkDelta, Usage,
 };
+use crate::cache::{
+    replay_chunks, CacheNamespace, ResponseCache, ResponseFlight, SemanticScope, SingleFlight,
+    StreamFlight, CACHE_HEADER, CACHE_SIMILARITY_HEADER, SINGLE_FLIGHT_HEADER,
+};
+use crate::config::{ContextOverflowMode, ModerationConfig};
+use crate::guardrails::{self, GuardBlocked, GuardReport, StreamGuard};
 use crate::logging::generate_request_id;
 use crate::registry::Backend;
 use crate::routing::reconciler::intent::{RejectionReason, TierEnforcementMode};
-use crate::routing::RequestRequirements;
+use crate::routing::{ContextFit, RequestFeatures, RequestRequirements, SemanticDecision};
+use crate::structured::{SchemaReport, StructuredOutput};
+use crate::tools::ToolEmulation;
 use axum::{
     extract::State,
     http::{HeaderMap, HeaderName, HeaderValue},
@@ -18,6 +28,7 @@ use axum::{
     },
     Json,
 };
+use futures::StreamExt;
 use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::{info, instrument, warn, Span};
@@ -37,6 +48,30 @@ const FLEXIBLE_HEADER: &str = "x-nexus-flexible";
 /// Header name for request priority (T028)
 const PRIORITY_HEADER: &str = "x-nexus-priority";
 
+/// Header name for context overflow handling: request mode and response action
+const CONTEXT_OVERFLOW_HEADER: &str = "x-nexus-context-overflow";
+
+/// Header name for the client-supplied tag matched by content routing rules
+const ROUTE_TAG_HEADER: &str = "x-nexus-route-tag";
+
+/// Header name reporting the content routing rule that matched
+const ROUTE_RULE_HEADER: &str = "x-nexus-route-rule";
+
+/// Header name listing the request transforms that were applied
+const TRANSFORMS_HEADER: &str = "x-nexus-transforms";
+
+/// Header name listing the output guardrails that redacted or flagged content
+const GUARDRAILS_HEADER: &str = "x-nexus-guardrails";
+
+/// Header name reporting JSON schema validation: passed, repaired or failed
+const SCHEMA_VALIDATION_HEADER: &str = "x-nexus-schema-validation";
+
+/// Header name reporting the model calls made to obtain schema-valid output
+const SCHEMA_ATTEMPTS_HEADER: &str = "x-nexus-schema-attempts";
+
+/// Header name reporting that tool calling was emulated for the model
+const TOOL_EMULATION_HEADER: &str = "x-nexus-tool-emulation";
+
 /// Extract tier enforcement mode from request headers (FR-007, FR-008, FR-009).
 ///
 /// # Header Priority
@@ -80,6 +115,166 @@ fn extract_priority(headers: &HeaderMap) -> crate::queue::Priority {
         .unwrap_or(crate::queue::Priority::Normal)
 }
 
+/// Request state computed before backend selection.
+struct PreparedRequest {
+    /// Requirements for the (possibly rewritten) request
+    requirements: RequestRequirements,
+    /// Context overflow action taken, if any
+    context_fit: ContextFit,
+    /// Pre-routing decisions that selected the model
+    attribution: RouteAttribution,
+}
+
+/// Pre-routing decisions reported in response headers.
+#[derive(Debug, Default)]
+struct RouteAttribution {
+    /// Names of the request transforms applied, in order
+    transforms: Vec<String>,
+    /// Name of the content routing rule that matched, if any
+    content_rule: Option<String>,
+    /// Semantic `nexus/auto` decision, if any
+    semantic: Option<SemanticDecision>,
+}
+
+impl RouteAttribution {
+    /// Attribute standard capability-match routing to the pre-routing decision
+    /// that selected the model, with optional detail for the route reason
+    /// header. Privacy, capacity and failover reasons take precedence.
+    fn route_reason(&self, reason: RouteReason) -> (RouteReason, Option<String>) {
+        if reason != RouteReason::CapabilityMatch {
+            return (reason, None);
+        }
+        if let Some(decision) = &self.semantic {
+            let reason = if decision.route.is_some() {
+                RouteReason::SemanticMatch
+            } else {
+                RouteReason::SemanticFallback
+            };
+            return (reason, Some(decision.detail()));
+        }
+        if self.content_rule.is_some() {
+            return (RouteReason::ContentRule, None);
+        }
+        (reason, None)
+    }
+
+    /// Report the applied transforms and the content routing rule that
+    /// matched, if any.
+    fn inject_into_response<B>(&self, response: &mut Response<B>) {
+        if !self.transforms.is_empty() {
+            if let Ok(header_value) = HeaderValue::from_str(&self.transforms.join(",")) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(TRANSFORMS_HEADER), header_value);
+            }
+        }
+        if let Some(rule) = &self.content_rule {
+            if let Ok(header_value) = HeaderValue::from_str(rule) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(ROUTE_RULE_HEADER), header_value);
+            }
+        }
+    }
+}
+
+/// Apply request transforms, count prompt tokens, apply content routing rules
+/// and semantic `nexus/auto` selection, recount the prompt with the backend's
+/// tokenizer where available, then apply context overflow handling before
+/// routing.
+///
+/// The overflow mode comes from the `X-Nexus-Context-Overflow` header, falling
+/// back to the matching traffic policy. Without either, oversized requests are
+/// left for the router, which finds no backend with a large enough context window.
+async fn prepare_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &mut ChatCompletionRequest,
+) -> Result<PreparedRequest, ApiError> {
+    let tenant = crate::cache::authenticated_tenant(headers);
+    let transforms = state.router.apply_transforms(request, tenant.as_deref());
+    let mut requirements = state.router.requirements_for(request);
+
+    let route_tag = headers.get(ROUTE_TAG_HEADER).and_then(|v| v.to_str().ok());
+    let content_rule = state
+        .router
+        .apply_content_rules(request, &mut requirements, route_tag);
+    let semantic = state
+        .router
+        .apply_semantic_routing(request, &mut requirements)
+        .await;
+    state
+        .router
+        .count_tokens_on_backend(request, &mut requirements)
+        .await;
+
+    let context_fit = fit_context_window(state, headers, request, &mut requirements)?;
+
+    // Screen last: the classifier sees the final messages, and the risk score
+    // survives the requirement rebuilds done by the stages above.
+    state
+        .router
+        .apply_injection_screening(request, &mut requirements, tenant.as_deref())
+        .await;
+    Ok(PreparedRequest {
+        requirements,
+        context_fit,
+        attribution: RouteAttribution {
+            transforms,
+            content_rule,
+            semantic,
+        },
+    })
+}
+
+/// Apply the requested context overflow handling, if any.
+fn fit_context_window(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &mut ChatCompletionRequest,
+    requirements: &mut RequestRequirements,
+) -> Result<ContextFit, ApiError> {
+    let mode = headers
+        .get(CONTEXT_OVERFLOW_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(ContextOverflowMode::from_header)
+        .or_else(|| state.router.context_overflow_policy(&request.model));
+    let Some(mode) = mode else {
+        return Ok(ContextFit::Fits);
+    };
+
+    state
+        .router
+        .fit_context_window(request, requirements, mode)
+        .map_err(|e| {
+            warn!(
+                model = %e.model,
+                required_tokens = e.required_tokens,
+                context_length = e.context_length,
+                "Context window exceeded"
+            );
+            let sanitized_model = state.metrics_collector.sanitize_label(&e.model);
+            metrics::counter!("nexus_errors_total",
+                "error_type" => "context_length_exceeded",
+                "model" => sanitized_model
+            )
+            .increment(1);
+            ApiError::context_length_exceeded(&e.to_string())
+        })
+}
+
+/// Report the context overflow action taken, if any.
+fn inject_context_overflow_header<B>(response: &mut Response<B>, context_fit: &ContextFit) {
+    if let Some(value) = context_fit.header_value() {
+        if let Ok(header_value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(
+                HeaderName::from_static(CONTEXT_OVERFLOW_HEADER),
+                header_value,
+            );
+        }
+    }
+}
+
 /// Inject budget-related response headers (F14: T037-T040).
 ///
 /// Adds the following headers based on budget status:
@@ -159,12 +354,13 @@ fn rejection_response(
         .collect();
     let reconciler_list: Vec<&str> = reconcilers.into_iter().collect();
 
-    // Extract privacy zone from rejection reasons (if PrivacyReconciler rejected)
+    // Extract privacy zone from rejection reasons (if PrivacyReconciler or
+    // PiiReconciler rejected; PII always requires the restricted zone)
     let privacy_zone_required = rejection_reasons
         .iter()
-        .find(|r| r.reconciler == "PrivacyReconciler")
+        .find(|r| r.reconciler == "PrivacyReconciler" || r.reconciler == "PiiReconciler")
         .map(|r| {
-            if r.reason.contains("restricted") {
+            if r.reconciler == "PiiReconciler" || r.reason.contains("restricted") {
                 "restricted".to_string()
             } else {
                 "open".to_string()
@@ -205,9 +401,12 @@ fn rejection_response(
     let mut response = error.into_response();
 
     // T089b: Add Retry-After header when rejections are from LifecycleReconciler
+    // or CircuitBreakerReconciler (both report an ETA in the suggested action)
     let lifecycle_rejections: Vec<&RejectionReason> = rejection_reasons
         .iter()
-        .filter(|r| r.reconciler == "LifecycleReconciler")
+        .filter(|r| {
+            r.reconciler == "LifecycleReconciler" || r.reconciler == "CircuitBreakerReconciler"
+        })
         .collect();
     if !lifecycle_rejections.is_empty() {
         // Estimate retry time from ETA in rejection reasons
@@ -291,13 +490,20 @@ pub async fn handle(
 
     info!(model = %request.model, stream = request.stream, "Chat completion request");
 
+    // Count prompt tokens, apply content routing rules and opt-in context
+    // overflow handling
+    let mut request = request;
+    let prepared = prepare_request(&state, &headers, &mut request).await?;
+
     // For streaming requests, use streaming handler
     if request.stream {
-        return handle_streaming(state, headers, request).await;
+        return handle_streaming(state, headers, request, prepared).await;
     }
-
-    // Extract request requirements
-    let requirements = RequestRequirements::from_request(&request);
+    let PreparedRequest {
+        requirements,
+        context_fit,
+        attribution,
+    } = prepared;
 
     // Extract tier enforcement mode from request headers (T032, FR-007, FR-008, FR-009)
     let tier_mode = extract_tier_enforcement_mode(&headers);
@@ -506,8 +712,39 @@ pub async fn handle(
     let actual_model = routing_result.actual_model.clone();
 
     // Replace alias with resolved model name before forwarding to backend
-    let mut request = request;
     request.model = actual_model.clone();
+    let structured = prepare_structured_output(&state, backend, &mut request);
+    let tool_emulation = prepare_tool_emulation(&state, backend, &mut request);
+
+    let privacy_zone = backend_privacy_zone(&state, backend);
+
+    // Serve identical (or semantically similar) requests from the response cache
+    let mut cache_slot = response_cache_slot(&state, &headers, &request, privacy_zone);
+    if let Some(slot) = cache_slot.as_mut() {
+        if let Some(hit) = lookup_response_cache(&state, &request, &requirements, slot).await {
+            info!(model = %actual_model, similarity = ?hit.similarity, "Response cache hit");
+            return Ok(cached_response(hit, false));
+        }
+    }
+
+    // Attach to an identical in-flight request instead of dispatching a copy;
+    // if that request fails, dispatch on our own
+    let mut flight_lease = None;
+    if let Some(key) = single_flight_key(&state, &headers, &request, privacy_zone, false) {
+        match state.single_flight.join_response(&key) {
+            ResponseFlight::Leader(lease) => flight_lease = Some(lease),
+            ResponseFlight::Follower(rx) => {
+                if let Some(shared) = SingleFlight::wait(rx).await {
+                    info!(model = %actual_model, "Shared response of identical in-flight request");
+                    // Report the leader's routing, as for any other response
+                    let mut resp = Json(shared.response).into_response();
+                    resp.headers_mut().extend(shared.headers);
+                    inject_single_flight_header(&mut resp);
+                    return Ok(resp);
+                }
+            }
+        }
+    }
 
     // Record routing fields in span
     Span::current().record("backend", backend.id.as_str());
@@ -535,20 +772,21 @@ pub async fn handle(
             info!(backend_id = %backend.id, attempt, "Trying backend");
         }
 
-        // Increment pending requests
+        // Increment pending requests and reserve a half-open trial slot if needed
         let _ = state.registry.increment_pending(&backend.id);
+        let _permit = state.router.circuit_breakers().on_dispatch(&backend.id);
 
         match proxy_request(&state, backend, &headers, &request).await {
-            Ok(response) => {
+            Ok(mut response) => {
                 let _ = state.registry.decrement_pending(&backend.id);
                 info!(backend_id = %backend.id, "Request succeeded");
 
-                // Record quality outcome: success with TTFT
-                let ttft_ms = start_time.elapsed().as_millis() as u32;
-                state
-                    .router
-                    .quality_store()
-                    .record_outcome(&backend.id, true, ttft_ms);
+                // Record quality outcome: success with TTFT, preferring the
+                // backend's own measurement over end-to-end latency
+                let ttft_ms = response
+                    .timings
+                    .map_or(start_time.elapsed().as_millis() as u32, |t| t.ttft_ms);
+                record_backend_outcome(&state, &backend.id, true, ttft_ms);
 
                 // Record success metrics
                 let duration = start_time.elapsed().as_secs_f64();
@@ -604,6 +842,17 @@ pub async fn handle(
                     Span::current().record("tokens_total", usage.total_tokens);
                 }
 
+                // Record generation throughput when the backend reports timings
+                if let Some(tokens_per_second) =
+                    response.timings.and_then(|t| t.tokens_per_second())
+                {
+                    metrics::histogram!("nexus_generation_tokens_per_second",
+                        "model" => sanitized_model.clone(),
+                        "backend" => sanitized_backend.clone()
+                    )
+                    .record(tokens_per_second);
+                }
+
                 // Record completion fields in span
                 let latency = start_time.elapsed().as_millis() as u64;
                 Span::current().record("latency_ms", latency);
@@ -627,23 +876,59 @@ pub async fn handle(
                         .estimate_cost(&actual_model, u.prompt_tokens, u.completion_tokens)
                 });
 
+                if let Some(emulation) = &tool_emulation {
+                    emulation.convert_response(&mut response);
+                }
+
+                // Enforce the requested JSON schema before the response is
+                // filtered, cached or shared
+                let schema_report = match &structured {
+                    Some(structured) => {
+                        let report = enforce_structured_output(
+                            &state,
+                            &headers,
+                            backend,
+                            structured,
+                            &request,
+                            &mut response,
+                        )
+                        .await;
+                        if !report.errors.is_empty() {
+                            return Ok(schema_failure_response(&report));
+                        }
+                        Some(report)
+                    }
+                    None => None,
+                };
+
+                // Filter the response before it is cached or shared
+                let guard_report = apply_output_guardrails(
+                    &state,
+                    &headers,
+                    &request,
+                    &mut response,
+                    backend_privacy_zone(&state, backend),
+                )
+                .await?;
+
+                // Store deterministic responses for identical follow-up requests
+                if let Some(slot) = &cache_slot {
+                    store_response_cache(&state, slot, &response).await;
+                }
                 // Create response with fallback header if applicable
-                let mut resp = Json(response).into_response();
+                let mut resp = Json(&response).into_response();
+                if cache_slot.is_some() {
+                    inject_cache_header(&mut resp, "miss");
+                }
 
                 // T035/T047: Inject X-Nexus-* transparent headers (F12)
                 // Derive RouteReason from routing_result.route_reason string
-                let route_reason = determine_route_reason(
-                    &routing_result.route_reason,
-                    routing_result.fallback_used,
-                    attempt as u32,
-                );
-
-                // Get privacy zone from agent profile, or use backend type default
-                let privacy_zone = state
-                    .registry
-                    .get_agent(&backend.id)
-                    .map(|agent| agent.profile().privacy_zone)
-                    .unwrap_or_else(|| backend.backend_type.default_privacy_zone());
+                let (route_reason, route_detail) =
+                    attribution.route_reason(determine_route_reason(
+                        &routing_result.route_reason,
+                        routing_result.fallback_used,
+                        attempt as u32,
+                    ));
 
                 let header_inject_start = std::time::Instant::now();
                 let nexus_headers = NexusTransparentHeaders::new(
@@ -652,7 +937,8 @@ pub async fn handle(
                     route_reason,
                     privacy_zone,
                     cost_estimated,
-                );
+                )
+                .with_route_detail(route_detail);
                 nexus_headers.inject_into_response(&mut resp);
 
                 let header_inject_time_us = header_inject_start.elapsed().as_micros();
@@ -668,6 +954,18 @@ pub async fn handle(
 
                 // Inject budget headers (F14: T037-T040)
                 inject_budget_headers(&mut resp, &routing_result);
+                inject_context_overflow_header(&mut resp, &context_fit);
+                attribution.inject_into_response(&mut resp);
+                inject_guardrails_header(&mut resp, guard_report.as_ref());
+                inject_schema_headers(&mut resp, schema_report.as_ref());
+                if tool_emulation.is_some() {
+                    inject_tool_emulation_header(&mut resp);
+                }
+
+                // Share the response with identical requests that waited on it
+                if let Some(lease) = flight_lease.take() {
+                    lease.complete(&response, resp.headers());
+                }
 
                 return Ok(resp);
             }
@@ -676,10 +974,7 @@ pub async fn handle(
 
                 // Record quality outcome: failure
                 let ttft_ms = start_time.elapsed().as_millis() as u32;
-                state
-                    .router
-                    .quality_store()
-                    .record_outcome(&backend.id, false, ttft_ms);
+                record_backend_failure(&state, &backend.id, e.is_client_error(), ttft_ms);
 
                 // Track this backend in fallback chain
                 if !fallback_chain_vec.contains(&backend.id) {
@@ -759,12 +1054,40 @@ pub async fn handle(
     Err(last_error.unwrap_or_else(|| ApiError::bad_gateway("All backends failed")))
 }
 
-/// Proxy request to backend.
+/// Proxy request to backend, redacting PII for cloud agents when enabled.
+///
+/// The placeholder mapping lives only for this call and is restored into
+/// the response before it is returned.
 async fn proxy_request(
     state: &Arc<AppState>,
     backend: &crate::registry::Backend,
     headers: &HeaderMap,
     request: &ChatCompletionRequest,
+) -> Result<ChatCompletionResponse, ApiError> {
+    let Some(scanner) = state.router.pii_redactor(backend.backend_type) else {
+        return dispatch_request(state, backend, headers, request).await;
+    };
+    let mut redacted = request.clone();
+    let redaction = scanner.redact_request(&mut redacted);
+    if redaction.is_empty() {
+        return dispatch_request(state, backend, headers, request).await;
+    }
+    tracing::debug!(
+        backend_id = %backend.id,
+        entities = redaction.len(),
+        "PII redacted for cloud dispatch"
+    );
+    let mut response = dispatch_request(state, backend, headers, &redacted).await?;
+    redaction.restore_response(&mut response);
+    Ok(response)
+}
+
+/// Dispatch request to backend.
+async fn dispatch_request(
+    state: &Arc<AppState>,
+    backend: &crate::registry::Backend,
+    headers: &HeaderMap,
+    request: &ChatCompletionRequest,
 ) -> Result<ChatCompletionResponse, ApiError> {
     // Try to get agent from registry (T036)
     if let Some(agent) = state.registry.get_agent(&backend.id) {
@@ -816,6 +1139,260 @@ async fn proxy_request(
     }
 }
 
+/// Apply output guardrails to a complete response: rules first, then the
+/// moderation model. A blocked response becomes a content filter error.
+///
+/// `privacy_zone` is the zone of the backend that produced the response; the
+/// moderation model is held to it.
+async fn apply_output_guardrails(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    request: &ChatCompletionRequest,
+    response: &mut ChatCompletionResponse,
+    privacy_zone: PrivacyZone,
+) -> Result<Option<GuardReport>, ApiError> {
+    let Some(guard) = &state.guardrails else {
+        return Ok(None);
+    };
+    let mut report = guard.check_response(response);
+
+    if let (None, Some(moderation)) = (&report.blocked, guard.moderation()) {
+        match moderate_response(state, headers, moderation, request, response, privacy_zone).await {
+            Ok(true) => guard.record_moderation(&mut report),
+            Ok(false) => {}
+            Err(e) if moderation.fail_open => {
+                warn!(error = %e.error.message, "Moderation check failed, delivering response");
+            }
+            Err(e) => {
+                warn!(error = %e.error.message, "Moderation check failed, blocking response");
+                return Err(ApiError::service_unavailable(
+                    "Moderation check unavailable",
+                ));
+            }
+        }
+    }
+
+    if let Some(rule) = &report.blocked {
+        return Err(ApiError::content_filtered(&format!(
+            "Response blocked by guardrail '{}'",
+            rule
+        )));
+    }
+    Ok(Some(report))
+}
+
+/// Adapt a `json_schema` request to the selected backend.
+///
+/// Returns the schema to validate the response against, or None if the
+/// request has no schema or enforcement is disabled.
+fn prepare_structured_output(
+    state: &AppState,
+    backend: &Backend,
+    request: &mut ChatCompletionRequest,
+) -> Option<StructuredOutput> {
+    if !state.config.structured_output.enabled {
+        return None;
+    }
+    let structured = StructuredOutput::from_request(request)?;
+    if !backend.backend_type.supports_json_schema() {
+        let json_mode = backend
+            .models
+            .iter()
+            .any(|m| m.id == request.model && m.supports_json_mode);
+        structured.adapt_request(request, json_mode);
+    }
+    Some(structured)
+}
+
+/// Validate a `json_schema` response, sending invalid output back to the
+/// model with the validation errors up to `max_repair_attempts` times.
+///
+/// The returned response reports the token usage of all attempts.
+async fn enforce_structured_output(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    backend: &Backend,
+    structured: &StructuredOutput,
+    request: &ChatCompletionRequest,
+    response: &mut ChatCompletionResponse,
+) -> SchemaReport {
+    let mut report = SchemaReport {
+        attempts: 1,
+        errors: structured.check(response),
+    };
+    let max_attempts = 1 + state.config.structured_output.max_repair_attempts;
+    while !report.errors.is_empty() && report.attempts < max_attempts {
+        info!(
+            backend_id = %backend.id,
+            attempt = report.attempts,
+            errors = report.errors.len(),
+            "Response does not match JSON schema, requesting repair"
+        );
+        let repair = structured.repair_request(request, response, &report.errors);
+        let _permit = state.router.circuit_breakers().on_dispatch(&backend.id);
+        let _ = state.registry.increment_pending(&backend.id);
+        let start = std::time::Instant::now();
+        let reply = proxy_request(state, backend, headers, &repair).await;
+        let latency_ms = start.elapsed().as_millis() as u32;
+        let _ = state.registry.decrement_pending(&backend.id);
+        report.attempts += 1;
+        match reply {
+            Ok(mut reply) => {
+                let ttft_ms = reply.timings.map_or(latency_ms, |t| t.ttft_ms);
+                record_backend_outcome(state, &backend.id, true, ttft_ms);
+                reply.usage = sum_usage(response.usage.take(), reply.usage.take());
+                *response = reply;
+                report.errors = structured.check(response);
+            }
+            Err(e) => {
+                record_backend_failure(state, &backend.id, e.is_client_error(), latency_ms);
+                warn!(error = %e.error.message, "Schema repair request failed");
+                break;
+            }
+        }
+    }
+    report.record();
+    report
+}
+
+/// Token usage of two calls combined; None only if neither reported usage.
+fn sum_usage(first: Option<Usage>, second: Option<Usage>) -> Option<Usage> {
+    match (first, second) {
+        (Some(a), Some(b)) => Some(Usage {
+            prompt_tokens: a.prompt_tokens + b.prompt_tokens,
+            completion_tokens: a.completion_tokens + b.completion_tokens,
+            total_tokens: a.total_tokens + b.total_tokens,
+        }),
+        (usage, None) | (None, usage) => usage,
+    }
+}
+
+/// 422 response for output that stayed invalid after all repair attempts.
+fn schema_failure_response(report: &SchemaReport) -> Response {
+    warn!(
+        attempts = report.attempts,
+        errors = ?report.errors,
+        "Response does not match JSON schema"
+    );
+    let mut resp = ApiError::schema_validation_failed(&format!(
+        "Response does not match the requested JSON schema after {} attempt(s): {}",
+        report.attempts,
+        report.errors.join("; ")
+    ))
+    .into_response();
+    inject_schema_headers(&mut resp, Some(report));
+    resp
+}
+
+/// Report the JSON schema validation outcome and attempt count, if validated.
+fn inject_schema_headers<B>(response: &mut Response<B>, report: Option<&SchemaReport>) {
+    let Some(report) = report else {
+        return;
+    };
+    let headers = response.headers_mut();
+    headers.insert(
+        HeaderName::from_static(SCHEMA_VALIDATION_HEADER),
+        HeaderValue::from_static(report.outcome()),
+    );
+    headers.insert(
+        HeaderName::from_static(SCHEMA_ATTEMPTS_HEADER),
+        HeaderValue::from(report.attempts),
+    );
+}
+
+/// Emulate tool calling when the selected model lacks native support.
+///
+/// Returns the declared tools to parse the response for, or None if the
+/// request has no tools, the model calls tools natively or emulation is
+/// disabled.
+fn prepare_tool_emulation(
+    state: &AppState,
+    backend: &Backend,
+    request: &mut ChatCompletionRequest,
+) -> Option<ToolEmulation> {
+    let config = &state.config.tool_emulation;
+    if !config.enabled {
+        return None;
+    }
+    let native = backend
+        .models
+        .iter()
+        .any(|m| m.id == request.model && m.supports_tools);
+    if native {
+        return None;
+    }
+    let emulation = ToolEmulation::from_request(request, config.format)?;
+    emulation.adapt_request(request);
+    info!(backend_id = %backend.id, model = %request.model, "Emulating tool calling");
+    Some(emulation)
+}
+
+/// Mark a response whose tool calls were emulated.
+fn inject_tool_emulation_header<B>(response: &mut Response<B>) {
+    response.headers_mut().insert(
+        HeaderName::from_static(TOOL_EMULATION_HEADER),
+        HeaderValue::from_static("true"),
+    );
+}
+
+/// Ask the moderation model, routed like any other request, whether a
+/// response is unsafe.
+///
+/// The prompt and answer never leave the privacy zone of the backend that
+/// produced the answer.
+async fn moderate_response(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    moderation: &ModerationConfig,
+    request: &ChatCompletionRequest,
+    response: &ChatCompletionResponse,
+    privacy_zone: PrivacyZone,
+) -> Result<bool, ApiError> {
+    let mut moderation_request = guardrails::moderation_request(moderation, request, response);
+    let mut requirements = state.router.requirements_for(&moderation_request);
+    if privacy_zone == PrivacyZone::Restricted {
+        requirements.privacy_constraint = Some(PrivacyZone::Restricted);
+    }
+    let routing_result = state
+        .router
+        .select_backend(&requirements, None)
+        .map_err(|e| ApiError::service_unavailable(&e.to_string()))?;
+    moderation_request.model = routing_result.actual_model.clone();
+
+    let backend = &routing_result.backend;
+    let _permit = state.router.circuit_breakers().on_dispatch(&backend.id);
+    let _ = state.registry.increment_pending(&backend.id);
+    let start = std::time::Instant::now();
+    let reply = dispatch_request(state, backend, headers, &moderation_request).await;
+    let latency_ms = start.elapsed().as_millis() as u32;
+    let _ = state.registry.decrement_pending(&backend.id);
+    match &reply {
+        Ok(_) => record_backend_outcome(state, &backend.id, true, latency_ms),
+        Err(e) => record_backend_failure(state, &backend.id, e.is_client_error(), latency_ms),
+    }
+    Ok(guardrails::is_unsafe(&reply?))
+}
+
+/// Privacy zone of a backend: its agent profile's, or the backend type default.
+fn backend_privacy_zone(state: &AppState, backend: &Backend) -> PrivacyZone {
+    state
+        .registry
+        .get_agent(&backend.id)
+        .map(|agent| agent.profile().privacy_zone)
+        .unwrap_or_else(|| backend.backend_type.default_privacy_zone())
+}
+
+/// Report the guardrails that redacted or flagged the response, if any.
+fn inject_guardrails_header<B>(response: &mut Response<B>, report: Option<&GuardReport>) {
+    if let Some(value) = report.and_then(GuardReport::header_value) {
+        if let Ok(header_value) = HeaderValue::from_str(&value) {
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static(GUARDRAILS_HEADER), header_value);
+        }
+    }
+}
+
 /// Get list of available models for error messages.
 fn available_models(state: &Arc<AppState>) -> Vec<String> {
     let backends = state.registry.get_all_backends();
@@ -846,9 +1423,15 @@ async fn handle_streaming(
     state: Arc<AppState>,
     headers: HeaderMap,
     request: ChatCompletionRequest,
+    prepared: PreparedRequest,
 ) -> Result<Response, ApiError> {
+    let PreparedRequest {
+        requirements,
+        context_fit,
+        attribution,
+    } = prepared;
+
     // Use router to select backend
-    let requirements = RequestRequirements::from_request(&request);
     let tier_mode = extract_tier_enforcement_mode(&headers);
     let routing_result = state.router.select_backend(&requirements, Some(tier_mode));
 
@@ -913,15 +1496,45 @@ async fn handle_streaming(
     let actual_model = routing_result.actual_model.clone();
     let backend_id = backend.id.clone();
 
-    // Replace alias with resolved model name before forwarding to backend
+    // Replace alias with resolved model name before forwarding to backend.
+    // Streamed structured output is adapted for the backend but not validated.
     let mut request = request;
     request.model = actual_model.clone();
+    prepare_structured_output(&state, backend, &mut request);
+    let tool_emulation = prepare_tool_emulation(&state, backend, &mut request);
+    let emulated = tool_emulation.is_some();
+
+    let privacy_zone = backend_privacy_zone(&state, backend);
+
+    // Replay cached responses as SSE; streamed responses are not stored
+    let mut cache_slot = response_cache_slot(&state, &headers, &request, privacy_zone);
+    if let Some(slot) = cache_slot.as_mut() {
+        if let Some(hit) = lookup_response_cache(&state, &request, &requirements, slot).await {
+            info!(
+                model = %actual_model,
+                similarity = ?hit.similarity,
+                "Response cache hit (streaming replay)"
+            );
+            return Ok(cached_response(hit, true));
+        }
+    }
+
+    // Fan out an identical in-flight stream instead of opening another one
+    let flight = single_flight_key(&state, &headers, &request, privacy_zone, true)
+        .map(|key| state.single_flight.join_stream(&key));
+    if let Some(StreamFlight::Follower(fanout)) = &flight {
+        info!(model = %actual_model, "Attached to identical in-flight stream");
+        let mut resp = Sse::new(fanout.subscribe().map(sse_event)).into_response();
+        inject_single_flight_header(&mut resp);
+        return Ok(resp);
+    }
 
     // Track start time for quality metrics
     let start_time = std::time::Instant::now();
 
-    // Increment pending requests
+    // Increment pending requests and reserve a half-open trial slot if needed
     let _ = state.registry.increment_pending(&backend_id);
+    let permit = state.router.circuit_breakers().on_dispatch(&backend_id);
 
     info!(backend_id = %backend_id, "Starting streaming request");
 
@@ -931,26 +1544,33 @@ async fn handle_streaming(
         Arc::clone(backend),
         headers,
         request,
+        tool_emulation,
         start_time,
+        permit,
     );
 
+    let stream = match flight {
+        Some(StreamFlight::Leader(lease)) => {
+            let abandoned = vec![
+                serde_json::to_string(&create_error_chunk("Shared stream abandoned"))
+                    .unwrap_or_default(),
+                "[DONE]".to_string(),
+            ];
+            lease.broadcast(stream, abandoned).boxed()
+        }
+        _ => stream.boxed(),
+    };
+
     // Create SSE response and add headers
-    let mut resp = Sse::new(stream).into_response();
+    let mut resp = Sse::new(stream.map(sse_event)).into_response();
 
     // T036/T047: Inject X-Nexus-* transparent headers for streaming (F12)
     // Headers must be injected BEFORE first SSE chunk
-    let route_reason = determine_route_reason(
+    let (route_reason, route_detail) = attribution.route_reason(determine_route_reason(
         &routing_result.route_reason,
         fallback_used,
         0, // No retries for streaming
-    );
-
-    // Get privacy zone from agent profile, or use backend type default
-    let privacy_zone = state
-        .registry
-        .get_agent(&backend.id)
-        .map(|agent| agent.profile().privacy_zone)
-        .unwrap_or_else(|| backend.backend_type.default_privacy_zone());
+    ));
 
     let header_inject_start = std::time::Instant::now();
     let nexus_headers = NexusTransparentHeaders::new(
@@ -959,7 +1579,8 @@ async fn handle_streaming(
         route_reason,
         privacy_zone,
         routing_result.cost_estimated,
-    );
+    )
+    .with_route_detail(route_detail);
     nexus_headers.inject_into_response(&mut resp);
 
     let header_inject_time_us = header_inject_start.elapsed().as_micros();
@@ -975,63 +1596,327 @@ async fn handle_streaming(
 
     // Inject budget headers (F14: T037-T040)
     inject_budget_headers(&mut resp, &routing_result);
+    inject_context_overflow_header(&mut resp, &context_fit);
+    attribution.inject_into_response(&mut resp);
+    if cache_slot.is_some() {
+        inject_cache_header(&mut resp, "miss");
+    }
+    if emulated {
+        inject_tool_emulation_header(&mut resp);
+    }
 
     Ok(resp)
 }
 
-/// Create an SSE stream that proxies chunks from the backend.
+/// Wrap an SSE data payload in an event.
+fn sse_event(data: String) -> Result<Event, std::convert::Infallible> {
+    Ok(Event::default().data(data))
+}
+
+/// Single-flight key for a routed request, or None when deduplication does
+/// not apply. Uses the response cache key, so tenants and privacy zones
+/// never share in-flight results.
+fn single_flight_key(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &ChatCompletionRequest,
+    privacy_zone: crate::agent::PrivacyZone,
+    stream: bool,
+) -> Option<String> {
+    let config = &state.config.cache.single_flight;
+    if !config.enabled || (stream && !config.streaming) {
+        return None;
+    }
+    if !state.response_cache.is_shareable(request) {
+        return None;
+    }
+    let namespace = CacheNamespace::new(headers, privacy_zone);
+    Some(ResponseCache::key(&namespace, request))
+}
+
+/// Mark a response shared from an identical in-flight request.
+fn inject_single_flight_header<B>(response: &mut Response<B>) {
+    response.headers_mut().insert(
+        HeaderName::from_static(SINGLE_FLIGHT_HEADER),
+        HeaderValue::from_static("follower"),
+    );
+}
+
+/// Cache identity of a routed request.
+struct CacheSlot {
+    /// Exact-match key
+    key: String,
+    /// Semantic cache scope (model, tenant, privacy zone and request context)
+    scope: SemanticScope,
+    /// Prompt embedding, computed on an exact-match miss when the semantic
+    /// cache is enabled
+    embedding: Option<Vec<f32>>,
+}
+
+/// A response served from the cache.
+struct CacheHit {
+    response: ChatCompletionResponse,
+    /// Prompt similarity for semantic hits, None for exact matches
+    similarity: Option<f32>,
+}
+
+/// Cache identity for a routed request, or None when caching does not apply.
+fn response_cache_slot(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &ChatCompletionRequest,
+    privacy_zone: crate::agent::PrivacyZone,
+) -> Option<CacheSlot> {
+    let policy = state.router.cache_policy(&request.model);
+    if !state.response_cache.is_cacheable(request, policy) {
+        return None;
+    }
+    let namespace = CacheNamespace::new(headers, privacy_zone);
+    Some(CacheSlot {
+        key: ResponseCache::key(&namespace, request),
+        scope: SemanticScope::for_request(namespace, request),
+        embedding: None,
+    })
+}
+
+/// Look up the exact-match cache, then the semantic cache.
+///
+/// The prompt embedding is kept on the slot so a miss can be stored under it
+/// without embedding the prompt twice. The prompt is embedded under the
+/// request's privacy constraint. Embedding failures skip the semantic lookup
+/// rather than failing the request.
+async fn lookup_response_cache(
+    state: &AppState,
+    request: &ChatCompletionRequest,
+    requirements: &RequestRequirements,
+    slot: &mut CacheSlot,
+) -> Option<CacheHit> {
+    if let Some(response) = state.response_cache.get(&slot.key).await {
+        return Some(CacheHit {
+            response,
+            similarity: None,
+        });
+    }
+
+    let semantic = state.response_cache.semantic()?;
+    let prompt = RequestFeatures::extract(request, 0, None).latest_user_text;
+    if prompt.is_empty() {
+        return None;
+    }
+    let embedding = match state
+        .router
+        .embed(
+            semantic.embedding_model(),
+            vec![prompt],
+            requirements.privacy_constraint,
+        )
+        .await
+    {
+        Ok(mut vectors) => vectors.pop()?,
+        Err(e) => {
+            warn!(
+                embedding_model = %semantic.embedding_model(),
+                error = %e,
+                "Semantic cache unavailable, skipping lookup"
+            );
+            return None;
+        }
+    };
+
+    let hit = semantic
+        .lookup(&slot.scope, &embedding)
+        .map(|(response, similarity)| CacheHit {
+            response,
+            similarity: Some(similarity),
+        });
+    slot.embedding = Some(embedding);
+    hit
+}
+
+/// Store a backend response in the exact-match cache and, when the prompt
+/// was embedded, the semantic cache.
+async fn store_response_cache(
+    state: &AppState,
+    slot: &CacheSlot,
+    response: &ChatCompletionResponse,
+) {
+    state
+        .response_cache
+        .insert(slot.key.clone(), response)
+        .await;
+    if let (Some(semantic), Some(embedding)) = (state.response_cache.semantic(), &slot.embedding) {
+        semantic.insert(slot.scope.clone(), embedding.clone(), response);
+    }
+}
+
+/// Build the response for a cache hit, replaying it as SSE for streaming clients.
+fn cached_response(hit: CacheHit, stream: bool) -> Response {
+    let mut resp = if stream {
+        let events = replay_chunks(&hit.response)
+            .into_iter()
+            .chain(std::iter::once("[DONE]".to_string()))
+            .map(|data| Ok::<_, std::convert::Infallible>(Event::default().data(data)));
+        Sse::new(futures::stream::iter(events)).into_response()
+    } else {
+        Json(hit.response).into_response()
+    };
+    inject_cache_header(&mut resp, "hit");
+    if let Some(similarity) = hit.similarity {
+        if let Ok(value) = HeaderValue::from_str(&format!("{:.4}", similarity)) {
+            resp.headers_mut()
+                .insert(HeaderName::from_static(CACHE_SIMILARITY_HEADER), value);
+        }
+    }
+    resp
+}
+
+/// Report whether the response cache answered the request.
+fn inject_cache_header<B>(response: &mut Response<B>, status: &'static str) {
+    response.headers_mut().insert(
+        HeaderName::from_static(CACHE_HEADER),
+        HeaderValue::from_static(status),
+    );
+}
+
+/// Create a stream of SSE data payloads proxied from the backend.
 fn create_sse_stream(
     state: Arc<AppState>,
     backend: Arc<Backend>,
     headers: HeaderMap,
     request: ChatCompletionRequest,
+    tool_emulation: Option<ToolEmulation>,
     start_time: std::time::Instant,
-) -> impl futures::Stream<Item = Result<Event, std::convert::Infallible>> {
+    permit: DispatchPermit,
+) -> impl futures::Stream<Item = String> {
     async_stream::stream! {
+        // Held until the stream ends or the client goes away
+        let _permit = permit;
         let backend_id = backend.id.clone();
 
+        // Redact PII for cloud agents; placeholders are restored per chunk
+        let mut request = request;
+        let redaction = match state.router.pii_redactor(backend.backend_type) {
+            Some(scanner) => scanner.redact_request(&mut request),
+            None => crate::pii::Redaction::default(),
+        };
+        if !redaction.is_empty() {
+            tracing::debug!(
+                backend_id = %backend_id,
+                entities = redaction.len(),
+                "PII redacted for cloud dispatch"
+            );
+        }
+        let mut restorer = redaction.chunk_restorer();
+
+        // Emulated tool calls are converted before guardrails see the text
+        let mut tools = tool_emulation
+            .map(ToolEmulation::stream)
+            .unwrap_or_default();
+
+        // Output guardrails filter the restored text before it is forwarded
+        let mut guard = match &state.guardrails {
+            Some(guardrails) => guardrails.stream_guard(),
+            None => StreamGuard::default(),
+        };
+
         // Try to get agent from registry (T037)
         if let Some(agent) = state.registry.get_agent(&backend_id) {
             // Use agent-based streaming (T037)
             match agent.chat_completion_stream(request.clone(), Some(&headers)).await {
                 Ok(mut stream) => {
                     let mut succeeded = true;
+                    let mut timings = None;
                     // Stream chunks from agent
-                    use futures::StreamExt;
                     while let Some(result) = stream.next().await {
                         match result {
                             Ok(chunk) => {
+                                timings = chunk.timings.or(timings);
                                 // Check if this is [DONE]
                                 if chunk.data == "[DONE]" {
-                                    yield Ok(Event::default().data("[DONE]"));
+                                    let rest = restorer.flush().unwrap_or_default();
+                                    let rest = tools.finish(&rest);
+                                    match guard.finish(&rest) {
+                                        Ok(rest) => {
+                                            if !rest.is_empty() {
+                                                yield rest;
+                                            }
+                                            yield "[DONE]".to_string();
+                                        }
+                                        Err(blocked) => {
+                                            for data in blocked_stream_end(&blocked) {
+                                                yield data;
+                                            }
+                                        }
+                                    }
                                     break;
                                 } else {
                                     // Forward the chunk data (already JSON)
-                                    yield Ok(Event::default().data(chunk.data));
+                                    let data = restorer.restore_chunk(&chunk.data);
+                                    let data = tools.filter_chunk(&data);
+                                    match guard.filter_chunk(&data) {
+                                        Ok(data) => {
+                                            if !data.is_empty() {
+                                                yield data;
+                                            }
+                                        }
+                                        Err(blocked) => {
+                                            for data in blocked_stream_end(&blocked) {
+                                                yield data;
+                                            }
+                                            break;
+                                        }
+                                    }
                                 }
                             }
                             Err(e) => {
                                 succeeded = false;
                                 warn!(backend_id = %backend_id, error = %e, "Stream error from agent");
                                 let error_chunk = create_error_chunk(&format!("Stream error: {}", e));
-                                yield Ok(Event::default().data(serde_json::to_string(&error_chunk).unwrap_or_default()));
-                                yield Ok(Event::default().data("[DONE]"));
+                                yield serde_json::to_string(&error_chunk).unwrap_or_default();
+                                yield "[DONE]".to_string();
                                 break;
                             }
                         }
                     }
-                    // Record quality outcome for streaming
-                    let ttft_ms = start_time.elapsed().as_millis() as u32;
-                    state.router.quality_store().record_outcome(&backend_id, succeeded, ttft_ms);
+                    // Emit anything still held back for placeholder restoration
+                    // or guardrail look-behind
+                    if succeeded {
+                        let rest = restorer.flush().unwrap_or_default();
+                        let rest = tools.finish(&rest);
+                        match guard.finish(&rest) {
+                            Ok(rest) => {
+                                if !rest.is_empty() {
+                                    yield rest;
+                                }
+                            }
+                            Err(blocked) => {
+                                for data in blocked_stream_end(&blocked) {
+                                    yield data;
+                                }
+                            }
+                        }
+                    }
+                    // Record quality outcome for streaming, preferring the
+                    // backend's own TTFT over end-to-end latency
+                    let ttft_ms = timings
+                        .map_or(start_time.elapsed().as_millis() as u32, |t| t.ttft_ms);
+                    record_backend_outcome(&state, &backend_id, succeeded, ttft_ms);
+                    if let Some(tokens_per_second) = timings.and_then(|t| t.tokens_per_second()) {
+                        metrics::histogram!("nexus_generation_tokens_per_second",
+                            "model" => state.metrics_collector.sanitize_label(&request.model),
+                            "backend" => state.metrics_collector.sanitize_label(&backend_id)
+                        )
+                        .record(tokens_per_second);
+                    }
                 }
                 Err(e) => {
                     warn!(backend_id = %backend_id, error = %e, "Failed to start streaming from agent");
                     let error_chunk = create_error_chunk(&format!("Failed to start streaming: {}", e));
-                    yield Ok(Event::default().data(serde_json::to_string(&error_chunk).unwrap_or_default()));
-                    yield Ok(Event::default().data("[DONE]"));
+                    yield serde_json::to_string(&error_chunk).unwrap_or_default();
+                    yield "[DONE]".to_string();
                     // Record quality outcome: failure
                     let ttft_ms = start_time.elapsed().as_millis() as u32;
-                    state.router.quality_store().record_outcome(&backend_id, false, ttft_ms);
+                    record_backend_failure(&state, &backend_id, e.is_client_error(), ttft_ms);
                 }
             }
         } else {
@@ -1053,11 +1938,11 @@ fn create_sse_stream(
                     let _ = state.registry.decrement_pending(&backend_id);
                     // Record quality outcome: connection failure
                     let ttft_ms = start_time.elapsed().as_millis() as u32;
-                    state.router.quality_store().record_outcome(&backend_id, false, ttft_ms);
+                    record_backend_outcome(&state, &backend_id, false, ttft_ms);
                     // Yield error as SSE event before closing
                     let error_chunk = create_error_chunk(&format!("Backend connection failed: {}", e));
-                    yield Ok(Event::default().data(serde_json::to_string(&error_chunk).unwrap_or_default()));
-                    yield Ok(Event::default().data("[DONE]"));
+                    yield serde_json::to_string(&error_chunk).unwrap_or_default();
+                    yield "[DONE]".to_string();
                     return;
                 }
             };
@@ -1070,19 +1955,18 @@ fn create_sse_stream(
                 let _ = state.registry.decrement_pending(&backend_id);
                 // Record quality outcome: backend error
                 let ttft_ms = start_time.elapsed().as_millis() as u32;
-                state.router.quality_store().record_outcome(&backend_id, false, ttft_ms);
+                record_backend_failure(&state, &backend_id, status.is_client_error(), ttft_ms);
                 let error_chunk = create_error_chunk(&format!("Backend returned {}: {}", status, body));
-                yield Ok(Event::default().data(serde_json::to_string(&error_chunk).unwrap_or_default()));
-                yield Ok(Event::default().data("[DONE]"));
+                yield serde_json::to_string(&error_chunk).unwrap_or_default();
+                yield "[DONE]".to_string();
                 return;
             }
 
             // Stream response body
-            use futures::StreamExt;
             let mut byte_stream = response.bytes_stream();
             let mut buffer = String::new();
 
-            while let Some(chunk_result) = byte_stream.next().await {
+            'read: while let Some(chunk_result) = byte_stream.next().await {
                 match chunk_result {
                     Ok(bytes) => {
                         buffer.push_str(&String::from_utf8_lossy(&bytes));
@@ -1099,11 +1983,29 @@ fn create_sse_stream(
 
                             // Parse SSE data lines
                             if let Some(data) = line.strip_prefix("data: ") {
-                                if data == "[DONE]" {
-                                    yield Ok(Event::default().data("[DONE]"));
+                                let filtered = if data == "[DONE]" {
+                                    let rest = restorer.flush().unwrap_or_default();
+                                    guard.finish(&tools.finish(&rest))
                                 } else {
-                                    // Forward the data as-is (already JSON)
-                                    yield Ok(Event::default().data(data));
+                                    // Forward the data (already JSON)
+                                    let data = tools.filter_chunk(&restorer.restore_chunk(data));
+                                    guard.filter_chunk(&data)
+                                };
+                                match filtered {
+                                    Ok(filtered) => {
+                                        if !filtered.is_empty() {
+                                            yield filtered;
+                                        }
+                                        if data == "[DONE]" {
+                                            yield "[DONE]".to_string();
+                                        }
+                                    }
+                                    Err(blocked) => {
+                                        for data in blocked_stream_end(&blocked) {
+                                            yield data;
+                                        }
+                                        break 'read;
+                                    }
                                 }
                             }
                         }
@@ -1114,11 +2016,11 @@ fn create_sse_stream(
                     }
                 }
             }
-        }
 
-        // Record quality outcome for legacy streaming: success
-        let ttft_ms = start_time.elapsed().as_millis() as u32;
-        state.router.quality_store().record_outcome(&backend_id, true, ttft_ms);
+            // Record quality outcome for legacy streaming: success
+            let ttft_ms = start_time.elapsed().as_millis() as u32;
+            record_backend_outcome(&state, &backend_id, true, ttft_ms);
+        }
 
         // Decrement pending requests
         let _ = state.registry.decrement_pending(&backend_id);
@@ -1126,6 +2028,16 @@ fn create_sse_stream(
     }
 }
 
+/// Final payloads of a stream stopped by an output guardrail.
+fn blocked_stream_end(blocked: &GuardBlocked) -> [String; 2] {
+    let error_chunk =
+        create_error_chunk(&format!("Response blocked by guardrail '{}'", blocked.rule));
+    [
+        serde_json::to_string(&error_chunk).unwrap_or_default(),
+        "[DONE]".to_string(),
+    ]
+}
+
 /// Create an error chunk in OpenAI streaming format.
 fn create_error_chunk(message: &str) -> ChatCompletionChunk {
     ChatCompletionChunk {
@@ -1138,12 +2050,27 @@ fn create_error_chunk(message: &str) -> ChatCompletionChunk {
             delta: ChunkDelta {
                 role: None,
                 content: Some(format!("[Error: {}]", message)),
+                tool_calls: None,
             },
             finish_reason: Some("error".to_string()),
         }],
     }
 }
 
+/// Record a request outcome in the quality store and the agent's circuit breaker.
+fn record_backend_outcome(state: &AppState, backend_id: &str, success: bool, ttft_ms: u32) {
+    state
+        .router
+        .record_backend_outcome(backend_id, success, ttft_ms);
+}
+
+/// Record a failed attempt; client errors do not count toward the breaker.
+fn record_backend_failure(state: &AppState, backend_id: &str, client_error: bool, ttft_ms: u32) {
+    state
+        .router
+        .record_backend_failure(backend_id, client_error, ttft_ms);
+}
+
 /// Record a completed request in history and broadcast to dashboard
 fn record_request_completion(
     state: &Arc<AppState>,
@@ -1349,6 +2276,49 @@ mod tests {
         assert_eq!(reason, RouteReason::CapabilityMatch);
     }
 
+    #[test]
+    fn content_rule_attributed_only_for_capability_match() {
+        let attribution = RouteAttribution {
+            content_rule: Some("code".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            attribution.route_reason(RouteReason::CapabilityMatch),
+            (RouteReason::ContentRule, None)
+        );
+        assert_eq!(
+            RouteAttribution::default().route_reason(RouteReason::CapabilityMatch),
+            (RouteReason::CapabilityMatch, None)
+        );
+        assert_eq!(
+            attribution.route_reason(RouteReason::Failover),
+            (RouteReason::Failover, None)
+        );
+    }
+
+    #[test]
+    fn semantic_decision_attributed_with_detail() {
+        let mut attribution = RouteAttribution {
+            semantic: Some(SemanticDecision {
+                route: Some("code".to_string()),
+                target_model: "coder".to_string(),
+                min_tier: None,
+                score: Some(0.912),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            attribution.route_reason(RouteReason::CapabilityMatch),
+            (RouteReason::SemanticMatch, Some("code:0.91".to_string()))
+        );
+
+        attribution.semantic.as_mut().unwrap().route = None;
+        assert_eq!(
+            attribution.route_reason(RouteReason::CapabilityMatch).0,
+            RouteReason::SemanticFallback
+        );
+    }
+
     // ── create_error_chunk tests ──
 
     #[test]
@@ -1728,6 +2698,7 @@ mod tests {
                         supports_vision: false,
                         supports_tools: false,
                         supports_json_mode: false,
+                        supports_embeddings: false,
                         max_output_tokens: None,
                     },
                     Model {
@@ -1737,6 +2708,7 @@ mod tests {
                         supports_vision: true,
                         supports_tools: false,
                         supports_json_mode: false,
+                        supports_embeddings: false,
                         max_output_tokens: None,
                     },
                 ],
@@ -1916,6 +2888,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -1982,6 +2955,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2139,6 +3113,7 @@ mod tests {
                         supports_vision: false,
                         supports_tools: false,
                         supports_json_mode: false,
+                        supports_embeddings: false,
                         max_output_tokens: None,
                     },
                     Model {
@@ -2148,6 +3123,7 @@ mod tests {
                         supports_vision: false,
                         supports_tools: false,
                         supports_json_mode: false,
+                        supports_embeddings: false,
                         max_output_tokens: None,
                     },
                 ],
@@ -2259,6 +3235,8 @@ mod tests {
                             },
                             name: None,
                             function_call: None,
+                            tool_calls: None,
+                            tool_call_id: None,
                         },
                         finish_reason: Some("stop".to_string()),
                     }],
@@ -2268,6 +3246,7 @@ mod tests {
                         total_tokens: 15,
                     }),
                     extra: std::collections::HashMap::new(),
+                    timings: None,
                 })
             }
             async fn chat_completion_stream(
@@ -2305,6 +3284,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2399,6 +3379,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2467,6 +3448,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2533,6 +3515,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2598,6 +3581,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2691,6 +3675,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2750,6 +3735,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -2864,9 +3850,11 @@ mod tests {
                 let chunks = vec![
                     Ok(StreamChunk {
                         data: r#"{"id":"c1","object":"chat.completion.chunk","choices":[{"index":0,"delta":{"content":"Hi"},"finish_reason":null}]}"#.to_string(),
+                        timings: None,
                     }),
                     Ok(StreamChunk {
                         data: "[DONE]".to_string(),
+                        timings: None,
                     }),
                 ];
                 Ok(Box::pin(stream::iter(chunks)))
@@ -2898,6 +3886,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -3013,6 +4002,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -3200,4 +4190,21 @@ mod tests {
         let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
         assert_eq!(json["context"]["privacy_zone_required"], "open");
     }
+
+    #[tokio::test]
+    async fn test_rejection_response_pii_requires_restricted_zone() {
+        let reasons = vec![RejectionReason {
+            agent_id: "a1".to_string(),
+            reconciler: "PiiReconciler".to_string(),
+            reason: "Request content matched PII detector(s) [email]; agent privacy zone Open is not allowed".to_string(),
+            suggested_action: "Remove the sensitive data from the prompt or use a local backend"
+                .to_string(),
+        }];
+        let resp = rejection_response(reasons, vec![]);
+        let body = axum::body::to_bytes(resp.into_body(), 1024 * 1024)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["context"]["privacy_zone_required"], "restricted");
+    }
 }
@@ -67,6 +67,17 @@ pub struct EmbeddingResponse {
 /// Maximum batch size for embedding requests (matches OpenAI's limit).
 const MAX_EMBEDDING_BATCH_SIZE: usize = 2048;
 
+/// Whether any backend lists the model as embedding-capable, so routing
+/// failures can tell "not an embedding model" from "no backend up".
+fn is_embedding_model(state: &AppState, model: &str) -> bool {
+    state
+        .registry
+        .get_backends_for_model(model)
+        .iter()
+        .flat_map(|backend| backend.models.iter())
+        .any(|m| m.id == model && m.supports_embeddings)
+}
+
 /// POST /v1/embeddings — Handle embedding requests.
 #[instrument(
     skip(state, _headers, request),
@@ -95,14 +106,22 @@ pub async fn handle(
     // Estimate tokens for routing
     let estimated_tokens: u32 = input_texts.iter().map(|s| s.len() as u32 / 4).sum();
 
-    // Build requirements for routing (embedding requests have no special caps)
+    // Build requirements for routing (only embedding-capable models qualify)
     let requirements = RequestRequirements {
         model: request.model.clone(),
         estimated_tokens,
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: true,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let routing_result = state
@@ -112,12 +131,20 @@ pub async fn handle(
             crate::routing::RoutingError::ModelNotFound { model } => {
                 ApiError::model_not_found(&model, &[])
             }
+            crate::routing::RoutingError::NoHealthyBackend { model }
+                if !is_embedding_model(&state, &model) =>
+            {
+                ApiError::bad_request(&format!("Model '{}' does not support embeddings", model))
+            }
             crate::routing::RoutingError::NoHealthyBackend { model } => {
                 ApiError::service_unavailable(&format!(
                     "No healthy backend available for model '{}'",
                     model
                 ))
             }
+            crate::routing::RoutingError::CapabilityMismatch { model, .. } => {
+                ApiError::bad_request(&format!("Model '{}' does not support embeddings", model))
+            }
             _ => ApiError::bad_gateway(&format!("Routing error: {}", e)),
         })?;
 
@@ -139,14 +166,21 @@ pub async fn handle(
     // Track pending request for load-aware routing
     let _ = state.registry.increment_pending(&backend.id);
 
-    // Delegate to agent.embeddings()
-    let vectors = agent
-        .embeddings(&request.model, input_texts)
-        .await
-        .map_err(|e| {
-            let _ = state.registry.decrement_pending(&backend.id);
-            ApiError::from_agent_error(e)
-        })?;
+    // Delegate to agent.embeddings(), forwarding only cache misses when the
+    // embedding cache is enabled
+    let fetch = |texts: Vec<String>| agent.embeddings(&request.model, texts);
+    let vectors = match &state.embedding_cache {
+        Some(cache) => {
+            cache
+                .embed(&routing_result.actual_model, input_texts, fetch)
+                .await
+        }
+        None => fetch(input_texts).await,
+    }
+    .map_err(|e| {
+        let _ = state.registry.decrement_pending(&backend.id);
+        ApiError::from_agent_error(e)
+    })?;
 
     let _ = state.registry.decrement_pending(&backend.id);
 
@@ -424,6 +458,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: true,
                     max_output_tokens: None,
                 }],
             )
@@ -491,6 +526,7 @@ mod tests {
                     privacy_zone: PrivacyZone::Restricted,
                     capabilities: AgentCapabilities {
                         embeddings: true,
+                        rerank: false,
                         model_lifecycle: false,
                         token_counting: false,
                         resource_monitoring: false,
@@ -553,6 +589,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: true,
                     max_output_tokens: None,
                 }],
             )
@@ -648,6 +685,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: true,
                     max_output_tokens: None,
                 }],
             )
@@ -749,6 +787,7 @@ mod tests {
                     privacy_zone: PrivacyZone::Restricted,
                     capabilities: AgentCapabilities {
                         embeddings: false,
+                        rerank: false,
                         model_lifecycle: false,
                         token_counting: false,
                         resource_monitoring: false,
@@ -804,6 +843,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: true,
                     max_output_tokens: None,
                 }],
             )
@@ -872,6 +912,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: true,
                     max_output_tokens: None,
                 }],
             )
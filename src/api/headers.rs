@@ -55,6 +55,15 @@ pub enum RouteReason {
 
     /// Previous backend failed, failed over to this one.
     Failover,
+
+    /// A content-based routing rule selected the model or constraints.
+    ContentRule,
+
+    /// Semantic `nexus/auto` classification matched a route.
+    SemanticMatch,
+
+    /// Semantic `nexus/auto` classification fell back to the default model.
+    SemanticFallback,
 }
 
 impl RouteReason {
@@ -65,6 +74,9 @@ impl RouteReason {
             RouteReason::CapacityOverflow => "capacity-overflow",
             RouteReason::PrivacyRequirement => "privacy-requirement",
             RouteReason::Failover => "failover",
+            RouteReason::ContentRule => "content-rule",
+            RouteReason::SemanticMatch => "semantic-match",
+            RouteReason::SemanticFallback => "semantic-fallback",
         }
     }
 }
@@ -92,6 +104,9 @@ pub struct NexusTransparentHeaders {
 
     /// Estimated cost in USD (optional, cloud backends only).
     pub cost_estimated: Option<f64>,
+
+    /// Optional detail appended to the route reason (e.g. "code:0.91").
+    pub route_detail: Option<String>,
 }
 
 impl NexusTransparentHeaders {
@@ -109,9 +124,16 @@ impl NexusTransparentHeaders {
             route_reason,
             privacy_zone,
             cost_estimated,
+            route_detail: None,
         }
     }
 
+    /// Append detail to the route reason header, as `<reason>:<detail>`.
+    pub fn with_route_detail(mut self, detail: Option<String>) -> Self {
+        self.route_detail = detail;
+        self
+    }
+
     /// Inject all X-Nexus-* headers into an HTTP response.
     ///
     /// This method is the single point of header injection for all responses
@@ -133,19 +155,28 @@ impl NexusTransparentHeaders {
             | BackendType::LlamaCpp
             | BackendType::Exo
             | BackendType::LMStudio
+            | BackendType::TGI
+            | BackendType::TEI
             | BackendType::Generic => "local",
-            BackendType::OpenAI | BackendType::Anthropic | BackendType::Google => "cloud",
+            BackendType::OpenAI
+            | BackendType::Anthropic
+            | BackendType::Google
+            | BackendType::AzureOpenAI
+            | BackendType::Bedrock
+            | BackendType::Cohere => "cloud",
         };
         headers.insert(
             HeaderName::from_static(HEADER_BACKEND_TYPE),
             HeaderValue::from_static(backend_type_str),
         );
 
-        // X-Nexus-Route-Reason: routing decision
-        headers.insert(
-            HeaderName::from_static(HEADER_ROUTE_REASON),
-            HeaderValue::from_static(self.route_reason.as_str()),
-        );
+        // X-Nexus-Route-Reason: routing decision, with optional detail
+        let route_reason = match &self.route_detail {
+            Some(detail) => HeaderValue::from_str(&format!("{}:{}", self.route_reason, detail))
+                .unwrap_or_else(|_| HeaderValue::from_static(self.route_reason.as_str())),
+            None => HeaderValue::from_static(self.route_reason.as_str()),
+        };
+        headers.insert(HeaderName::from_static(HEADER_ROUTE_REASON), route_reason);
 
         // X-Nexus-Privacy-Zone: "restricted" or "open"
         let privacy_zone_str = match self.privacy_zone {
@@ -181,6 +212,28 @@ mod tests {
             "privacy-requirement"
         );
         assert_eq!(RouteReason::Failover.as_str(), "failover");
+        assert_eq!(RouteReason::ContentRule.as_str(), "content-rule");
+        assert_eq!(RouteReason::SemanticMatch.as_str(), "semantic-match");
+        assert_eq!(RouteReason::SemanticFallback.as_str(), "semantic-fallback");
+    }
+
+    #[test]
+    fn test_route_detail_appended_to_reason() {
+        let headers = NexusTransparentHeaders::new(
+            "local".to_string(),
+            BackendType::Ollama,
+            RouteReason::SemanticMatch,
+            PrivacyZone::Restricted,
+            None,
+        )
+        .with_route_detail(Some("code:0.91".to_string()));
+
+        let mut response = Response::new(());
+        headers.inject_into_response(&mut response);
+        assert_eq!(
+            response.headers().get(HEADER_ROUTE_REASON).unwrap(),
+            "semantic-match:code:0.91"
+        );
     }
 
     #[test]
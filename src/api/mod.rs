@@ -60,6 +60,7 @@
 //! }
 //! ```
 
+pub mod cache;
 mod completions;
 pub mod embeddings;
 pub mod error;
@@ -67,6 +68,7 @@ pub mod headers;
 mod health;
 pub mod lifecycle;
 pub mod models;
+pub mod rerank;
 pub mod types;
 
 pub use types::*;
@@ -110,6 +112,14 @@ pub struct AppState {
     pub queue: Option<Arc<crate::queue::RequestQueue>>,
     /// Fleet intelligence tracker for pre-warming recommendations
     pub fleet_tracker: Arc<crate::routing::reconciler::fleet::FleetReconciler>,
+    /// Exact-match response cache
+    pub response_cache: Arc<crate::cache::ResponseCache>,
+    /// Embedding vector cache for `/v1/embeddings`, if enabled
+    pub embedding_cache: Option<Arc<crate::cache::EmbeddingCache>>,
+    /// In-flight chat requests for single-flight deduplication
+    pub single_flight: Arc<crate::cache::SingleFlight>,
+    /// Compiled output guardrails, if enabled
+    pub guardrails: Option<Arc<crate::guardrails::OutputGuard>>,
 }
 
 impl AppState {
@@ -132,7 +142,7 @@ impl AppState {
                 crate::config::PolicyMatcher::default()
             });
 
-        let router = Arc::new(routing::Router::with_aliases_fallbacks_and_policies(
+        let mut router = routing::Router::with_aliases_fallbacks_and_policies(
             Arc::clone(&registry),
             config.routing.strategy.into(),
             config.routing.weights.clone().into(),
@@ -140,7 +150,36 @@ impl AppState {
             config.routing.fallbacks.clone(),
             policy_matcher,
             config.quality.clone(),
-        ));
+        );
+        router.set_circuit_breaker_config(config.circuit_breaker.clone());
+        if let Err(e) = router.set_content_rules(config.routing.rules.clone()) {
+            tracing::warn!("Failed to compile content routing rules, ignoring: {}", e);
+        }
+        if let Err(e) = router.set_semantic_routing(config.routing.semantic.clone()) {
+            tracing::warn!("Invalid semantic routing config, disabling: {}", e);
+        }
+        if let Err(e) = router.set_transforms(&config.transforms) {
+            tracing::warn!("Invalid request transform config, ignoring: {}", e);
+        }
+        if let Err(e) = router.set_tokenizer_files(&config.routing.tokenizers) {
+            tracing::warn!("Failed to load tokenizer files, using defaults: {}", e);
+        }
+        if let Err(e) = router.set_pii_config(&config.pii) {
+            tracing::warn!("Failed to compile PII detectors, disabling: {}", e);
+        }
+        if let Err(e) = router.set_screening(&config.screening) {
+            tracing::warn!("Invalid injection screening config, disabling: {}", e);
+        }
+        router.set_tool_emulation(config.tool_emulation.enabled);
+        // Pricing for cost estimation, extended with provider catalogue prices
+        let pricing = crate::agent::pricing::PricingTable::new().with_models(
+            config
+                .provider_catalogue()
+                .into_values()
+                .flat_map(|spec| spec.pricing),
+        );
+        router.set_pricing(pricing.clone());
+        let router = Arc::new(router);
 
         // Initialize metrics (safe to call multiple times - will reuse existing if already set)
         let prometheus_handle = crate::metrics::setup_metrics().unwrap_or_else(|e| {
@@ -164,15 +203,32 @@ impl AppState {
         // Create WebSocket broadcast channel for dashboard real-time updates
         let (ws_broadcast, _) = broadcast::channel(1000);
 
-        // Create pricing table for cloud cost estimation
-        let pricing = Arc::new(crate::agent::pricing::PricingTable::new());
-
         // Create fleet intelligence tracker
         let fleet_tracker = Arc::new(crate::routing::reconciler::fleet::FleetReconciler::new(
             config.fleet.clone(),
             Arc::clone(&registry),
         ));
 
+        let response_cache = Arc::new(crate::cache::ResponseCache::new(config.cache.clone()));
+        let embedding_cache = config.cache.embeddings.enabled.then(|| {
+            Arc::new(crate::cache::EmbeddingCache::new(
+                &config.cache.embeddings,
+                config.cache.ttl_seconds,
+            ))
+        });
+
+        let guardrails = if config.guardrails.enabled {
+            match crate::guardrails::OutputGuard::new(&config.guardrails) {
+                Ok(guard) => Some(Arc::new(guard)),
+                Err(e) => {
+                    tracing::warn!("Invalid guardrails config, disabling: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         Self {
             registry,
             config,
@@ -182,9 +238,13 @@ impl AppState {
             metrics_collector,
             request_history,
             ws_broadcast,
-            pricing,
+            pricing: Arc::new(pricing),
             queue: None,
             fleet_tracker,
+            response_cache,
+            embedding_cache,
+            single_flight: Arc::new(crate::cache::SingleFlight::new()),
+            guardrails,
         }
     }
 }
@@ -199,6 +259,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         // API routes
         .route("/v1/chat/completions", post(completions::handle))
         .route("/v1/embeddings", post(embeddings::handle))
+        .route("/v1/rerank", post(rerank::handle))
         .route("/v1/models", get(models::handle))
         .route("/v1/history", get(crate::dashboard::history_handler))
         .route("/health", get(health::handle))
@@ -212,6 +273,8 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/v1/fleet/recommendations",
             get(lifecycle::handle_recommendations),
         )
+        // Response cache management
+        .route("/v1/cache/semantic", delete(cache::handle_invalidate))
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .with_state(state)
 }
@@ -0,0 +1,261 @@
+//! Rerank endpoint handler.
+//!
+//! Follows the Cohere/Jina rerank shape that TEI, vLLM and most hosted
+//! rerankers accept: a query plus a list of documents, answered with the
+//! documents sorted by relevance.
+
+use crate::api::headers::{NexusTransparentHeaders, RouteReason};
+use crate::api::{ApiError, AppState};
+use crate::routing::RequestRequirements;
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+/// Rerank request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RerankRequest {
+    pub model: String,
+    pub query: String,
+    pub documents: Vec<String>,
+    /// Only return the `top_n` most relevant documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_n: Option<usize>,
+    /// Echo each document's text back in its result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_documents: Option<bool>,
+}
+
+/// Document text echoed back when `return_documents` is set.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RerankDocument {
+    pub text: String,
+}
+
+/// A single scored document, `index` pointing into the request's documents.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RerankResult {
+    pub index: usize,
+    pub relevance_score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document: Option<RerankDocument>,
+}
+
+/// Token usage for rerank requests.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RerankUsage {
+    pub total_tokens: u32,
+}
+
+/// Rerank response, results sorted by descending relevance.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RerankResponse {
+    pub model: String,
+    pub results: Vec<RerankResult>,
+    pub usage: RerankUsage,
+}
+
+/// Maximum number of documents per rerank request.
+const MAX_RERANK_DOCUMENTS: usize = 1000;
+
+/// Pair scores with their documents, sort by relevance and apply `top_n`.
+fn rank(
+    documents: Vec<String>,
+    scores: Vec<f32>,
+    top_n: Option<usize>,
+    return_documents: bool,
+) -> Vec<RerankResult> {
+    let mut results: Vec<RerankResult> = documents
+        .into_iter()
+        .zip(scores)
+        .enumerate()
+        .map(|(index, (text, relevance_score))| RerankResult {
+            index,
+            relevance_score,
+            document: return_documents.then_some(RerankDocument { text }),
+        })
+        .collect();
+    results.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+    if let Some(top_n) = top_n {
+        results.truncate(top_n);
+    }
+    results
+}
+
+/// POST /v1/rerank — Handle rerank requests.
+#[instrument(
+    skip(state, _headers, request),
+    fields(model = %request.model)
+)]
+pub async fn handle(
+    State(state): State<Arc<AppState>>,
+    _headers: HeaderMap,
+    Json(request): Json<RerankRequest>,
+) -> Result<Response, ApiError> {
+    info!(
+        model = %request.model,
+        documents = request.documents.len(),
+        "Rerank request"
+    );
+
+    if request.documents.is_empty() {
+        return Err(ApiError::bad_request("Documents must not be empty"));
+    }
+
+    if request.documents.len() > MAX_RERANK_DOCUMENTS {
+        return Err(ApiError::bad_request(&format!(
+            "{} documents exceeds maximum of {}",
+            request.documents.len(),
+            MAX_RERANK_DOCUMENTS
+        )));
+    }
+
+    // Estimate tokens for routing: the query is scored against every document
+    let estimated_tokens: u32 = request
+        .documents
+        .iter()
+        .map(|d| (request.query.len() + d.len()) as u32 / 4)
+        .sum();
+
+    let requirements = RequestRequirements {
+        model: request.model.clone(),
+        estimated_tokens,
+        needs_vision: false,
+        needs_tools: false,
+        needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
+        prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
+    };
+
+    let routing_result = state
+        .router
+        .select_backend(&requirements, None)
+        .map_err(|e| match e {
+            crate::routing::RoutingError::ModelNotFound { model } => {
+                ApiError::model_not_found(&model, &[])
+            }
+            crate::routing::RoutingError::NoHealthyBackend { model } => {
+                ApiError::service_unavailable(&format!(
+                    "No healthy backend available for model '{}'",
+                    model
+                ))
+            }
+            _ => ApiError::bad_gateway(&format!("Routing error: {}", e)),
+        })?;
+
+    let backend = &routing_result.backend;
+
+    let agent = state.registry.get_agent(&backend.id).ok_or_else(|| {
+        ApiError::bad_gateway(&format!("No agent registered for backend '{}'", backend.id))
+    })?;
+
+    if !agent.profile().capabilities.rerank {
+        return Err(ApiError::service_unavailable(&format!(
+            "Backend '{}' does not support rerank",
+            backend.id
+        )));
+    }
+
+    let _ = state.registry.increment_pending(&backend.id);
+
+    let scores = agent
+        .rerank(
+            &routing_result.actual_model,
+            &request.query,
+            request.documents.clone(),
+        )
+        .await
+        .map_err(|e| {
+            let _ = state.registry.decrement_pending(&backend.id);
+            ApiError::from_agent_error(e)
+        })?;
+
+    let _ = state.registry.decrement_pending(&backend.id);
+
+    let response = RerankResponse {
+        model: request.model,
+        results: rank(
+            request.documents,
+            scores,
+            request.top_n,
+            request.return_documents.unwrap_or(false),
+        ),
+        usage: RerankUsage {
+            total_tokens: estimated_tokens,
+        },
+    };
+
+    let mut resp = Json(response).into_response();
+
+    // Inject X-Nexus-* transparent headers (Constitution Principle III)
+    let privacy_zone = agent.profile().privacy_zone;
+    let nexus_headers = NexusTransparentHeaders::new(
+        backend.id.clone(),
+        backend.backend_type,
+        RouteReason::CapabilityMatch,
+        privacy_zone,
+        routing_result.cost_estimated,
+    );
+    nexus_headers.inject_into_response(&mut resp);
+
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs() -> Vec<String> {
+        vec!["a".to_string(), "b".to_string(), "c".to_string()]
+    }
+
+    #[test]
+    fn rerank_request_deserialize_defaults() {
+        let json = r#"{"model":"bge-reranker","query":"q","documents":["a","b"]}"#;
+        let req: RerankRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.documents.len(), 2);
+        assert!(req.top_n.is_none());
+        assert!(req.return_documents.is_none());
+    }
+
+    #[test]
+    fn rank_sorts_by_descending_score() {
+        let results = rank(docs(), vec![0.1, 0.9, 0.5], None, false);
+        let order: Vec<usize> = results.iter().map(|r| r.index).collect();
+        assert_eq!(order, vec![1, 2, 0]);
+        assert!(results.iter().all(|r| r.document.is_none()));
+    }
+
+    #[test]
+    fn rank_applies_top_n_and_returns_documents() {
+        let results = rank(docs(), vec![0.1, 0.9, 0.5], Some(2), true);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].document.as_ref().unwrap().text, "b");
+        assert_eq!(results[1].document.as_ref().unwrap().text, "c");
+    }
+
+    #[test]
+    fn rerank_response_serialization() {
+        let response = RerankResponse {
+            model: "bge-reranker".to_string(),
+            results: rank(docs(), vec![0.1, 0.9, 0.5], Some(1), false),
+            usage: RerankUsage { total_tokens: 3 },
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["results"][0]["index"], 1);
+        assert!(json["results"][0].get("document").is_none());
+        assert_eq!(json["usage"]["total_tokens"], 3);
+    }
+}
@@ -44,6 +44,27 @@ pub struct ChatMessage {
     /// Function call (for assistant messages with function calls)
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub function_call: Option<FunctionCall>,
+    /// Tool calls (for assistant messages that call tools)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// Tool call answered by a `tool` message
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    /// Arguments of the message's tool calls and legacy function call.
+    pub fn call_arguments_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        self.tool_calls
+            .iter_mut()
+            .flatten()
+            .map(|call| &mut call.function.arguments)
+            .chain(
+                self.function_call
+                    .iter_mut()
+                    .map(|call| &mut call.arguments),
+            )
+    }
 }
 
 /// Function call information
@@ -53,6 +74,15 @@ pub struct FunctionCall {
     pub arguments: String,
 }
 
+/// Tool call made by the assistant.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub call_type: String,
+    pub function: FunctionCall,
+}
+
 /// Message content - either text or multimodal parts.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
@@ -129,6 +159,9 @@ pub struct ChatCompletionResponse {
     /// Additional fields (like system_fingerprint) preserved via flatten
     #[serde(flatten, default)]
     pub extra: HashMap<String, serde_json::Value>,
+    /// Backend-measured timings, used for quality metrics; never sent to clients
+    #[serde(skip)]
+    pub timings: Option<crate::agent::GenerationTimings>,
 }
 
 /// Token usage statistics.
@@ -165,6 +198,29 @@ pub struct ChunkDelta {
     pub role: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// Tool call fragment in a streaming chunk, merged by `index`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ToolCallDelta {
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub call_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<FunctionCallDelta>,
+}
+
+/// Function name and argument fragment of a streamed tool call.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FunctionCallDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
 }
 
 /// API error response in OpenAI format.
@@ -195,6 +251,18 @@ impl ApiError {
         }
     }
 
+    /// Create a context length exceeded error (400).
+    pub fn context_length_exceeded(message: &str) -> Self {
+        Self {
+            error: ApiErrorBody {
+                message: message.to_string(),
+                r#type: "invalid_request_error".to_string(),
+                param: Some("messages".to_string()),
+                code: Some("context_length_exceeded".to_string()),
+            },
+        }
+    }
+
     /// Create a model not found error (404) with available models hint.
     pub fn model_not_found(model: &str, available: &[String]) -> Self {
         let hint = if available.is_empty() {
@@ -272,6 +340,31 @@ impl ApiError {
         }
     }
 
+    /// Create a content filter error (422) for a response blocked by guardrails.
+    pub fn content_filtered(message: &str) -> Self {
+        Self {
+            error: ApiErrorBody {
+                message: message.to_string(),
+                r#type: "invalid_request_error".to_string(),
+                param: None,
+                code: Some("content_filter".to_string()),
+            },
+        }
+    }
+
+    /// Create a schema validation error (422) for structured output that
+    /// does not match the requested JSON schema.
+    pub fn schema_validation_failed(message: &str) -> Self {
+        Self {
+            error: ApiErrorBody {
+                message: message.to_string(),
+                r#type: "api_error".to_string(),
+                param: Some("response_format".to_string()),
+                code: Some("schema_validation_failed".to_string()),
+            },
+        }
+    }
+
     /// Create a conflict error (409).
     pub fn conflict(message: &str) -> Self {
         Self {
@@ -287,16 +380,21 @@ impl ApiError {
     /// Create error from raw backend response JSON (T050).
     ///
     /// This preserves the backend's error response unchanged, maintaining
-    /// OpenAI compatibility per Constitution Principle III.
+    /// OpenAI compatibility per Constitution Principle III. Azure OpenAI
+    /// content filter rejections are normalized to a `content_filter` error.
     ///
     /// Returns Ok with the preserved error, or Err if unable to parse at all.
     pub fn from_backend_json(status_code: u16, json_body: String) -> Result<Self, Self> {
         // Try to parse to verify it's valid JSON with error structure
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&json_body) {
-            if parsed.get("error").is_some() {
+            if let Some(error) = parsed.get("error") {
+                if let Some(filtered) = Self::from_content_filter(error) {
+                    return Ok(filtered);
+                }
                 // It has an error field, assume it's OpenAI-compatible
-                // Return a custom ApiError that will serialize to the raw JSON
-                return Ok(ApiError::from_raw_json(json_body));
+                if let Ok(body) = serde_json::from_value::<ApiErrorBody>(error.clone()) {
+                    return Ok(Self { error: body });
+                }
             }
         }
 
@@ -307,15 +405,35 @@ impl ApiError {
         )))
     }
 
-    /// Create from raw JSON string (for preserving backend errors exactly)
-    fn from_raw_json(json: String) -> Self {
-        // Parse to extract error fields, but will be re-serialized identically
-        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
-        let error_obj = value.get("error").unwrap();
+    /// Recognize an Azure OpenAI content filter rejection.
+    ///
+    /// Azure answers a filtered prompt with `code: "content_filter"` and an
+    /// `innererror` of `ResponsibleAIPolicyViolation` listing the triggered
+    /// categories, and sends `type: null`, which is not OpenAI-compatible.
+    fn from_content_filter(error: &serde_json::Value) -> Option<Self> {
+        let inner = &error["innererror"];
+        if error["code"] != "content_filter" && inner["code"] != "ResponsibleAIPolicyViolation" {
+            return None;
+        }
 
-        Self {
-            error: serde_json::from_value(error_obj.clone()).unwrap(),
+        let mut message = error["message"]
+            .as_str()
+            .unwrap_or("Request blocked by the backend's content filter")
+            .to_string();
+        if let Some(results) = inner["content_filter_result"].as_object() {
+            let categories: Vec<&str> = results
+                .iter()
+                .filter(|(_, result)| result["filtered"] == true)
+                .map(|(category, _)| category.as_str())
+                .collect();
+            if !categories.is_empty() {
+                message.push_str(&format!(" (filtered: {})", categories.join(", ")));
+            }
         }
+
+        let mut filtered = Self::content_filtered(&message);
+        filtered.error.param = error["param"].as_str().map(str::to_string);
+        Some(filtered)
     }
 
     /// Convert AgentError to ApiError (T038)
@@ -326,7 +444,12 @@ impl ApiError {
             }
             crate::agent::AgentError::Timeout(_) => Self::gateway_timeout(),
             crate::agent::AgentError::Upstream { status, message } => {
-                if status >= 500 {
+                let filtered = serde_json::from_str::<serde_json::Value>(&message)
+                    .ok()
+                    .and_then(|body| Self::from_content_filter(&body["error"]));
+                if let Some(filtered) = filtered {
+                    filtered
+                } else if status >= 500 {
                     Self::bad_gateway(&format!("Backend returned {}: {}", status, message))
                 } else if status == 404 {
                     Self {
@@ -353,13 +476,22 @@ impl ApiError {
         }
     }
 
+    /// Whether the request itself was rejected (4xx), as opposed to the
+    /// backend failing to serve it.
+    pub fn is_client_error(&self) -> bool {
+        self.status_code().is_client_error() || self.error.r#type == "invalid_request_error"
+    }
+
     /// Get the HTTP status code for this error.
     fn status_code(&self) -> StatusCode {
         match self.error.code.as_deref() {
             Some("invalid_request_error") => StatusCode::BAD_REQUEST,
+            Some("context_length_exceeded") => StatusCode::BAD_REQUEST,
             Some("model_not_found") => StatusCode::NOT_FOUND,
             Some("not_found") => StatusCode::NOT_FOUND,
             Some("conflict") => StatusCode::CONFLICT,
+            Some("content_filter") => StatusCode::UNPROCESSABLE_ENTITY,
+            Some("schema_validation_failed") => StatusCode::UNPROCESSABLE_ENTITY,
             Some("bad_gateway") => StatusCode::BAD_GATEWAY,
             Some("gateway_timeout") => StatusCode::GATEWAY_TIMEOUT,
             Some("service_unavailable") => StatusCode::SERVICE_UNAVAILABLE,
@@ -459,6 +591,7 @@ mod tests {
             choices: vec![],
             usage: None,
             extra: HashMap::new(),
+            timings: None,
         };
         let json = serde_json::to_value(&response).unwrap();
         assert_eq!(json["object"], "chat.completion");
@@ -520,6 +653,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             },
             finish_reason: Some("stop".to_string()),
         };
@@ -533,6 +668,7 @@ mod tests {
         let delta = ChunkDelta {
             role: Some("assistant".to_string()),
             content: Some("Hello".to_string()),
+            tool_calls: None,
         };
         let json = serde_json::to_value(&delta).unwrap();
         assert_eq!(json["role"], "assistant");
@@ -605,6 +741,12 @@ mod tests {
             ApiError::bad_request("x").into_response().status(),
             StatusCode::BAD_REQUEST
         );
+        assert_eq!(
+            ApiError::context_length_exceeded("x")
+                .into_response()
+                .status(),
+            StatusCode::BAD_REQUEST
+        );
         assert_eq!(
             ApiError::model_not_found("x", &[]).into_response().status(),
             StatusCode::NOT_FOUND
@@ -684,6 +826,38 @@ mod tests {
         assert_eq!(error.error.code.as_deref(), Some("bad_gateway"));
     }
 
+    #[test]
+    fn test_from_backend_json_azure_content_filter() {
+        let json_body = r#"{"error":{"message":"The response was filtered due to the prompt triggering Azure OpenAI's content management policy.","type":null,"param":"prompt","code":"content_filter","status":400,"innererror":{"code":"ResponsibleAIPolicyViolation","content_filter_result":{"hate":{"filtered":true,"severity":"high"},"self_harm":{"filtered":false,"severity":"safe"},"violence":{"filtered":true,"severity":"medium"}}}}}"#
+            .to_string();
+        let error = ApiError::from_backend_json(400, json_body).unwrap();
+        assert_eq!(error.error.code.as_deref(), Some("content_filter"));
+        assert_eq!(error.error.param.as_deref(), Some("prompt"));
+        assert!(error.error.message.ends_with("(filtered: hate, violence)"));
+        assert_eq!(
+            error.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn test_from_backend_json_non_openai_error_body_is_wrapped() {
+        let json_body = r#"{"error":{"message":"Bad things","type":null}}"#.to_string();
+        let error = ApiError::from_backend_json(400, json_body).unwrap_err();
+        assert_eq!(error.error.code.as_deref(), Some("bad_gateway"));
+    }
+
+    #[test]
+    fn test_from_agent_error_upstream_content_filter() {
+        let agent_err = crate::agent::AgentError::Upstream {
+            status: 400,
+            message: r#"{"error":{"code":"400","message":"Blocked","innererror":{"code":"ResponsibleAIPolicyViolation"}}}"#.to_string(),
+        };
+        let api_err = ApiError::from_agent_error(agent_err);
+        assert_eq!(api_err.error.code.as_deref(), Some("content_filter"));
+        assert_eq!(api_err.error.message, "Blocked");
+    }
+
     #[test]
     fn test_from_agent_error_network() {
         let agent_err = crate::agent::AgentError::Network("connection refused".to_string());
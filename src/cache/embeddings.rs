@@ -0,0 +1,393 @@
+//! Embedding vector cache with request coalescing
+//!
+//! Caches `/v1/embeddings` vectors per (resolved model, input text hash).
+//! Only uncached inputs are forwarded to the backend, and the cached and fresh
+//! vectors are returned in the original input order. Concurrent requests for
+//! the same input attach to the first request's backend call instead of
+//! issuing their own.
+
+use super::{hex_digest, now_secs};
+use crate::agent::AgentError;
+use crate::config::EmbeddingCacheConfig;
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::watch;
+
+/// (resolved model, SHA-256 of the input text)
+type EmbeddingKey = (String, String);
+
+/// Channel carrying a coalesced vector from the leading request.
+type Inflight = watch::Receiver<Option<Vec<f32>>>;
+
+/// Outcome of claiming a single input.
+enum Claim<'a> {
+    /// Vector served from the cache
+    Cached(Vec<f32>),
+    /// This request computes the vector and publishes it to followers
+    Leader(Lease<'a>),
+    /// Another request is already computing the vector
+    Follower(Inflight),
+}
+
+/// Ownership of an in-flight input. Dropping the lease without completing it
+/// (backend error or cancelled request) releases followers, which then fetch
+/// the input themselves.
+struct Lease<'a> {
+    cache: &'a EmbeddingCache,
+    key: EmbeddingKey,
+    tx: watch::Sender<Option<Vec<f32>>>,
+}
+
+impl Lease<'_> {
+    fn complete(self, vector: Vec<f32>) {
+        self.cache.store(self.key.clone(), vector.clone());
+        self.tx.send_replace(Some(vector));
+    }
+}
+
+impl Drop for Lease<'_> {
+    fn drop(&mut self) {
+        self.cache.in_flight.lock().remove(&self.key);
+    }
+}
+
+/// LRU cache of embedding vectors with TTL and in-flight coalescing.
+pub struct EmbeddingCache {
+    ttl_seconds: u64,
+    /// key → (stored_at, vector)
+    entries: Mutex<LruCache<EmbeddingKey, (u64, Vec<f32>)>>,
+    in_flight: Mutex<HashMap<EmbeddingKey, Inflight>>,
+    lookups: AtomicU64,
+    hits: AtomicU64,
+}
+
+impl EmbeddingCache {
+    /// Create an embedding cache; entries expire after `ttl_seconds`.
+    pub fn new(config: &EmbeddingCacheConfig, ttl_seconds: u64) -> Self {
+        let capacity = NonZeroUsize::new(config.max_entries).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            ttl_seconds,
+            entries: Mutex::new(LruCache::new(capacity)),
+            in_flight: Mutex::new(HashMap::new()),
+            lookups: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Embed `texts` with `model`, forwarding only uncached inputs to `fetch`.
+    ///
+    /// `fetch` receives the inputs this request leads, in their original
+    /// relative order, and must return one vector per input; any other count
+    /// fails the request. It is called a second time only for inputs whose
+    /// coalesced leader failed. Vectors are returned in the order of `texts`.
+    pub async fn embed<F, Fut, E>(
+        &self,
+        model: &str,
+        texts: Vec<String>,
+        fetch: F,
+    ) -> Result<Vec<Vec<f32>>, E>
+    where
+        F: Fn(Vec<String>) -> Fut,
+        Fut: Future<Output = Result<Vec<Vec<f32>>, E>>,
+        E: From<AgentError>,
+    {
+        let mut vectors: Vec<Option<Vec<f32>>> = vec![None; texts.len()];
+        let mut leases = Vec::new();
+        let mut followers = Vec::new();
+
+        for (index, text) in texts.iter().enumerate() {
+            match self.claim(model, text) {
+                Claim::Cached(vector) => vectors[index] = Some(vector),
+                Claim::Leader(lease) => leases.push((index, lease)),
+                Claim::Follower(rx) => followers.push((index, rx)),
+            }
+        }
+
+        if !leases.is_empty() {
+            let batch: Vec<String> = leases.iter().map(|(i, _)| texts[*i].clone()).collect();
+            let fresh = fetch_exact(&fetch, batch).await?;
+            for ((index, lease), vector) in leases.into_iter().zip(fresh) {
+                vectors[index] = Some(vector.clone());
+                lease.complete(vector);
+            }
+        }
+
+        let mut orphaned = Vec::new();
+        for (index, mut rx) in followers {
+            let coalesced = rx
+                .wait_for(Option::is_some)
+                .await
+                .ok()
+                .and_then(|vector| vector.clone());
+            match coalesced {
+                Some(vector) => vectors[index] = Some(vector),
+                None => orphaned.push(index),
+            }
+        }
+
+        if !orphaned.is_empty() {
+            let batch: Vec<String> = orphaned.iter().map(|i| texts[*i].clone()).collect();
+            let fresh = fetch_exact(&fetch, batch).await?;
+            for (index, vector) in orphaned.into_iter().zip(fresh) {
+                self.store(key(model, &texts[index]), vector.clone());
+                vectors[index] = Some(vector);
+            }
+        }
+
+        Ok(vectors.into_iter().flatten().collect())
+    }
+
+    /// Number of vectors currently cached.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// True when no vectors are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn claim(&self, model: &str, text: &str) -> Claim<'_> {
+        let key = key(model, text);
+        let lookups = self.lookups.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let cached = {
+            let mut entries = self.entries.lock();
+            match entries.get(&key) {
+                Some((stored_at, vector))
+                    if now_secs().saturating_sub(*stored_at) < self.ttl_seconds =>
+                {
+                    Some(vector.clone())
+                }
+                Some(_) => {
+                    entries.pop(&key);
+                    None
+                }
+                None => None,
+            }
+        };
+
+        let (claim, result) = match cached {
+            Some(vector) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                (Claim::Cached(vector), "hit")
+            }
+            None => {
+                let mut in_flight = self.in_flight.lock();
+                match in_flight.get(&key) {
+                    Some(rx) => (Claim::Follower(rx.clone()), "coalesced"),
+                    None => {
+                        let (tx, rx) = watch::channel(None);
+                        in_flight.insert(key.clone(), rx);
+                        let lease = Lease {
+                            cache: self,
+                            key,
+                            tx,
+                        };
+                        (Claim::Leader(lease), "miss")
+                    }
+                }
+            }
+        };
+
+        metrics::counter!("nexus_embedding_cache_lookups_total", "result" => result).increment(1);
+        let hits = self.hits.load(Ordering::Relaxed);
+        metrics::gauge!("nexus_embedding_cache_hit_ratio").set(hits as f64 / lookups as f64);
+        claim
+    }
+
+    fn store(&self, key: EmbeddingKey, vector: Vec<f32>) {
+        let mut entries = self.entries.lock();
+        entries.put(key, (now_secs(), vector));
+        metrics::gauge!("nexus_embedding_cache_entries").set(entries.len() as f64);
+    }
+}
+
+/// Call `fetch`, failing unless it returns exactly one vector per input.
+async fn fetch_exact<F, Fut, E>(fetch: &F, batch: Vec<String>) -> Result<Vec<Vec<f32>>, E>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: Future<Output = Result<Vec<Vec<f32>>, E>>,
+    E: From<AgentError>,
+{
+    let inputs = batch.len();
+    let vectors = fetch(batch).await?;
+    if vectors.len() != inputs {
+        return Err(AgentError::InvalidResponse(format!(
+            "Embedding backend returned {} vectors for {} inputs",
+            vectors.len(),
+            inputs
+        ))
+        .into());
+    }
+    Ok(vectors)
+}
+
+fn key(model: &str, text: &str) -> EmbeddingKey {
+    (model.to_string(), hex_digest(text.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    fn cache() -> EmbeddingCache {
+        EmbeddingCache::new(
+            &EmbeddingCacheConfig {
+                enabled: true,
+                max_entries: 100,
+            },
+            3600,
+        )
+    }
+
+    /// Fake backend: embeds each text as [len] and records every batch.
+    fn backend(
+        calls: &Arc<Mutex<Vec<Vec<String>>>>,
+    ) -> impl Fn(Vec<String>) -> std::future::Ready<Result<Vec<Vec<f32>>, AgentError>> + '_ {
+        move |texts| {
+            calls.lock().push(texts.clone());
+            std::future::ready(Ok(texts.iter().map(|t| vec![t.len() as f32]).collect()))
+        }
+    }
+
+    fn texts(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn only_misses_are_forwarded_in_original_order() {
+        let cache = cache();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        cache
+            .embed("embed", texts(&["bb"]), backend(&calls))
+            .await
+            .unwrap();
+        let vectors = cache
+            .embed("embed", texts(&["a", "bb", "cccc"]), backend(&calls))
+            .await
+            .unwrap();
+
+        assert_eq!(vectors, vec![vec![1.0], vec![2.0], vec![4.0]]);
+        assert_eq!(calls.lock()[1], texts(&["a", "cccc"]));
+    }
+
+    #[tokio::test]
+    async fn keys_are_scoped_per_model() {
+        let cache = cache();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        cache
+            .embed("embed-a", texts(&["x"]), backend(&calls))
+            .await
+            .unwrap();
+        cache
+            .embed("embed-b", texts(&["x"]), backend(&calls))
+            .await
+            .unwrap();
+
+        assert_eq!(calls.lock().len(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn duplicate_inputs_in_one_request_share_a_call() {
+        let cache = cache();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let vectors = cache
+            .embed("embed", texts(&["dup", "dup"]), backend(&calls))
+            .await
+            .unwrap();
+
+        assert_eq!(vectors, vec![vec![3.0], vec![3.0]]);
+        assert_eq!(calls.lock()[0], texts(&["dup"]));
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_inputs_are_coalesced() {
+        let cache = Arc::new(cache());
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let (release_tx, release_rx) = watch::channel(false);
+
+        let fetch = |fetches: Arc<AtomicUsize>, release: watch::Receiver<bool>| {
+            move |texts: Vec<String>| {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                let mut release = release.clone();
+                async move {
+                    let _ = release.wait_for(|go| *go).await;
+                    Ok::<_, AgentError>(texts.iter().map(|_| vec![1.0]).collect())
+                }
+            }
+        };
+
+        let leader = tokio::spawn({
+            let cache = Arc::clone(&cache);
+            let fetch = fetch(Arc::clone(&fetches), release_rx.clone());
+            async move { cache.embed("embed", texts(&["q"]), fetch).await }
+        });
+        while cache.in_flight.lock().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let follower = tokio::spawn({
+            let cache = Arc::clone(&cache);
+            let fetch = fetch(Arc::clone(&fetches), release_rx.clone());
+            async move { cache.embed("embed", texts(&["q"]), fetch).await }
+        });
+        tokio::task::yield_now().await;
+        release_tx.send_replace(true);
+
+        assert_eq!(leader.await.unwrap().unwrap(), vec![vec![1.0]]);
+        assert_eq!(follower.await.unwrap().unwrap(), vec![vec![1.0]]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_is_not_cached() {
+        let cache = cache();
+        let result = cache
+            .embed("embed", texts(&["x"]), |_| async {
+                Err::<Vec<Vec<f32>>, _>(AgentError::Network("backend down".to_string()))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(cache.is_empty());
+        assert!(cache.in_flight.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn missing_vectors_fail_the_request() {
+        let cache = cache();
+        let result = cache
+            .embed("embed", texts(&["a", "b"]), |_| async {
+                Ok::<_, AgentError>(vec![vec![1.0]])
+            })
+            .await;
+
+        assert!(matches!(result, Err(AgentError::InvalidResponse(_))));
+        assert!(cache.is_empty());
+        assert!(cache.in_flight.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn expired_vectors_are_refetched() {
+        let cache = EmbeddingCache::new(&EmbeddingCacheConfig::default(), 0);
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        for _ in 0..2 {
+            cache
+                .embed("embed", texts(&["x"]), backend(&calls))
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.lock().len(), 2);
+    }
+}
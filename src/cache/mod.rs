@@ -0,0 +1,536 @@
+//! Exact-match response cache
+//!
+//! Caches non-streaming chat completion responses keyed on the normalised
+//! request: resolved model, messages, sampling parameters and any extra
+//! fields such as `tools`. Keys are scoped to a [`CacheNamespace`] so tenants
+//! and privacy zones never share entries.
+//!
+//! Entries live in an in-memory LRU and, when `disk_path` is configured, are
+//! also written to disk as JSON so they survive restarts. An optional
+//! [`SemanticCache`] answers near-identical prompts on exact-match misses, and
+//! the [`EmbeddingCache`] serves repeated `/v1/embeddings` inputs.
+//! [`SingleFlight`] shares one backend call between identical in-flight
+//! requests.
+
+pub mod embeddings;
+pub mod semantic;
+pub mod single_flight;
+
+pub use embeddings::EmbeddingCache;
+pub use semantic::{SemanticCache, SemanticScope};
+pub use single_flight::{ResponseFlight, SharedResponse, SingleFlight, StreamFlight};
+
+use crate::agent::PrivacyZone;
+use crate::api::{
+    ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse, ChunkChoice, ChunkDelta,
+    FunctionCallDelta, MessageContent, ToolCallDelta,
+};
+use crate::config::CacheConfig;
+use axum::http::HeaderMap;
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Response header reporting whether the cache answered the request.
+pub const CACHE_HEADER: &str = "x-nexus-cache";
+
+/// Response header carrying the prompt similarity of a semantic cache hit.
+pub const CACHE_SIMILARITY_HEADER: &str = "x-nexus-cache-similarity";
+
+/// Response header marking a response shared from an identical in-flight request.
+pub const SINGLE_FLIGHT_HEADER: &str = "x-nexus-single-flight";
+
+/// Request fields that do not change the response and are left out of the key.
+const UNKEYED_FIELDS: [&str; 2] = ["stream", "stream_options"];
+
+/// Isolation scope for cache entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheNamespace {
+    /// Hash of the caller's credentials ("anonymous" without Authorization)
+    tenant: String,
+    /// Privacy zone of the backend the request routes to
+    privacy_zone: PrivacyZone,
+}
+
+impl CacheNamespace {
+    /// Derive the namespace from the request's Authorization header and the
+    /// privacy zone of the selected backend.
+    pub fn new(headers: &HeaderMap, privacy_zone: PrivacyZone) -> Self {
+        Self {
+            tenant: tenant_id(headers),
+            privacy_zone,
+        }
+    }
+}
+
+/// Tenant identifier: a hash of the Authorization header, or "anonymous".
+fn tenant_id(headers: &HeaderMap) -> String {
+    authenticated_tenant(headers).unwrap_or_else(|| "anonymous".to_string())
+}
+
+/// Authenticated tenant identity: the first 16 hex digits of the SHA-256 of
+/// the Authorization header value, or `None` for unauthenticated requests.
+pub fn authenticated_tenant(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("authorization")
+        .map(|value| hex_digest(value.as_bytes())[..16].to_string())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Unix timestamp (seconds) when the entry was stored
+    stored_at: u64,
+    response: ChatCompletionResponse,
+}
+
+/// Exact-match response cache with LRU eviction, TTL and optional disk storage.
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<LruCache<String, CacheEntry>>,
+    semantic: Option<SemanticCache>,
+}
+
+impl ResponseCache {
+    /// Create a cache from configuration.
+    pub fn new(config: CacheConfig) -> Self {
+        let capacity = NonZeroUsize::new(config.max_entries).unwrap_or(NonZeroUsize::MIN);
+        if let Some(dir) = &config.disk_path {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                tracing::warn!(path = %dir.display(), error = %e, "Failed to create cache directory");
+            }
+        }
+        let semantic = config
+            .semantic
+            .enabled
+            .then(|| SemanticCache::new(config.semantic.clone(), config.ttl_seconds));
+        Self {
+            config,
+            entries: Mutex::new(LruCache::new(capacity)),
+            semantic,
+        }
+    }
+
+    /// Semantic cache, if enabled.
+    pub fn semantic(&self) -> Option<&SemanticCache> {
+        self.semantic.as_ref()
+    }
+
+    /// Whether caching applies to this request.
+    ///
+    /// `policy` is the matching traffic policy's `cache` override, if any.
+    pub fn is_cacheable(&self, request: &ChatCompletionRequest, policy: Option<bool>) -> bool {
+        policy.unwrap_or(self.config.enabled) && self.is_shareable(request)
+    }
+
+    /// Whether one response may answer identical copies of this request
+    /// (any request unless `deterministic_only` requires `temperature: 0`).
+    pub fn is_shareable(&self, request: &ChatCompletionRequest) -> bool {
+        !self.config.deterministic_only || request.temperature == Some(0.0)
+    }
+
+    /// Compute the cache key for a request (model must already be resolved).
+    pub fn key(namespace: &CacheNamespace, request: &ChatCompletionRequest) -> String {
+        let extra: BTreeMap<&String, &serde_json::Value> = request
+            .extra
+            .iter()
+            .filter(|(field, _)| !UNKEYED_FIELDS.contains(&field.as_str()))
+            .collect();
+        let normalised = serde_json::json!({
+            "tenant": namespace.tenant,
+            "privacy_zone": format!("{:?}", namespace.privacy_zone),
+            "model": request.model,
+            "messages": request.messages,
+            "temperature": request.temperature,
+            "max_tokens": request.max_tokens,
+            "top_p": request.top_p,
+            "stop": request.stop,
+            "presence_penalty": request.presence_penalty,
+            "frequency_penalty": request.frequency_penalty,
+            "extra": extra,
+        });
+        hex_digest(normalised.to_string().as_bytes())
+    }
+
+    /// Look up a response, falling back to disk on a memory miss.
+    pub async fn get(&self, key: &str) -> Option<ChatCompletionResponse> {
+        let cached = self.entries.lock().get(key).cloned();
+        let entry = match cached {
+            Some(entry) => Some(entry),
+            None => self.load_from_disk(key).await,
+        };
+
+        match entry {
+            Some(entry) if !self.is_expired(&entry) => {
+                metrics::counter!("nexus_cache_lookups_total", "result" => "hit").increment(1);
+                self.entries.lock().put(key.to_string(), entry.clone());
+                Some(entry.response)
+            }
+            Some(_) => {
+                self.entries.lock().pop(key);
+                self.remove_from_disk(key).await;
+                metrics::counter!("nexus_cache_lookups_total", "result" => "miss").increment(1);
+                None
+            }
+            None => {
+                metrics::counter!("nexus_cache_lookups_total", "result" => "miss").increment(1);
+                None
+            }
+        }
+    }
+
+    /// Store a response under the given key.
+    pub async fn insert(&self, key: String, response: &ChatCompletionResponse) {
+        let entry = CacheEntry {
+            stored_at: now_secs(),
+            response: response.clone(),
+        };
+        if let Some(path) = self.disk_path(&key) {
+            match serde_json::to_vec(&entry) {
+                Ok(bytes) => {
+                    if let Err(e) = tokio::fs::write(&path, bytes).await {
+                        tracing::warn!(path = %path.display(), error = %e, "Failed to write cache entry");
+                    }
+                }
+                Err(e) => tracing::warn!(error = %e, "Failed to serialize cache entry"),
+            }
+        }
+
+        let mut entries = self.entries.lock();
+        if let Some((evicted, _)) = entries.push(key.clone(), entry) {
+            if evicted != key {
+                metrics::counter!("nexus_cache_evictions_total").increment(1);
+            }
+        }
+        metrics::counter!("nexus_cache_stores_total").increment(1);
+        metrics::gauge!("nexus_cache_entries").set(entries.len() as f64);
+    }
+
+    /// Number of entries currently held in memory.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// True when no entries are held in memory.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn is_expired(&self, entry: &CacheEntry) -> bool {
+        now_secs().saturating_sub(entry.stored_at) >= self.config.ttl_seconds
+    }
+
+    fn disk_path(&self, key: &str) -> Option<PathBuf> {
+        self.config
+            .disk_path
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.json", key)))
+    }
+
+    async fn load_from_disk(&self, key: &str) -> Option<CacheEntry> {
+        let bytes = tokio::fs::read(self.disk_path(key)?).await.ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    async fn remove_from_disk(&self, key: &str) {
+        if let Some(path) = self.disk_path(key) {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+    }
+}
+
+/// Convert a cached response into `chat.completion.chunk` SSE payloads
+/// (one content and tool call chunk and one finish chunk per choice).
+pub fn replay_chunks(response: &ChatCompletionResponse) -> Vec<String> {
+    let chunk = |choices: Vec<ChunkChoice>| ChatCompletionChunk {
+        id: response.id.clone(),
+        object: "chat.completion.chunk".to_string(),
+        created: response.created,
+        model: response.model.clone(),
+        choices,
+    };
+
+    let mut chunks = Vec::with_capacity(response.choices.len() * 2);
+    for choice in &response.choices {
+        let content = match &choice.message.content {
+            MessageContent::Text { content } => content.clone(),
+            MessageContent::Parts { content } => content
+                .iter()
+                .filter_map(|part| part.text.as_deref())
+                .collect(),
+        };
+        let tool_calls = choice.message.tool_calls.as_ref().map(|calls| {
+            calls
+                .iter()
+                .enumerate()
+                .map(|(index, call)| ToolCallDelta {
+                    index: index as u32,
+                    id: Some(call.id.clone()),
+                    call_type: Some(call.call_type.clone()),
+                    function: Some(FunctionCallDelta {
+                        name: Some(call.function.name.clone()),
+                        arguments: Some(call.function.arguments.clone()),
+                    }),
+                })
+                .collect()
+        });
+        chunks.push(chunk(vec![ChunkChoice {
+            index: choice.index,
+            delta: ChunkDelta {
+                role: Some(choice.message.role.clone()),
+                content: (tool_calls.is_none() || !content.is_empty()).then_some(content),
+                tool_calls,
+            },
+            finish_reason: None,
+        }]));
+        chunks.push(chunk(vec![ChunkChoice {
+            index: choice.index,
+            delta: ChunkDelta {
+                role: None,
+                content: None,
+                tool_calls: None,
+            },
+            finish_reason: Some(
+                choice
+                    .finish_reason
+                    .clone()
+                    .unwrap_or_else(|| "stop".to_string()),
+            ),
+        }]));
+    }
+    chunks
+        .iter()
+        .filter_map(|chunk| serde_json::to_string(chunk).ok())
+        .collect()
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn request(model: &str, content: &str) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": model,
+            "temperature": 0.0,
+            "messages": [{"role": "user", "content": content}]
+        }))
+        .unwrap()
+    }
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn cache(config: CacheConfig) -> ResponseCache {
+        ResponseCache::new(CacheConfig {
+            enabled: true,
+            ..config
+        })
+    }
+
+    fn namespace() -> CacheNamespace {
+        CacheNamespace::new(&HeaderMap::new(), PrivacyZone::Restricted)
+    }
+
+    #[test]
+    fn key_ignores_stream_flag() {
+        let plain = request("llama3", "hi");
+        let mut streaming = plain.clone();
+        streaming.stream = true;
+        streaming
+            .extra
+            .insert("stream_options".to_string(), serde_json::json!({}));
+
+        assert_eq!(
+            ResponseCache::key(&namespace(), &plain),
+            ResponseCache::key(&namespace(), &streaming)
+        );
+    }
+
+    #[test]
+    fn key_covers_model_sampling_and_tools() {
+        let base = request("llama3", "hi");
+        let key = ResponseCache::key(&namespace(), &base);
+
+        let mut other_model = base.clone();
+        other_model.model = "mistral".to_string();
+        let mut other_top_p = base.clone();
+        other_top_p.top_p = Some(0.5);
+        let mut with_tools = base.clone();
+        with_tools.extra.insert(
+            "tools".to_string(),
+            serde_json::json!([{"type": "function"}]),
+        );
+
+        for variant in [other_model, other_top_p, with_tools] {
+            assert_ne!(ResponseCache::key(&namespace(), &variant), key);
+        }
+    }
+
+    #[test]
+    fn namespaces_separate_tenants_and_zones() {
+        let req = request("llama3", "hi");
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer team-a"));
+        let team_a = CacheNamespace::new(&headers, PrivacyZone::Restricted);
+        let open = CacheNamespace::new(&HeaderMap::new(), PrivacyZone::Open);
+
+        let key = ResponseCache::key(&namespace(), &req);
+        assert_ne!(ResponseCache::key(&team_a, &req), key);
+        assert_ne!(ResponseCache::key(&open, &req), key);
+    }
+
+    #[test]
+    fn only_deterministic_requests_are_cacheable() {
+        let cache = cache(CacheConfig::default());
+        let mut req = request("llama3", "hi");
+        assert!(cache.is_cacheable(&req, None));
+        assert!(!cache.is_cacheable(&req, Some(false)));
+
+        req.temperature = Some(0.7);
+        assert!(!cache.is_cacheable(&req, None));
+    }
+
+    #[test]
+    fn policy_can_enable_when_globally_disabled() {
+        let cache = ResponseCache::new(CacheConfig::default());
+        let req = request("llama3", "hi");
+        assert!(!cache.is_cacheable(&req, None));
+        assert!(cache.is_cacheable(&req, Some(true)));
+    }
+
+    #[tokio::test]
+    async fn stores_and_returns_responses() {
+        let cache = cache(CacheConfig::default());
+        assert!(cache.get("k").await.is_none());
+
+        cache.insert("k".to_string(), &response("cached")).await;
+        let hit = cache.get("k").await.unwrap();
+        assert_eq!(hit.choices[0].message.role, "assistant");
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_dropped() {
+        let cache = cache(CacheConfig {
+            ttl_seconds: 0,
+            ..Default::default()
+        });
+        cache.insert("k".to_string(), &response("cached")).await;
+
+        assert!(cache.get("k").await.is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_entry_is_evicted() {
+        let cache = cache(CacheConfig {
+            max_entries: 2,
+            ..Default::default()
+        });
+        cache.insert("a".to_string(), &response("a")).await;
+        cache.insert("b".to_string(), &response("b")).await;
+        cache.get("a").await;
+        cache.insert("c".to_string(), &response("c")).await;
+
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("b").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn disk_entries_survive_a_new_cache() {
+        let dir = std::env::temp_dir().join(format!("nexus-cache-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = CacheConfig {
+            disk_path: Some(dir.clone()),
+            ..Default::default()
+        };
+
+        cache(config.clone())
+            .insert("k".to_string(), &response("persisted"))
+            .await;
+        assert!(cache(config).get("k").await.is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn replay_produces_content_then_finish_chunk() {
+        let chunks = replay_chunks(&response("Hello"));
+        assert_eq!(chunks.len(), 2);
+
+        let first: serde_json::Value = serde_json::from_str(&chunks[0]).unwrap();
+        assert_eq!(first["object"], "chat.completion.chunk");
+        assert_eq!(first["choices"][0]["delta"]["content"], "Hello");
+        let last: serde_json::Value = serde_json::from_str(&chunks[1]).unwrap();
+        assert_eq!(last["choices"][0]["finish_reason"], "stop");
+    }
+
+    #[test]
+    fn replay_includes_tool_calls() {
+        let response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{\"city\":\"Oslo\"}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }]
+        }))
+        .unwrap();
+        let chunks = replay_chunks(&response);
+
+        let first: serde_json::Value = serde_json::from_str(&chunks[0]).unwrap();
+        let delta = &first["choices"][0]["delta"];
+        assert!(delta.get("content").is_none());
+        assert_eq!(delta["tool_calls"][0]["index"], 0);
+        assert_eq!(delta["tool_calls"][0]["id"], "call_1");
+        assert_eq!(delta["tool_calls"][0]["function"]["name"], "get_weather");
+        assert_eq!(
+            delta["tool_calls"][0]["function"]["arguments"],
+            "{\"city\":\"Oslo\"}"
+        );
+        let last: serde_json::Value = serde_json::from_str(&chunks[1]).unwrap();
+        assert_eq!(last["choices"][0]["finish_reason"], "tool_calls");
+    }
+}
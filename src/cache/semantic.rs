@@ -0,0 +1,370 @@
+//! Semantic response cache
+//!
+//! Stores prompt embeddings alongside their responses and answers new
+//! requests whose prompt is similar enough to a cached one. Entries are scoped
+//! to a [`SemanticScope`] (model, tenant, privacy zone and the request's
+//! earlier conversation, tools and response format) and searched
+//! with a flat in-process index: a linear cosine-similarity scan, which is
+//! cheap at the configured entry limits.
+
+use super::{hex_digest, now_secs, tenant_id, CacheNamespace};
+use crate::api::{ChatCompletionRequest, ChatCompletionResponse};
+use crate::config::SemanticCacheConfig;
+use crate::routing::semantic::cosine_similarity;
+use axum::http::HeaderMap;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+/// Lookup scope for semantic entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticScope {
+    namespace: CacheNamespace,
+    model: String,
+    /// Hash of the request context besides the latest user turn
+    context: String,
+}
+
+/// Request fields that shape the answer as much as the prompt does.
+const CONTEXT_FIELDS: &[&str] = &[
+    "tools",
+    "tool_choice",
+    "functions",
+    "function_call",
+    "response_format",
+];
+
+impl SemanticScope {
+    /// Scope entries to a resolved model within a cache namespace.
+    pub fn new(namespace: CacheNamespace, model: &str) -> Self {
+        Self {
+            namespace,
+            model: model.to_string(),
+            context: String::new(),
+        }
+    }
+
+    /// Scope entries to a request's resolved model and context.
+    ///
+    /// Only the latest user turn is embedded, so requests that differ in any
+    /// other message (system prompt, earlier turns, tool calls and results),
+    /// tools, tool choice or response format must never answer each other;
+    /// they land in different scopes.
+    pub fn for_request(namespace: CacheNamespace, request: &ChatCompletionRequest) -> Self {
+        let latest_user = request.messages.iter().rposition(|m| m.role == "user");
+        let history: Vec<_> = request
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != latest_user)
+            .map(|(_, m)| m)
+            .collect();
+        let fields: Vec<_> = CONTEXT_FIELDS
+            .iter()
+            .map(|field| request.extra.get(*field))
+            .collect();
+        let context = serde_json::json!({"history": history, "fields": fields});
+        Self {
+            context: hex_digest(context.to_string().as_bytes()),
+            ..Self::new(namespace, &request.model)
+        }
+    }
+}
+
+struct SemanticEntry {
+    scope: SemanticScope,
+    embedding: Vec<f32>,
+    /// Unix timestamp (seconds) when the entry was stored
+    stored_at: u64,
+    response: ChatCompletionResponse,
+}
+
+/// Nearest-neighbour cache over prompt embeddings.
+pub struct SemanticCache {
+    config: SemanticCacheConfig,
+    ttl_seconds: u64,
+    entries: Mutex<VecDeque<SemanticEntry>>,
+}
+
+impl SemanticCache {
+    /// Create a semantic cache; entries expire after `ttl_seconds`.
+    pub fn new(config: SemanticCacheConfig, ttl_seconds: u64) -> Self {
+        Self {
+            config,
+            ttl_seconds,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Embedding model used for prompts.
+    pub fn embedding_model(&self) -> &str {
+        &self.config.embedding_model
+    }
+
+    /// Most similar cached response in scope, with its similarity, if it
+    /// reaches the configured threshold.
+    pub fn lookup(
+        &self,
+        scope: &SemanticScope,
+        embedding: &[f32],
+    ) -> Option<(ChatCompletionResponse, f32)> {
+        let now = now_secs();
+        let mut entries = self.entries.lock();
+        entries.retain(|entry| now.saturating_sub(entry.stored_at) < self.ttl_seconds);
+
+        let best = entries
+            .iter()
+            .filter(|entry| &entry.scope == scope)
+            .map(|entry| (entry, cosine_similarity(embedding, &entry.embedding)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best {
+            Some((entry, score)) if score >= self.config.threshold => {
+                metrics::counter!("nexus_semantic_cache_lookups_total", "result" => "hit")
+                    .increment(1);
+                Some((entry.response.clone(), score))
+            }
+            _ => {
+                metrics::counter!("nexus_semantic_cache_lookups_total", "result" => "miss")
+                    .increment(1);
+                None
+            }
+        }
+    }
+
+    /// Store a response under its prompt embedding, evicting the oldest entry
+    /// when full.
+    pub fn insert(
+        &self,
+        scope: SemanticScope,
+        embedding: Vec<f32>,
+        response: &ChatCompletionResponse,
+    ) {
+        let mut entries = self.entries.lock();
+        while entries.len() >= self.config.max_entries {
+            entries.pop_front();
+            metrics::counter!("nexus_semantic_cache_evictions_total").increment(1);
+        }
+        entries.push_back(SemanticEntry {
+            scope,
+            embedding,
+            stored_at: now_secs(),
+            response: response.clone(),
+        });
+        metrics::gauge!("nexus_semantic_cache_entries").set(entries.len() as f64);
+    }
+
+    /// Drop the caller's entries (in any privacy zone), optionally only those
+    /// for one model. The tenant is taken from the Authorization header.
+    /// Returns the number of entries removed.
+    pub fn invalidate(&self, headers: &HeaderMap, model: Option<&str>) -> usize {
+        let tenant = tenant_id(headers);
+        let mut entries = self.entries.lock();
+        let before = entries.len();
+        entries.retain(|entry| {
+            entry.scope.namespace.tenant != tenant
+                || model.is_some_and(|model| entry.scope.model != model)
+        });
+        metrics::gauge!("nexus_semantic_cache_entries").set(entries.len() as f64);
+        before - entries.len()
+    }
+
+    /// Number of entries currently held.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// True when no entries are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::PrivacyZone;
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn cache(max_entries: usize) -> SemanticCache {
+        SemanticCache::new(
+            SemanticCacheConfig {
+                enabled: true,
+                embedding_model: "embed".to_string(),
+                threshold: 0.9,
+                max_entries,
+            },
+            3600,
+        )
+    }
+
+    fn headers(auth: Option<&'static str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(auth) = auth {
+            headers.insert("authorization", HeaderValue::from_static(auth));
+        }
+        headers
+    }
+
+    fn tenant(auth: Option<&'static str>) -> CacheNamespace {
+        CacheNamespace::new(&headers(auth), PrivacyZone::Restricted)
+    }
+
+    fn scope(model: &str) -> SemanticScope {
+        SemanticScope::new(tenant(None), model)
+    }
+
+    #[test]
+    fn similar_prompt_above_threshold_hits() {
+        let cache = cache(10);
+        cache.insert(scope("llama3"), vec![1.0, 0.0], &response("a"));
+        cache.insert(scope("llama3"), vec![0.0, 1.0], &response("b"));
+
+        let (_, score) = cache.lookup(&scope("llama3"), &[0.98, 0.1]).unwrap();
+        assert!(score > 0.99);
+        assert!(cache.lookup(&scope("llama3"), &[0.7, 0.7]).is_none());
+    }
+
+    #[test]
+    fn lookups_stay_within_scope() {
+        let cache = cache(10);
+        cache.insert(scope("llama3"), vec![1.0, 0.0], &response("a"));
+
+        assert!(cache.lookup(&scope("mistral"), &[1.0, 0.0]).is_none());
+        let other_tenant = SemanticScope::new(tenant(Some("Bearer team-b")), "llama3");
+        assert!(cache.lookup(&other_tenant, &[1.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn request_context_separates_scopes() {
+        let request = |extra: serde_json::Value| -> ChatCompletionRequest {
+            let mut body = serde_json::json!({
+                "model": "llama3",
+                "messages": [
+                    {"role": "system", "content": "Answer in French."},
+                    {"role": "user", "content": "Hello"}
+                ]
+            });
+            body.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(body).unwrap()
+        };
+        let plain = SemanticScope::for_request(tenant(None), &request(json!({})));
+        let cache = cache(10);
+        cache.insert(plain.clone(), vec![1.0, 0.0], &response("a"));
+
+        assert_eq!(
+            plain,
+            SemanticScope::for_request(tenant(None), &request(json!({})))
+        );
+        for extra in [
+            json!({"tools": [{"type": "function", "function": {"name": "f"}}]}),
+            json!({"tool_choice": "required"}),
+            json!({"response_format": {"type": "json_object"}}),
+        ] {
+            let scope = SemanticScope::for_request(tenant(None), &request(extra));
+            assert!(cache.lookup(&scope, &[1.0, 0.0]).is_none());
+        }
+
+        let mut other_system = request(json!({}));
+        other_system.messages[0].content = crate::api::types::MessageContent::Text {
+            content: "Answer in German.".to_string(),
+        };
+        let scope = SemanticScope::for_request(tenant(None), &other_system);
+        assert!(cache.lookup(&scope, &[1.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn earlier_turns_separate_scopes() {
+        let conversation = |messages: serde_json::Value| -> ChatCompletionRequest {
+            serde_json::from_value(json!({"model": "llama3", "messages": messages})).unwrap()
+        };
+        let deploy = conversation(json!([
+            {"role": "user", "content": "Should I deploy to production?"},
+            {"role": "assistant", "content": "Run the migrations first."},
+            {"role": "user", "content": "yes, go ahead"}
+        ]));
+        let delete = conversation(json!([
+            {"role": "user", "content": "Should I delete the old backups?"},
+            {"role": "assistant", "content": "That cannot be undone."},
+            {"role": "user", "content": "yes, go ahead"}
+        ]));
+        let tool_call = |args: &str| {
+            conversation(json!([
+                {"role": "user", "content": "Weather?"},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1", "type": "function",
+                    "function": {"name": "weather", "arguments": args}
+                }]},
+                {"role": "tool", "tool_call_id": "call_1", "content": "sunny"},
+                {"role": "user", "content": "continue"}
+            ]))
+        };
+
+        let scope =
+            |request: &ChatCompletionRequest| SemanticScope::for_request(tenant(None), request);
+        let cache = cache(10);
+        cache.insert(scope(&deploy), vec![1.0, 0.0], &response("deploying"));
+        cache.insert(
+            scope(&tool_call(r#"{"city":"Paris"}"#)),
+            vec![0.0, 1.0],
+            &response("sunny in Paris"),
+        );
+
+        assert!(cache.lookup(&scope(&deploy), &[1.0, 0.0]).is_some());
+        assert!(cache.lookup(&scope(&delete), &[1.0, 0.0]).is_none());
+        assert!(cache
+            .lookup(&scope(&tool_call(r#"{"city":"Oslo"}"#)), &[0.0, 1.0])
+            .is_none());
+    }
+
+    #[test]
+    fn expired_entries_are_dropped() {
+        let cache = SemanticCache::new(cache(10).config, 0);
+        cache.insert(scope("llama3"), vec![1.0, 0.0], &response("a"));
+
+        assert!(cache.lookup(&scope("llama3"), &[1.0, 0.0]).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn oldest_entry_is_evicted() {
+        let cache = cache(2);
+        cache.insert(scope("a"), vec![1.0], &response("a"));
+        cache.insert(scope("b"), vec![1.0], &response("b"));
+        cache.insert(scope("c"), vec![1.0], &response("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.lookup(&scope("a"), &[1.0]).is_none());
+        assert!(cache.lookup(&scope("c"), &[1.0]).is_some());
+    }
+
+    #[test]
+    fn invalidate_by_tenant_and_model() {
+        let cache = cache(10);
+        cache.insert(scope("llama3"), vec![1.0], &response("a"));
+        cache.insert(scope("mistral"), vec![1.0], &response("b"));
+        let other = SemanticScope::new(tenant(Some("Bearer team-b")), "llama3");
+        cache.insert(other.clone(), vec![1.0], &response("c"));
+
+        assert_eq!(cache.invalidate(&headers(None), Some("llama3")), 1);
+        assert_eq!(cache.invalidate(&headers(None), None), 1);
+        assert!(cache.lookup(&other, &[1.0]).is_some());
+    }
+}
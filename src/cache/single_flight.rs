@@ -0,0 +1,321 @@
+//! Single-flight deduplication of in-flight chat requests
+//!
+//! Identical requests (same cache key) that arrive while a first copy is
+//! still being served attach to that request's result instead of dispatching
+//! their own. Non-streaming followers wait for the leader's response and the
+//! headers it was delivered with; if the leader fails they fall back to
+//! dispatching themselves.
+//!
+//! Streams are fanned out through a shared, append-only chunk buffer that a
+//! background task fills from the backend. Every subscriber (including the
+//! leader) replays the buffer from the start and then follows it live, so a
+//! subscriber that joins late or disconnects early never affects the others.
+
+use crate::api::ChatCompletionResponse;
+use axum::http::HeaderMap;
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Channel carrying the leader's response to non-streaming followers.
+type ResponseRx = watch::Receiver<Option<SharedResponse>>;
+
+/// A leader's response and the headers it was delivered with.
+#[derive(Debug, Clone)]
+pub struct SharedResponse {
+    pub response: ChatCompletionResponse,
+    pub headers: HeaderMap,
+}
+
+/// Outcome of joining a non-streaming flight.
+pub enum ResponseFlight {
+    /// First request for the key: dispatch and complete the lease
+    Leader(ResponseLease),
+    /// Identical request already in flight: await its response
+    Follower(ResponseRx),
+}
+
+/// Outcome of joining a streaming flight.
+pub enum StreamFlight {
+    /// First request for the key: broadcast the backend stream
+    Leader(StreamLease),
+    /// Identical stream already in flight: subscribe to it
+    Follower(Arc<StreamFanout>),
+}
+
+/// Registry of in-flight requests keyed by cache key.
+#[derive(Default)]
+pub struct SingleFlight {
+    responses: Mutex<HashMap<String, ResponseRx>>,
+    streams: Mutex<HashMap<String, Arc<StreamFanout>>>,
+}
+
+impl SingleFlight {
+    /// Create an empty single-flight registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lead or follow the non-streaming request for `key`.
+    pub fn join_response(self: &Arc<Self>, key: &str) -> ResponseFlight {
+        let mut responses = self.responses.lock();
+        if let Some(rx) = responses.get(key) {
+            metrics::counter!("nexus_single_flight_total", "role" => "follower").increment(1);
+            return ResponseFlight::Follower(rx.clone());
+        }
+        let (tx, rx) = watch::channel(None);
+        responses.insert(key.to_string(), rx);
+        metrics::counter!("nexus_single_flight_total", "role" => "leader").increment(1);
+        ResponseFlight::Leader(ResponseLease {
+            flight: Arc::clone(self),
+            key: key.to_string(),
+            tx,
+        })
+    }
+
+    /// Wait for the leader's response; None if the leader failed.
+    pub async fn wait(mut rx: ResponseRx) -> Option<SharedResponse> {
+        rx.wait_for(Option::is_some)
+            .await
+            .ok()
+            .and_then(|response| response.clone())
+    }
+
+    /// Lead or follow the streaming request for `key`.
+    pub fn join_stream(self: &Arc<Self>, key: &str) -> StreamFlight {
+        let mut streams = self.streams.lock();
+        if let Some(fanout) = streams.get(key) {
+            metrics::counter!("nexus_single_flight_total", "role" => "stream_follower")
+                .increment(1);
+            return StreamFlight::Follower(Arc::clone(fanout));
+        }
+        let fanout = Arc::new(StreamFanout::new());
+        streams.insert(key.to_string(), Arc::clone(&fanout));
+        metrics::counter!("nexus_single_flight_total", "role" => "stream_leader").increment(1);
+        StreamFlight::Leader(StreamLease {
+            flight: Arc::clone(self),
+            key: key.to_string(),
+            fanout,
+        })
+    }
+}
+
+/// Ownership of an in-flight non-streaming request. Dropping the lease
+/// without completing it releases followers to dispatch on their own.
+pub struct ResponseLease {
+    flight: Arc<SingleFlight>,
+    key: String,
+    tx: watch::Sender<Option<SharedResponse>>,
+}
+
+impl ResponseLease {
+    /// Publish the response and its headers to every follower.
+    pub fn complete(self, response: &ChatCompletionResponse, headers: &HeaderMap) {
+        self.tx.send_replace(Some(SharedResponse {
+            response: response.clone(),
+            headers: headers.clone(),
+        }));
+    }
+}
+
+impl Drop for ResponseLease {
+    fn drop(&mut self) {
+        self.flight.responses.lock().remove(&self.key);
+    }
+}
+
+/// Ownership of an in-flight stream.
+pub struct StreamLease {
+    flight: Arc<SingleFlight>,
+    key: String,
+    fanout: Arc<StreamFanout>,
+}
+
+impl StreamLease {
+    /// Drive `source` in a background task and return the leader's view of it.
+    ///
+    /// The task stops early once every subscriber has gone away; `abandoned`
+    /// payloads (e.g. an error chunk and `[DONE]`) are then appended so that a
+    /// subscriber joining at that moment still sees a terminated stream.
+    pub fn broadcast<S>(self, source: S, abandoned: Vec<String>) -> impl Stream<Item = String>
+    where
+        S: Stream<Item = String> + Send + 'static,
+    {
+        let leader = self.fanout.subscribe();
+        tokio::spawn(async move {
+            let mut source = std::pin::pin!(source);
+            while let Some(data) = source.next().await {
+                self.fanout.push(data);
+                if self.fanout.progress.receiver_count() == 0 {
+                    tracing::debug!(key = %self.key, "All single-flight subscribers left");
+                    for data in abandoned {
+                        self.fanout.push(data);
+                    }
+                    break;
+                }
+            }
+            self.flight.streams.lock().remove(&self.key);
+            self.fanout.finish();
+        });
+        leader
+    }
+}
+
+/// Progress of a fanned-out stream.
+#[derive(Debug, Clone, Copy, Default)]
+struct Progress {
+    /// Number of payloads buffered so far
+    len: usize,
+    /// Whether the source stream has ended
+    done: bool,
+}
+
+/// Append-only buffer of SSE payloads shared by all subscribers of a stream.
+pub struct StreamFanout {
+    chunks: Mutex<Vec<String>>,
+    progress: watch::Sender<Progress>,
+}
+
+impl StreamFanout {
+    fn new() -> Self {
+        Self {
+            chunks: Mutex::new(Vec::new()),
+            progress: watch::Sender::new(Progress::default()),
+        }
+    }
+
+    fn push(&self, data: String) {
+        let mut chunks = self.chunks.lock();
+        chunks.push(data);
+        let len = chunks.len();
+        self.progress.send_modify(|progress| progress.len = len);
+    }
+
+    fn finish(&self) {
+        self.progress.send_modify(|progress| progress.done = true);
+    }
+
+    /// Replay the stream from its first payload and follow it to the end.
+    pub fn subscribe(self: &Arc<Self>) -> impl Stream<Item = String> {
+        let fanout = Arc::clone(self);
+        let mut progress = fanout.progress.subscribe();
+        async_stream::stream! {
+            let mut cursor = 0;
+            loop {
+                let current = match progress
+                    .wait_for(|progress| progress.len > cursor || progress.done)
+                    .await
+                {
+                    Ok(current) => *current,
+                    Err(_) => break,
+                };
+                let pending: Vec<String> = fanout.chunks.lock()[cursor..].to_vec();
+                cursor += pending.len();
+                for data in pending {
+                    yield data;
+                }
+                if current.done && cursor >= current.len {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "hi"},
+                "finish_reason": "stop"
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn followers_receive_leader_response() {
+        let flight = Arc::new(SingleFlight::new());
+        let ResponseFlight::Leader(lease) = flight.join_response("k") else {
+            panic!("first request should lead");
+        };
+        let ResponseFlight::Follower(rx) = flight.join_response("k") else {
+            panic!("second request should follow");
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-nexus-backend", "local".parse().unwrap());
+        lease.complete(&response(), &headers);
+        let shared = SingleFlight::wait(rx).await.unwrap();
+        assert_eq!(shared.response.id, "chatcmpl-1");
+        assert_eq!(shared.headers["x-nexus-backend"], "local");
+        assert!(matches!(
+            flight.join_response("k"),
+            ResponseFlight::Leader(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_leader_releases_followers() {
+        let flight = Arc::new(SingleFlight::new());
+        let lease = flight.join_response("k");
+        let ResponseFlight::Follower(rx) = flight.join_response("k") else {
+            panic!("second request should follow");
+        };
+
+        drop(lease);
+        assert!(SingleFlight::wait(rx).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn late_stream_subscribers_replay_from_start() {
+        let flight = Arc::new(SingleFlight::new());
+        let StreamFlight::Leader(lease) = flight.join_stream("k") else {
+            panic!("first stream should lead");
+        };
+        let (tx, source) = futures::channel::mpsc::unbounded::<String>();
+        let leader = lease.broadcast(source, vec![]);
+
+        tx.unbounded_send("a".to_string()).unwrap();
+        let StreamFlight::Follower(fanout) = flight.join_stream("k") else {
+            panic!("second stream should follow");
+        };
+        let follower = fanout.subscribe();
+        tx.unbounded_send("b".to_string()).unwrap();
+        drop(tx);
+
+        let (leader, follower): (Vec<String>, Vec<String>) =
+            tokio::join!(leader.collect(), follower.collect());
+        assert_eq!(leader, vec!["a", "b"]);
+        assert_eq!(follower, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn abandoned_stream_is_terminated() {
+        let flight = Arc::new(SingleFlight::new());
+        let StreamFlight::Leader(lease) = flight.join_stream("k") else {
+            panic!("first stream should lead");
+        };
+        let StreamFlight::Follower(fanout) = flight.join_stream("k") else {
+            panic!("second stream should follow");
+        };
+        let source = futures::stream::iter(vec!["a".to_string(), "b".to_string()]);
+        drop(lease.broadcast(source, vec!["[DONE]".to_string()]));
+
+        while !fanout.progress.borrow().done {
+            tokio::task::yield_now().await;
+        }
+        let replay: Vec<String> = fanout.subscribe().collect().await;
+        assert_eq!(replay, vec!["a", "[DONE]"]);
+    }
+}
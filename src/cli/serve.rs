@@ -103,6 +103,38 @@ pub fn load_backends_from_config(
         if let Some(api_key_env) = &backend_config.api_key_env {
             metadata.insert("api_key_env".to_string(), api_key_env.clone());
         }
+        if let Some(api_version) = &backend_config.api_version {
+            metadata.insert("api_version".to_string(), api_version.clone());
+        }
+        for (model, deployment) in &backend_config.deployments {
+            metadata.insert(format!("deployment:{}", model), deployment.clone());
+        }
+        if let Some(region) = &backend_config.region {
+            metadata.insert("region".to_string(), region.clone());
+        }
+        if let Some(creds) = &backend_config.aws_credentials_env {
+            metadata.insert(
+                "aws_access_key_id_env".to_string(),
+                creds.access_key_id.clone(),
+            );
+            metadata.insert(
+                "aws_secret_access_key_env".to_string(),
+                creds.secret_access_key.clone(),
+            );
+            if let Some(token) = &creds.session_token {
+                metadata.insert("aws_session_token_env".to_string(), token.clone());
+            }
+        }
+        if let Some((name, spec)) = backend_config
+            .provider
+            .as_ref()
+            .and_then(|name| Some((name, config.provider(name)?)))
+        {
+            metadata.insert("provider".to_string(), name.clone());
+            metadata.insert("auth_header".to_string(), spec.auth_header);
+            metadata.insert("auth_scheme".to_string(), spec.auth_scheme);
+            metadata.insert("models_path".to_string(), spec.models_path);
+        }
 
         let backend = Backend::new(
             id.clone(),
@@ -456,6 +488,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -517,6 +554,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
         config.backends.push(BackendConfig {
             name: "vllm-test".to_string(),
@@ -526,6 +568,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -559,6 +606,11 @@ mod tests {
             api_key_env: Some("NEXUS_TEST_OPENAI_KEY".to_string()),
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -593,6 +645,11 @@ mod tests {
             api_key_env: Some("NEXUS_NONEXISTENT_KEY_FOR_TEST".to_string()),
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -697,6 +754,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         load_backends_from_config(&config, &registry).unwrap();
@@ -749,6 +811,11 @@ mod tests {
                 api_key_env: None,
                 zone: None,
                 tier: None,
+                api_version: None,
+                deployments: HashMap::new(),
+                region: None,
+                aws_credentials_env: None,
+                provider: None,
             });
         }
 
@@ -1097,6 +1164,11 @@ max_wait_seconds = 45
             api_key_env: None,
             zone: Some(crate::agent::types::PrivacyZone::Restricted),
             tier: Some(3),
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -1125,6 +1197,11 @@ max_wait_seconds = 45
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -1206,6 +1283,11 @@ max_wait_seconds = 45
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
@@ -1232,6 +1314,11 @@ max_wait_seconds = 45
             api_key_env: Some("NEXUS_TEST_OPENAI_KEY_ALL".to_string()),
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
         config.backends.push(BackendConfig {
             name: "anthropic-all".to_string(),
@@ -1241,6 +1328,11 @@ max_wait_seconds = 45
             api_key_env: Some("NEXUS_TEST_ANTHROPIC_KEY_ALL".to_string()),
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
         config.backends.push(BackendConfig {
             name: "google-all".to_string(),
@@ -1250,6 +1342,11 @@ max_wait_seconds = 45
             api_key_env: Some("NEXUS_TEST_GOOGLE_KEY_ALL".to_string()),
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
         // Local backends
         config.backends.push(BackendConfig {
@@ -1260,6 +1357,11 @@ max_wait_seconds = 45
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
         config.backends.push(BackendConfig {
             name: "lmstudio-all".to_string(),
@@ -1269,6 +1371,11 @@ max_wait_seconds = 45
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
         config.backends.push(BackendConfig {
             name: "generic-all".to_string(),
@@ -1278,6 +1385,11 @@ max_wait_seconds = 45
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let registry = Arc::new(Registry::new());
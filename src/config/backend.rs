@@ -1,7 +1,9 @@
 //! Backend configuration
 
+use super::provider::ProviderSpec;
 use crate::agent::types::PrivacyZone;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // Re-export BackendType from registry
 pub use crate::registry::BackendType;
@@ -10,6 +12,8 @@ pub use crate::registry::BackendType;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendConfig {
     pub name: String,
+    /// May be omitted when `provider` supplies the base URL
+    #[serde(default)]
     pub url: String,
     #[serde(rename = "type")]
     pub backend_type: BackendType,
@@ -28,6 +32,41 @@ pub struct BackendConfig {
     /// Defaults to 3 if not specified
     #[serde(default)]
     pub tier: Option<u8>,
+
+    /// Azure OpenAI REST API version, sent as the `api-version` query parameter.
+    /// Defaults to a recent GA version if not specified
+    #[serde(default)]
+    pub api_version: Option<String>,
+
+    /// Model name → Azure OpenAI deployment name, or Bedrock model ID /
+    /// inference profile ID, for each model the backend serves
+    #[serde(default)]
+    pub deployments: HashMap<String, String>,
+
+    /// AWS region for Bedrock request signing.
+    /// Defaults to the region in the endpoint URL, then `AWS_REGION`
+    #[serde(default)]
+    pub region: Option<String>,
+
+    /// Environment variables holding Bedrock credentials.
+    /// Defaults to the standard `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` /
+    /// `AWS_SESSION_TOKEN` variables if not specified
+    #[serde(default)]
+    pub aws_credentials_env: Option<AwsCredentialsEnv>,
+
+    /// Provider catalogue entry (e.g. "mistral", "groq", "cohere") supplying
+    /// defaults for `url`, `api_key_env`, `zone`, `tier` and pricing
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+/// Names of the environment variables holding AWS credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AwsCredentialsEnv {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    #[serde(default)]
+    pub session_token: Option<String>,
 }
 
 fn default_priority() -> i32 {
@@ -46,12 +85,32 @@ impl BackendConfig {
         self.tier.unwrap_or(1)
     }
 
+    /// Fill fields left unset from the provider spec this backend references
+    pub fn apply_provider(&mut self, spec: &ProviderSpec) {
+        if self.url.is_empty() {
+            self.url = spec.base_url.clone();
+        }
+        if self.api_key_env.is_none() {
+            self.api_key_env = spec.api_key_env.clone();
+        }
+        if self.zone.is_none() {
+            self.zone = Some(spec.zone);
+        }
+        if self.tier.is_none() {
+            self.tier = spec.tier;
+        }
+    }
+
     /// Validate configuration fields
     pub fn validate(&self) -> Result<(), String> {
         // Cloud backends require api_key_env
         if matches!(
             self.backend_type,
-            BackendType::OpenAI | BackendType::Anthropic | BackendType::Google
+            BackendType::OpenAI
+                | BackendType::Anthropic
+                | BackendType::Google
+                | BackendType::AzureOpenAI
+                | BackendType::Cohere
         ) && self.api_key_env.is_none()
         {
             return Err(format!(
@@ -60,6 +119,19 @@ impl BackendConfig {
             ));
         }
 
+        // Azure and Bedrock route by deployment / model ID, so the models
+        // they serve must be listed
+        if matches!(
+            self.backend_type,
+            BackendType::AzureOpenAI | BackendType::Bedrock
+        ) && self.deployments.is_empty()
+        {
+            return Err(format!(
+                "Backend '{}' of type {:?} requires at least one entry in 'deployments'",
+                self.name, self.backend_type
+            ));
+        }
+
         // Validate tier range if specified
         if let Some(tier) = self.tier {
             if !(1..=5).contains(&tier) {
@@ -87,6 +159,11 @@ mod tests {
             api_key_env: Some("TEST_API_KEY".to_string()),
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         }
     }
 
@@ -99,6 +176,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         }
     }
 
@@ -108,6 +190,7 @@ mod tests {
             BackendType::OpenAI,
             BackendType::Anthropic,
             BackendType::Google,
+            BackendType::Cohere,
         ] {
             let mut cfg = cloud_backend(bt);
             cfg.api_key_env = None;
@@ -132,6 +215,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_azure_requires_deployments() {
+        let mut cfg = cloud_backend(BackendType::AzureOpenAI);
+        let result = cfg.validate();
+        assert!(result.unwrap_err().contains("deployments"));
+
+        cfg.deployments
+            .insert("gpt-4o".to_string(), "prod-gpt4o".to_string());
+        assert!(cfg.validate().is_ok());
+
+        cfg.api_key_env = None;
+        assert!(cfg.validate().unwrap_err().contains("api_key_env"));
+    }
+
+    #[test]
+    fn test_azure_config_deserializes() {
+        let cfg: BackendConfig = toml::from_str(
+            r#"
+            name = "azure"
+            url = "https://my-resource.openai.azure.com"
+            type = "azure_openai"
+            api_key_env = "AZURE_OPENAI_API_KEY"
+            api_version = "2024-10-21"
+
+            [deployments]
+            "gpt-4o" = "prod-gpt4o"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.backend_type, BackendType::AzureOpenAI);
+        assert_eq!(cfg.api_version.as_deref(), Some("2024-10-21"));
+        assert_eq!(cfg.deployments["gpt-4o"], "prod-gpt4o");
+        assert_eq!(cfg.effective_privacy_zone(), PrivacyZone::Open);
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn test_bedrock_config_deserializes() {
+        let cfg: BackendConfig = toml::from_str(
+            r#"
+            name = "bedrock"
+            url = "https://bedrock-runtime.eu-central-1.amazonaws.com"
+            type = "bedrock"
+            region = "eu-central-1"
+
+            [aws_credentials_env]
+            access_key_id = "BEDROCK_ACCESS_KEY_ID"
+            secret_access_key = "BEDROCK_SECRET_ACCESS_KEY"
+
+            [deployments]
+            "claude-3-5-sonnet" = "eu.anthropic.claude-3-5-sonnet-20240620-v1:0"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.backend_type, BackendType::Bedrock);
+        assert_eq!(cfg.region.as_deref(), Some("eu-central-1"));
+        let creds = cfg.aws_credentials_env.as_ref().unwrap();
+        assert_eq!(creds.access_key_id, "BEDROCK_ACCESS_KEY_ID");
+        assert_eq!(creds.session_token, None);
+        assert_eq!(cfg.effective_privacy_zone(), PrivacyZone::Open);
+        // Credentials come from the environment, not api_key_env
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_bedrock_requires_models() {
+        let mut cfg = local_backend();
+        cfg.backend_type = BackendType::Bedrock;
+        assert!(cfg.validate().unwrap_err().contains("deployments"));
+    }
+
+    #[test]
+    fn test_apply_provider_fills_unset_fields() {
+        let mut cfg: BackendConfig = toml::from_str(
+            r#"
+            name = "groq"
+            type = "openai"
+            provider = "groq"
+            tier = 2
+            "#,
+        )
+        .unwrap();
+        let spec = crate::config::provider::builtin_providers()
+            .remove("groq")
+            .unwrap();
+
+        cfg.apply_provider(&spec);
+
+        assert_eq!(cfg.url, "https://api.groq.com/openai");
+        assert_eq!(cfg.api_key_env.as_deref(), Some("GROQ_API_KEY"));
+        assert_eq!(cfg.zone, Some(PrivacyZone::Open));
+        // Explicit settings win over the spec
+        assert_eq!(cfg.tier, Some(2));
+        assert!(cfg.validate().is_ok());
+    }
+
     #[test]
     fn test_validate_local_no_api_key_required() {
         let cfg = local_backend();
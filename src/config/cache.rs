@@ -0,0 +1,255 @@
+//! Response cache configuration
+
+use super::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Configuration for the exact-match response cache.
+///
+/// Identical deterministic chat requests (same resolved model, messages,
+/// sampling parameters and tools) are answered from the cache instead of a
+/// backend. Entries are namespaced per tenant (Authorization header) and
+/// privacy zone. Traffic policies can enable or disable caching per model
+/// with `cache = true|false`.
+///
+/// # Example
+///
+/// ```toml
+/// [cache]
+/// enabled = true
+/// max_entries = 1000
+/// ttl_seconds = 3600
+/// deterministic_only = true
+/// disk_path = "/var/cache/nexus"
+///
+/// [cache.semantic]
+/// enabled = true
+/// embedding_model = "nomic-embed-text"
+/// threshold = 0.95
+///
+/// [cache.embeddings]
+/// enabled = true
+/// max_entries = 10000
+///
+/// [cache.single_flight]
+/// enabled = true
+/// streaming = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Whether response caching is enabled for models without a policy override.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Maximum number of entries kept in memory (least recently used evicted).
+    ///
+    /// Default: 1000
+    pub max_entries: usize,
+
+    /// How long an entry stays valid.
+    ///
+    /// Default: 3600 seconds
+    pub ttl_seconds: u64,
+
+    /// Only cache requests with `temperature: 0`.
+    ///
+    /// Default: true
+    pub deterministic_only: bool,
+
+    /// Directory for on-disk entries; memory only when unset.
+    ///
+    /// Default: None
+    pub disk_path: Option<PathBuf>,
+
+    /// Semantic cache for near-identical prompts
+    pub semantic: SemanticCacheConfig,
+
+    /// Embedding vector cache for `/v1/embeddings`
+    pub embeddings: EmbeddingCacheConfig,
+
+    /// Deduplication of identical in-flight chat requests
+    pub single_flight: SingleFlightConfig,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 1000,
+            ttl_seconds: 3600,
+            deterministic_only: true,
+            disk_path: None,
+            semantic: SemanticCacheConfig::default(),
+            embeddings: EmbeddingCacheConfig::default(),
+            single_flight: SingleFlightConfig::default(),
+        }
+    }
+}
+
+/// Semantic response cache
+///
+/// On an exact-match miss, the latest user message is embedded with
+/// `embedding_model` and compared against cached prompts for the same model,
+/// tenant and privacy zone. The most similar entry is returned if its cosine
+/// similarity reaches `threshold`. Applies to the same requests as the exact
+/// cache (policy enablement and `deterministic_only`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticCacheConfig {
+    /// Enable the semantic cache
+    pub enabled: bool,
+
+    /// Embedding model used for prompts (served by any backend)
+    pub embedding_model: String,
+
+    /// Minimum cosine similarity for a cached answer to be returned
+    pub threshold: f32,
+
+    /// Maximum number of prompt embeddings kept (oldest evicted)
+    pub max_entries: usize,
+}
+
+impl Default for SemanticCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            embedding_model: String::new(),
+            threshold: 0.95,
+            max_entries: 1000,
+        }
+    }
+}
+
+impl SemanticCacheConfig {
+    /// Validate the semantic cache section (only checked when enabled)
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let invalid = |field: &str, message: &str| ConfigError::Validation {
+            field: format!("cache.semantic.{}", field),
+            message: message.to_string(),
+        };
+        if self.embedding_model.is_empty() {
+            return Err(invalid(
+                "embedding_model",
+                "embedding_model cannot be empty",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(invalid(
+                "threshold",
+                "threshold must be between 0.0 and 1.0",
+            ));
+        }
+        if self.max_entries == 0 {
+            return Err(invalid("max_entries", "max_entries must be at least 1"));
+        }
+        Ok(())
+    }
+}
+
+/// Embedding vector cache
+///
+/// Caches `/v1/embeddings` vectors per resolved model and input text so only
+/// uncached inputs are forwarded to a backend. Concurrent requests for the
+/// same input share a single backend call. Independent of `[cache] enabled`;
+/// entries expire after the shared `ttl_seconds`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingCacheConfig {
+    /// Enable the embedding cache
+    pub enabled: bool,
+
+    /// Maximum number of vectors kept in memory (least recently used evicted)
+    pub max_entries: usize,
+}
+
+impl Default for EmbeddingCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entries: 10_000,
+        }
+    }
+}
+
+/// Single-flight request deduplication
+///
+/// Identical chat requests (same key as the response cache) that arrive while
+/// a first copy is in flight attach to its result instead of reaching a
+/// backend. Independent of `[cache] enabled`, but honours `deterministic_only`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SingleFlightConfig {
+    /// Deduplicate non-streaming requests
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Also fan out identical streaming requests from one backend stream
+    ///
+    /// Default: false
+    pub streaming: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_disabled_memory_only() {
+        let config = CacheConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.max_entries, 1000);
+        assert_eq!(config.ttl_seconds, 3600);
+        assert!(config.deterministic_only);
+        assert!(config.disk_path.is_none());
+        assert!(!config.semantic.enabled);
+        assert!(!config.embeddings.enabled);
+        assert_eq!(config.embeddings.max_entries, 10_000);
+        assert!(!config.single_flight.enabled);
+        assert!(!config.single_flight.streaming);
+    }
+
+    #[test]
+    fn parses_disk_path() {
+        let config: CacheConfig = toml::from_str(
+            r#"
+            enabled = true
+            ttl_seconds = 60
+            disk_path = "/tmp/nexus-cache"
+        "#,
+        )
+        .unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.ttl_seconds, 60);
+        assert_eq!(config.disk_path, Some(PathBuf::from("/tmp/nexus-cache")));
+    }
+
+    #[test]
+    fn semantic_section_validation() {
+        let config: CacheConfig = toml::from_str(
+            r#"
+            [semantic]
+            enabled = true
+            embedding_model = "nomic-embed-text"
+            threshold = 0.9
+        "#,
+        )
+        .unwrap();
+        assert!(config.semantic.validate().is_ok());
+        assert_eq!(config.semantic.threshold, 0.9);
+
+        let mut missing_model = config.semantic.clone();
+        missing_model.embedding_model.clear();
+        assert!(missing_model.validate().is_err());
+
+        let mut bad_threshold = config.semantic;
+        bad_threshold.threshold = 1.5;
+        let err = bad_threshold.validate().unwrap_err().to_string();
+        assert!(err.contains("cache.semantic.threshold"));
+    }
+}
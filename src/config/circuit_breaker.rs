@@ -0,0 +1,61 @@
+//! Circuit breaker configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for per-agent circuit breakers.
+///
+/// A circuit opens after `failure_threshold` consecutive failures (including
+/// timeouts), rejects traffic for `open_duration_seconds`, then moves to
+/// half-open and admits up to `half_open_max_requests` trial requests.
+///
+/// # Example
+///
+/// ```toml
+/// [circuit_breaker]
+/// enabled = true
+/// failure_threshold = 5
+/// open_duration_seconds = 30
+/// half_open_max_requests = 1
+/// success_threshold = 1
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    /// Whether circuit breaking is enabled.
+    ///
+    /// Default: true
+    /// When false, all agents are treated as closed circuits.
+    pub enabled: bool,
+
+    /// Consecutive failures required to open the circuit.
+    ///
+    /// Default: 5
+    pub failure_threshold: u32,
+
+    /// How long an open circuit rejects traffic before half-open probing.
+    ///
+    /// Default: 30 seconds
+    pub open_duration_seconds: u64,
+
+    /// Maximum concurrent trial requests while half-open.
+    ///
+    /// Default: 1
+    pub half_open_max_requests: u32,
+
+    /// Successful trial requests required to close a half-open circuit.
+    ///
+    /// Default: 1
+    pub success_threshold: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 5,
+            open_duration_seconds: 30,
+            half_open_max_requests: 1,
+            success_threshold: 1,
+        }
+    }
+}
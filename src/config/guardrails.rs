@@ -0,0 +1,243 @@
+//! Output guardrail configuration
+
+use super::ConfigError;
+use serde::{Deserialize, Serialize};
+
+/// What happens when a guardrail matches a response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardrailAction {
+    /// Reject the response (streams end with an error chunk)
+    #[default]
+    Block,
+    /// Replace the matched text and deliver the rest
+    Redact,
+    /// Deliver unchanged, but log and count the match
+    Flag,
+}
+
+impl GuardrailAction {
+    /// Action name used in logs, metrics and response headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GuardrailAction::Block => "block",
+            GuardrailAction::Redact => "redact",
+            GuardrailAction::Flag => "flag",
+        }
+    }
+}
+
+/// Operator-defined response rule.
+///
+/// Matches when the response text matches `pattern` or contains any of the
+/// `keywords` (case-insensitive).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuardrailRule {
+    /// Rule name reported in logs, metrics and the `X-Nexus-Guardrails` header
+    pub name: String,
+    /// Regular expression matched against response content
+    pub pattern: Option<String>,
+    /// Banned terms, matched case-insensitively
+    pub keywords: Vec<String>,
+    /// Action taken on a match
+    pub action: GuardrailAction,
+}
+
+/// Moderation model check for complete responses.
+///
+/// The final user message and the response are sent as a conversation to
+/// `model`, routed through Nexus like any other request. A reply whose first
+/// line is `unsafe` (the Llama Guard convention) triggers `action`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    /// Whether the moderation model is consulted.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Model used for moderation.
+    ///
+    /// Default: "llama-guard3"
+    pub model: String,
+
+    /// Action taken on an `unsafe` verdict (`redact` is treated as `block`).
+    ///
+    /// Default: block
+    pub action: GuardrailAction,
+
+    /// Deliver the response when the moderation call fails.
+    ///
+    /// Default: true
+    pub fail_open: bool,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: "llama-guard3".to_string(),
+            action: GuardrailAction::Block,
+            fail_open: true,
+        }
+    }
+}
+
+/// Configuration for response-side guardrails.
+///
+/// Responses are checked after the backend returns and before they reach the
+/// client. Streams are filtered chunk by chunk, holding back the last
+/// `lookbehind_bytes` of text so matches split across deltas are caught; a
+/// blocked stream ends with an error chunk and `[DONE]`. The moderation model
+/// only runs on non-streaming responses.
+///
+/// # Example
+///
+/// ```toml
+/// [guardrails]
+/// enabled = true
+/// lookbehind_bytes = 256
+/// redaction = "[REDACTED]"
+///
+/// [[guardrails.rules]]
+/// name = "api_key"
+/// pattern = "sk-[A-Za-z0-9]{20,}"
+/// action = "redact"
+///
+/// [[guardrails.rules]]
+/// name = "project_codenames"
+/// keywords = ["bluebird", "nightjar"]
+/// action = "block"
+///
+/// [guardrails.moderation]
+/// enabled = true
+/// model = "llama-guard3"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuardrailsConfig {
+    /// Whether output guardrails are enabled.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Response rules, all evaluated on every response.
+    pub rules: Vec<GuardrailRule>,
+
+    /// Streamed text held back per choice to catch matches split across
+    /// deltas; matches longer than this may be partially delivered.
+    ///
+    /// Default: 256
+    pub lookbehind_bytes: usize,
+
+    /// Replacement text for redacted matches.
+    ///
+    /// Default: "[REDACTED]"
+    pub redaction: String,
+
+    /// Moderation model check
+    pub moderation: ModerationConfig,
+}
+
+impl Default for GuardrailsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: Vec::new(),
+            lookbehind_bytes: 256,
+            redaction: "[REDACTED]".to_string(),
+            moderation: ModerationConfig::default(),
+        }
+    }
+}
+
+impl GuardrailsConfig {
+    /// Validate rule names, patterns and the moderation model
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (i, rule) in self.rules.iter().enumerate() {
+            let invalid = |name: &str, message: String| ConfigError::Validation {
+                field: format!("guardrails.rules[{}].{}", i, name),
+                message,
+            };
+            if rule.name.is_empty() {
+                return Err(invalid("name", "name cannot be empty".to_string()));
+            }
+            if rule.pattern.is_none() && rule.keywords.iter().all(|k| k.is_empty()) {
+                return Err(invalid(
+                    "pattern",
+                    format!("rule '{}' needs a pattern or keywords", rule.name),
+                ));
+            }
+            if let Some(pattern) = &rule.pattern {
+                regex::Regex::new(pattern)
+                    .map_err(|e| invalid("pattern", format!("Invalid regex: {}", e)))?;
+            }
+        }
+        if self.moderation.enabled && self.moderation.model.is_empty() {
+            return Err(ConfigError::Validation {
+                field: "guardrails.moderation.model".to_string(),
+                message: "model cannot be empty when moderation is enabled".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_disabled() {
+        let config = GuardrailsConfig::default();
+        assert!(!config.enabled);
+        assert!(!config.moderation.enabled);
+        assert_eq!(config.lookbehind_bytes, 256);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn parses_rules_and_moderation() {
+        let config: GuardrailsConfig = toml::from_str(
+            r#"
+            enabled = true
+
+            [[rules]]
+            name = "api_key"
+            pattern = "sk-[A-Za-z0-9]{20,}"
+            action = "redact"
+
+            [[rules]]
+            name = "codenames"
+            keywords = ["bluebird"]
+
+            [moderation]
+            enabled = true
+            model = "guard"
+            action = "flag"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.rules[0].action, GuardrailAction::Redact);
+        assert_eq!(config.rules[1].action, GuardrailAction::Block);
+        assert_eq!(config.moderation.action, GuardrailAction::Flag);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_rules_without_matchers_or_bad_regex() {
+        let mut config = GuardrailsConfig {
+            rules: vec![GuardrailRule {
+                name: "empty".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.rules[0].pattern = Some("(unclosed".to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("guardrails.rules[0].pattern"));
+    }
+}
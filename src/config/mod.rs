@@ -28,34 +28,54 @@
 //! ```
 
 pub mod backend;
+pub mod cache;
+pub mod circuit_breaker;
 pub mod discovery;
 pub mod error;
 pub mod fleet;
+pub mod guardrails;
 pub mod lifecycle;
 pub mod logging;
+pub mod pii;
+pub mod provider;
 pub mod quality;
 pub mod queue;
 pub mod routing;
+pub mod screening;
 pub mod server;
+pub mod structured;
+pub mod tools;
+pub mod transform;
 
-pub use backend::{BackendConfig, BackendType};
+pub use backend::{AwsCredentialsEnv, BackendConfig, BackendType};
+pub use cache::{CacheConfig, EmbeddingCacheConfig, SemanticCacheConfig, SingleFlightConfig};
+pub use circuit_breaker::CircuitBreakerConfig;
 pub use discovery::DiscoveryConfig;
 pub use error::ConfigError;
 pub use fleet::FleetConfig;
+pub use guardrails::{GuardrailAction, GuardrailRule, GuardrailsConfig, ModerationConfig};
 pub use lifecycle::LifecycleConfig;
 pub use logging::{LogFormat, LoggingConfig};
+pub use pii::{PiiConfig, PiiDetectorKind, PiiPattern};
+pub use provider::ProviderSpec;
 pub use quality::QualityConfig;
 pub use queue::QueueConfig;
 pub use routing::{
-    BudgetConfig, HardLimitAction, PolicyMatcher, PrivacyConstraint, RoutingConfig,
-    RoutingStrategy, RoutingWeights, TrafficPolicy,
+    BudgetConfig, ContentRule, ContextOverflowMode, HardLimitAction, PolicyMatcher,
+    PrivacyConstraint, RoutingConfig, RoutingStrategy, RoutingWeights, SemanticRoute,
+    SemanticRoutingConfig, TokenizerFile, TrafficPolicy,
 };
+pub use screening::{InjectionAction, ScreeningConfig};
 pub use server::ServerConfig;
+pub use structured::StructuredOutputConfig;
+pub use tools::{ToolCallFormat, ToolEmulationConfig};
+pub use transform::{TenantTransforms, TransformConfig, TransformRule, ValueRange};
 
 // Re-export HealthCheckConfig from health module
 pub use crate::health::HealthCheckConfig;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Unified configuration for the Nexus server.
@@ -89,12 +109,30 @@ pub struct NexusConfig {
     pub logging: LoggingConfig,
     /// Quality tracking configuration
     pub quality: QualityConfig,
+    /// Per-agent circuit breaker configuration
+    pub circuit_breaker: CircuitBreakerConfig,
     /// Request queue configuration
     pub queue: QueueConfig,
     /// Model lifecycle management configuration
     pub lifecycle: LifecycleConfig,
     /// Fleet intelligence configuration
     pub fleet: FleetConfig,
+    /// PII detection configuration
+    pub pii: PiiConfig,
+    /// Response cache configuration
+    pub cache: CacheConfig,
+    /// Request transformation policies
+    pub transforms: TransformConfig,
+    /// Output guardrail configuration
+    pub guardrails: GuardrailsConfig,
+    /// Prompt injection screening configuration
+    pub screening: ScreeningConfig,
+    /// JSON schema structured output configuration
+    pub structured_output: StructuredOutputConfig,
+    /// Tool-calling emulation configuration
+    pub tool_emulation: ToolEmulationConfig,
+    /// Cloud provider catalogue entries, added to (or replacing) the built-ins
+    pub providers: HashMap<String, ProviderSpec>,
 }
 
 impl NexusConfig {
@@ -109,12 +147,45 @@ impl NexusConfig {
                     return Err(ConfigError::NotFound(p.to_path_buf()));
                 }
                 let content = std::fs::read_to_string(p)?;
-                toml::from_str(&content).map_err(|e| ConfigError::Parse(e.to_string()))
+                let mut config: Self =
+                    toml::from_str(&content).map_err(|e| ConfigError::Parse(e.to_string()))?;
+                config.resolve_providers()?;
+                Ok(config)
             }
             None => Ok(Self::default()),
         }
     }
 
+    /// Look up a provider spec, preferring `[providers.<name>]` over the built-ins
+    pub fn provider(&self, name: &str) -> Option<ProviderSpec> {
+        self.providers
+            .get(name)
+            .cloned()
+            .or_else(|| provider::builtin_providers().remove(name))
+    }
+
+    /// All provider specs: the built-ins overlaid with `[providers.*]`
+    pub fn provider_catalogue(&self) -> HashMap<String, ProviderSpec> {
+        let mut catalogue = provider::builtin_providers();
+        catalogue.extend(self.providers.clone());
+        catalogue
+    }
+
+    /// Fill backend defaults from the provider each backend references
+    pub fn resolve_providers(&mut self) -> Result<(), ConfigError> {
+        let catalogue = self.provider_catalogue();
+        for (i, backend) in self.backends.iter_mut().enumerate() {
+            let Some(name) = &backend.provider else {
+                continue;
+            };
+            let spec = catalogue
+                .get(name)
+                .ok_or_else(|| unknown_provider(i, name))?;
+            backend.apply_provider(spec);
+        }
+        Ok(())
+    }
+
     /// Apply environment variable overrides
     ///
     /// Supports NEXUS_* environment variables for common settings.
@@ -161,8 +232,17 @@ impl NexusConfig {
             });
         }
 
+        for (name, spec) in &self.providers {
+            spec.validate(name)?;
+        }
+
         // Validate backends
         for (i, backend) in self.backends.iter().enumerate() {
+            if let Some(name) = &backend.provider {
+                if self.provider(name).is_none() {
+                    return Err(unknown_provider(i, name));
+                }
+            }
             if backend.url.is_empty() {
                 return Err(ConfigError::Validation {
                     field: format!("backends[{}].url", i),
@@ -177,13 +257,65 @@ impl NexusConfig {
             }
         }
 
+        // Validate circuit breaker thresholds
+        if self.circuit_breaker.enabled {
+            if self.circuit_breaker.failure_threshold == 0 {
+                return Err(ConfigError::Validation {
+                    field: "circuit_breaker.failure_threshold".to_string(),
+                    message: "failure_threshold must be at least 1".to_string(),
+                });
+            }
+            if self.circuit_breaker.half_open_max_requests == 0 {
+                return Err(ConfigError::Validation {
+                    field: "circuit_breaker.half_open_max_requests".to_string(),
+                    message: "half_open_max_requests must be at least 1".to_string(),
+                });
+            }
+        }
+
         // Validate routing aliases for circular references
         routing::validate_aliases(&self.routing.aliases)?;
 
+        // Validate content routing rules
+        routing::validate_rules(&self.routing.rules)?;
+        self.routing.semantic.validate()?;
+        routing::validate_tokenizers(&self.routing.tokenizers)?;
+
+        if self.cache.max_entries == 0 {
+            return Err(ConfigError::Validation {
+                field: "cache.max_entries".to_string(),
+                message: "max_entries must be at least 1".to_string(),
+            });
+        }
+        self.cache.semantic.validate()?;
+        if self.cache.embeddings.enabled && self.cache.embeddings.max_entries == 0 {
+            return Err(ConfigError::Validation {
+                field: "cache.embeddings.max_entries".to_string(),
+                message: "max_entries must be at least 1".to_string(),
+            });
+        }
+
+        self.transforms.validate()?;
+        self.guardrails.validate()?;
+        self.screening.validate()?;
+        self.structured_output.validate()?;
+
+        // Validate PII patterns
+        if self.pii.enabled {
+            crate::pii::PiiScanner::new(&self.pii)?;
+        }
+
         Ok(())
     }
 }
 
+fn unknown_provider(index: usize, name: &str) -> ConfigError {
+    ConfigError::Validation {
+        field: format!("backends[{}].provider", index),
+        message: format!("unknown provider '{}'", name),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +376,60 @@ mod tests {
         assert_eq!(config.server.port, 8080);
     }
 
+    #[test]
+    fn test_config_load_resolves_providers() {
+        let temp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            temp.path(),
+            r#"
+            [[backends]]
+            name = "mistral"
+            type = "openai"
+            provider = "mistral"
+
+            [[backends]]
+            name = "acme"
+            type = "openai"
+            provider = "acme"
+
+            [providers.acme]
+            base_url = "https://api.acme.ai"
+            api_key_env = "ACME_API_KEY"
+            zone = "Restricted"
+            "#,
+        )
+        .unwrap();
+
+        let config = NexusConfig::load(Some(temp.path())).unwrap();
+        assert_eq!(config.backends[0].url, "https://api.mistral.ai");
+        assert_eq!(
+            config.backends[0].api_key_env.as_deref(),
+            Some("MISTRAL_API_KEY")
+        );
+        assert_eq!(config.backends[1].url, "https://api.acme.ai");
+        assert_eq!(
+            config.backends[1].effective_privacy_zone(),
+            crate::agent::PrivacyZone::Restricted
+        );
+        assert!(config.provider_catalogue().contains_key("cohere"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_unknown_provider_rejected() {
+        let temp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            temp.path(),
+            "[[backends]]\nname = \"x\"\ntype = \"openai\"\nprovider = \"nope\"",
+        )
+        .unwrap();
+
+        let err = NexusConfig::load(Some(temp.path()))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown provider 'nope'"), "{err}");
+    }
+
     #[test]
     fn test_config_missing_file_error() {
         let result = NexusConfig::load(Some(Path::new("/nonexistent/config.toml")));
@@ -385,6 +571,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: std::collections::HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let result = config.validate();
@@ -405,6 +596,11 @@ mod tests {
             api_key_env: None,
             zone: None,
             tier: None,
+            api_version: None,
+            deployments: std::collections::HashMap::new(),
+            region: None,
+            aws_credentials_env: None,
+            provider: None,
         });
 
         let result = config.validate();
@@ -414,6 +610,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_config_validation_zero_circuit_failure_threshold() {
+        let mut config = NexusConfig::default();
+        config.circuit_breaker.failure_threshold = 0;
+
+        let result = config.validate();
+        assert!(matches!(
+            result,
+            Err(ConfigError::Validation { ref field, .. })
+                if field == "circuit_breaker.failure_threshold"
+        ));
+    }
+
     #[test]
     fn test_config_load_none_returns_defaults() {
         let config = NexusConfig::load(None).unwrap();
@@ -0,0 +1,139 @@
+//! PII detection configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Built-in PII detectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiDetectorKind {
+    /// Email addresses
+    Email,
+    /// International (+CC) and North American phone numbers
+    Phone,
+    /// Payment card numbers (Luhn-validated)
+    CreditCard,
+    /// International bank account numbers (mod-97 validated)
+    Iban,
+    /// API keys, access tokens and private keys
+    Secret,
+}
+
+impl PiiDetectorKind {
+    /// All built-in detectors.
+    pub const ALL: [PiiDetectorKind; 5] = [
+        PiiDetectorKind::Email,
+        PiiDetectorKind::Phone,
+        PiiDetectorKind::CreditCard,
+        PiiDetectorKind::Iban,
+        PiiDetectorKind::Secret,
+    ];
+
+    /// Detector name used in logs and rejection reasons.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiDetectorKind::Email => "email",
+            PiiDetectorKind::Phone => "phone",
+            PiiDetectorKind::CreditCard => "credit_card",
+            PiiDetectorKind::Iban => "iban",
+            PiiDetectorKind::Secret => "secret",
+        }
+    }
+}
+
+/// Operator-defined PII pattern.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PiiPattern {
+    /// Detector name reported in rejection reasons
+    pub name: String,
+    /// Regular expression matched against message content
+    pub pattern: String,
+}
+
+/// Configuration for PII detection.
+///
+/// When enabled, message content is scanned before routing and requests
+/// containing PII are restricted to local (restricted-zone) backends. With
+/// `redact_cloud`, the OpenAI, Anthropic and Google AI agents stay eligible
+/// and receive the request with entities replaced by placeholders, which are
+/// restored in the response.
+///
+/// # Example
+///
+/// ```toml
+/// [pii]
+/// enabled = true
+/// detectors = ["email", "phone", "credit_card", "iban", "secret"]
+/// redact_cloud = false
+///
+/// [[pii.patterns]]
+/// name = "employee_id"
+/// pattern = "EMP-\\d{6}"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PiiConfig {
+    /// Whether PII detection is enabled.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Built-in detectors to run.
+    ///
+    /// Default: all detectors
+    pub detectors: Vec<PiiDetectorKind>,
+
+    /// Additional operator-defined patterns.
+    pub patterns: Vec<PiiPattern>,
+
+    /// Redact PII for cloud agents instead of restricting to local backends.
+    ///
+    /// Default: false
+    pub redact_cloud: bool,
+}
+
+impl Default for PiiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            detectors: PiiDetectorKind::ALL.to_vec(),
+            patterns: Vec::new(),
+            redact_cloud: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_disabled_with_all_detectors() {
+        let config = PiiConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.detectors.len(), 5);
+        assert!(config.patterns.is_empty());
+        assert!(!config.redact_cloud);
+    }
+
+    #[test]
+    fn parses_detectors_and_patterns() {
+        let toml_str = r#"
+            enabled = true
+            detectors = ["email", "credit_card"]
+            redact_cloud = true
+
+            [[patterns]]
+            name = "employee_id"
+            pattern = "EMP-\\d{6}"
+        "#;
+        let config: PiiConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.enabled);
+        assert!(config.redact_cloud);
+        assert_eq!(
+            config.detectors,
+            vec![PiiDetectorKind::Email, PiiDetectorKind::CreditCard]
+        );
+        assert_eq!(config.patterns[0].name, "employee_id");
+        assert_eq!(config.patterns[0].pattern, r"EMP-\d{6}");
+    }
+}
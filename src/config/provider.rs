@@ -0,0 +1,247 @@
+//! Cloud provider catalogue
+//!
+//! A provider spec describes a hosted API once — endpoint, credentials, auth
+//! header style, model list endpoint, pricing and default zone/tier — so
+//! backends can reference it by name. Built-in specs cover Mistral, Groq,
+//! DeepSeek and Cohere; `[providers.<name>]` tables add
+//! OpenAI-compatible clouds or replace a built-in spec entirely.
+
+use super::ConfigError;
+use crate::agent::pricing::ModelPricing;
+use crate::agent::types::PrivacyZone;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Declarative description of a cloud provider.
+///
+/// # Example
+///
+/// ```toml
+/// [providers.acme]
+/// base_url = "https://api.acme.ai"
+/// api_key_env = "ACME_API_KEY"
+/// auth_header = "x-api-key"
+/// auth_scheme = ""
+/// models_path = "/v1/models"
+/// tier = 3
+///
+/// [providers.acme.pricing]
+/// "acme-large" = { input_price_per_1k = 0.002, output_price_per_1k = 0.006 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderSpec {
+    /// API root, without the `/v1/...` path
+    pub base_url: String,
+
+    /// Environment variable holding the API key
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+
+    /// Header carrying the API key.
+    ///
+    /// Default: "authorization"
+    #[serde(default = "default_auth_header")]
+    pub auth_header: String,
+
+    /// Scheme prefixed to the API key; empty sends the bare key.
+    ///
+    /// Default: "Bearer"
+    #[serde(default = "default_auth_scheme")]
+    pub auth_scheme: String,
+
+    /// Path of the model list endpoint, used for health checks and discovery.
+    ///
+    /// Default: "/v1/models"
+    #[serde(default = "default_models_path")]
+    pub models_path: String,
+
+    /// Default privacy zone for backends using this provider.
+    ///
+    /// Default: open
+    #[serde(default = "default_zone")]
+    pub zone: PrivacyZone,
+
+    /// Default capability tier (1-5) for backends using this provider
+    #[serde(default)]
+    pub tier: Option<u8>,
+
+    /// Model name → price in USD per 1K input and output tokens
+    #[serde(default)]
+    pub pricing: HashMap<String, ModelPricing>,
+}
+
+fn default_auth_header() -> String {
+    "authorization".to_string()
+}
+
+fn default_auth_scheme() -> String {
+    "Bearer".to_string()
+}
+
+fn default_models_path() -> String {
+    "/v1/models".to_string()
+}
+
+fn default_zone() -> PrivacyZone {
+    PrivacyZone::Open
+}
+
+impl ProviderSpec {
+    /// Spec for a bearer-token API with the default model list endpoint.
+    fn bearer(base_url: &str, api_key_env: &str, tier: u8, pricing: &[(&str, f64, f64)]) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            api_key_env: Some(api_key_env.to_string()),
+            auth_header: default_auth_header(),
+            auth_scheme: default_auth_scheme(),
+            models_path: default_models_path(),
+            zone: default_zone(),
+            tier: Some(tier),
+            pricing: pricing
+                .iter()
+                .map(|&(model, input, output)| {
+                    (
+                        model.to_string(),
+                        ModelPricing {
+                            input_price_per_1k: input,
+                            output_price_per_1k: output,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Validate the spec registered under `name`
+    pub fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        if self.base_url.is_empty() {
+            return Err(ConfigError::Validation {
+                field: format!("providers.{}.base_url", name),
+                message: "base_url cannot be empty".to_string(),
+            });
+        }
+        if let Some(tier) = self.tier {
+            if !(1..=5).contains(&tier) {
+                return Err(ConfigError::Validation {
+                    field: format!("providers.{}.tier", name),
+                    message: format!("tier must be 1-5, got {}", tier),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Providers known without configuration. Prices as of early 2025.
+pub fn builtin_providers() -> HashMap<String, ProviderSpec> {
+    HashMap::from([
+        (
+            "mistral".to_string(),
+            ProviderSpec::bearer(
+                "https://api.mistral.ai",
+                "MISTRAL_API_KEY",
+                4,
+                &[
+                    ("mistral-large-latest", 0.002, 0.006),
+                    ("mistral-small-latest", 0.0002, 0.0006),
+                    ("codestral-latest", 0.0003, 0.0009),
+                    ("open-mistral-nemo", 0.00015, 0.00015),
+                    ("mistral-embed", 0.0001, 0.0),
+                ],
+            ),
+        ),
+        (
+            "groq".to_string(),
+            ProviderSpec::bearer(
+                "https://api.groq.com/openai",
+                "GROQ_API_KEY",
+                3,
+                &[
+                    ("llama-3.3-70b-versatile", 0.00059, 0.00079),
+                    ("llama-3.1-8b-instant", 0.00005, 0.00008),
+                    ("gemma2-9b-it", 0.0002, 0.0002),
+                ],
+            ),
+        ),
+        (
+            "deepseek".to_string(),
+            ProviderSpec::bearer(
+                "https://api.deepseek.com",
+                "DEEPSEEK_API_KEY",
+                4,
+                &[
+                    ("deepseek-chat", 0.00027, 0.0011),
+                    ("deepseek-reasoner", 0.00055, 0.00219),
+                ],
+            ),
+        ),
+        (
+            "cohere".to_string(),
+            ProviderSpec::bearer(
+                "https://api.cohere.com",
+                "CO_API_KEY",
+                4,
+                &[
+                    ("command-a-03-2025", 0.0025, 0.01),
+                    ("command-r-plus", 0.0025, 0.01),
+                    ("command-r", 0.00015, 0.0006),
+                    ("command-r7b-12-2024", 0.0000375, 0.00015),
+                    ("embed-english-v3.0", 0.0001, 0.0),
+                    ("embed-multilingual-v3.0", 0.0001, 0.0),
+                ],
+            ),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_providers_are_valid() {
+        for (name, spec) in builtin_providers() {
+            assert!(spec.validate(&name).is_ok(), "{name} should be valid");
+            assert!(spec.api_key_env.is_some(), "{name} should name a key");
+            assert_eq!(spec.zone, PrivacyZone::Open);
+        }
+    }
+
+    #[test]
+    fn test_provider_spec_defaults() {
+        let spec: ProviderSpec = toml::from_str(
+            r#"
+            base_url = "https://api.acme.ai"
+
+            [pricing]
+            "acme-large" = { input_price_per_1k = 0.002, output_price_per_1k = 0.006 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(spec.auth_header, "authorization");
+        assert_eq!(spec.auth_scheme, "Bearer");
+        assert_eq!(spec.models_path, "/v1/models");
+        assert_eq!(spec.zone, PrivacyZone::Open);
+        assert_eq!(spec.pricing["acme-large"].output_price_per_1k, 0.006);
+    }
+
+    #[test]
+    fn test_provider_spec_validation() {
+        let mut spec = builtin_providers().remove("groq").unwrap();
+        spec.tier = Some(6);
+        assert!(spec
+            .validate("groq")
+            .unwrap_err()
+            .to_string()
+            .contains("tier"));
+
+        spec.tier = None;
+        spec.base_url.clear();
+        assert!(spec
+            .validate("groq")
+            .unwrap_err()
+            .to_string()
+            .contains("base_url"));
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::agent::PrivacyZone;
 use crate::config::error::ConfigError;
+use crate::config::screening::InjectionAction;
 
 /// Routing strategy for backend selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -58,6 +59,34 @@ impl PrivacyConstraint {
     }
 }
 
+/// Handling for requests whose prompt plus `max_tokens` exceeds the
+/// context window of every backend serving the requested model.
+///
+/// Selected per request via the `X-Nexus-Context-Overflow` header, or per
+/// model via `context_overflow` on a traffic policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextOverflowMode {
+    /// Route to a model with a larger context window
+    Upgrade,
+    /// Drop the oldest non-system messages until the request fits
+    Truncate,
+    /// Fail with a 400 describing the overflow
+    Reject,
+}
+
+impl ContextOverflowMode {
+    /// Parse a header value (case-insensitive). Unknown values return None.
+    pub fn from_header(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "upgrade" => Some(Self::Upgrade),
+            "truncate" => Some(Self::Truncate),
+            "reject" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+}
+
 /// Traffic policy for model-pattern-based routing constraints (FR-035)
 ///
 /// Each policy matches a glob pattern against model names and applies
@@ -83,6 +112,21 @@ pub struct TrafficPolicy {
     /// Whether fallback to other models is allowed
     #[serde(default = "default_fallback_allowed")]
     pub fallback_allowed: bool,
+
+    /// How to handle requests that exceed the model's context window.
+    /// None keeps the default behavior (no backend matches, request fails).
+    #[serde(default)]
+    pub context_overflow: Option<ContextOverflowMode>,
+
+    /// Enable or disable response caching for matched models.
+    /// None follows the global `[cache] enabled` setting.
+    #[serde(default)]
+    pub cache: Option<bool>,
+
+    /// Action for requests flagged by injection screening.
+    /// None follows the global `[screening] action` setting.
+    #[serde(default)]
+    pub injection_action: Option<InjectionAction>,
 }
 
 fn default_fallback_allowed() -> bool {
@@ -131,6 +175,262 @@ impl PolicyMatcher {
     }
 }
 
+/// Content-based routing rule
+///
+/// Matches on features of the request itself rather than just the model
+/// name, and rewrites the target model or constrains privacy/tier for the
+/// matched request. Rules are evaluated in TOML declaration order (first
+/// match wins); every condition that is set must match.
+///
+/// ```toml
+/// [[routing.rules]]
+/// name = "long-code"
+/// model_pattern = "auto"
+/// has_code = true
+/// min_prompt_tokens = 2000
+/// target_model = "llama3:70b"
+///
+/// [[routing.rules]]
+/// name = "chit-chat"
+/// model_pattern = "auto"
+/// max_prompt_tokens = 200
+/// target_model = "llama3:8b"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentRule {
+    /// Rule name, reported in the `X-Nexus-Route-Rule` response header
+    pub name: String,
+
+    /// Glob pattern for the requested model. None matches any model.
+    pub model_pattern: Option<String>,
+
+    // === Conditions ===
+    /// Match the `X-Nexus-Route-Tag` request header (exact, case-insensitive)
+    pub route_tag: Option<String>,
+
+    /// Match requests with (true) or without (false) fenced code blocks
+    pub has_code: Option<bool>,
+
+    /// ISO 639-3 codes (e.g. "eng", "deu") of the latest user message
+    pub languages: Vec<String>,
+
+    /// Lower bound (inclusive) on estimated prompt tokens
+    pub min_prompt_tokens: Option<u32>,
+
+    /// Upper bound (inclusive) on estimated prompt tokens
+    pub max_prompt_tokens: Option<u32>,
+
+    /// Case-insensitive keywords; any one appearing in the prompt matches
+    pub keywords: Vec<String>,
+
+    /// Regular expression matched against the prompt text
+    pub pattern: Option<String>,
+
+    /// Match requests with (true) or without (false) image content
+    pub has_images: Option<bool>,
+
+    /// Match requests with (true) or without (false) tool definitions
+    pub has_tools: Option<bool>,
+
+    // === Actions ===
+    /// Rewrite the requested model
+    pub target_model: Option<String>,
+
+    /// Constrain the request to a privacy zone
+    pub privacy: Option<PrivacyConstraint>,
+
+    /// Require a minimum capability tier
+    pub min_tier: Option<u8>,
+}
+
+impl ContentRule {
+    /// Returns true if the rule changes routing when it matches
+    pub fn has_action(&self) -> bool {
+        self.target_model.is_some()
+            || self.privacy == Some(PrivacyConstraint::Restricted)
+            || self.min_tier.is_some()
+    }
+}
+
+/// Validate content routing rules: names, actions, globs and regexes
+pub fn validate_rules(rules: &[ContentRule]) -> Result<(), ConfigError> {
+    for (i, rule) in rules.iter().enumerate() {
+        let field = |name: &str| format!("routing.rules[{}].{}", i, name);
+        if rule.name.is_empty() {
+            return Err(ConfigError::Validation {
+                field: field("name"),
+                message: "name cannot be empty".to_string(),
+            });
+        }
+        if !rule.has_action() {
+            return Err(ConfigError::Validation {
+                field: field("target_model"),
+                message: format!(
+                    "rule '{}' must set target_model, privacy = \"restricted\" or min_tier",
+                    rule.name
+                ),
+            });
+        }
+        if let Some(pattern) = &rule.model_pattern {
+            globset::Glob::new(pattern).map_err(|e| ConfigError::Validation {
+                field: field("model_pattern"),
+                message: format!("Invalid glob pattern: {}", e),
+            })?;
+        }
+        if let Some(pattern) = &rule.pattern {
+            regex::Regex::new(pattern).map_err(|e| ConfigError::Validation {
+                field: field("pattern"),
+                message: format!("Invalid regex: {}", e),
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Semantic `nexus/auto` model selection
+///
+/// Requests for the `nexus/auto` model are classified by embedding the latest
+/// user message and comparing it against operator-provided example prompts.
+/// The route with the most similar example wins if its cosine similarity
+/// reaches `threshold`; otherwise the request goes to `fallback_model`.
+///
+/// ```toml
+/// [routing.semantic]
+/// enabled = true
+/// embedding_model = "nomic-embed-text"
+/// threshold = 0.75
+/// fallback_model = "llama3:8b"
+///
+/// [[routing.semantic.routes]]
+/// name = "code"
+/// target_model = "qwen2.5-coder:32b"
+/// min_tier = 3
+/// examples = ["Write a function that parses JSON", "Why does this code panic?"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticRoutingConfig {
+    /// Enable semantic routing for `nexus/auto`
+    pub enabled: bool,
+
+    /// Embedding model used for prompts and examples (served by any backend)
+    pub embedding_model: String,
+
+    /// Minimum cosine similarity for a route to be selected
+    pub threshold: f32,
+
+    /// Model used when no route reaches the threshold or embedding fails
+    pub fallback_model: String,
+
+    /// Candidate routes with example prompts
+    pub routes: Vec<SemanticRoute>,
+}
+
+impl Default for SemanticRoutingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            embedding_model: String::new(),
+            threshold: 0.75,
+            fallback_model: String::new(),
+            routes: Vec::new(),
+        }
+    }
+}
+
+/// A semantic route: example prompts mapped to a target model and tier
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticRoute {
+    /// Route name, reported in the `X-Nexus-Route-Reason` response header
+    pub name: String,
+
+    /// Model that serves requests classified into this route
+    pub target_model: String,
+
+    /// Minimum capability tier for this route
+    pub min_tier: Option<u8>,
+
+    /// Example prompts that characterize this route
+    pub examples: Vec<String>,
+}
+
+impl SemanticRoutingConfig {
+    /// Validate the semantic routing section (only checked when enabled)
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let invalid = |field: &str, message: &str| ConfigError::Validation {
+            field: format!("routing.semantic.{}", field),
+            message: message.to_string(),
+        };
+        if self.embedding_model.is_empty() {
+            return Err(invalid(
+                "embedding_model",
+                "embedding_model cannot be empty",
+            ));
+        }
+        if self.fallback_model.is_empty() {
+            return Err(invalid("fallback_model", "fallback_model cannot be empty"));
+        }
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(invalid(
+                "threshold",
+                "threshold must be between 0.0 and 1.0",
+            ));
+        }
+        if self.routes.is_empty() {
+            return Err(invalid("routes", "at least one route is required"));
+        }
+        for (i, route) in self.routes.iter().enumerate() {
+            if route.name.is_empty() || route.target_model.is_empty() {
+                return Err(invalid(
+                    &format!("routes[{}]", i),
+                    "name and target_model cannot be empty",
+                ));
+            }
+            if route.examples.is_empty() {
+                return Err(invalid(
+                    &format!("routes[{}].examples", i),
+                    "at least one example prompt is required",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Local Hugging Face `tokenizer.json` used to count tokens for matching models.
+///
+/// Entries are checked in order, before the built-in tiktoken patterns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenizerFile {
+    /// Glob pattern to match model names (e.g., "llama3*", "qwen2.5*")
+    pub model_pattern: String,
+
+    /// Path to the model's `tokenizer.json`
+    pub path: std::path::PathBuf,
+}
+
+/// Validate tokenizer file mappings
+pub fn validate_tokenizers(tokenizers: &[TokenizerFile]) -> Result<(), ConfigError> {
+    for (i, tokenizer) in tokenizers.iter().enumerate() {
+        globset::Glob::new(&tokenizer.model_pattern).map_err(|e| ConfigError::Validation {
+            field: format!("routing.tokenizers[{}].model_pattern", i),
+            message: format!("Invalid glob pattern: {}", e),
+        })?;
+        if tokenizer.path.as_os_str().is_empty() {
+            return Err(ConfigError::Validation {
+                field: format!("routing.tokenizers[{}].path", i),
+                message: "path cannot be empty".to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
 /// Action to take when hard budget limit is reached (FR-021)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -202,10 +502,22 @@ pub struct RoutingConfig {
     /// Optional: zero-config by default (no policies = unrestricted)
     #[serde(default)]
     pub policies: Vec<TrafficPolicy>,
+    /// Content-based routing rules (first match wins)
+    /// Optional: zero-config by default (no rules = route by model name only)
+    #[serde(default)]
+    pub rules: Vec<ContentRule>,
+    /// Semantic `nexus/auto` model selection via an embedding classifier
+    /// Optional: disabled by default
+    #[serde(default)]
+    pub semantic: SemanticRoutingConfig,
     /// Budget management configuration (FR-016)
     /// Optional: zero-config by default (no budget = no enforcement)
     #[serde(default)]
     pub budget: BudgetConfig,
+    /// Hugging Face tokenizer files for exact token counting
+    /// Optional: models without a match use the built-in tokenizers
+    #[serde(default)]
+    pub tokenizers: Vec<TokenizerFile>,
 }
 
 /// Routing weights for backend selection
@@ -225,7 +537,10 @@ impl Default for RoutingConfig {
             aliases: HashMap::new(),
             fallbacks: HashMap::new(),
             policies: Vec::new(),
+            rules: Vec::new(),
+            semantic: SemanticRoutingConfig::default(),
             budget: BudgetConfig::default(),
+            tokenizers: Vec::new(),
         }
     }
 }
@@ -431,6 +746,9 @@ mod tests {
             max_cost_per_request: None,
             min_tier: None,
             fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
         }];
         let matcher = PolicyMatcher::compile(policies).unwrap();
         assert!(matcher.find_policy("gpt-4").is_some());
@@ -445,6 +763,9 @@ mod tests {
             max_cost_per_request: None,
             min_tier: None,
             fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
         }];
         let matcher = PolicyMatcher::compile(policies).unwrap();
         assert!(matcher.find_policy("gpt-4").is_some());
@@ -462,6 +783,9 @@ mod tests {
                 max_cost_per_request: None,
                 min_tier: None,
                 fallback_allowed: true,
+                context_overflow: None,
+                cache: None,
+                injection_action: None,
             },
             TrafficPolicy {
                 model_pattern: "gpt-*".to_string(),
@@ -469,6 +793,9 @@ mod tests {
                 max_cost_per_request: None,
                 min_tier: None,
                 fallback_allowed: true,
+                context_overflow: None,
+                cache: None,
+                injection_action: None,
             },
         ];
         let matcher = PolicyMatcher::compile(policies).unwrap();
@@ -491,6 +818,9 @@ mod tests {
             max_cost_per_request: None,
             min_tier: None,
             fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
         }];
         assert!(PolicyMatcher::compile(policies).is_err());
     }
@@ -522,6 +852,34 @@ mod tests {
         assert!(policy.max_cost_per_request.is_none());
         assert!(policy.min_tier.is_none());
         assert!(policy.fallback_allowed);
+        assert!(policy.context_overflow.is_none());
+    }
+
+    #[test]
+    fn traffic_policy_context_overflow() {
+        let toml_str = r#"
+            model_pattern = "llama*"
+            context_overflow = "truncate"
+        "#;
+        let policy: TrafficPolicy = toml::from_str(toml_str).unwrap();
+        assert_eq!(policy.context_overflow, Some(ContextOverflowMode::Truncate));
+    }
+
+    #[test]
+    fn context_overflow_mode_from_header() {
+        assert_eq!(
+            ContextOverflowMode::from_header("Upgrade"),
+            Some(ContextOverflowMode::Upgrade)
+        );
+        assert_eq!(
+            ContextOverflowMode::from_header(" truncate "),
+            Some(ContextOverflowMode::Truncate)
+        );
+        assert_eq!(
+            ContextOverflowMode::from_header("reject"),
+            Some(ContextOverflowMode::Reject)
+        );
+        assert_eq!(ContextOverflowMode::from_header("shrink"), None);
     }
 
     #[test]
@@ -543,4 +901,113 @@ mod tests {
         assert_eq!(config.policies[0].privacy, PrivacyConstraint::Restricted);
         assert_eq!(config.policies[1].privacy, PrivacyConstraint::Unrestricted);
     }
+
+    #[test]
+    fn routing_config_with_rules_serde() {
+        let toml_str = r#"
+            [[rules]]
+            name = "code"
+            model_pattern = "auto"
+            has_code = true
+            min_prompt_tokens = 500
+            target_model = "llama3:70b"
+
+            [[rules]]
+            name = "sensitive"
+            keywords = ["confidential"]
+            privacy = "restricted"
+        "#;
+        let config: RoutingConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].has_code, Some(true));
+        assert_eq!(config.rules[0].min_prompt_tokens, Some(500));
+        assert_eq!(config.rules[0].target_model.as_deref(), Some("llama3:70b"));
+        assert_eq!(config.rules[1].model_pattern, None);
+        assert_eq!(config.rules[1].privacy, Some(PrivacyConstraint::Restricted));
+        assert!(validate_rules(&config.rules).is_ok());
+    }
+
+    #[test]
+    fn validate_rules_rejects_rule_without_action() {
+        let rules = vec![ContentRule {
+            name: "noop".to_string(),
+            has_code: Some(true),
+            ..Default::default()
+        }];
+        let err = validate_rules(&rules).unwrap_err();
+        assert!(err.to_string().contains("routing.rules[0]"));
+    }
+
+    #[test]
+    fn validate_rules_rejects_invalid_regex() {
+        let rules = vec![ContentRule {
+            name: "bad".to_string(),
+            pattern: Some("(unclosed".to_string()),
+            min_tier: Some(2),
+            ..Default::default()
+        }];
+        let err = validate_rules(&rules).unwrap_err();
+        assert!(err.to_string().contains("routing.rules[0].pattern"));
+    }
+
+    #[test]
+    fn semantic_routing_config_serde_and_validation() {
+        let toml_str = r#"
+            [semantic]
+            enabled = true
+            embedding_model = "nomic-embed-text"
+            fallback_model = "llama3:8b"
+
+            [[semantic.routes]]
+            name = "code"
+            target_model = "llama3:70b"
+            min_tier = 3
+            examples = ["Write a sorting function"]
+        "#;
+        let config: RoutingConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.semantic.enabled);
+        assert_eq!(config.semantic.threshold, 0.75);
+        assert_eq!(config.semantic.routes[0].min_tier, Some(3));
+        assert!(config.semantic.validate().is_ok());
+
+        let mut missing_fallback = config.semantic.clone();
+        missing_fallback.fallback_model.clear();
+        assert!(missing_fallback.validate().is_err());
+
+        let mut no_examples = config.semantic;
+        no_examples.routes[0].examples.clear();
+        let err = no_examples.validate().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("routing.semantic.routes[0].examples"));
+    }
+
+    #[test]
+    fn semantic_routing_disabled_by_default() {
+        let config = SemanticRoutingConfig::default();
+        assert!(!config.enabled);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn tokenizer_files_serde_and_validation() {
+        let toml_str = r#"
+            [[tokenizers]]
+            model_pattern = "llama3*"
+            path = "/models/llama3/tokenizer.json"
+        "#;
+        let config: RoutingConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.tokenizers.len(), 1);
+        assert_eq!(config.tokenizers[0].model_pattern, "llama3*");
+        assert!(validate_tokenizers(&config.tokenizers).is_ok());
+
+        let bad_glob = vec![TokenizerFile {
+            model_pattern: "llama[".to_string(),
+            path: "/models/tokenizer.json".into(),
+        }];
+        let err = validate_tokenizers(&bad_glob).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("routing.tokenizers[0].model_pattern"));
+    }
 }
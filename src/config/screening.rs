@@ -0,0 +1,153 @@
+//! Prompt injection screening configuration
+
+use super::ConfigError;
+use serde::{Deserialize, Serialize};
+
+/// What happens to a request whose injection risk reaches the threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectionAction {
+    /// Reject the request before it reaches any backend
+    Reject,
+    /// Remove tool definitions and route without tool access
+    DowngradeTools,
+    /// Route unchanged, but log and count the request
+    #[default]
+    Log,
+}
+
+impl InjectionAction {
+    /// Action name used in logs and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InjectionAction::Reject => "reject",
+            InjectionAction::DowngradeTools => "downgrade_tools",
+            InjectionAction::Log => "log",
+        }
+    }
+}
+
+/// Configuration for prompt injection / jailbreak screening.
+///
+/// User content is sent to a small classification model, routed through
+/// Nexus but restricted to local (restricted zone) backends, before the
+/// request is routed. The model should answer with a risk score between 0
+/// and 1, or a label such as `injection` / `benign`. Requests scoring at or
+/// above `threshold` get `action`, which traffic policies may override per
+/// model with `injection_action`.
+///
+/// # Example
+///
+/// ```toml
+/// [screening]
+/// enabled = true
+/// model = "prompt-guard"
+/// threshold = 0.8
+/// action = "downgrade_tools"
+/// trusted_tenants = ["3f1c9a0e5b7d2c48"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreeningConfig {
+    /// Whether requests are screened.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Classification model; only served from restricted-zone backends.
+    ///
+    /// Default: "prompt-guard"
+    pub model: String,
+
+    /// Risk score (0.0-1.0) at which `action` applies.
+    ///
+    /// Default: 0.8
+    pub threshold: f32,
+
+    /// Action for requests at or above the threshold.
+    ///
+    /// Default: log
+    pub action: InjectionAction,
+
+    /// Tenants whose requests are not screened, as authenticated tenant ids:
+    /// the first 16 hex digits of the SHA-256 of the Authorization header value.
+    pub trusted_tenants: Vec<String>,
+
+    /// Route the request unscreened when the classifier call fails.
+    /// When false, a failed call is treated as maximum risk.
+    ///
+    /// Default: true
+    pub fail_open: bool,
+}
+
+impl Default for ScreeningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: "prompt-guard".to_string(),
+            threshold: 0.8,
+            action: InjectionAction::Log,
+            trusted_tenants: Vec::new(),
+            fail_open: true,
+        }
+    }
+}
+
+impl ScreeningConfig {
+    /// Validate the model and threshold
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.enabled && self.model.is_empty() {
+            return Err(ConfigError::Validation {
+                field: "screening.model".to_string(),
+                message: "model cannot be empty when screening is enabled".to_string(),
+            });
+        }
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(ConfigError::Validation {
+                field: "screening.threshold".to_string(),
+                message: format!("must be between 0.0 and 1.0, got {}", self.threshold),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_disabled_and_valid() {
+        let config = ScreeningConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.action, InjectionAction::Log);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn parses_action_and_trusted_tenants() {
+        let config: ScreeningConfig = toml::from_str(
+            r#"
+            enabled = true
+            model = "guard"
+            threshold = 0.6
+            action = "downgrade_tools"
+            trusted_tenants = ["internal"]
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.action, InjectionAction::DowngradeTools);
+        assert_eq!(config.trusted_tenants, vec!["internal"]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_threshold_out_of_range() {
+        let config = ScreeningConfig {
+            threshold: 1.5,
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("screening.threshold"));
+    }
+}
@@ -0,0 +1,81 @@
+//! Structured output configuration
+
+use super::ConfigError;
+use serde::{Deserialize, Serialize};
+
+/// Upper bound for `max_repair_attempts`; each attempt is a full model call.
+const MAX_REPAIR_ATTEMPTS: u32 = 10;
+
+/// Configuration for `response_format: {type: "json_schema"}` enforcement.
+///
+/// Requests with a JSON schema prefer backends that enforce schemas
+/// natively. Other backends receive the schema as a system instruction, and
+/// their non-streaming responses are validated by the gateway; invalid
+/// responses are sent back to the model with the validation errors up to
+/// `max_repair_attempts` times before the request fails with 422.
+///
+/// # Example
+///
+/// ```toml
+/// [structured_output]
+/// enabled = true
+/// max_repair_attempts = 2
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StructuredOutputConfig {
+    /// Whether JSON schema responses are validated and repaired.
+    /// Native routing preference applies either way.
+    ///
+    /// Default: true
+    pub enabled: bool,
+
+    /// Repair requests sent after an invalid response (0 disables repair).
+    ///
+    /// Default: 2
+    pub max_repair_attempts: u32,
+}
+
+impl Default for StructuredOutputConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_repair_attempts: 2,
+        }
+    }
+}
+
+impl StructuredOutputConfig {
+    /// Validate the repair attempt limit
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_repair_attempts > MAX_REPAIR_ATTEMPTS {
+            return Err(ConfigError::Validation {
+                field: "structured_output.max_repair_attempts".to_string(),
+                message: format!(
+                    "must be at most {}, got {}",
+                    MAX_REPAIR_ATTEMPTS, self.max_repair_attempts
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_enabled_with_two_repairs() {
+        let config = StructuredOutputConfig::default();
+        assert!(config.enabled);
+        assert_eq!(config.max_repair_attempts, 2);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_excessive_repair_attempts() {
+        let config: StructuredOutputConfig = toml::from_str("max_repair_attempts = 50").unwrap();
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Tool-calling emulation configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Format the model is asked to use for emulated tool calls.
+///
+/// Both formats are accepted when parsing; this only selects the instruction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCallFormat {
+    /// `<tool_call>{"name": ..., "arguments": {...}}</tool_call>` blocks
+    #[default]
+    Xml,
+    /// A single `{"tool_calls": [{"name": ..., "arguments": {...}}]}` object
+    Json,
+}
+
+/// Configuration for tool calling on models without native function calling.
+///
+/// When enabled, requests with `tools` may route to models whose
+/// `supports_tools` is false, although models with native support are still
+/// preferred. The tool definitions are rendered into a system prompt, and the
+/// model's output is parsed back into OpenAI `tool_calls` (also for streamed
+/// responses).
+///
+/// # Example
+///
+/// ```toml
+/// [tool_emulation]
+/// enabled = true
+/// format = "xml"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolEmulationConfig {
+    /// Whether tool calling is emulated for models without native support.
+    ///
+    /// Default: false
+    pub enabled: bool,
+
+    /// Tool call format requested from the model.
+    ///
+    /// Default: xml
+    pub format: ToolCallFormat,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_disabled_with_xml_format() {
+        let config = ToolEmulationConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.format, ToolCallFormat::Xml);
+    }
+
+    #[test]
+    fn parses_json_format() {
+        let config: ToolEmulationConfig =
+            toml::from_str("enabled = true\nformat = \"json\"").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.format, ToolCallFormat::Json);
+    }
+}
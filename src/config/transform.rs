@@ -0,0 +1,288 @@
+//! Request transformation configuration
+
+use super::ConfigError;
+use serde::{Deserialize, Serialize};
+
+/// Allowed range for a numeric request parameter.
+///
+/// Values outside the range are clamped; unset parameters are left unset,
+/// except `max_tokens`, which defaults to the range's `max`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValueRange {
+    /// Lower bound (inclusive)
+    pub min: Option<f64>,
+    /// Upper bound (inclusive)
+    pub max: Option<f64>,
+}
+
+impl ValueRange {
+    /// Clamp a value into the range.
+    pub fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+}
+
+/// A single request transform.
+///
+/// Every action that is set is applied to requests whose model matches
+/// `model_pattern` (all models when unset).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransformRule {
+    /// Rule name, reported in the `X-Nexus-Transforms` response header
+    pub name: String,
+
+    /// Glob pattern for the requested model. None matches any model.
+    pub model_pattern: Option<String>,
+
+    /// System prompt inserted before all other messages
+    pub system_prompt: Option<String>,
+
+    /// Allowed range for `max_tokens`; requests without one get the `max`
+    pub max_tokens: Option<ValueRange>,
+
+    /// Allowed range for `temperature`
+    pub temperature: Option<ValueRange>,
+
+    /// Allowed range for `top_p`
+    pub top_p: Option<ValueRange>,
+
+    /// Extra request parameters to remove (e.g. "logit_bias", "seed")
+    pub strip_params: Vec<String>,
+
+    /// Stop sequences set when the request has none
+    pub default_stop: Vec<String>,
+
+    /// Whether tenant overrides may skip or replace this rule
+    pub overridable: bool,
+}
+
+impl TransformRule {
+    /// Returns true if the rule changes anything when it matches
+    pub fn has_action(&self) -> bool {
+        self.system_prompt.is_some()
+            || self.max_tokens.is_some()
+            || self.temperature.is_some()
+            || self.top_p.is_some()
+            || !self.strip_params.is_empty()
+            || !self.default_stop.is_empty()
+    }
+}
+
+/// Transform overrides for one tenant.
+///
+/// Tenant rules replace global rules with the same name and are otherwise
+/// appended to the chain; `skip` removes global rules by name. Only global
+/// rules marked `overridable` may be skipped or replaced.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenantTransforms {
+    /// Authenticated tenant identifier: the first 16 hex digits of the
+    /// SHA-256 of the request's Authorization header value
+    pub tenant: String,
+
+    /// Global rules not applied for this tenant
+    pub skip: Vec<String>,
+
+    /// Tenant-specific rules
+    pub rules: Vec<TransformRule>,
+}
+
+/// Request transformation policies
+///
+/// An ordered chain of declarative transforms applied to chat requests before
+/// routing requirements are computed. Applied rule names are reported in the
+/// `X-Nexus-Transforms` response header.
+///
+/// # Example
+///
+/// ```toml
+/// [[transforms.rules]]
+/// name = "company-prompt"
+/// model_pattern = "llama*"
+/// system_prompt = "You are Acme's internal assistant."
+/// overridable = true
+///
+/// [[transforms.rules]]
+/// name = "limits"
+/// max_tokens = { max = 2048 }
+/// temperature = { min = 0.0, max = 1.0 }
+/// strip_params = ["logit_bias"]
+/// default_stop = ["</s>"]
+///
+/// [[transforms.tenants]]
+/// tenant = "3f1c9a0e5b7d2c48"
+/// skip = ["company-prompt"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransformConfig {
+    /// Global transform chain, applied in declaration order
+    pub rules: Vec<TransformRule>,
+
+    /// Per-tenant overrides
+    pub tenants: Vec<TenantTransforms>,
+}
+
+impl TransformConfig {
+    /// Validate rule names, actions, globs, ranges and tenant overrides
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        validate_rules("transforms.rules", &self.rules)?;
+        for (i, tenant) in self.tenants.iter().enumerate() {
+            if tenant.tenant.is_empty() {
+                return Err(ConfigError::Validation {
+                    field: format!("transforms.tenants[{}].tenant", i),
+                    message: "tenant cannot be empty".to_string(),
+                });
+            }
+            validate_rules(&format!("transforms.tenants[{}].rules", i), &tenant.rules)?;
+            let overridden = tenant
+                .skip
+                .iter()
+                .map(|name| ("skip", name))
+                .chain(tenant.rules.iter().map(|rule| ("rules", &rule.name)));
+            for (field, name) in overridden {
+                let mandatory = self
+                    .rules
+                    .iter()
+                    .any(|rule| &rule.name == name && !rule.overridable);
+                if mandatory {
+                    return Err(ConfigError::Validation {
+                        field: format!("transforms.tenants[{}].{}", i, field),
+                        message: format!("global rule '{}' is not overridable", name),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_rules(prefix: &str, rules: &[TransformRule]) -> Result<(), ConfigError> {
+    for (i, rule) in rules.iter().enumerate() {
+        let invalid = |name: &str, message: String| ConfigError::Validation {
+            field: format!("{}[{}].{}", prefix, i, name),
+            message,
+        };
+        if rule.name.is_empty() {
+            return Err(invalid("name", "name cannot be empty".to_string()));
+        }
+        if !rule.has_action() {
+            return Err(invalid(
+                "system_prompt",
+                format!("rule '{}' must set at least one transform", rule.name),
+            ));
+        }
+        if let Some(pattern) = &rule.model_pattern {
+            globset::Glob::new(pattern)
+                .map_err(|e| invalid("model_pattern", format!("Invalid glob pattern: {}", e)))?;
+        }
+        let ranges = [
+            ("max_tokens", rule.max_tokens),
+            ("temperature", rule.temperature),
+            ("top_p", rule.top_p),
+        ];
+        for (name, range) in ranges {
+            if let Some(ValueRange {
+                min: Some(min),
+                max: Some(max),
+            }) = range
+            {
+                if min > max {
+                    return Err(invalid(name, format!("min {} exceeds max {}", min, max)));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules_and_tenant_overrides() {
+        let config: TransformConfig = toml::from_str(
+            r#"
+            [[rules]]
+            name = "limits"
+            max_tokens = { max = 2048 }
+            temperature = { min = 0.0, max = 1.0 }
+            strip_params = ["logit_bias"]
+            overridable = true
+
+            [[tenants]]
+            tenant = "research"
+            skip = ["limits"]
+        "#,
+        )
+        .unwrap();
+        assert!(config.rules[0].overridable);
+        assert_eq!(config.rules[0].max_tokens.unwrap().max, Some(2048.0));
+        assert_eq!(config.tenants[0].skip, vec!["limits"]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_rules_without_actions_or_bad_ranges() {
+        let mut config = TransformConfig {
+            rules: vec![TransformRule {
+                name: "noop".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.rules[0].temperature = Some(ValueRange {
+            min: Some(1.0),
+            max: Some(0.5),
+        });
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("transforms.rules[0].temperature"));
+    }
+
+    #[test]
+    fn rejects_overrides_of_mandatory_rules() {
+        let mut config: TransformConfig = toml::from_str(
+            r#"
+            [[rules]]
+            name = "company-prompt"
+            system_prompt = "Be helpful."
+
+            [[tenants]]
+            tenant = "research"
+            skip = ["company-prompt"]
+        "#,
+        )
+        .unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("transforms.tenants[0].skip"));
+
+        config.tenants[0].skip.clear();
+        config.tenants[0].rules.push(TransformRule {
+            name: "company-prompt".to_string(),
+            system_prompt: Some("Be terse.".to_string()),
+            ..Default::default()
+        });
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("transforms.tenants[0].rules"));
+
+        config.rules[0].overridable = true;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn range_clamps_both_bounds() {
+        let range = ValueRange {
+            min: Some(0.1),
+            max: Some(1.0),
+        };
+        assert_eq!(range.clamp(2.0), 1.0);
+        assert_eq!(range.clamp(0.0), 0.1);
+        assert_eq!(ValueRange::default().clamp(5.0), 5.0);
+    }
+}
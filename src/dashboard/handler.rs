@@ -39,7 +39,11 @@ pub async fn dashboard_handler(State(state): State<Arc<AppState>>) -> Response {
             // Generate initial stats data
             state.metrics_collector.update_fleet_gauges();
             let registry = state.metrics_collector.registry();
-            let backend_stats = crate::metrics::handler::compute_backend_stats(registry, None);
+            let backend_stats = crate::metrics::handler::compute_backend_stats(
+                registry,
+                None,
+                Some(state.router.circuit_breakers()),
+            );
             let stats = crate::metrics::types::StatsResponse {
                 uptime_seconds: state.metrics_collector.uptime_seconds(),
                 requests: crate::metrics::handler::compute_request_stats(&backend_stats),
@@ -409,6 +413,7 @@ mod tests {
                         supports_vision: false,
                         supports_tools: true,
                         supports_json_mode: true,
+                        supports_embeddings: false,
                         max_output_tokens: None,
                     },
                     Model {
@@ -418,6 +423,7 @@ mod tests {
                         supports_vision: false,
                         supports_tools: false,
                         supports_json_mode: false,
+                        supports_embeddings: false,
                         max_output_tokens: Some(4096),
                     },
                 ],
@@ -448,6 +454,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             )
@@ -248,6 +248,7 @@ mod tests {
                 supports_vision: i % 2 == 0,
                 supports_tools: i % 3 == 0,
                 supports_json_mode: true,
+                supports_embeddings: false,
                 max_output_tokens: Some(2048),
             })
             .collect();
@@ -377,6 +378,7 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
@@ -574,6 +576,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: true,
                     supports_json_mode: true,
+                    supports_embeddings: false,
                     max_output_tokens: Some(4096),
                 },
                 Model {
@@ -583,6 +586,7 @@ mod tests {
                     supports_vision: true,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 },
             ],
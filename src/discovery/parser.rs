@@ -28,7 +28,8 @@ pub fn parse_txt_records(txt: &HashMap<String, String>, service_type: &str) -> P
         .unwrap_or_else(|| {
             // Default based on backend type
             match backend_type {
-                BackendType::Ollama => String::new(),
+                // Native APIs served from the root
+                BackendType::Ollama | BackendType::TGI | BackendType::TEI => String::new(),
                 _ => "/v1".to_string(),
             }
         });
@@ -50,6 +51,8 @@ fn parse_backend_type(type_str: &str) -> BackendType {
         "llamacpp" | "llama.cpp" => BackendType::LlamaCpp,
         "exo" => BackendType::Exo,
         "openai" => BackendType::OpenAI,
+        "tgi" | "text-generation-inference" => BackendType::TGI,
+        "tei" | "text-embeddings-inference" => BackendType::TEI,
         _ => BackendType::Generic,
     }
 }
@@ -193,4 +196,26 @@ mod tests {
         let parsed = parse_txt_records(&txt, "_llm._tcp.local");
         assert_eq!(parsed.api_path, "/v1");
     }
+
+    #[test]
+    fn test_parse_txt_type_tgi() {
+        for type_str in ["tgi", "text-generation-inference"] {
+            let mut txt = HashMap::new();
+            txt.insert("type".to_string(), type_str.to_string());
+            let parsed = parse_txt_records(&txt, "_llm._tcp.local");
+            assert_eq!(parsed.backend_type, BackendType::TGI);
+            assert_eq!(parsed.api_path, "");
+        }
+    }
+
+    #[test]
+    fn test_parse_txt_type_tei() {
+        for type_str in ["tei", "text-embeddings-inference"] {
+            let mut txt = HashMap::new();
+            txt.insert("type".to_string(), type_str.to_string());
+            let parsed = parse_txt_records(&txt, "_llm._tcp.local");
+            assert_eq!(parsed.backend_type, BackendType::TEI);
+            assert_eq!(parsed.api_path, "");
+        }
+    }
 }
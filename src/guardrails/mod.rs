@@ -0,0 +1,468 @@
+//! Output guardrails
+//!
+//! Response-side filter applied after the agent returns: operator-defined
+//! regex and keyword rules that block, redact or flag matching content, plus
+//! an optional moderation model consulted for complete responses. Like PII
+//! detection, guardrails report *which rule* fired; callers must never log
+//! the matched content itself.
+
+mod stream;
+
+pub use stream::{GuardBlocked, StreamGuard};
+
+use crate::api::types::{ChatCompletionRequest, ChatMessage, MessageContent};
+use crate::api::ChatCompletionResponse;
+use crate::config::{ConfigError, GuardrailAction, GuardrailsConfig, ModerationConfig};
+use regex::Regex;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// A guardrail rule with its pattern and keywords compiled into one regex.
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    name: String,
+    action: GuardrailAction,
+    regex: Regex,
+}
+
+/// A rule that fired on a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trigger {
+    /// Rule name ("moderation" for the moderation model)
+    pub rule: String,
+    /// Action taken
+    pub action: GuardrailAction,
+}
+
+/// Rules that fired on a non-streaming response.
+#[derive(Debug, Clone, Default)]
+pub struct GuardReport {
+    /// Redact and flag rules that fired, in order of first match
+    pub triggered: Vec<Trigger>,
+    /// Block rule that rejected the response, if any
+    pub blocked: Option<String>,
+}
+
+impl GuardReport {
+    /// Record a fired rule once per response.
+    fn push(&mut self, rule: &str, action: GuardrailAction) {
+        if action == GuardrailAction::Block {
+            if self.blocked.is_none() {
+                record_trigger(rule, action);
+                self.blocked = Some(rule.to_string());
+            }
+            return;
+        }
+        if !self.triggered.iter().any(|t| t.rule == rule) {
+            record_trigger(rule, action);
+            self.triggered.push(Trigger {
+                rule: rule.to_string(),
+                action,
+            });
+        }
+    }
+
+    /// Value for the `X-Nexus-Guardrails` header (`action:rule,...`).
+    pub fn header_value(&self) -> Option<String> {
+        if self.triggered.is_empty() {
+            return None;
+        }
+        Some(
+            self.triggered
+                .iter()
+                .map(|t| format!("{}:{}", t.action.as_str(), t.rule))
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    }
+}
+
+/// Log and count a fired rule.
+fn record_trigger(rule: &str, action: GuardrailAction) {
+    match action {
+        GuardrailAction::Block => tracing::warn!(rule, "Guardrail blocked response"),
+        _ => tracing::info!(rule, action = action.as_str(), "Guardrail matched response"),
+    }
+    metrics::counter!("nexus_guardrail_triggers_total",
+        "rule" => rule.to_string(),
+        "action" => action.as_str()
+    )
+    .increment(1);
+}
+
+/// Compiled output guardrails.
+#[derive(Debug, Clone)]
+pub struct OutputGuard {
+    rules: Vec<CompiledRule>,
+    lookbehind: usize,
+    redaction: String,
+    moderation: ModerationConfig,
+}
+
+impl OutputGuard {
+    /// Compile the rules from configuration.
+    pub fn new(config: &GuardrailsConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let mut alternatives: Vec<String> = rule.pattern.iter().cloned().collect();
+                let keywords: Vec<String> = rule
+                    .keywords
+                    .iter()
+                    .filter(|k| !k.is_empty())
+                    .map(|k| regex::escape(k))
+                    .collect();
+                if !keywords.is_empty() {
+                    alternatives.push(format!("(?i:{})", keywords.join("|")));
+                }
+                let pattern = alternatives
+                    .iter()
+                    .map(|p| format!("(?:{})", p))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                Regex::new(&pattern)
+                    .map(|regex| CompiledRule {
+                        name: rule.name.clone(),
+                        action: rule.action,
+                        regex,
+                    })
+                    .map_err(|e| ConfigError::Validation {
+                        field: format!("guardrails.rules[{}].pattern", i),
+                        message: format!("Invalid regex: {}", e),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            rules,
+            lookbehind: config.lookbehind_bytes,
+            redaction: config.redaction.clone(),
+            moderation: config.moderation.clone(),
+        })
+    }
+
+    /// Moderation settings, if the moderation model is enabled.
+    pub fn moderation(&self) -> Option<&ModerationConfig> {
+        self.moderation.enabled.then_some(&self.moderation)
+    }
+
+    /// Start filtering a streamed response.
+    pub fn stream_guard(self: &Arc<Self>) -> StreamGuard {
+        StreamGuard::new(Arc::clone(self))
+    }
+
+    /// Apply the rules to the text and tool-call arguments of every choice of
+    /// a complete response, redacting in place. Check `GuardReport::blocked`
+    /// before delivering the response.
+    pub fn check_response(&self, response: &mut ChatCompletionResponse) -> GuardReport {
+        let mut report = GuardReport::default();
+        for choice in &mut response.choices {
+            match &mut choice.message.content {
+                MessageContent::Text { content } => {
+                    *content = self.apply(content, &mut report);
+                }
+                MessageContent::Parts { content } => {
+                    for text in content.iter_mut().filter_map(|part| part.text.as_mut()) {
+                        *text = self.apply(text, &mut report);
+                    }
+                }
+            }
+            for arguments in choice.message.call_arguments_mut() {
+                *arguments = self.apply(arguments, &mut report);
+            }
+        }
+        report
+    }
+
+    /// Record moderation model verdicts on the report.
+    pub fn record_moderation(&self, report: &mut GuardReport) {
+        let action = match self.moderation.action {
+            GuardrailAction::Redact => GuardrailAction::Block,
+            action => action,
+        };
+        report.push("moderation", action);
+    }
+
+    /// Apply the rules to a complete text.
+    fn apply(&self, text: &str, report: &mut GuardReport) -> String {
+        let (matches, redactions) = self.scan(text);
+        for rule in &matches {
+            report.push(&rule.name, rule.action);
+        }
+        self.redact(text, &redactions)
+    }
+
+    /// Find the rules that match (once per match), plus the non-overlapping
+    /// redaction ranges ordered by position (earliest, longest first).
+    fn scan(&self, text: &str) -> (Vec<&CompiledRule>, Vec<Range<usize>>) {
+        let mut matches = Vec::new();
+        let mut ranges = Vec::new();
+        for rule in &self.rules {
+            for m in rule.regex.find_iter(text).filter(|m| !m.is_empty()) {
+                matches.push(rule);
+                if rule.action == GuardrailAction::Redact {
+                    ranges.push(m.range());
+                }
+            }
+        }
+
+        ranges.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+        let mut resolved: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match resolved.last() {
+                Some(last) if range.start < last.end => {}
+                _ => resolved.push(range),
+            }
+        }
+        (matches, resolved)
+    }
+
+    /// Replace the given (sorted, non-overlapping) ranges of `text`.
+    fn redact(&self, text: &str, ranges: &[Range<usize>]) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for range in ranges {
+            out.push_str(&text[cursor..range.start]);
+            out.push_str(&self.redaction);
+            cursor = range.end;
+        }
+        out.push_str(&text[cursor..]);
+        out
+    }
+}
+
+/// Build the moderation request: the final user message and the response
+/// text as a two-turn conversation for the moderation model to classify.
+pub fn moderation_request(
+    config: &ModerationConfig,
+    request: &ChatCompletionRequest,
+    response: &ChatCompletionResponse,
+) -> ChatCompletionRequest {
+    let text_of = |content: &MessageContent| match content {
+        MessageContent::Text { content } => content.clone(),
+        MessageContent::Parts { content } => content
+            .iter()
+            .filter_map(|part| part.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    let prompt = request
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| text_of(&m.content))
+        .unwrap_or_default();
+    let answer = response
+        .choices
+        .iter()
+        .map(|choice| text_of(&choice.message.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let message = |role: &str, content: String| ChatMessage {
+        role: role.to_string(),
+        content: MessageContent::Text { content },
+        name: None,
+        function_call: None,
+        tool_calls: None,
+        tool_call_id: None,
+    };
+    ChatCompletionRequest {
+        model: config.model.clone(),
+        messages: vec![message("user", prompt), message("assistant", answer)],
+        stream: false,
+        temperature: Some(0.0),
+        max_tokens: Some(16),
+        top_p: None,
+        stop: None,
+        presence_penalty: None,
+        frequency_penalty: None,
+        user: None,
+        extra: HashMap::new(),
+    }
+}
+
+/// Whether a moderation reply classifies the response as unsafe.
+pub fn is_unsafe(reply: &ChatCompletionResponse) -> bool {
+    reply.choices.first().is_some_and(|choice| {
+        let MessageContent::Text { content } = &choice.message.content else {
+            return false;
+        };
+        content
+            .trim_start()
+            .lines()
+            .next()
+            .is_some_and(|line| line.trim().eq_ignore_ascii_case("unsafe"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GuardrailRule;
+
+    pub(super) fn guard(rules: Vec<(&str, &str, GuardrailAction)>) -> OutputGuard {
+        OutputGuard::new(&GuardrailsConfig {
+            enabled: true,
+            rules: rules
+                .into_iter()
+                .map(|(name, pattern, action)| GuardrailRule {
+                    name: name.to_string(),
+                    pattern: Some(pattern.to_string()),
+                    keywords: vec![],
+                    action,
+                })
+                .collect(),
+            lookbehind_bytes: 16,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn content(response: &ChatCompletionResponse) -> &str {
+        match &response.choices[0].message.content {
+            MessageContent::Text { content } => content,
+            MessageContent::Parts { .. } => panic!("expected text"),
+        }
+    }
+
+    #[test]
+    fn redacts_and_flags_without_blocking() {
+        let guard = guard(vec![
+            ("api_key", r"sk-[a-z0-9]{8,}", GuardrailAction::Redact),
+            ("mention", r"(?i)competitor", GuardrailAction::Flag),
+        ]);
+        let mut resp = response("Use sk-abcdef123456 unlike Competitor.");
+
+        let report = guard.check_response(&mut resp);
+
+        assert!(report.blocked.is_none());
+        assert_eq!(content(&resp), "Use [REDACTED] unlike Competitor.");
+        assert_eq!(
+            report.header_value().as_deref(),
+            Some("redact:api_key,flag:mention")
+        );
+    }
+
+    #[test]
+    fn block_rule_rejects_response() {
+        let guard = guard(vec![("secret", r"TOP SECRET", GuardrailAction::Block)]);
+        let mut resp = response("This is TOP SECRET material.");
+        assert_eq!(
+            guard.check_response(&mut resp).blocked.as_deref(),
+            Some("secret")
+        );
+    }
+
+    #[test]
+    fn rules_apply_to_tool_call_arguments() {
+        let guard = guard(vec![
+            ("api_key", r"sk-[a-z0-9]{8,}", GuardrailAction::Redact),
+            ("secret", r"TOP SECRET", GuardrailAction::Block),
+        ]);
+        let call = |arguments: &str| -> ChatCompletionResponse {
+            serde_json::from_value(serde_json::json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 0,
+                "model": "llama3",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": [{
+                            "id": "call_1",
+                            "type": "function",
+                            "function": {"name": "send", "arguments": arguments}
+                        }]
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            }))
+            .unwrap()
+        };
+
+        let mut resp = call(r#"{"key": "sk-abcdef123456"}"#);
+        assert!(guard.check_response(&mut resp).blocked.is_none());
+        let calls = resp.choices[0].message.tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].function.arguments, r#"{"key": "[REDACTED]"}"#);
+
+        let mut resp = call(r#"{"note": "TOP SECRET"}"#);
+        assert_eq!(
+            guard.check_response(&mut resp).blocked.as_deref(),
+            Some("secret")
+        );
+    }
+
+    #[test]
+    fn keywords_match_case_insensitively_and_literally() {
+        let guard = OutputGuard::new(&GuardrailsConfig {
+            enabled: true,
+            rules: vec![GuardrailRule {
+                name: "codenames".to_string(),
+                pattern: None,
+                keywords: vec!["blue.bird".to_string()],
+                action: GuardrailAction::Redact,
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut resp = response("Project BLUE.BIRD, not bluexbird.");
+        guard.check_response(&mut resp);
+        assert_eq!(content(&resp), "Project [REDACTED], not bluexbird.");
+    }
+
+    #[test]
+    fn moderation_reply_parsing() {
+        assert!(is_unsafe(&response("unsafe\nS1")));
+        assert!(is_unsafe(&response("  Unsafe")));
+        assert!(!is_unsafe(&response("safe")));
+        assert!(!is_unsafe(&response("the answer is unsafe")));
+    }
+
+    #[test]
+    fn moderation_request_pairs_prompt_and_answer() {
+        let request = moderation_request(
+            &ModerationConfig::default(),
+            &serde_json::from_value(serde_json::json!({
+                "model": "llama3",
+                "messages": [
+                    {"role": "system", "content": "sys"},
+                    {"role": "user", "content": "question"}
+                ]
+            }))
+            .unwrap(),
+            &response("answer"),
+        );
+        assert_eq!(request.model, "llama-guard3");
+        let roles: Vec<_> = request.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, vec!["user", "assistant"]);
+        let MessageContent::Text { content } = &request.messages[1].content else {
+            panic!("expected text");
+        };
+        assert_eq!(content, "answer");
+    }
+}
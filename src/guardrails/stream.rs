@@ -0,0 +1,464 @@
+//! Streaming guardrail filter
+//!
+//! Applies the guardrail rules to streamed `chat.completion.chunk` payloads.
+//! Each choice keeps a bounded look-behind buffer: the last
+//! `lookbehind_bytes` of text are held back until the next delta, so a match
+//! split across deltas is still caught (and redacted before release). Held
+//! text is released when the choice finishes or the stream ends. Streamed
+//! tool-call arguments are held back and filtered the same way, per call.
+
+use super::{GuardReport, OutputGuard};
+use crate::config::GuardrailAction;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A stream was stopped by a block rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardBlocked {
+    /// Name of the block rule that matched
+    pub rule: String,
+}
+
+/// Filters one streamed response. A default (disabled) guard passes data
+/// through unchanged.
+///
+/// Accepts either bare JSON payloads or raw SSE text (`data: {...}` lines, as
+/// forwarded by most agents, possibly split mid-line).
+#[derive(Default)]
+pub struct StreamGuard {
+    guard: Option<Arc<OutputGuard>>,
+    /// Held-back text per (choice index, tool call index); a choice's content
+    /// has no tool call index
+    pending: HashMap<(u64, Option<u64>), String>,
+    /// Last chunk seen, used to emit held-back text at end of stream
+    template: Option<serde_json::Value>,
+    /// Whether input arrives as raw SSE text rather than bare payloads
+    sse_framed: bool,
+    /// Incomplete trailing SSE line
+    line_buffer: String,
+    /// Rules that fired so far
+    report: GuardReport,
+}
+
+impl StreamGuard {
+    pub(super) fn new(guard: Arc<OutputGuard>) -> Self {
+        Self {
+            guard: Some(guard),
+            ..Default::default()
+        }
+    }
+
+    /// Filter one streamed chunk. Non-JSON payloads pass through unchanged.
+    ///
+    /// The result may be empty while text is held back or a line is incomplete.
+    pub fn filter_chunk(&mut self, data: &str) -> Result<String, GuardBlocked> {
+        if self.guard.is_none() {
+            return Ok(data.to_string());
+        }
+        if self.sse_framed || data.trim_start().starts_with("data:") {
+            self.sse_framed = true;
+            return self.filter_sse(data);
+        }
+        self.filter_payload(data)
+    }
+
+    /// Filter the final data before `[DONE]` and release all held-back text.
+    pub fn finish(&mut self, tail: &str) -> Result<String, GuardBlocked> {
+        let mut out = self.filter_chunk(tail)?;
+        if !self.line_buffer.is_empty() {
+            let rest = std::mem::take(&mut self.line_buffer);
+            out.push_str(&self.filter_sse(&format!("{}\n", rest))?);
+        }
+        if let Some(payload) = self.flush_payload()? {
+            if self.sse_framed {
+                out.push_str(&format!("data: {}\n\n", payload));
+            } else {
+                out.push_str(&payload);
+            }
+        }
+        Ok(out)
+    }
+
+    fn filter_sse(&mut self, text: &str) -> Result<String, GuardBlocked> {
+        self.line_buffer.push_str(text);
+        let mut out = String::new();
+        while let Some(pos) = self.line_buffer.find('\n') {
+            let line: String = self.line_buffer.drain(..=pos).collect();
+            let payload = line
+                .strip_prefix("data:")
+                .map(|rest| rest.trim_start_matches(' ').trim_end_matches(['\r', '\n']));
+            match payload {
+                Some("[DONE]") => {
+                    if let Some(payload) = self.flush_payload()? {
+                        out.push_str(&format!("data: {}\n\n", payload));
+                    }
+                    out.push_str(&line);
+                }
+                Some(payload) => {
+                    out.push_str("data: ");
+                    out.push_str(&self.filter_payload(payload)?);
+                    out.push('\n');
+                }
+                None => out.push_str(&line),
+            }
+        }
+        Ok(out)
+    }
+
+    fn filter_payload(&mut self, data: &str) -> Result<String, GuardBlocked> {
+        let Some(guard) = self.guard.clone() else {
+            return Ok(data.to_string());
+        };
+        let Ok(mut chunk) = serde_json::from_str::<serde_json::Value>(data) else {
+            return Ok(data.to_string());
+        };
+        let Some(choices) = chunk.get_mut("choices").and_then(|c| c.as_array_mut()) else {
+            return Ok(data.to_string());
+        };
+        for choice in choices.iter_mut() {
+            let index = choice.get("index").and_then(|i| i.as_u64()).unwrap_or(0);
+            let finished = choice
+                .get("finish_reason")
+                .is_some_and(|reason| !reason.is_null());
+            let Some(delta) = choice.get_mut("delta").and_then(|d| d.as_object_mut()) else {
+                continue;
+            };
+
+            let content = delta.get("content").and_then(|c| c.as_str()).unwrap_or("");
+            let content = content.to_string();
+            if let Some(released) = self.release_key(&guard, (index, None), &content, finished)? {
+                delta.insert("content".to_string(), serde_json::Value::String(released));
+            }
+
+            let mut seen = Vec::new();
+            if let Some(calls) = delta.get_mut("tool_calls").and_then(|c| c.as_array_mut()) {
+                for call in calls.iter_mut() {
+                    let call_index = call.get("index").and_then(|i| i.as_u64()).unwrap_or(0);
+                    seen.push(call_index);
+                    let Some(arguments) = call.pointer_mut("/function/arguments") else {
+                        continue;
+                    };
+                    let text = arguments.as_str().unwrap_or("").to_string();
+                    let key = (index, Some(call_index));
+                    if let Some(released) = self.release_key(&guard, key, &text, finished)? {
+                        *arguments = serde_json::Value::String(released);
+                    }
+                }
+            }
+            if finished {
+                let rest: Vec<_> = self
+                    .take_pending_calls(&guard, index)?
+                    .into_iter()
+                    .filter(|call| !seen.contains(&call["index"].as_u64().unwrap_or(0)))
+                    .collect();
+                if !rest.is_empty() {
+                    let calls = delta
+                        .entry("tool_calls")
+                        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+                    if let Some(calls) = calls.as_array_mut() {
+                        calls.extend(rest);
+                    }
+                }
+            }
+        }
+        self.template = Some(chunk.clone());
+        Ok(serde_json::to_string(&chunk).unwrap_or_else(|_| data.to_string()))
+    }
+
+    /// Append `text` to the text held back under `key` and release what can
+    /// no longer change. None when nothing is held or added.
+    fn release_key(
+        &mut self,
+        guard: &OutputGuard,
+        key: (u64, Option<u64>),
+        text: &str,
+        finished: bool,
+    ) -> Result<Option<String>, GuardBlocked> {
+        let pending = self.pending.entry(key).or_default();
+        if text.is_empty() && pending.is_empty() {
+            return Ok(None);
+        }
+        pending.push_str(text);
+        release(guard, pending, finished, &mut self.report).map(Some)
+    }
+
+    /// Release all held-back tool-call arguments of a choice as `tool_calls`
+    /// delta entries.
+    fn take_pending_calls(
+        &mut self,
+        guard: &OutputGuard,
+        index: u64,
+    ) -> Result<Vec<serde_json::Value>, GuardBlocked> {
+        let mut keys: Vec<_> = self
+            .pending
+            .keys()
+            .filter(|(choice, call)| *choice == index && call.is_some())
+            .copied()
+            .collect();
+        keys.sort();
+        let mut calls = Vec::new();
+        for key in keys {
+            let Some(mut pending) = self.pending.remove(&key) else {
+                continue;
+            };
+            if pending.is_empty() {
+                continue;
+            }
+            let released = release(guard, &mut pending, true, &mut self.report)?;
+            calls.push(serde_json::json!({
+                "index": key.1,
+                "function": { "arguments": released },
+            }));
+        }
+        Ok(calls)
+    }
+
+    fn flush_payload(&mut self) -> Result<Option<String>, GuardBlocked> {
+        let Some(guard) = self.guard.clone() else {
+            return Ok(None);
+        };
+        let Some(mut chunk) = self.template.take() else {
+            return Ok(None);
+        };
+        let mut indices: Vec<u64> = self.pending.keys().map(|(choice, _)| *choice).collect();
+        indices.sort();
+        indices.dedup();
+        let mut choices = Vec::new();
+        for index in indices {
+            let mut delta = serde_json::Map::new();
+            if let Some(mut pending) = self.pending.remove(&(index, None)) {
+                if !pending.is_empty() {
+                    let released = release(&guard, &mut pending, true, &mut self.report)?;
+                    delta.insert("content".to_string(), serde_json::Value::String(released));
+                }
+            }
+            let calls = self.take_pending_calls(&guard, index)?;
+            if !calls.is_empty() {
+                delta.insert("tool_calls".to_string(), serde_json::Value::Array(calls));
+            }
+            if !delta.is_empty() {
+                choices.push(serde_json::json!({ "index": index, "delta": delta }));
+            }
+        }
+        if choices.is_empty() {
+            return Ok(None);
+        }
+        chunk["choices"] = serde_json::Value::Array(choices);
+        Ok(serde_json::to_string(&chunk).ok())
+    }
+}
+
+/// Scan a choice's pending text and release what can no longer change.
+///
+/// Unless the choice is `finished`, the last `lookbehind` bytes stay pending,
+/// as does any redaction match that reaches the held-back tail (it may still
+/// grow with the next delta).
+fn release(
+    guard: &OutputGuard,
+    pending: &mut String,
+    finished: bool,
+    report: &mut GuardReport,
+) -> Result<String, GuardBlocked> {
+    let (matches, redactions) = guard.scan(pending);
+    for rule in &matches {
+        report.push(&rule.name, rule.action);
+        if rule.action == GuardrailAction::Block {
+            return Err(GuardBlocked {
+                rule: rule.name.clone(),
+            });
+        }
+    }
+
+    let mut boundary = pending.len();
+    if !finished {
+        boundary = floor_char_boundary(pending, pending.len().saturating_sub(guard.lookbehind));
+        for range in &redactions {
+            if range.end == pending.len() || (range.start < boundary && range.end > boundary) {
+                boundary = boundary.min(range.start);
+            }
+        }
+    }
+    let settled: Vec<_> = redactions
+        .into_iter()
+        .filter(|range| range.end <= boundary)
+        .collect();
+    let released = guard.redact(&pending[..boundary], &settled);
+    pending.drain(..boundary);
+    Ok(released)
+}
+
+/// Largest char boundary of `text` not after `index`.
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::guard;
+    use super::*;
+
+    fn chunk(content: &str, finish: Option<&str>) -> String {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": finish}]
+        })
+        .to_string()
+    }
+
+    fn delta(payload: &str) -> String {
+        let chunk: serde_json::Value = serde_json::from_str(payload).unwrap();
+        chunk["choices"][0]["delta"]["content"]
+            .as_str()
+            .unwrap_or("")
+            .to_string()
+    }
+
+    fn call_chunk(arguments: &str, finish: Option<&str>) -> String {
+        serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "delta": {"tool_calls": [{"index": 0, "function": {"arguments": arguments}}]},
+                "finish_reason": finish
+            }]
+        })
+        .to_string()
+    }
+
+    fn call_arguments(payload: &str) -> String {
+        let chunk: serde_json::Value = serde_json::from_str(payload).unwrap();
+        chunk["choices"][0]["delta"]["tool_calls"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|call| call["function"]["arguments"].as_str())
+            .collect()
+    }
+
+    fn key_guard() -> StreamGuard {
+        Arc::new(guard(vec![
+            ("api_key", r"sk-[a-z0-9]{8,}", GuardrailAction::Redact),
+            ("secret", r"TOP SECRET", GuardrailAction::Block),
+        ]))
+        .stream_guard()
+    }
+
+    #[test]
+    fn disabled_guard_passes_through() {
+        let mut stream = StreamGuard::default();
+        let data = chunk("sk-abcdefgh1234", None);
+        assert_eq!(stream.filter_chunk(&data).unwrap(), data);
+        assert_eq!(stream.finish("").unwrap(), "");
+    }
+
+    #[test]
+    fn redacts_match_split_across_deltas() {
+        let mut stream = key_guard();
+        let mut text = String::new();
+        for (content, finish) in [
+            ("Your key is sk-abc", None),
+            ("def1234 and", None),
+            (" more text.", Some("stop")),
+        ] {
+            text.push_str(&delta(
+                &stream.filter_chunk(&chunk(content, finish)).unwrap(),
+            ));
+        }
+        assert_eq!(text, "Your key is [REDACTED] and more text.");
+        assert_eq!(stream.report.triggered[0].rule, "api_key");
+    }
+
+    #[test]
+    fn held_back_text_is_released_at_end_of_stream() {
+        let mut stream = key_guard();
+        let first = delta(&stream.filter_chunk(&chunk("short", None)).unwrap());
+        assert_eq!(first, "");
+        let rest = stream.finish("").unwrap();
+        assert_eq!(delta(&rest), "short");
+    }
+
+    #[test]
+    fn blocks_match_split_across_deltas() {
+        let mut stream = key_guard();
+        assert!(stream.filter_chunk(&chunk("This is TOP ", None)).is_ok());
+        let blocked = stream.filter_chunk(&chunk("SECRET stuff", None));
+        assert_eq!(
+            blocked,
+            Err(GuardBlocked {
+                rule: "secret".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn filters_raw_sse_text_split_mid_line() {
+        let mut stream = key_guard();
+        let raw = format!(
+            "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+            chunk("key sk-abcdefgh12", None),
+            chunk("34 done", Some("stop"))
+        );
+        let (head, tail) = raw.split_at(30);
+        let mut out = stream.filter_chunk(head).unwrap();
+        out.push_str(&stream.filter_chunk(tail).unwrap());
+        out.push_str(&stream.finish("").unwrap());
+
+        let text: String = out
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter(|payload| *payload != "[DONE]")
+            .map(delta)
+            .collect();
+        assert_eq!(text, "key [REDACTED] done");
+        assert!(out.ends_with("data: [DONE]\n\n"));
+    }
+
+    #[test]
+    fn redacts_tool_call_arguments_split_across_deltas() {
+        let mut stream = key_guard();
+        let mut arguments = String::new();
+        for (part, finish) in [
+            (r#"{"key": "sk-abc"#, None),
+            (r#"def1234"}"#, None),
+            ("", Some("tool_calls")),
+        ] {
+            arguments.push_str(&call_arguments(
+                &stream.filter_chunk(&call_chunk(part, finish)).unwrap(),
+            ));
+        }
+        assert_eq!(arguments, r#"{"key": "[REDACTED]"}"#);
+    }
+
+    #[test]
+    fn held_back_tool_call_arguments_are_released_at_end_of_stream() {
+        let mut stream = key_guard();
+        let first = stream
+            .filter_chunk(&call_chunk(r#"{"a": 1}"#, None))
+            .unwrap();
+        assert_eq!(call_arguments(&first), "");
+        assert_eq!(call_arguments(&stream.finish("").unwrap()), r#"{"a": 1}"#);
+    }
+
+    #[test]
+    fn blocks_tool_call_arguments() {
+        let mut stream = key_guard();
+        assert!(stream
+            .filter_chunk(&call_chunk(r#"{"q": "TOP "#, None))
+            .is_ok());
+        assert_eq!(
+            stream.filter_chunk(&call_chunk(r#"SECRET"}"#, None)),
+            Err(GuardBlocked {
+                rule: "secret".to_string()
+            })
+        );
+    }
+}
@@ -5,7 +5,7 @@
 
 mod config;
 mod error;
-mod parser;
+pub(crate) mod parser;
 mod state;
 
 #[cfg(test)]
@@ -95,6 +95,8 @@ impl HealthChecker {
         match backend_type {
             BackendType::Ollama => "/api/tags",
             BackendType::LlamaCpp => "/health",
+            // /health has no body; /info answers once the model is loaded
+            BackendType::TGI | BackendType::TEI => "/info",
             BackendType::VLLM
             | BackendType::Exo
             | BackendType::OpenAI
@@ -102,6 +104,11 @@ impl HealthChecker {
             | BackendType::Generic => "/v1/models",
             // Cloud backends (Phase 6) - use OpenAI-compatible endpoint
             BackendType::Anthropic | BackendType::Google => "/v1/models",
+            // Azure OpenAI; the agent adds the required api-version parameter
+            BackendType::AzureOpenAI => "/openai/models",
+            // Bedrock requests must be signed, so this is only a reachability probe
+            BackendType::Bedrock => "/foundation-models",
+            BackendType::Cohere => "/v1/models",
         }
     }
 
@@ -265,6 +272,27 @@ impl HealthChecker {
                     }
                 }
             },
+            BackendType::TGI | BackendType::TEI | BackendType::Cohere => {
+                let parsed = match backend.backend_type {
+                    BackendType::TGI => parser::parse_tgi_info_response(body),
+                    BackendType::TEI => parser::parse_tei_info_response(body),
+                    _ => parser::parse_cohere_models_response(body),
+                };
+                match parsed {
+                    Ok(models) => HealthCheckResult::Success { latency_ms, models },
+                    Err(error) => {
+                        tracing::warn!(
+                            backend_type = ?backend.backend_type,
+                            error = %error,
+                            "Backend returned 200 but invalid JSON, treating as healthy"
+                        );
+                        HealthCheckResult::SuccessWithParseError {
+                            latency_ms,
+                            parse_error: error.to_string(),
+                        }
+                    }
+                }
+            }
             BackendType::LlamaCpp => {
                 match parser::parse_llamacpp_response(body) {
                     Ok(healthy) if healthy => HealthCheckResult::Success {
@@ -293,7 +321,9 @@ impl HealthChecker {
             | BackendType::LMStudio
             | BackendType::Generic
             | BackendType::Anthropic
-            | BackendType::Google => match parser::parse_openai_response(body) {
+            | BackendType::Google
+            | BackendType::AzureOpenAI
+            | BackendType::Bedrock => match parser::parse_openai_response(body) {
                 Ok(models) => HealthCheckResult::Success { latency_ms, models },
                 Err(error) => {
                     tracing::warn!(
@@ -412,6 +442,9 @@ impl HealthChecker {
         for (id, backend) in backends {
             let result = self.check_backend(&backend).await;
             self.apply_result(&id, result.clone());
+            if !matches!(result, HealthCheckResult::Failure { .. }) {
+                self.update_resource_usage(&id).await;
+            }
             results.push((id, result));
         }
 
@@ -427,6 +460,28 @@ impl HealthChecker {
         results
     }
 
+    /// Record the queue depth reported by agents with resource monitoring, so
+    /// scheduling sees load from clients other than Nexus.
+    pub async fn update_resource_usage(&self, backend_id: &str) {
+        let Some(agent) = self.registry.get_agent(backend_id) else {
+            return;
+        };
+        if !agent.profile().capabilities.resource_monitoring {
+            return;
+        }
+
+        let usage = agent.resource_usage().await;
+        // Unreported depth resets to 0 rather than leaving a stale value
+        let depth = usage.pending_requests.unwrap_or(0);
+        let _ = self.registry.update_reported_queue_depth(backend_id, depth);
+        metrics::gauge!("nexus_backend_queue_depth", "backend" => backend_id.to_string())
+            .set(depth as f64);
+        if let Some(kv_cache_usage) = usage.kv_cache_usage {
+            metrics::gauge!("nexus_backend_kv_cache_usage", "backend" => backend_id.to_string())
+                .set(kv_cache_usage);
+        }
+    }
+
     /// Check for timed-out lifecycle operations and mark them as failed (T037).
     ///
     /// This should be called periodically during health checks to detect operations
@@ -1,6 +1,7 @@
 //! Response parsing for different backend types.
 
 use super::error::HealthCheckError;
+use crate::agent::embeddings::is_embedding_model;
 use crate::registry::Model;
 use serde::Deserialize;
 use std::time::Duration;
@@ -41,6 +42,7 @@ pub fn parse_ollama_response(body: &str) -> Result<Vec<Model>, HealthCheckError>
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         })
         .collect())
@@ -67,6 +69,8 @@ pub async fn enrich_ollama_models(
                     if let Ok(show) = serde_json::from_str::<OllamaShowResponse>(&text) {
                         model.supports_vision = show.capabilities.iter().any(|c| c == "vision");
                         model.supports_tools = show.capabilities.iter().any(|c| c == "tools");
+                        model.supports_embeddings =
+                            show.capabilities.iter().any(|c| c == "embedding");
 
                         // Extract context_length from model_info
                         // Keys vary by architecture: llama.context_length, etc.
@@ -121,6 +125,9 @@ pub fn apply_name_heuristics(model: &mut Model) {
         || name.contains("qwen3")
         || name.contains("command-r")
         || name.contains("firefunction");
+
+    // Embedding model families
+    model.supports_embeddings = model.supports_embeddings || is_embedding_model(&name);
 }
 
 /// OpenAI /v1/models response format
@@ -152,6 +159,7 @@ pub fn parse_openai_response(body: &str) -> Result<Vec<Model>, HealthCheckError>
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             };
             apply_name_heuristics(&mut model);
@@ -160,6 +168,112 @@ pub fn parse_openai_response(body: &str) -> Result<Vec<Model>, HealthCheckError>
         .collect())
 }
 
+/// TGI /info response format
+#[derive(Deserialize)]
+struct TgiInfoResponse {
+    model_id: String,
+    /// Named `max_input_length` before TGI 2.0
+    #[serde(default, alias = "max_input_length")]
+    max_input_tokens: Option<u32>,
+}
+
+/// Parse TGI /info response into the single model the server runs.
+///
+/// TGI constrains generation with grammars, so every model accepts tools and
+/// JSON mode. The context length is the server's `max_input_tokens` limit.
+pub fn parse_tgi_info_response(body: &str) -> Result<Vec<Model>, HealthCheckError> {
+    let info: TgiInfoResponse =
+        serde_json::from_str(body).map_err(|e| HealthCheckError::ParseError(e.to_string()))?;
+
+    let mut model = Model {
+        id: info.model_id.clone(),
+        name: info.model_id,
+        context_length: info.max_input_tokens.unwrap_or(4096),
+        supports_vision: false,
+        supports_tools: true,
+        supports_json_mode: true,
+        supports_embeddings: false,
+        max_output_tokens: None,
+    };
+    apply_name_heuristics(&mut model);
+    Ok(vec![model])
+}
+
+/// TEI /info response format
+#[derive(Deserialize)]
+struct TeiInfoResponse {
+    model_id: String,
+    /// `{"embedding": {...}}`, `{"reranker": {...}}` or `{"classifier": {...}}`
+    #[serde(default)]
+    model_type: serde_json::Value,
+    #[serde(default)]
+    max_input_length: Option<u32>,
+}
+
+/// Parse TEI /info response into the single model the server runs.
+///
+/// Only embedding models are marked embedding-capable; rerankers and
+/// classifiers serve `/rerank` and `/predict` instead.
+pub fn parse_tei_info_response(body: &str) -> Result<Vec<Model>, HealthCheckError> {
+    let info: TeiInfoResponse =
+        serde_json::from_str(body).map_err(|e| HealthCheckError::ParseError(e.to_string()))?;
+
+    Ok(vec![Model {
+        id: info.model_id.clone(),
+        name: info.model_id,
+        context_length: info.max_input_length.unwrap_or(512),
+        supports_vision: false,
+        supports_tools: false,
+        supports_json_mode: false,
+        supports_embeddings: info.model_type.get("embedding").is_some(),
+        max_output_tokens: None,
+    }])
+}
+
+/// Cohere /v1/models response format
+#[derive(Deserialize)]
+struct CohereModelsResponse {
+    models: Vec<CohereModel>,
+}
+
+#[derive(Deserialize)]
+struct CohereModel {
+    name: String,
+    /// API endpoints the model serves: "chat", "embed", "rerank", ...
+    #[serde(default)]
+    endpoints: Option<Vec<String>>,
+    /// "tools", "json_mode", "vision", ...; null for some models
+    #[serde(default)]
+    features: Option<Vec<String>>,
+    #[serde(default)]
+    context_length: Option<f64>,
+}
+
+/// Parse Cohere /v1/models response, taking capabilities from each model's
+/// endpoints and features instead of name heuristics.
+pub fn parse_cohere_models_response(body: &str) -> Result<Vec<Model>, HealthCheckError> {
+    let response: CohereModelsResponse =
+        serde_json::from_str(body).map_err(|e| HealthCheckError::ParseError(e.to_string()))?;
+
+    Ok(response
+        .models
+        .into_iter()
+        .map(|m| {
+            let feature = |name: &str| m.features.iter().flatten().any(|f| f == name);
+            Model {
+                id: m.name.clone(),
+                name: m.name.clone(),
+                context_length: m.context_length.map(|c| c as u32).unwrap_or(4096),
+                supports_vision: feature("vision"),
+                supports_tools: feature("tools"),
+                supports_json_mode: feature("json_mode"),
+                supports_embeddings: m.endpoints.iter().flatten().any(|e| e == "embed"),
+                max_output_tokens: None,
+            }
+        })
+        .collect())
+}
+
 /// LlamaCpp /health response format
 #[derive(Deserialize)]
 struct LlamaCppHealthResponse {
@@ -252,6 +366,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -267,6 +382,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -282,6 +398,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -297,6 +414,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -313,6 +431,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -328,6 +447,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -344,6 +464,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -359,6 +480,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -374,6 +496,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -389,6 +512,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -404,6 +528,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -419,6 +544,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -434,6 +560,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -474,6 +601,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }];
 
@@ -503,6 +631,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }];
 
@@ -531,6 +660,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }];
 
@@ -561,6 +691,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }];
 
@@ -581,6 +712,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
@@ -596,9 +728,67 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         apply_name_heuristics(&mut model);
         assert!(model.supports_tools);
     }
+
+    #[test]
+    fn test_parse_tgi_info_response() {
+        let body = r#"{"model_id":"meta-llama/Llama-3.1-8B-Instruct","model_sha":"0e9e39f","model_pipeline_tag":"text-generation","max_concurrent_requests":128,"max_input_tokens":8191,"max_total_tokens":8192,"router":"text-generation-router","version":"2.4.0"}"#;
+        let models = parse_tgi_info_response(body).unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "meta-llama/Llama-3.1-8B-Instruct");
+        assert_eq!(models[0].context_length, 8191);
+        assert!(models[0].supports_tools);
+        assert!(models[0].supports_json_mode);
+    }
+
+    #[test]
+    fn test_parse_tgi_info_response_legacy_field() {
+        let body =
+            r#"{"model_id":"bigcode/starcoder","max_input_length":1024,"max_total_tokens":2048}"#;
+        let models = parse_tgi_info_response(body).unwrap();
+        assert_eq!(models[0].context_length, 1024);
+    }
+
+    #[test]
+    fn test_parse_tei_info_response_embedding() {
+        let body = r#"{"model_id":"BAAI/bge-large-en-v1.5","model_sha":null,"model_dtype":"float16","model_type":{"embedding":{"pooling":"cls"}},"max_concurrent_requests":512,"max_input_length":512,"max_batch_tokens":16384,"version":"1.5.0"}"#;
+        let models = parse_tei_info_response(body).unwrap();
+        assert_eq!(models[0].id, "BAAI/bge-large-en-v1.5");
+        assert_eq!(models[0].context_length, 512);
+        assert!(models[0].supports_embeddings);
+    }
+
+    #[test]
+    fn test_parse_tei_info_response_reranker() {
+        let body = r#"{"model_id":"BAAI/bge-reranker-large","model_type":{"reranker":{"id2label":{"0":"LABEL_0"},"label2id":{"LABEL_0":0}}},"max_input_length":512}"#;
+        let models = parse_tei_info_response(body).unwrap();
+        assert!(!models[0].supports_embeddings);
+    }
+
+    #[test]
+    fn test_parse_tgi_info_response_invalid_json() {
+        assert!(parse_tgi_info_response("not json").is_err());
+        assert!(parse_tei_info_response("{}").is_err());
+    }
+
+    #[test]
+    fn test_parse_cohere_models_response() {
+        let body = r#"{"models":[
+            {"name":"command-r-plus","endpoints":["generate","chat","summarize"],"finetuned":false,"context_length":128000,"features":["tools","json_mode","json_schema"]},
+            {"name":"embed-english-v3.0","endpoints":["embed","classify"],"context_length":512,"features":null},
+            {"name":"rerank-v3.5","endpoints":["rerank"],"context_length":4096}
+        ],"next_page_token":null}"#;
+        let models = parse_cohere_models_response(body).unwrap();
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[0].context_length, 128000);
+        assert!(models[0].supports_tools && models[0].supports_json_mode);
+        assert!(!models[0].supports_embeddings);
+        assert!(models[1].supports_embeddings);
+        assert!(!models[2].supports_embeddings);
+    }
 }
@@ -259,6 +259,7 @@ fn test_heuristics_vision_models() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         parser::apply_name_heuristics(&mut model);
@@ -287,6 +288,7 @@ fn test_heuristics_tool_models() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         parser::apply_name_heuristics(&mut model);
@@ -305,6 +307,7 @@ fn test_heuristics_no_false_positives() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         parser::apply_name_heuristics(&mut model);
@@ -325,6 +328,7 @@ fn test_heuristics_preserve_existing_true() {
         supports_vision: true,
         supports_tools: true,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     };
     parser::apply_name_heuristics(&mut model);
@@ -558,6 +562,14 @@ fn test_endpoint_selection_lmstudio() {
     assert_eq!(endpoint, "/v1/models");
 }
 
+#[test]
+fn test_endpoint_selection_tgi_tei() {
+    for backend_type in [BackendType::TGI, BackendType::TEI] {
+        let endpoint = crate::health::HealthChecker::get_health_endpoint(backend_type);
+        assert_eq!(endpoint, "/info");
+    }
+}
+
 #[test]
 fn test_endpoint_selection_generic() {
     let endpoint = crate::health::HealthChecker::get_health_endpoint(BackendType::Generic);
@@ -826,6 +838,7 @@ async fn test_check_backend_agent_healthy_with_models() {
                 supports_vision: false,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
                 capability_tier: None,
             },
@@ -836,6 +849,7 @@ async fn test_check_backend_agent_healthy_with_models() {
                 supports_vision: true,
                 supports_tools: true,
                 supports_json_mode: true,
+                supports_embeddings: false,
                 max_output_tokens: Some(4096),
                 capability_tier: None,
             },
@@ -1064,6 +1078,7 @@ async fn test_apply_result_success_updates_registry() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }];
 
@@ -1127,6 +1142,7 @@ async fn test_apply_result_parse_error_preserves_models() {
         supports_vision: false,
         supports_tools: false,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }];
     checker.apply_result(
@@ -1171,6 +1187,7 @@ async fn test_check_all_backends_with_agents() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
             capability_tier: None,
         }]),
@@ -1358,6 +1375,7 @@ async fn test_check_backend_agent_returns_models() {
                 privacy_zone: PrivacyZone::Restricted,
                 capabilities: AgentCapabilities {
                     embeddings: false,
+                    rerank: false,
                     model_lifecycle: false,
                     token_counting: false,
                     resource_monitoring: false,
@@ -1376,6 +1394,7 @@ async fn test_check_backend_agent_returns_models() {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
                 capability_tier: None,
             }])
@@ -1458,6 +1477,7 @@ async fn test_check_backend_agent_loading_status() {
                 privacy_zone: PrivacyZone::Restricted,
                 capabilities: AgentCapabilities {
                     embeddings: false,
+                    rerank: false,
                     model_lifecycle: false,
                     token_counting: false,
                     resource_monitoring: false,
@@ -1552,6 +1572,7 @@ async fn test_check_backend_agent_draining_status() {
                 privacy_zone: PrivacyZone::Restricted,
                 capabilities: AgentCapabilities {
                     embeddings: false,
+                    rerank: false,
                     model_lifecycle: false,
                     token_counting: false,
                     resource_monitoring: false,
@@ -1642,6 +1663,7 @@ async fn test_check_backend_agent_unhealthy_status() {
                 privacy_zone: PrivacyZone::Restricted,
                 capabilities: AgentCapabilities {
                     embeddings: false,
+                    rerank: false,
                     model_lifecycle: false,
                     token_counting: false,
                     resource_monitoring: false,
@@ -1732,6 +1754,7 @@ async fn test_check_backend_agent_list_models_error() {
                 privacy_zone: PrivacyZone::Restricted,
                 capabilities: AgentCapabilities {
                     embeddings: false,
+                    rerank: false,
                     model_lifecycle: false,
                     token_counting: false,
                     resource_monitoring: false,
@@ -1822,6 +1845,7 @@ async fn test_check_backend_agent_health_check_error() {
                 privacy_zone: PrivacyZone::Restricted,
                 capabilities: AgentCapabilities {
                     embeddings: false,
+                    rerank: false,
                     model_lifecycle: false,
                     token_counting: false,
                     resource_monitoring: false,
@@ -1953,6 +1977,69 @@ async fn test_parse_and_enrich_llamacpp_invalid_json() {
     ));
 }
 
+#[tokio::test]
+async fn test_parse_and_enrich_tgi_info() {
+    let registry = Arc::new(Registry::new());
+    let checker = HealthChecker::new(registry, HealthCheckConfig::default());
+
+    let backend = Backend::new(
+        "b-tgi".to_string(),
+        "TGI".to_string(),
+        "http://localhost:8080".to_string(),
+        BackendType::TGI,
+        vec![],
+        crate::registry::DiscoverySource::Static,
+        std::collections::HashMap::new(),
+    );
+
+    let result = checker
+        .parse_and_enrich(
+            &backend,
+            r#"{"model_id":"mistralai/Mistral-7B-Instruct-v0.3","max_input_tokens":32767,"max_total_tokens":32768}"#,
+            10,
+        )
+        .await;
+    match result {
+        HealthCheckResult::Success { models, .. } => {
+            assert_eq!(models.len(), 1);
+            assert_eq!(models[0].id, "mistralai/Mistral-7B-Instruct-v0.3");
+            assert_eq!(models[0].context_length, 32767);
+        }
+        other => panic!("Expected Success, got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_parse_and_enrich_tei_info() {
+    let registry = Arc::new(Registry::new());
+    let checker = HealthChecker::new(registry, HealthCheckConfig::default());
+
+    let backend = Backend::new(
+        "b-tei".to_string(),
+        "TEI".to_string(),
+        "http://localhost:8081".to_string(),
+        BackendType::TEI,
+        vec![],
+        crate::registry::DiscoverySource::Static,
+        std::collections::HashMap::new(),
+    );
+
+    let result = checker
+        .parse_and_enrich(
+            &backend,
+            r#"{"model_id":"BAAI/bge-small-en-v1.5","model_type":{"embedding":{"pooling":"cls"}},"max_input_length":512}"#,
+            10,
+        )
+        .await;
+    match result {
+        HealthCheckResult::Success { models, .. } => {
+            assert!(models[0].supports_embeddings);
+            assert_eq!(models[0].context_length, 512);
+        }
+        other => panic!("Expected Success, got: {:?}", other),
+    }
+}
+
 #[tokio::test]
 async fn test_parse_and_enrich_vllm() {
     let registry = Arc::new(Registry::new());
@@ -2020,6 +2107,7 @@ fn test_broadcast_model_change_with_sender() {
         supports_vision: true,
         supports_tools: false,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }];
 
@@ -2042,6 +2130,7 @@ fn test_broadcast_model_change_without_sender() {
         supports_vision: false,
         supports_tools: false,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }];
 
@@ -2082,6 +2171,7 @@ async fn test_apply_result_failure_broadcasts_empty_models() {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
         },
@@ -2128,6 +2218,7 @@ async fn test_apply_result_success_with_parse_error_preserves_models() {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
         },
@@ -2365,6 +2456,7 @@ async fn test_broadcast_model_change_with_models() {
         supports_vision: false,
         supports_tools: false,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }];
 
@@ -2446,6 +2538,7 @@ fn test_apply_result_failure_broadcasts_empty_model_list() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     };
@@ -5,13 +5,18 @@
 
 pub mod agent;
 pub mod api;
+pub mod cache;
 pub mod cli;
 pub mod config;
 pub mod dashboard;
 pub mod discovery;
+pub mod guardrails;
 pub mod health;
 pub mod logging;
 pub mod metrics;
+pub mod pii;
 pub mod queue;
 pub mod registry;
 pub mod routing;
+pub mod structured;
+pub mod tools;
@@ -22,6 +22,7 @@ use crate::api::{ApiError, ChatCompletionResponse};
 /// #   choices: vec![],
 /// #   usage: None,
 /// #   extra: std::collections::HashMap::new(),
+/// #   timings: None,
 /// };
 /// let (prompt, completion, total) = extract_tokens(&response);
 /// ```
@@ -95,7 +96,7 @@ pub fn extract_status(
 ///         content: MessageContent::Text {
 ///             content: "Hello, world!".to_string(),
 ///         },
-///         name: None, function_call: None,
+///         name: None, function_call: None, tool_calls: None, tool_call_id: None,
 ///     }],
 ///     stream: false,
 ///     temperature: None,
@@ -181,6 +182,7 @@ mod tests {
                 total_tokens: 150,
             }),
             extra: std::collections::HashMap::new(),
+            timings: None,
         };
 
         let (prompt, completion, total) = extract_tokens(&response);
@@ -221,6 +223,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -246,6 +250,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -271,6 +277,8 @@ mod tests {
                 content: crate::api::MessageContent::Text { content: long_text },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -316,6 +324,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -354,6 +364,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -386,6 +398,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -410,6 +424,7 @@ mod tests {
             choices: vec![],
             usage: None,
             extra: std::collections::HashMap::new(),
+            timings: None,
         };
 
         let (prompt, completion, total) = extract_tokens(&response);
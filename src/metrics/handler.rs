@@ -39,7 +39,8 @@ pub async fn stats_handler(State(state): State<Arc<AppState>>) -> impl IntoRespo
     // Compute stats from Registry atomics
     let uptime_seconds = state.metrics_collector.uptime_seconds();
     let quality_store = state.router.quality_store();
-    let backends = compute_backend_stats(registry, Some(quality_store));
+    let circuit_breakers = state.router.circuit_breakers();
+    let backends = compute_backend_stats(registry, Some(quality_store), Some(circuit_breakers));
     let requests = compute_request_stats(&backends);
     let models = compute_model_stats(registry);
     let budget = compute_budget_stats(&state);
@@ -70,6 +71,7 @@ pub fn compute_request_stats(backends: &[BackendStats]) -> RequestStats {
 pub fn compute_backend_stats(
     registry: &crate::registry::Registry,
     quality_store: Option<&crate::agent::quality::QualityMetricsStore>,
+    circuit_breakers: Option<&crate::agent::circuit_breaker::CircuitBreakerStore>,
 ) -> Vec<BackendStats> {
     // Get all backends from registry
     let backends = registry.get_all_backends();
@@ -110,6 +112,7 @@ pub fn compute_backend_stats(
                 error_rate_1h,
                 avg_ttft_ms,
                 success_rate_24h,
+                circuit: circuit_breakers.map(|store| store.snapshot(&backend.id)),
             }
         })
         .collect()
@@ -227,6 +230,7 @@ mod tests {
                 error_rate_1h: None,
                 avg_ttft_ms: None,
                 success_rate_24h: None,
+                circuit: None,
             },
             BackendStats {
                 id: "b2".to_string(),
@@ -237,6 +241,7 @@ mod tests {
                 error_rate_1h: None,
                 avg_ttft_ms: None,
                 success_rate_24h: None,
+                circuit: None,
             },
         ];
         let stats = compute_request_stats(&backends);
@@ -246,7 +251,7 @@ mod tests {
     #[test]
     fn test_compute_backend_stats_empty() {
         let registry = crate::registry::Registry::new();
-        let stats = compute_backend_stats(&registry, None);
+        let stats = compute_backend_stats(&registry, None, None);
         assert_eq!(stats.len(), 0);
     }
 
@@ -265,6 +270,7 @@ mod tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }
     }
@@ -356,7 +362,7 @@ mod tests {
             .store(3, std::sync::atomic::Ordering::SeqCst);
         registry.add_backend(backend).unwrap();
 
-        let stats = compute_backend_stats(&registry, None);
+        let stats = compute_backend_stats(&registry, None, None);
         assert_eq!(stats.len(), 1);
         assert_eq!(stats[0].id, "b1");
         assert_eq!(stats[0].name, "backend-1");
@@ -376,6 +382,7 @@ mod tests {
             error_rate_1h: None,
             avg_ttft_ms: None,
             success_rate_24h: None,
+            circuit: None,
         }];
         let stats = compute_request_stats(&backends);
         assert_eq!(stats.total, 100);
@@ -395,6 +402,7 @@ mod tests {
                 error_rate_1h: None,
                 avg_ttft_ms: None,
                 success_rate_24h: None,
+                circuit: None,
             },
             BackendStats {
                 id: "b2".to_string(),
@@ -405,6 +413,7 @@ mod tests {
                 error_rate_1h: None,
                 avg_ttft_ms: None,
                 success_rate_24h: None,
+                circuit: None,
             },
             BackendStats {
                 id: "b3".to_string(),
@@ -415,6 +424,7 @@ mod tests {
                 error_rate_1h: None,
                 avg_ttft_ms: None,
                 success_rate_24h: None,
+                circuit: None,
             },
         ];
         let stats = compute_request_stats(&backends);
@@ -27,6 +27,8 @@
 //! - `nexus_backends_healthy` - Healthy backends count
 //! - `nexus_models_available` - Unique models available
 //! - `nexus_pending_requests{backend}` - Pending requests per backend
+//! - `nexus_backend_queue_depth{backend}` - Running + waiting requests reported by vLLM/llama.cpp
+//! - `nexus_backend_kv_cache_usage{backend}` - KV cache usage ratio reported by vLLM/llama.cpp
 //!
 //! **Additional Counters:**
 //! - `nexus_reconciler_exclusions_total{reconciler}` - Agents excluded per reconciler
@@ -174,6 +176,9 @@ pub fn setup_metrics(
     // TTFT buckets for agent quality tracking
     let ttft_buckets = &[0.05, 0.1, 0.5, 1.0, 5.0];
 
+    // Output tokens per second for backends that report generation timings
+    let throughput_buckets = &[1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0];
+
     let handle = PrometheusBuilder::new()
         .set_buckets_for_metric(
             Matcher::Full("nexus_request_duration_seconds".to_string()),
@@ -199,6 +204,10 @@ pub fn setup_metrics(
             Matcher::Full("nexus_agent_ttft_seconds".to_string()),
             ttft_buckets,
         )?
+        .set_buckets_for_metric(
+            Matcher::Full("nexus_generation_tokens_per_second".to_string()),
+            throughput_buckets,
+        )?
         .install_recorder()?;
 
     Ok(handle)
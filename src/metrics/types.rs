@@ -2,6 +2,7 @@
 //!
 //! Data structures for JSON stats API responses.
 
+use crate::agent::circuit_breaker::CircuitSnapshot;
 use serde::Serialize;
 
 /// JSON response for GET /v1/stats endpoint.
@@ -53,6 +54,9 @@ pub struct BackendStats {
     /// Success rate over last 24 hours (0.0–1.0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub success_rate_24h: Option<f32>,
+    /// Circuit breaker state for this backend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit: Option<CircuitSnapshot>,
 }
 
 /// Per-model statistics.
@@ -119,6 +123,7 @@ mod tests {
                 error_rate_1h: None,
                 avg_ttft_ms: None,
                 success_rate_24h: None,
+                circuit: None,
             }],
             models: vec![ModelStats {
                 name: "llama3:70b".to_string(),
@@ -0,0 +1,613 @@
+//! Reversible PII redaction for cloud-bound requests
+//!
+//! Detected entities are replaced with stable placeholders (`[EMAIL_1]`) before
+//! dispatch and restored in the response. Message text and tool-call arguments
+//! are both covered. The mapping lives in a [`Redaction`]
+//! owned by the request and is dropped with it.
+
+use super::PiiScanner;
+use crate::api::{ChatCompletionRequest, ChatCompletionResponse, MessageContent};
+use crate::registry::BackendType;
+use std::collections::HashMap;
+
+/// Whether requests dispatched to this backend type are redacted.
+///
+/// Only the hosted cloud agents (OpenAI, Azure OpenAI, AWS Bedrock, Anthropic,
+/// Google AI, Cohere) qualify.
+pub fn supports_redaction(backend_type: BackendType) -> bool {
+    matches!(
+        backend_type,
+        BackendType::OpenAI
+            | BackendType::AzureOpenAI
+            | BackendType::Bedrock
+            | BackendType::Anthropic
+            | BackendType::Google
+            | BackendType::Cohere
+    )
+}
+
+/// Placeholder ↔ original mapping for a single request.
+#[derive(Debug, Default)]
+pub struct Redaction {
+    /// (placeholder, original) pairs in assignment order
+    entries: Vec<(String, String)>,
+}
+
+impl Redaction {
+    /// True when nothing was redacted.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of distinct redacted entities.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn placeholder_for(&mut self, detector: &str, original: &str) -> String {
+        if let Some((placeholder, _)) = self.entries.iter().find(|(_, o)| o == original) {
+            return placeholder.clone();
+        }
+        let label = detector.to_ascii_uppercase();
+        let prefix = format!("[{}_", label);
+        let n = self
+            .entries
+            .iter()
+            .filter(|(p, _)| p.starts_with(&prefix))
+            .count()
+            + 1;
+        let placeholder = format!("{}{}]", prefix, n);
+        self.entries
+            .push((placeholder.clone(), original.to_string()));
+        placeholder
+    }
+
+    /// Replace every placeholder in `text` with its original value.
+    pub fn restore(&self, text: &str) -> String {
+        let mut restored = text.to_string();
+        for (placeholder, original) in &self.entries {
+            if restored.contains(placeholder.as_str()) {
+                restored = restored.replace(placeholder.as_str(), original);
+            }
+        }
+        restored
+    }
+
+    /// Restore placeholders in every choice of a non-streaming response,
+    /// including tool-call arguments.
+    pub fn restore_response(&self, response: &mut ChatCompletionResponse) {
+        if self.is_empty() {
+            return;
+        }
+        for choice in &mut response.choices {
+            restore_content(self, &mut choice.message.content);
+            for arguments in choice.message.call_arguments_mut() {
+                *arguments = self.restore(arguments);
+            }
+        }
+    }
+
+    /// Create a restorer for the SSE chunks of a streaming response.
+    pub fn chunk_restorer(&self) -> ChunkRestorer<'_> {
+        ChunkRestorer {
+            redaction: self,
+            pending: HashMap::new(),
+            template: None,
+            sse_framed: false,
+            line_buffer: String::new(),
+        }
+    }
+
+    /// Length of the longest suffix of `text` that could still grow into a placeholder.
+    fn pending_suffix_len(&self, text: &str) -> usize {
+        let Some(start) = text.rfind('[') else {
+            return 0;
+        };
+        let tail = &text[start..];
+        let is_partial = self.entries.iter().any(|(placeholder, _)| {
+            placeholder.len() > tail.len() && placeholder.starts_with(tail)
+        });
+        if is_partial {
+            tail.len()
+        } else {
+            0
+        }
+    }
+}
+
+fn restore_content(redaction: &Redaction, content: &mut MessageContent) {
+    match content {
+        MessageContent::Text { content } => *content = redaction.restore(content),
+        MessageContent::Parts { content } => {
+            for part in content.iter_mut() {
+                if let Some(text) = &mut part.text {
+                    *text = redaction.restore(text);
+                }
+            }
+        }
+    }
+}
+
+impl PiiScanner {
+    /// Replace detected entities in message text and tool-call arguments with
+    /// stable placeholders.
+    ///
+    /// The same value always maps to the same placeholder within a request.
+    pub fn redact_request(&self, request: &mut ChatCompletionRequest) -> Redaction {
+        let mut redaction = Redaction::default();
+        for message in &mut request.messages {
+            for arguments in message.call_arguments_mut() {
+                *arguments = self.redact_text(arguments, &mut redaction);
+            }
+            match &mut message.content {
+                MessageContent::Text { content } => {
+                    *content = self.redact_text(content, &mut redaction);
+                }
+                MessageContent::Parts { content } => {
+                    for part in content.iter_mut() {
+                        if let Some(text) = &mut part.text {
+                            *text = self.redact_text(text, &mut redaction);
+                        }
+                    }
+                }
+            }
+        }
+        redaction
+    }
+
+    fn redact_text(&self, text: &str, redaction: &mut Redaction) -> String {
+        let matches = self.find_all(text);
+        if matches.is_empty() {
+            return text.to_string();
+        }
+        let mut redacted = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for m in matches {
+            redacted.push_str(&text[cursor..m.range.start]);
+            redacted.push_str(&redaction.placeholder_for(&m.detector, &text[m.range.clone()]));
+            metrics::counter!("nexus_pii_redactions_total", "detector" => m.detector).increment(1);
+            cursor = m.range.end;
+        }
+        redacted.push_str(&text[cursor..]);
+        redacted
+    }
+}
+
+/// Restores placeholders in streamed `chat.completion.chunk` payloads.
+///
+/// Accepts either bare JSON payloads or raw SSE text (`data: {...}` lines, as
+/// forwarded by the cloud agents, possibly split mid-line). A placeholder may
+/// be split across deltas, so any trailing text that could still become one
+/// is held back until the next delta (or the choice's `finish_reason`)
+/// resolves it. Streamed tool-call arguments are restored the same way.
+pub struct ChunkRestorer<'a> {
+    redaction: &'a Redaction,
+    /// Held-back text per (choice index, tool call index); the content of a
+    /// choice has no tool call index
+    pending: HashMap<(u64, Option<u64>), String>,
+    /// Last chunk seen, used to emit held-back text at end of stream
+    template: Option<serde_json::Value>,
+    /// Whether input arrives as raw SSE text rather than bare payloads
+    sse_framed: bool,
+    /// Incomplete trailing SSE line
+    line_buffer: String,
+}
+
+impl ChunkRestorer<'_> {
+    /// Restore one streamed chunk. Non-JSON payloads pass through unchanged.
+    ///
+    /// For raw SSE input the result may be empty while a line is incomplete.
+    pub fn restore_chunk(&mut self, data: &str) -> String {
+        if self.redaction.is_empty() {
+            return data.to_string();
+        }
+        if self.sse_framed || data.trim_start().starts_with("data:") {
+            self.sse_framed = true;
+            return self.restore_sse(data);
+        }
+        self.restore_payload(data)
+    }
+
+    /// Emit any held-back text as a final chunk (call before `[DONE]`).
+    pub fn flush(&mut self) -> Option<String> {
+        let mut out = String::new();
+        if let Some(payload) = self.flush_payload() {
+            if self.sse_framed {
+                out.push_str(&format!("data: {}\n\n", payload));
+            } else {
+                out.push_str(&payload);
+            }
+        }
+        if !self.line_buffer.is_empty() {
+            out.push_str(&self.redaction.restore(&self.line_buffer));
+            self.line_buffer.clear();
+        }
+        (!out.is_empty()).then_some(out)
+    }
+
+    fn restore_sse(&mut self, text: &str) -> String {
+        self.line_buffer.push_str(text);
+        let mut out = String::new();
+        while let Some(pos) = self.line_buffer.find('\n') {
+            let line: String = self.line_buffer.drain(..=pos).collect();
+            let payload = line
+                .strip_prefix("data:")
+                .map(|rest| rest.trim_start_matches(' ').trim_end_matches(['\r', '\n']));
+            match payload {
+                Some("[DONE]") => {
+                    if let Some(payload) = self.flush_payload() {
+                        out.push_str(&format!("data: {}\n\n", payload));
+                    }
+                    out.push_str(&line);
+                }
+                Some(payload) => {
+                    out.push_str("data: ");
+                    out.push_str(&self.restore_payload(payload));
+                    out.push('\n');
+                }
+                None => out.push_str(&line),
+            }
+        }
+        out
+    }
+
+    fn restore_payload(&mut self, data: &str) -> String {
+        let Ok(mut chunk) = serde_json::from_str::<serde_json::Value>(data) else {
+            return data.to_string();
+        };
+        let Some(choices) = chunk.get_mut("choices").and_then(|c| c.as_array_mut()) else {
+            return data.to_string();
+        };
+        for choice in choices.iter_mut() {
+            let index = choice.get("index").and_then(|i| i.as_u64()).unwrap_or(0);
+            let finished = choice
+                .get("finish_reason")
+                .is_some_and(|reason| !reason.is_null());
+            let Some(delta) = choice.get_mut("delta").and_then(|d| d.as_object_mut()) else {
+                continue;
+            };
+
+            let content = delta.get("content").and_then(|c| c.as_str()).unwrap_or("");
+            if let Some(emitted) = self.release((index, None), content, finished) {
+                delta.insert("content".to_string(), serde_json::Value::String(emitted));
+            }
+
+            let mut seen = Vec::new();
+            if let Some(calls) = delta.get_mut("tool_calls").and_then(|c| c.as_array_mut()) {
+                for call in calls.iter_mut() {
+                    let call_index = call.get("index").and_then(|i| i.as_u64()).unwrap_or(0);
+                    seen.push(call_index);
+                    let Some(arguments) = call.pointer_mut("/function/arguments") else {
+                        continue;
+                    };
+                    let text = arguments.as_str().unwrap_or("").to_string();
+                    if let Some(emitted) = self.release((index, Some(call_index)), &text, finished)
+                    {
+                        *arguments = serde_json::Value::String(emitted);
+                    }
+                }
+            }
+            if finished {
+                let rest: Vec<serde_json::Value> = self
+                    .take_pending_calls(index)
+                    .into_iter()
+                    .filter(|call| !seen.contains(&call["index"].as_u64().unwrap_or(0)))
+                    .collect();
+                if !rest.is_empty() {
+                    let calls = delta
+                        .entry("tool_calls")
+                        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+                    if let Some(calls) = calls.as_array_mut() {
+                        calls.extend(rest);
+                    }
+                }
+            }
+        }
+        self.template = Some(chunk.clone());
+        serde_json::to_string(&chunk).unwrap_or_else(|_| data.to_string())
+    }
+
+    /// Append `text` to the held-back text for `key` and return what can be
+    /// emitted, restored; None when there is nothing to emit or hold.
+    fn release(&mut self, key: (u64, Option<u64>), text: &str, finished: bool) -> Option<String> {
+        let pending = self.pending.entry(key).or_default();
+        if text.is_empty() && pending.is_empty() {
+            return None;
+        }
+        pending.push_str(text);
+        let hold = if finished {
+            0
+        } else {
+            self.redaction.pending_suffix_len(pending)
+        };
+        let ready = pending.len() - hold;
+        let emitted = self.redaction.restore(&pending[..ready]);
+        pending.drain(..ready);
+        Some(emitted)
+    }
+
+    /// Remove held-back tool-call arguments of a choice as restored
+    /// `tool_calls` delta entries.
+    fn take_pending_calls(&mut self, index: u64) -> Vec<serde_json::Value> {
+        let mut keys: Vec<_> = self
+            .pending
+            .keys()
+            .filter(|(choice, call)| *choice == index && call.is_some())
+            .copied()
+            .collect();
+        keys.sort();
+        keys.into_iter()
+            .filter_map(|key| {
+                let pending = self.pending.remove(&key)?;
+                (!pending.is_empty()).then(|| {
+                    serde_json::json!({
+                        "index": key.1,
+                        "function": { "arguments": self.redaction.restore(&pending) },
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn flush_payload(&mut self) -> Option<String> {
+        let mut chunk = self.template.take()?;
+        let mut indices: Vec<u64> = self.pending.keys().map(|(choice, _)| *choice).collect();
+        indices.sort();
+        indices.dedup();
+        let choices: Vec<serde_json::Value> = indices
+            .into_iter()
+            .filter_map(|index| {
+                let mut delta = serde_json::Map::new();
+                if let Some(pending) = self.pending.remove(&(index, None)) {
+                    if !pending.is_empty() {
+                        delta.insert(
+                            "content".to_string(),
+                            serde_json::Value::String(self.redaction.restore(&pending)),
+                        );
+                    }
+                }
+                let calls = self.take_pending_calls(index);
+                if !calls.is_empty() {
+                    delta.insert("tool_calls".to_string(), serde_json::Value::Array(calls));
+                }
+                (!delta.is_empty()).then(|| serde_json::json!({ "index": index, "delta": delta }))
+            })
+            .collect();
+        self.pending.clear();
+        if choices.is_empty() {
+            return None;
+        }
+        chunk["choices"] = serde_json::Value::Array(choices);
+        serde_json::to_string(&chunk).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ChatMessage;
+    use crate::config::PiiConfig;
+
+    fn scanner() -> PiiScanner {
+        PiiScanner::new(&PiiConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn request(content: &str) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "gpt-4",
+            "messages": [{"role": "user", "content": content}]
+        }))
+        .unwrap()
+    }
+
+    fn text(message: &ChatMessage) -> &str {
+        match &message.content {
+            MessageContent::Text { content } => content,
+            MessageContent::Parts { .. } => panic!("expected text content"),
+        }
+    }
+
+    fn delta(content: &str) -> String {
+        serde_json::json!({
+            "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}]
+        })
+        .to_string()
+    }
+
+    fn content_of(data: &str) -> String {
+        let chunk: serde_json::Value = serde_json::from_str(data).unwrap();
+        chunk["choices"][0]["delta"]["content"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn placeholders_are_stable_per_value() {
+        let mut req = request("Mail a@b.io, then c@d.io, then a@b.io again");
+        let redaction = scanner().redact_request(&mut req);
+
+        assert_eq!(
+            text(&req.messages[0]),
+            "Mail [EMAIL_1], then [EMAIL_2], then [EMAIL_1] again"
+        );
+        assert_eq!(redaction.len(), 2);
+    }
+
+    #[test]
+    fn restore_round_trips_original_text() {
+        let original = "Card 4111 1111 1111 1111 for jane@example.com";
+        let mut req = request(original);
+        let redaction = scanner().redact_request(&mut req);
+
+        assert!(!text(&req.messages[0]).contains("jane@example.com"));
+        assert_eq!(redaction.restore(text(&req.messages[0])), original);
+    }
+
+    #[test]
+    fn clean_request_is_untouched() {
+        let mut req = request("Explain lifetimes");
+        let redaction = scanner().redact_request(&mut req);
+
+        assert!(redaction.is_empty());
+        assert_eq!(text(&req.messages[0]), "Explain lifetimes");
+    }
+
+    #[test]
+    fn chunk_restorer_handles_split_placeholders() {
+        let mut req = request("Reply to jane@example.com");
+        let redaction = scanner().redact_request(&mut req);
+        let mut restorer = redaction.chunk_restorer();
+
+        let mut out = String::new();
+        for piece in ["Sent to [EM", "AIL", "_1] ", "and done ["] {
+            out.push_str(&content_of(&restorer.restore_chunk(&delta(piece))));
+        }
+        assert_eq!(out, "Sent to jane@example.com and done ");
+        out.push_str(&content_of(&restorer.flush().unwrap()));
+        assert_eq!(out, "Sent to jane@example.com and done [");
+    }
+
+    #[test]
+    fn finish_reason_flushes_held_text() {
+        let mut req = request("Reply to jane@example.com");
+        let redaction = scanner().redact_request(&mut req);
+        let mut restorer = redaction.chunk_restorer();
+
+        assert_eq!(
+            content_of(&restorer.restore_chunk(&delta("x [EMAIL_"))),
+            "x "
+        );
+        let last = serde_json::json!({
+            "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4",
+            "choices": [{"index": 0, "delta": {}, "finish_reason": "stop"}]
+        })
+        .to_string();
+        assert_eq!(content_of(&restorer.restore_chunk(&last)), "[EMAIL_");
+        assert!(restorer.flush().is_none());
+    }
+
+    #[test]
+    fn chunk_restorer_handles_raw_sse_split_mid_line() {
+        let mut req = request("Reply to jane@example.com");
+        let redaction = scanner().redact_request(&mut req);
+        let mut restorer = redaction.chunk_restorer();
+
+        let raw = format!(
+            "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+            delta("Hi [EMAIL"),
+            delta("_1]!")
+        );
+        let (first, second) = raw.split_at(20);
+        let out = restorer.restore_chunk(first) + &restorer.restore_chunk(second);
+
+        let content: String = out
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .filter(|data| *data != "[DONE]")
+            .map(content_of)
+            .collect();
+        assert_eq!(content, "Hi jane@example.com!");
+        assert!(out.ends_with("data: [DONE]\n\n"));
+        assert!(restorer.flush().is_none());
+    }
+
+    #[test]
+    fn tool_call_arguments_are_redacted_and_restored() {
+        let mut req: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "gpt-4",
+            "messages": [
+                {"role": "user", "content": "Email jane@example.com"},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1", "type": "function",
+                    "function": {"name": "send", "arguments": "{\"to\":\"jane@example.com\"}"}
+                }]}
+            ]
+        }))
+        .unwrap();
+        let redaction = scanner().redact_request(&mut req);
+        let arguments = &req.messages[1].tool_calls.as_ref().unwrap()[0]
+            .function
+            .arguments;
+        assert_eq!(arguments, r#"{"to":"[EMAIL_1]"}"#);
+
+        let mut response: ChatCompletionResponse = serde_json::from_value(serde_json::json!({
+            "id": "c1", "object": "chat.completion", "created": 0, "model": "gpt-4",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_2", "type": "function",
+                    "function": {"name": "send", "arguments": "{\"cc\":\"[EMAIL_1]\"}"}
+                }]},
+                "finish_reason": "tool_calls"
+            }]
+        }))
+        .unwrap();
+        redaction.restore_response(&mut response);
+        let arguments = &response.choices[0].message.tool_calls.as_ref().unwrap()[0]
+            .function
+            .arguments;
+        assert_eq!(arguments, r#"{"cc":"jane@example.com"}"#);
+    }
+
+    #[test]
+    fn chunk_restorer_restores_streamed_tool_call_arguments() {
+        let mut req = request("Reply to jane@example.com");
+        let redaction = scanner().redact_request(&mut req);
+        let mut restorer = redaction.chunk_restorer();
+        let call_delta = |arguments: &str, finish_reason: Option<&str>| {
+            serde_json::json!({
+                "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "delta": {"tool_calls": [{"index": 0, "function": {"arguments": arguments}}]},
+                    "finish_reason": finish_reason
+                }]
+            })
+            .to_string()
+        };
+        let arguments_of = |data: &str| {
+            let chunk: serde_json::Value = serde_json::from_str(data).unwrap();
+            chunk["choices"][0]["delta"]["tool_calls"][0]["function"]["arguments"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let mut out = String::new();
+        out.push_str(&arguments_of(
+            &restorer.restore_chunk(&call_delta("{\"to\":\"[EMA", None)),
+        ));
+        out.push_str(&arguments_of(
+            &restorer.restore_chunk(&call_delta("IL_1]\"}", None)),
+        ));
+        assert_eq!(out, r#"{"to":"jane@example.com"}"#);
+
+        // Held-back arguments are emitted with the finishing chunk
+        restorer.restore_chunk(&call_delta("[EMAIL_", None));
+        let last = serde_json::json!({
+            "id": "c1", "object": "chat.completion.chunk", "created": 0, "model": "gpt-4",
+            "choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]
+        })
+        .to_string();
+        assert_eq!(arguments_of(&restorer.restore_chunk(&last)), "[EMAIL_");
+        assert!(restorer.flush().is_none());
+    }
+
+    #[test]
+    fn only_cloud_agents_support_redaction() {
+        assert!(supports_redaction(BackendType::OpenAI));
+        assert!(supports_redaction(BackendType::Anthropic));
+        assert!(supports_redaction(BackendType::Google));
+        assert!(supports_redaction(BackendType::AzureOpenAI));
+        assert!(supports_redaction(BackendType::Bedrock));
+        assert!(supports_redaction(BackendType::Cohere));
+        assert!(!supports_redaction(BackendType::Ollama));
+        assert!(!supports_redaction(BackendType::Generic));
+    }
+}
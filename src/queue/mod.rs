@@ -188,7 +188,6 @@ pub async fn queue_drain_loop(
     cancel: tokio_util::sync::CancellationToken,
 ) {
     use crate::routing::reconciler::intent::TierEnforcementMode;
-    use crate::routing::RequestRequirements;
     use std::time::Duration;
 
     tracing::info!("Queue drain loop started");
@@ -224,11 +223,10 @@ pub async fn queue_drain_loop(
                         continue;
                     }
 
-                    // Re-run routing
-                    let requirements =
-                        RequestRequirements::from_request(&queued.request);
+                    // Re-run routing with the requirements computed at enqueue
+                    // time, which carry content-rule privacy/tier constraints
                     let result = state.router.select_backend(
-                        &requirements,
+                        &queued.intent.requirements,
                         Some(TierEnforcementMode::Strict),
                     );
 
@@ -359,7 +357,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec![],
         )
@@ -789,6 +795,8 @@ mod tests {
                             },
                             name: None,
                             function_call: None,
+                            tool_calls: None,
+                            tool_call_id: None,
                         },
                         finish_reason: Some("stop".to_string()),
                     }],
@@ -798,6 +806,7 @@ mod tests {
                         total_tokens: 8,
                     }),
                     extra: std::collections::HashMap::new(),
+                    timings: None,
                 })
             }
             async fn chat_completion_stream(
@@ -2,7 +2,7 @@ use crate::agent::types::PrivacyZone;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU32, AtomicU64};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 /// Backend type indicating API compatibility.
 ///
@@ -22,12 +22,25 @@ pub enum BackendType {
     OpenAI,
     /// LM Studio backend (<https://lmstudio.ai>)
     LMStudio,
+    /// Hugging Face Text Generation Inference
+    /// (<https://github.com/huggingface/text-generation-inference>)
+    TGI,
+    /// Hugging Face Text Embeddings Inference
+    /// (<https://github.com/huggingface/text-embeddings-inference>)
+    TEI,
     /// Generic/unknown backend type
     Generic,
     /// Anthropic Claude API (F12: Cloud Backend Support)
     Anthropic,
     /// Google Generative AI API (F12: Cloud Backend Support)
     Google,
+    /// Azure OpenAI Service, addressed by deployment name
+    #[serde(rename = "azure_openai")]
+    AzureOpenAI,
+    /// AWS Bedrock Converse API, signed with SigV4
+    Bedrock,
+    /// Cohere v2 API (chat, embed and rerank)
+    Cohere,
 }
 
 impl BackendType {
@@ -42,11 +55,33 @@ impl BackendType {
             | BackendType::LlamaCpp
             | BackendType::Exo
             | BackendType::LMStudio
+            | BackendType::TGI
+            | BackendType::TEI
             | BackendType::Generic => PrivacyZone::Restricted,
 
-            BackendType::OpenAI | BackendType::Anthropic | BackendType::Google => PrivacyZone::Open,
+            BackendType::OpenAI
+            | BackendType::Anthropic
+            | BackendType::Google
+            | BackendType::AzureOpenAI
+            | BackendType::Bedrock
+            | BackendType::Cohere => PrivacyZone::Open,
         }
     }
+
+    /// Whether the backend enforces `response_format: json_schema` itself.
+    ///
+    /// Responses from other backends are validated against the schema by
+    /// the gateway.
+    pub fn supports_json_schema(&self) -> bool {
+        matches!(
+            self,
+            BackendType::OpenAI
+                | BackendType::AzureOpenAI
+                | BackendType::VLLM
+                | BackendType::LlamaCpp
+                | BackendType::LMStudio
+        )
+    }
 }
 
 /// Backend health status.
@@ -94,6 +129,9 @@ pub struct Model {
     pub supports_tools: bool,
     /// Whether the model supports JSON mode
     pub supports_json_mode: bool,
+    /// Whether the model produces embeddings
+    #[serde(default)]
+    pub supports_embeddings: bool,
     /// Maximum output tokens (if limited)
     pub max_output_tokens: Option<u32>,
 }
@@ -146,6 +184,9 @@ pub struct Backend {
     pub total_requests: AtomicU64,
     /// Rolling average latency in milliseconds (atomic, EMA with α=0.2)
     pub avg_latency_ms: AtomicU32,
+    /// Running + waiting requests reported by the backend's metrics endpoint
+    /// (atomic, 0 if not reported)
+    pub reported_queue_depth: AtomicU32,
     /// How this backend was discovered
     pub discovery_source: DiscoverySource,
     /// Additional metadata key-value pairs
@@ -180,11 +221,21 @@ impl Backend {
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(0),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source,
             metadata,
             current_operation: None,
         }
     }
+
+    /// Load used for scheduling: the larger of Nexus's in-flight requests and
+    /// the queue depth last reported by the backend, which also counts
+    /// requests from other clients.
+    pub fn queue_depth(&self) -> u32 {
+        self.pending_requests
+            .load(Ordering::Relaxed)
+            .max(self.reported_queue_depth.load(Ordering::Relaxed))
+    }
 }
 
 /// Serializable view of Backend (atomic fields converted to regular values).
@@ -270,6 +321,8 @@ mod tests {
             BackendType::LlamaCpp,
             BackendType::Exo,
             BackendType::LMStudio,
+            BackendType::TGI,
+            BackendType::TEI,
             BackendType::Generic,
         ];
 
@@ -288,6 +341,7 @@ mod tests {
             BackendType::OpenAI,
             BackendType::Anthropic,
             BackendType::Google,
+            BackendType::Cohere,
         ];
 
         for bt in cloud_types {
@@ -298,4 +352,12 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_json_schema_support() {
+        assert!(BackendType::VLLM.supports_json_schema());
+        assert!(BackendType::OpenAI.supports_json_schema());
+        assert!(!BackendType::Ollama.supports_json_schema());
+        assert!(!BackendType::Generic.supports_json_schema());
+    }
 }
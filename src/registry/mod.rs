@@ -275,6 +275,11 @@ impl Registry {
                         .avg_latency_ms
                         .load(std::sync::atomic::Ordering::SeqCst),
                 ),
+                reported_queue_depth: std::sync::atomic::AtomicU32::new(
+                    backend
+                        .reported_queue_depth
+                        .load(std::sync::atomic::Ordering::SeqCst),
+                ),
                 discovery_source: backend.discovery_source,
                 metadata: backend.metadata.clone(),
                 current_operation: backend.current_operation.clone(),
@@ -315,6 +320,11 @@ impl Registry {
                             .avg_latency_ms
                             .load(std::sync::atomic::Ordering::SeqCst),
                     ),
+                    reported_queue_depth: std::sync::atomic::AtomicU32::new(
+                        backend
+                            .reported_queue_depth
+                            .load(std::sync::atomic::Ordering::SeqCst),
+                    ),
                     discovery_source: backend.discovery_source,
                     metadata: backend.metadata.clone(),
                     current_operation: backend.current_operation.clone(),
@@ -376,6 +386,11 @@ impl Registry {
                             .avg_latency_ms
                             .load(std::sync::atomic::Ordering::SeqCst),
                     ),
+                    reported_queue_depth: std::sync::atomic::AtomicU32::new(
+                        backend
+                            .reported_queue_depth
+                            .load(std::sync::atomic::Ordering::SeqCst),
+                    ),
                     discovery_source: backend.discovery_source,
                     metadata: backend.metadata.clone(),
                     current_operation: backend.current_operation.clone(),
@@ -514,6 +529,19 @@ impl Registry {
         Ok(new_val)
     }
 
+    /// Record the queue depth reported by a backend's metrics endpoint.
+    pub fn update_reported_queue_depth(&self, id: &str, depth: u32) -> Result<(), RegistryError> {
+        let backend = self
+            .backends
+            .get(id)
+            .ok_or_else(|| RegistryError::BackendNotFound(id.to_string()))?;
+
+        backend
+            .reported_queue_depth
+            .store(depth, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
+
     /// Update rolling average latency using EMA: new = (sample + 4*old) / 5.
     ///
     /// Uses integer math with α=0.2. First sample sets the initial value.
@@ -53,6 +53,7 @@ fn test_model_creation() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     };
 
@@ -77,6 +78,7 @@ fn test_model_json_roundtrip() {
         supports_vision: true,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     };
 
@@ -104,6 +106,7 @@ fn test_backend_creation() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -177,6 +180,7 @@ fn test_backend_view_from_backend() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -216,6 +220,7 @@ fn test_backend_view_json_roundtrip() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -485,6 +490,7 @@ fn test_model_index_updated_on_add() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
         Model {
@@ -494,6 +500,7 @@ fn test_model_index_updated_on_add() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
     ];
@@ -532,6 +539,7 @@ fn test_model_index_updated_on_remove() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -571,6 +579,7 @@ fn test_get_backends_for_model_single() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -604,6 +613,7 @@ fn test_get_backends_for_model_multiple() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         }];
 
@@ -745,6 +755,7 @@ fn test_model_count() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
         Model {
@@ -754,6 +765,7 @@ fn test_model_count() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
     ];
@@ -777,6 +789,7 @@ fn test_model_count() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
         Model {
@@ -786,6 +799,7 @@ fn test_model_count() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
     ];
@@ -950,6 +964,7 @@ fn test_update_models_replaces_list() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -974,6 +989,7 @@ fn test_update_models_replaces_list() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
         Model {
@@ -983,6 +999,7 @@ fn test_update_models_replaces_list() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
     ];
@@ -1010,6 +1027,7 @@ fn test_update_models_updates_index() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
         Model {
@@ -1019,6 +1037,7 @@ fn test_update_models_updates_index() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
     ];
@@ -1048,6 +1067,7 @@ fn test_update_models_updates_index() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
         Model {
@@ -1057,6 +1077,7 @@ fn test_update_models_updates_index() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         },
     ];
@@ -1081,6 +1102,7 @@ fn test_update_models_not_found() {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: true,
+        supports_embeddings: false,
         max_output_tokens: Some(4096),
     }];
 
@@ -1639,6 +1661,7 @@ async fn test_concurrent_model_queries() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         }];
 
@@ -1823,6 +1846,7 @@ fn test_add_backend_with_agent_and_get_agent() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -2038,6 +2062,7 @@ fn test_remove_backend_cleans_up_model_index_completely() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -0,0 +1,488 @@
+//! Content-based routing rules
+//!
+//! Extracts routing-relevant features from a request (code blocks, language,
+//! prompt length, keywords, images, tools, route tag) and evaluates the
+//! configured `[[routing.rules]]` against them. The first matching rule may
+//! rewrite the target model and constrain privacy zone or capability tier.
+
+use super::{RequestRequirements, Router};
+use crate::agent::PrivacyZone;
+use crate::api::types::{ChatCompletionRequest, MessageContent};
+use crate::config::{ConfigError, ContentRule, PrivacyConstraint};
+
+/// Routing-relevant features extracted from a chat request.
+#[derive(Debug, Clone, Default)]
+pub struct RequestFeatures {
+    /// Concatenated text of all messages
+    pub text: String,
+    /// Text of the latest user message (used for language detection)
+    pub latest_user_text: String,
+    /// Whether any message contains a fenced code block
+    pub has_code: bool,
+    /// Whether any message contains image content
+    pub has_images: bool,
+    /// Whether the request defines tools
+    pub has_tools: bool,
+    /// Estimated prompt tokens
+    pub prompt_tokens: u32,
+    /// Value of the `X-Nexus-Route-Tag` request header
+    pub route_tag: Option<String>,
+}
+
+impl RequestFeatures {
+    /// Extract features from a request.
+    pub fn extract(
+        request: &ChatCompletionRequest,
+        prompt_tokens: u32,
+        route_tag: Option<&str>,
+    ) -> Self {
+        let mut features = Self {
+            has_tools: request.extra.contains_key("tools"),
+            prompt_tokens,
+            route_tag: route_tag.map(|tag| tag.trim().to_string()),
+            ..Default::default()
+        };
+
+        for message in &request.messages {
+            let mut message_text = String::new();
+            match &message.content {
+                MessageContent::Text { content } => message_text.push_str(content),
+                MessageContent::Parts { content } => {
+                    for part in content {
+                        if part.part_type == "image_url" {
+                            features.has_images = true;
+                        } else if let Some(text) = &part.text {
+                            if !message_text.is_empty() {
+                                message_text.push('\n');
+                            }
+                            message_text.push_str(text);
+                        }
+                    }
+                }
+            }
+
+            if message_text.contains("```") || message_text.contains("~~~") {
+                features.has_code = true;
+            }
+            if message.role == "user" {
+                features.latest_user_text = message_text.clone();
+            }
+            if !features.text.is_empty() {
+                features.text.push('\n');
+            }
+            features.text.push_str(&message_text);
+        }
+
+        features
+    }
+
+    /// Detect the language of the latest user message as an ISO 639-3 code.
+    pub fn language(&self) -> Option<&'static str> {
+        whatlang::detect(&self.latest_user_text)
+            .filter(|info| info.is_reliable())
+            .map(|info| info.lang().code())
+    }
+}
+
+/// A content rule with its glob and regex pre-compiled.
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    rule: ContentRule,
+    model: Option<globset::GlobMatcher>,
+    pattern: Option<regex::Regex>,
+    keywords: Vec<String>,
+}
+
+impl CompiledRule {
+    fn matches(&self, model: &str, features: &RequestFeatures, language: Option<&str>) -> bool {
+        let rule = &self.rule;
+
+        if let Some(glob) = &self.model {
+            if !glob.is_match(model) {
+                return false;
+            }
+        }
+        if let Some(tag) = &rule.route_tag {
+            match &features.route_tag {
+                Some(value) if value.eq_ignore_ascii_case(tag) => {}
+                _ => return false,
+            }
+        }
+        if rule.has_code.is_some_and(|v| v != features.has_code)
+            || rule.has_images.is_some_and(|v| v != features.has_images)
+            || rule.has_tools.is_some_and(|v| v != features.has_tools)
+        {
+            return false;
+        }
+        if rule
+            .min_prompt_tokens
+            .is_some_and(|min| features.prompt_tokens < min)
+            || rule
+                .max_prompt_tokens
+                .is_some_and(|max| features.prompt_tokens > max)
+        {
+            return false;
+        }
+        if !rule.languages.is_empty() {
+            match language {
+                Some(lang) if rule.languages.iter().any(|l| l.eq_ignore_ascii_case(lang)) => {}
+                _ => return false,
+            }
+        }
+        if !self.keywords.is_empty() {
+            let text = features.text.to_lowercase();
+            if !self.keywords.iter().any(|keyword| text.contains(keyword)) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(&features.text) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Pre-compiled content rule matcher (first match wins).
+#[derive(Debug, Clone, Default)]
+pub struct ContentRuleMatcher {
+    rules: Vec<CompiledRule>,
+}
+
+impl ContentRuleMatcher {
+    /// Compile content rules.
+    ///
+    /// Returns an error if a rule is invalid (see `config::routing::validate_rules`).
+    pub fn compile(rules: Vec<ContentRule>) -> Result<Self, ConfigError> {
+        crate::config::routing::validate_rules(&rules)?;
+
+        let rules = rules
+            .into_iter()
+            .map(|rule| CompiledRule {
+                model: rule
+                    .model_pattern
+                    .as_deref()
+                    .and_then(|p| globset::Glob::new(p).ok())
+                    .map(|glob| glob.compile_matcher()),
+                pattern: rule
+                    .pattern
+                    .as_deref()
+                    .and_then(|p| regex::Regex::new(p).ok()),
+                keywords: rule.keywords.iter().map(|k| k.to_lowercase()).collect(),
+                rule,
+            })
+            .collect();
+        Ok(Self { rules })
+    }
+
+    /// Find the first rule matching the requested model and request features.
+    pub fn find_rule(&self, model: &str, features: &RequestFeatures) -> Option<&ContentRule> {
+        // Language detection is the costliest feature; only run it if needed
+        let language = if self.rules.iter().any(|r| !r.rule.languages.is_empty()) {
+            features.language()
+        } else {
+            None
+        };
+
+        self.rules
+            .iter()
+            .find(|compiled| compiled.matches(model, features, language))
+            .map(|compiled| &compiled.rule)
+    }
+
+    /// Returns true if no rules are configured
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl Router {
+    /// Replace the content routing rules.
+    pub fn set_content_rules(&mut self, rules: Vec<ContentRule>) -> Result<(), ConfigError> {
+        self.content_rules = ContentRuleMatcher::compile(rules)?;
+        Ok(())
+    }
+
+    /// Apply the first matching content rule to a request.
+    ///
+    /// Rewrites `request.model` (recomputing requirements for the new model)
+    /// and records privacy/tier constraints on `requirements` for the
+    /// reconciler pipeline. Returns the name of the matched rule.
+    pub fn apply_content_rules(
+        &self,
+        request: &mut ChatCompletionRequest,
+        requirements: &mut RequestRequirements,
+        route_tag: Option<&str>,
+    ) -> Option<String> {
+        if self.content_rules.is_empty() {
+            return None;
+        }
+
+        let features = RequestFeatures::extract(request, requirements.estimated_tokens, route_tag);
+        let rule = self.content_rules.find_rule(&request.model, &features)?;
+
+        tracing::debug!(
+            rule = %rule.name,
+            model = %request.model,
+            target_model = ?rule.target_model,
+            "Content routing rule matched"
+        );
+
+        if let Some(target) = &rule.target_model {
+            request.model = target.clone();
+            *requirements = self.requirements_for(request);
+        }
+        if rule.privacy == Some(PrivacyConstraint::Restricted) {
+            requirements.privacy_constraint = Some(PrivacyZone::Restricted);
+        }
+        if let Some(min_tier) = rule.min_tier {
+            requirements.min_capability_tier = Some(min_tier);
+        }
+
+        Some(rule.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{ChatMessage, ContentPart, ImageUrl};
+    use crate::registry::Registry;
+    use crate::routing::{RoutingStrategy, ScoringWeights};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: MessageContent::Text {
+                content: content.to_string(),
+            },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+
+    fn request(model: &str, messages: Vec<ChatMessage>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model.to_string(),
+            messages,
+            stream: false,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            user: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    fn rule(name: &str, target: &str) -> ContentRule {
+        ContentRule {
+            name: name.to_string(),
+            target_model: Some(target.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn features(text: &str) -> RequestFeatures {
+        RequestFeatures::extract(&request("auto", vec![message("user", text)]), 10, None)
+    }
+
+    #[test]
+    fn extracts_code_images_and_tools() {
+        let mut req = request(
+            "auto",
+            vec![
+                message("system", "You are helpful."),
+                message("user", "Fix this:\n```rust\nfn main() {}\n```"),
+            ],
+        );
+        req.extra.insert(
+            "tools".to_string(),
+            serde_json::json!([{"type": "function"}]),
+        );
+        let features = RequestFeatures::extract(&req, 42, Some(" coding "));
+        assert!(features.has_code);
+        assert!(features.has_tools);
+        assert!(!features.has_images);
+        assert_eq!(features.prompt_tokens, 42);
+        assert_eq!(features.route_tag.as_deref(), Some("coding"));
+
+        req.messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: MessageContent::Parts {
+                content: vec![ContentPart {
+                    part_type: "image_url".to_string(),
+                    text: None,
+                    image_url: Some(ImageUrl {
+                        url: "data:image/png;base64,xx".to_string(),
+                    }),
+                }],
+            },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        });
+        assert!(RequestFeatures::extract(&req, 0, None).has_images);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let matcher = ContentRuleMatcher::compile(vec![
+            ContentRule {
+                has_code: Some(true),
+                ..rule("code", "big")
+            },
+            rule("catch-all", "small"),
+        ])
+        .unwrap();
+
+        let code = features("```\nlet x = 1;\n```");
+        assert_eq!(matcher.find_rule("auto", &code).unwrap().name, "code");
+        let chat = features("hello there");
+        assert_eq!(matcher.find_rule("auto", &chat).unwrap().name, "catch-all");
+    }
+
+    #[test]
+    fn model_pattern_restricts_rule() {
+        let matcher = ContentRuleMatcher::compile(vec![ContentRule {
+            model_pattern: Some("auto".to_string()),
+            ..rule("auto-only", "small")
+        }])
+        .unwrap();
+        assert!(matcher.find_rule("auto", &features("hi")).is_some());
+        assert!(matcher.find_rule("llama3", &features("hi")).is_none());
+    }
+
+    #[test]
+    fn prompt_length_buckets() {
+        let matcher = ContentRuleMatcher::compile(vec![
+            ContentRule {
+                max_prompt_tokens: Some(100),
+                ..rule("short", "small")
+            },
+            ContentRule {
+                min_prompt_tokens: Some(101),
+                ..rule("long", "big")
+            },
+        ])
+        .unwrap();
+        let mut f = features("hi");
+        f.prompt_tokens = 100;
+        assert_eq!(matcher.find_rule("auto", &f).unwrap().name, "short");
+        f.prompt_tokens = 101;
+        assert_eq!(matcher.find_rule("auto", &f).unwrap().name, "long");
+    }
+
+    #[test]
+    fn keywords_and_regex_match_prompt_text() {
+        let matcher = ContentRuleMatcher::compile(vec![
+            ContentRule {
+                keywords: vec!["Invoice".to_string()],
+                ..rule("keyword", "a")
+            },
+            ContentRule {
+                pattern: Some(r"\b\d{3}-\d{2}-\d{4}\b".to_string()),
+                ..rule("regex", "b")
+            },
+        ])
+        .unwrap();
+        let f = features("please check this INVOICE");
+        assert_eq!(matcher.find_rule("auto", &f).unwrap().name, "keyword");
+        let f = features("my number is 123-45-6789");
+        assert_eq!(matcher.find_rule("auto", &f).unwrap().name, "regex");
+        assert!(matcher.find_rule("auto", &features("nothing")).is_none());
+    }
+
+    #[test]
+    fn route_tag_matches_case_insensitively() {
+        let matcher = ContentRuleMatcher::compile(vec![ContentRule {
+            route_tag: Some("batch".to_string()),
+            ..rule("tagged", "small")
+        }])
+        .unwrap();
+        let mut f = features("hi");
+        assert!(matcher.find_rule("auto", &f).is_none());
+        f.route_tag = Some("Batch".to_string());
+        assert!(matcher.find_rule("auto", &f).is_some());
+    }
+
+    #[test]
+    fn language_detection_matches_iso_codes() {
+        let matcher = ContentRuleMatcher::compile(vec![ContentRule {
+            languages: vec!["deu".to_string()],
+            ..rule("german", "de-model")
+        }])
+        .unwrap();
+        let german =
+            features("Guten Morgen, wie geht es Ihnen heute? Ich habe eine Frage zum Vertrag.");
+        assert!(matcher.find_rule("auto", &german).is_some());
+        let english =
+            features("Good morning, how are you today? I have a question about the contract.");
+        assert!(matcher.find_rule("auto", &english).is_none());
+    }
+
+    #[test]
+    fn compile_rejects_invalid_rules() {
+        let result = ContentRuleMatcher::compile(vec![ContentRule {
+            name: "broken".to_string(),
+            pattern: Some("[".to_string()),
+            min_tier: Some(2),
+            ..Default::default()
+        }]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn apply_rewrites_model_and_sets_constraints() {
+        let mut router = Router::new(
+            Arc::new(Registry::new()),
+            RoutingStrategy::Smart,
+            ScoringWeights::default(),
+        );
+        router
+            .set_content_rules(vec![ContentRule {
+                privacy: Some(PrivacyConstraint::Restricted),
+                min_tier: Some(3),
+                ..rule("all", "llama3:70b")
+            }])
+            .unwrap();
+
+        let mut req = request("auto", vec![message("user", "hello")]);
+        let mut requirements = router.requirements_for(&req);
+        let matched = router.apply_content_rules(&mut req, &mut requirements, None);
+
+        assert_eq!(matched.as_deref(), Some("all"));
+        assert_eq!(req.model, "llama3:70b");
+        assert_eq!(requirements.model, "llama3:70b");
+        assert_eq!(
+            requirements.privacy_constraint,
+            Some(PrivacyZone::Restricted)
+        );
+        assert_eq!(requirements.min_capability_tier, Some(3));
+    }
+
+    #[test]
+    fn apply_without_rules_is_noop() {
+        let router = Router::new(
+            Arc::new(Registry::new()),
+            RoutingStrategy::Smart,
+            ScoringWeights::default(),
+        );
+        let mut req = request("auto", vec![message("user", "hello")]);
+        let mut requirements = router.requirements_for(&req);
+        let before = requirements.clone();
+        assert!(router
+            .apply_content_rules(&mut req, &mut requirements, None)
+            .is_none());
+        assert_eq!(req.model, "auto");
+        assert_eq!(requirements, before);
+    }
+}
@@ -0,0 +1,894 @@
+//! Context window fitting
+//!
+//! Checks whether a request (prompt plus `max_tokens` headroom) fits the
+//! context window of the backends serving its model, and applies the
+//! opt-in overflow handling selected by header or traffic policy.
+
+use super::reconciler::intent::RoutingIntent;
+use super::reconciler::privacy::PrivacyReconciler;
+use super::reconciler::scheduler::missing_capabilities;
+use super::reconciler::tier::TierReconciler;
+use super::reconciler::Reconciler;
+use super::requirements::count_prompt_tokens;
+use super::{RequestRequirements, Router};
+use crate::agent::tokenizer::{Tokenizer, TokenizerError, TIER_EXACT};
+use crate::agent::{PrivacyZone, TokenCount};
+use crate::api::types::{ChatCompletionRequest, ChatMessage, MessageContent};
+use crate::config::ContextOverflowMode;
+use crate::registry::{Backend, BackendStatus, BackendType, Model};
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Total time allowed for counting a prompt on the backend, across all texts.
+const BACKEND_COUNT_DEADLINE: Duration = Duration::from_secs(2);
+
+/// Maximum tokenize calls in flight for one prompt.
+const BACKEND_COUNT_CONCURRENCY: usize = 8;
+
+/// Token counts obtained from a backend for the texts of one request.
+struct CountedTexts<'a>(HashMap<&'a str, u32>);
+
+impl Tokenizer for CountedTexts<'_> {
+    fn count_tokens(&self, text: &str) -> Result<u32, TokenizerError> {
+        if text.is_empty() {
+            return Ok(0);
+        }
+        self.0
+            .get(text)
+            .copied()
+            .ok_or_else(|| TokenizerError::Encoding("text was not counted".to_string()))
+    }
+
+    fn tier(&self) -> u8 {
+        TIER_EXACT
+    }
+
+    fn name(&self) -> &str {
+        "backend"
+    }
+}
+
+/// Outcome of fitting a request into an available context window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextFit {
+    /// Request fits the requested model as-is
+    Fits,
+    /// Request moved to a model with a larger context window
+    Upgraded { from: String, to: String },
+    /// Oldest non-system messages were dropped to make the request fit
+    Truncated { dropped: usize },
+}
+
+impl ContextFit {
+    /// Value for the `X-Nexus-Context-Overflow` response header, if any.
+    pub fn header_value(&self) -> Option<String> {
+        match self {
+            ContextFit::Fits => None,
+            ContextFit::Upgraded { to, .. } => Some(format!("upgraded:{}", to)),
+            ContextFit::Truncated { dropped } => Some(format!("truncated:{}", dropped)),
+        }
+    }
+}
+
+/// Request cannot be made to fit any available context window.
+#[derive(Debug, Error)]
+#[error(
+    "Request needs {required_tokens} tokens ({prompt_tokens} prompt + {max_tokens} max_tokens) \
+     but the largest context window for model '{model}' is {context_length} tokens. \
+     {suggestion}"
+)]
+pub struct ContextOverflowError {
+    pub model: String,
+    pub prompt_tokens: u32,
+    pub max_tokens: u32,
+    pub required_tokens: u32,
+    pub context_length: u32,
+    pub suggestion: &'static str,
+}
+
+impl Router {
+    /// Extract requirements with prompt tokens counted by the model's tokenizer.
+    pub fn requirements_for(&self, request: &ChatCompletionRequest) -> RequestRequirements {
+        let model = self.resolve_alias(&request.model);
+        let tokenizer = self.tokenizer_registry.get_tokenizer(&model);
+        let mut requirements =
+            RequestRequirements::from_request_with_tokenizer(request, tokenizer.as_ref());
+        if self.pii_scanner.is_some() {
+            requirements.prompt_text = Some(super::requirements::prompt_text(request).into());
+        }
+        requirements
+    }
+
+    /// Recount prompt tokens with the model's own tokenizer on a backend that
+    /// exposes one, marking the count exact.
+    ///
+    /// The estimate is left unchanged when no healthy backend for the model
+    /// counts tokens, when any part of the prompt could not be counted, or
+    /// when counting takes longer than `BACKEND_COUNT_DEADLINE` in total.
+    pub async fn count_tokens_on_backend(
+        &self,
+        request: &ChatCompletionRequest,
+        requirements: &mut RequestRequirements,
+    ) {
+        let model = self.resolve_alias(&request.model);
+        let Some(agent) = self
+            .registry
+            .get_backends_for_model(&model)
+            .iter()
+            .filter(|backend| backend.status == BackendStatus::Healthy)
+            .filter_map(|backend| self.registry.get_agent(&backend.id))
+            .find(|agent| agent.profile().capabilities.token_counting)
+        else {
+            return;
+        };
+
+        let mut texts = HashSet::new();
+        for message in &request.messages {
+            texts.insert(message.role.as_str());
+            match &message.content {
+                MessageContent::Text { content } => {
+                    texts.insert(content.as_str());
+                }
+                MessageContent::Parts { content } => {
+                    texts.extend(content.iter().filter_map(|part| part.text.as_deref()));
+                }
+            }
+        }
+        texts.remove("");
+
+        let calls: Vec<_> = texts
+            .into_iter()
+            .map(|text| {
+                let agent = &agent;
+                let model = &model;
+                async move { (text, agent.count_tokens(model, text).await) }
+            })
+            .collect();
+        let counting = stream::iter(calls)
+            .buffer_unordered(BACKEND_COUNT_CONCURRENCY)
+            .collect::<Vec<_>>();
+        let Ok(counts) = tokio::time::timeout(BACKEND_COUNT_DEADLINE, counting).await else {
+            tracing::debug!(model = %model, "Backend token counting timed out");
+            return;
+        };
+
+        let mut exact = HashMap::new();
+        for (text, count) in counts {
+            match count {
+                TokenCount::Exact(count) => {
+                    exact.insert(text, count);
+                }
+                _ => return,
+            }
+        }
+        requirements.estimated_tokens = count_prompt_tokens(request, &CountedTexts(exact));
+        requirements.prompt_tokens_exact = true;
+    }
+
+    /// Overflow handling configured by traffic policy for a model, if any.
+    pub fn context_overflow_policy(&self, model: &str) -> Option<ContextOverflowMode> {
+        let model = self.resolve_alias(model);
+        self.policy_matcher
+            .find_policy(&model)
+            .and_then(|policy| policy.context_overflow)
+    }
+
+    /// Largest context window among healthy backends serving a model.
+    fn max_context_length(&self, model: &str) -> Option<u32> {
+        self.registry
+            .get_backends_for_model(model)
+            .iter()
+            .filter(|backend| backend.status == BackendStatus::Healthy)
+            .flat_map(|backend| backend.models.iter().filter(|m| m.id == model))
+            .map(|m| m.context_length)
+            .max()
+    }
+
+    /// Make a request fit an available context window using the given mode.
+    ///
+    /// Requests that already fit, or whose model has no healthy backend, are
+    /// left untouched so normal routing produces the appropriate result. On
+    /// success `request` and `requirements` are updated in place.
+    pub fn fit_context_window(
+        &self,
+        request: &mut ChatCompletionRequest,
+        requirements: &mut RequestRequirements,
+        mode: ContextOverflowMode,
+    ) -> Result<ContextFit, ContextOverflowError> {
+        let model = self.resolve_alias(&request.model);
+        let Some(context_length) = self.max_context_length(&model) else {
+            return Ok(ContextFit::Fits);
+        };
+        if requirements.required_context() <= context_length {
+            return Ok(ContextFit::Fits);
+        }
+
+        let overflow = |requirements: &RequestRequirements, suggestion| ContextOverflowError {
+            model: model.clone(),
+            prompt_tokens: requirements.estimated_tokens,
+            max_tokens: requirements.max_tokens.unwrap_or(0),
+            required_tokens: requirements.required_context(),
+            context_length,
+            suggestion,
+        };
+
+        match mode {
+            ContextOverflowMode::Reject => Err(overflow(
+                requirements,
+                "Shorten the conversation, lower max_tokens, or retry with \
+                 X-Nexus-Context-Overflow: truncate or upgrade.",
+            )),
+            ContextOverflowMode::Upgrade => {
+                let Some((target, upgraded)) =
+                    self.find_larger_context_model(&model, request, requirements)
+                else {
+                    return Err(overflow(
+                        requirements,
+                        "No available model has a large enough context window; \
+                         shorten the conversation or lower max_tokens.",
+                    ));
+                };
+                tracing::info!(
+                    from = %model,
+                    to = %target,
+                    required_tokens = upgraded.required_context(),
+                    "Context overflow: upgrading to larger-context model"
+                );
+                request.model = target.clone();
+                *requirements = upgraded;
+                Ok(ContextFit::Upgraded {
+                    from: model,
+                    to: target,
+                })
+            }
+            ContextOverflowMode::Truncate => {
+                let mut truncated = request.clone();
+                let mut dropped = 0;
+                let mut fitted = requirements.clone();
+
+                while fitted.required_context() > context_length {
+                    // Keep system messages and the latest message
+                    let Some(group) = oldest_droppable_group(&truncated.messages) else {
+                        return Err(overflow(
+                            &fitted,
+                            "The system prompt and latest message alone exceed the \
+                             context window; shorten them or lower max_tokens.",
+                        ));
+                    };
+                    dropped += group.len();
+                    truncated.messages.drain(group);
+                    fitted = self.recount(&truncated, requirements, &model);
+                }
+
+                tracing::info!(
+                    model = %model,
+                    dropped,
+                    required_tokens = fitted.required_context(),
+                    "Context overflow: dropped oldest messages"
+                );
+                *request = truncated;
+                *requirements = fitted;
+                Ok(ContextFit::Truncated { dropped })
+            }
+        }
+    }
+
+    /// Requirements for `request` on `model`, recounted with that model's
+    /// tokenizer and keeping the constraints already set on `requirements`.
+    fn recount(
+        &self,
+        request: &ChatCompletionRequest,
+        requirements: &RequestRequirements,
+        model: &str,
+    ) -> RequestRequirements {
+        let tokenizer = self.tokenizer_registry.get_tokenizer(model);
+        let mut recounted = requirements.clone();
+        recounted.model = model.to_string();
+        recounted.estimated_tokens = count_prompt_tokens(request, tokenizer.as_ref());
+        recounted.prompt_tokens_exact = false;
+        if recounted.prompt_text.is_some() {
+            recounted.prompt_text = Some(super::requirements::prompt_text(request).into());
+        }
+        recounted
+    }
+
+    /// Find a model whose context window fits the request.
+    ///
+    /// The fallback chain is tried first, in order. Otherwise the healthy
+    /// model with the smallest sufficient context window is chosen.
+    ///
+    /// Candidates go through the same privacy, tier and capability filters
+    /// as routing, with the constraints of the requested model carried over,
+    /// so an upgrade never leaves the privacy zone or tier the request was
+    /// held to, and never lands on an embedding or rerank model.
+    fn find_larger_context_model(
+        &self,
+        model: &str,
+        request: &ChatCompletionRequest,
+        requirements: &RequestRequirements,
+    ) -> Option<(String, RequestRequirements)> {
+        let privacy =
+            PrivacyReconciler::new(Arc::clone(&self.registry), self.policy_matcher.clone());
+        let tier = TierReconciler::new(Arc::clone(&self.registry), self.policy_matcher.clone());
+        let constrained = self.upgrade_constraints(model, requirements, &privacy, &tier)?;
+
+        let fits = |candidate: &str| {
+            let upgraded = self.recount(request, &constrained, candidate);
+            let context_length =
+                self.eligible_context_length(candidate, &upgraded, &privacy, &tier)?;
+            Some((context_length, upgraded))
+        };
+
+        for fallback in self.get_fallbacks(model) {
+            if let Some((_, requirements)) = fits(&fallback) {
+                return Some((fallback, requirements));
+            }
+        }
+
+        let mut models: Vec<String> = self
+            .registry
+            .get_healthy_backends()
+            .into_iter()
+            .flat_map(|backend| backend.models.into_iter().map(|m| m.id))
+            .filter(|id| id != model)
+            .collect();
+        models.sort();
+        models.dedup();
+
+        models
+            .into_iter()
+            .filter_map(|candidate| {
+                fits(&candidate)
+                    .map(|(context_length, requirements)| (context_length, candidate, requirements))
+            })
+            .min_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)))
+            .map(|(_, candidate, requirements)| (candidate, requirements))
+    }
+
+    /// Privacy and tier constraints the requested model imposes, recorded on
+    /// the requirements so they stay in force for the upgraded model.
+    fn upgrade_constraints(
+        &self,
+        model: &str,
+        requirements: &RequestRequirements,
+        privacy: &PrivacyReconciler,
+        tier: &TierReconciler,
+    ) -> Option<RequestRequirements> {
+        let mut constrained = requirements.clone();
+        let pii_restricted = !self.pii_redact_cloud
+            && self.pii_scanner.as_ref().is_some_and(|scanner| {
+                requirements
+                    .prompt_text
+                    .as_deref()
+                    .is_some_and(|text| !scanner.detect(text).is_empty())
+            });
+        if pii_restricted {
+            constrained.privacy_constraint = Some(PrivacyZone::Restricted);
+        }
+
+        let mut intent = RoutingIntent::new(
+            String::new(),
+            model.to_string(),
+            model.to_string(),
+            constrained.clone(),
+            Vec::new(),
+        );
+        privacy.reconcile(&mut intent).ok()?;
+        tier.reconcile(&mut intent).ok()?;
+        constrained.privacy_constraint = intent.privacy_constraint;
+        constrained.min_capability_tier = intent.min_capability_tier;
+        Some(constrained)
+    }
+
+    /// Largest context window among healthy backends that may serve the
+    /// request on `candidate`, or `None` when none may.
+    fn eligible_context_length(
+        &self,
+        candidate: &str,
+        requirements: &RequestRequirements,
+        privacy: &PrivacyReconciler,
+        tier: &TierReconciler,
+    ) -> Option<u32> {
+        let eligible: Vec<(String, u32)> = self
+            .registry
+            .get_backends_for_model(candidate)
+            .iter()
+            .filter(|backend| backend.status == BackendStatus::Healthy)
+            .filter_map(|backend| {
+                let m = backend.models.iter().find(|m| m.id == candidate)?;
+                let mut missing = missing_capabilities(m, requirements);
+                if self.tool_emulation {
+                    missing.retain(|capability| *capability != "tools");
+                }
+                (serves_chat(backend, m) && missing.is_empty())
+                    .then(|| (backend.id.clone(), m.context_length))
+            })
+            .collect();
+
+        let mut intent = RoutingIntent::new(
+            String::new(),
+            candidate.to_string(),
+            candidate.to_string(),
+            requirements.clone(),
+            eligible.iter().map(|(id, _)| id.clone()).collect(),
+        );
+        privacy.reconcile(&mut intent).ok()?;
+        tier.reconcile(&mut intent).ok()?;
+
+        eligible
+            .into_iter()
+            .filter(|(id, _)| intent.candidate_agents.contains(id))
+            .map(|(_, context_length)| context_length)
+            .max()
+    }
+}
+
+/// Whether a backend's model can answer chat completions.
+fn serves_chat(backend: &Backend, model: &Model) -> bool {
+    backend.backend_type != BackendType::TEI
+        && !model.supports_embeddings
+        && !model.id.to_lowercase().contains("rerank")
+}
+
+/// The oldest group of messages that can be dropped, if any.
+///
+/// System messages and the latest message are kept. An assistant message
+/// that calls tools is dropped together with the tool replies that follow
+/// it, since backends reject a tool reply without its call and vice versa.
+fn oldest_droppable_group(messages: &[ChatMessage]) -> Option<Range<usize>> {
+    let last = messages.len().saturating_sub(1);
+    let mut start = 0;
+    while start < last {
+        let mut end = start + 1;
+        if messages[start].tool_calls.is_some() {
+            end += messages[end..]
+                .iter()
+                .take_while(|m| m.role == "tool")
+                .count();
+        }
+        if end > last {
+            return None;
+        }
+        if messages[start].role != "system" {
+            return Some(start..end);
+        }
+        start = end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{ChatMessage, FunctionCall, MessageContent, ToolCall};
+    use crate::config::{PolicyMatcher, PrivacyConstraint, QualityConfig, TrafficPolicy};
+    use crate::registry::{DiscoverySource, Registry};
+    use crate::routing::{RoutingStrategy, ScoringWeights};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn model(id: &str, context_length: u32) -> Model {
+        Model {
+            id: id.to_string(),
+            name: id.to_string(),
+            context_length,
+            supports_vision: false,
+            supports_tools: false,
+            supports_json_mode: false,
+            supports_embeddings: false,
+            max_output_tokens: None,
+        }
+    }
+
+    fn router_with(models: Vec<Model>, fallbacks: HashMap<String, Vec<String>>) -> Router {
+        let registry = Arc::new(Registry::new());
+        let backend = Backend::new(
+            "b1".to_string(),
+            "Backend 1".to_string(),
+            "http://localhost:11434".to_string(),
+            BackendType::Ollama,
+            models,
+            DiscoverySource::Static,
+            HashMap::new(),
+        );
+        registry.add_backend(backend).unwrap();
+        registry
+            .update_status("b1", BackendStatus::Healthy, None)
+            .unwrap();
+        Router::with_aliases_and_fallbacks(
+            registry,
+            RoutingStrategy::Smart,
+            ScoringWeights::default(),
+            HashMap::new(),
+            fallbacks,
+        )
+    }
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: MessageContent::Text {
+                content: content.to_string(),
+            },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+
+    fn request(model: &str, messages: Vec<ChatMessage>, max_tokens: u32) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model.to_string(),
+            messages,
+            stream: false,
+            temperature: None,
+            max_tokens: Some(max_tokens),
+            top_p: None,
+            stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            user: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    fn long_conversation() -> Vec<ChatMessage> {
+        let filler = "word ".repeat(200);
+        vec![
+            message("system", "You are helpful."),
+            message("user", &filler),
+            message("assistant", &filler),
+            message("user", &filler),
+            message("assistant", &filler),
+            message("user", "Summarize."),
+        ]
+    }
+
+    #[test]
+    fn request_that_fits_is_untouched() {
+        let router = router_with(vec![model("small", 4096)], HashMap::new());
+        let mut req = request("small", vec![message("user", "Hi")], 100);
+        let mut requirements = router.requirements_for(&req);
+
+        let fit = router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Reject)
+            .unwrap();
+
+        assert_eq!(fit, ContextFit::Fits);
+        assert_eq!(req.messages.len(), 1);
+    }
+
+    #[test]
+    fn reject_mode_reports_token_counts() {
+        let router = router_with(vec![model("small", 1000)], HashMap::new());
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+
+        let err = router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Reject)
+            .unwrap_err();
+
+        assert_eq!(err.context_length, 1000);
+        assert_eq!(err.max_tokens, 2000);
+        assert!(err.to_string().contains("max_tokens"));
+    }
+
+    #[test]
+    fn upgrade_prefers_fallback_chain() {
+        let fallbacks = HashMap::from([("small".to_string(), vec!["large".to_string()])]);
+        let router = router_with(
+            vec![
+                model("small", 1000),
+                model("medium", 8192),
+                model("large", 32768),
+            ],
+            fallbacks,
+        );
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+
+        let fit = router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .unwrap();
+
+        assert_eq!(
+            fit,
+            ContextFit::Upgraded {
+                from: "small".to_string(),
+                to: "large".to_string()
+            }
+        );
+        assert_eq!(req.model, "large");
+        assert_eq!(requirements.model, "large");
+    }
+
+    #[test]
+    fn upgrade_picks_smallest_sufficient_model() {
+        let router = router_with(
+            vec![
+                model("small", 1000),
+                model("medium", 8192),
+                model("large", 32768),
+            ],
+            HashMap::new(),
+        );
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+
+        router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .unwrap();
+
+        assert_eq!(req.model, "medium");
+    }
+
+    #[test]
+    fn upgrade_without_larger_model_fails() {
+        let router = router_with(vec![model("small", 1000)], HashMap::new());
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+
+        assert!(router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .is_err());
+    }
+
+    #[test]
+    fn truncate_drops_oldest_non_system_messages() {
+        let router = router_with(vec![model("small", 800)], HashMap::new());
+        let mut req = request("small", long_conversation(), 100);
+        let mut requirements = router.requirements_for(&req);
+
+        let fit = router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Truncate)
+            .unwrap();
+
+        let ContextFit::Truncated { dropped } = fit else {
+            panic!("expected truncation, got {:?}", fit);
+        };
+        assert!(dropped > 0);
+        assert_eq!(req.messages.len(), 6 - dropped);
+        assert_eq!(req.messages[0].role, "system");
+        assert!(requirements.required_context() <= 800);
+        let MessageContent::Text { content } = &req.messages.last().unwrap().content else {
+            panic!("expected text content");
+        };
+        assert_eq!(content, "Summarize.");
+    }
+
+    #[test]
+    fn truncate_fails_when_latest_message_alone_overflows() {
+        let router = router_with(vec![model("small", 100)], HashMap::new());
+        let mut req = request(
+            "small",
+            vec![
+                message("system", "You are helpful."),
+                message("user", &"word ".repeat(200)),
+            ],
+            10,
+        );
+        let mut requirements = router.requirements_for(&req);
+
+        let err = router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Truncate)
+            .unwrap_err();
+
+        assert!(err.suggestion.contains("latest message"));
+        assert_eq!(req.messages.len(), 2);
+    }
+
+    fn add_backend(router: &Router, id: &str, backend_type: BackendType, models: Vec<Model>) {
+        let backend = Backend::new(
+            id.to_string(),
+            id.to_string(),
+            "http://localhost:8000".to_string(),
+            backend_type,
+            models,
+            DiscoverySource::Static,
+            HashMap::new(),
+        );
+        router.registry.add_backend(backend).unwrap();
+        router
+            .registry
+            .update_status(id, BackendStatus::Healthy, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn upgrade_skips_embedding_and_rerank_models() {
+        let router = router_with(
+            vec![
+                model("small", 1000),
+                Model {
+                    supports_embeddings: true,
+                    ..model("nomic-embed-text", 8192)
+                },
+                model("bge-reranker", 8192),
+                model("large", 32768),
+            ],
+            HashMap::new(),
+        );
+        add_backend(&router, "tei", BackendType::TEI, vec![model("gte", 16384)]);
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+
+        router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .unwrap();
+
+        assert_eq!(req.model, "large");
+    }
+
+    #[test]
+    fn upgrade_keeps_restricted_requests_local() {
+        let router = router_with(vec![model("small", 1000)], HashMap::new());
+        add_backend(
+            &router,
+            "cloud",
+            BackendType::OpenAI,
+            vec![model("gpt-4o", 128000)],
+        );
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+        requirements.privacy_constraint = Some(PrivacyZone::Restricted);
+
+        assert!(router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .is_err());
+        assert_eq!(req.model, "small");
+
+        // The same request without the constraint may use the cloud model
+        let mut requirements = router.requirements_for(&req);
+        router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .unwrap();
+        assert_eq!(req.model, "gpt-4o");
+    }
+
+    #[test]
+    fn upgrade_honours_policy_of_requested_model() {
+        let registry = Arc::new(Registry::new());
+        let policies = PolicyMatcher::compile(vec![TrafficPolicy {
+            model_pattern: "small".to_string(),
+            privacy: PrivacyConstraint::Restricted,
+            max_cost_per_request: None,
+            min_tier: None,
+            fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
+        }])
+        .unwrap();
+        let router = Router::with_aliases_fallbacks_and_policies(
+            registry,
+            RoutingStrategy::Smart,
+            ScoringWeights::default(),
+            HashMap::new(),
+            HashMap::new(),
+            policies,
+            QualityConfig::default(),
+        );
+        add_backend(
+            &router,
+            "local",
+            BackendType::Ollama,
+            vec![model("small", 1000)],
+        );
+        add_backend(
+            &router,
+            "cloud",
+            BackendType::OpenAI,
+            vec![model("gpt-4o", 128000)],
+        );
+        add_backend(
+            &router,
+            "local-large",
+            BackendType::VLLM,
+            vec![model("llama-70b", 32768)],
+        );
+        let mut req = request("small", vec![message("user", "Hi")], 2000);
+        let mut requirements = router.requirements_for(&req);
+
+        router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Upgrade)
+            .unwrap();
+
+        assert_eq!(req.model, "llama-70b");
+        assert_eq!(
+            requirements.privacy_constraint,
+            Some(PrivacyZone::Restricted)
+        );
+    }
+
+    #[test]
+    fn truncate_drops_tool_calls_with_their_replies() {
+        let filler = "word ".repeat(200);
+        let mut call = message("assistant", "");
+        call.tool_calls = Some(vec![ToolCall {
+            id: "call_1".to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: "search".to_string(),
+                arguments: "{}".to_string(),
+            },
+        }]);
+        let mut reply = message("tool", &filler);
+        reply.tool_call_id = Some("call_1".to_string());
+        let router = router_with(vec![model("small", 400)], HashMap::new());
+        let mut req = request(
+            "small",
+            vec![
+                message("system", "You are helpful."),
+                call,
+                reply,
+                message("assistant", "Found it."),
+                message("user", "Summarize."),
+            ],
+            100,
+        );
+        let mut requirements = router.requirements_for(&req);
+
+        let fit = router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Truncate)
+            .unwrap();
+
+        assert_eq!(fit, ContextFit::Truncated { dropped: 2 });
+        let roles: Vec<&str> = req.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "assistant", "user"]);
+        assert!(req.messages[1].tool_calls.is_none());
+    }
+
+    #[test]
+    fn truncate_keeps_call_answered_by_latest_message() {
+        let mut call = message("assistant", &"word ".repeat(200));
+        call.tool_calls = Some(vec![]);
+        let router = router_with(vec![model("small", 100)], HashMap::new());
+        let mut req = request(
+            "small",
+            vec![message("system", "Be brief."), call, message("tool", "42")],
+            10,
+        );
+        let mut requirements = router.requirements_for(&req);
+
+        assert!(router
+            .fit_context_window(&mut req, &mut requirements, ContextOverflowMode::Truncate)
+            .is_err());
+        assert_eq!(req.messages.len(), 3);
+    }
+
+    #[test]
+    fn policy_selects_overflow_mode() {
+        let registry = Arc::new(Registry::new());
+        let policies = PolicyMatcher::compile(vec![TrafficPolicy {
+            model_pattern: "llama*".to_string(),
+            privacy: PrivacyConstraint::Unrestricted,
+            max_cost_per_request: None,
+            min_tier: None,
+            fallback_allowed: true,
+            context_overflow: Some(ContextOverflowMode::Truncate),
+            cache: None,
+            injection_action: None,
+        }])
+        .unwrap();
+        let router = Router::with_aliases_fallbacks_and_policies(
+            registry,
+            RoutingStrategy::Smart,
+            ScoringWeights::default(),
+            HashMap::from([("chat".to_string(), "llama3:8b".to_string())]),
+            HashMap::new(),
+            policies,
+            QualityConfig::default(),
+        );
+
+        assert_eq!(
+            router.context_overflow_policy("chat"),
+            Some(ContextOverflowMode::Truncate)
+        );
+        assert_eq!(router.context_overflow_policy("mistral"), None);
+    }
+}
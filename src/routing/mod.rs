@@ -8,27 +8,45 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, AtomicU64};
 use std::sync::Arc;
 
+pub mod content;
+pub mod context;
 pub mod error;
 pub mod reconciler;
 pub mod requirements;
 pub mod scoring;
+pub mod screening;
+pub mod semantic;
 pub mod strategies; // Reconciler pipeline module
+pub mod transform;
 
+pub use content::{ContentRuleMatcher, RequestFeatures};
+pub use context::{ContextFit, ContextOverflowError};
 pub use error::RoutingError;
 pub use requirements::RequestRequirements;
 pub use scoring::{score_backend, ScoringWeights};
+pub use semantic::{SemanticDecision, SEMANTIC_AUTO_MODEL};
 pub use strategies::RoutingStrategy;
+pub use transform::TransformChain;
 
+use crate::agent::circuit_breaker::CircuitBreakerStore;
+use crate::agent::pricing::PricingTable;
 use crate::agent::quality::QualityMetricsStore;
-use crate::agent::tokenizer::TokenizerRegistry;
-use crate::config::{BudgetConfig, PolicyMatcher, QualityConfig};
-use crate::registry::{Backend, BackendStatus, Registry};
+use crate::agent::tokenizer::{TokenizerError, TokenizerRegistry};
+use crate::config::{
+    BudgetConfig, CircuitBreakerConfig, ConfigError, PiiConfig, PolicyMatcher, QualityConfig,
+    ScreeningConfig, TokenizerFile,
+};
+use crate::pii::PiiScanner;
+use crate::registry::{Backend, BackendStatus, BackendType, Registry};
 use crate::routing::reconciler::budget::BudgetMetrics;
 use dashmap::DashMap;
 use reconciler::budget::BudgetReconciler;
+use reconciler::circuit_breaker::CircuitBreakerReconciler;
 use reconciler::decision::RoutingDecision;
+use reconciler::injection::InjectionReconciler;
 use reconciler::intent::RoutingIntent;
 use reconciler::lifecycle::LifecycleReconciler;
+use reconciler::pii::PiiReconciler;
 use reconciler::privacy::PrivacyReconciler;
 use reconciler::quality::QualityReconciler;
 use reconciler::request_analyzer::RequestAnalyzer;
@@ -83,6 +101,27 @@ pub struct Router {
     /// Pre-compiled traffic policy matcher for privacy enforcement
     policy_matcher: PolicyMatcher,
 
+    /// Pre-compiled content-based routing rules
+    content_rules: ContentRuleMatcher,
+
+    /// Embedding classifier for `nexus/auto` (None when disabled)
+    semantic: Option<semantic::SemanticClassifier>,
+
+    /// Pre-compiled request transform chain
+    transforms: TransformChain,
+
+    /// Prompt injection screening settings (None when disabled)
+    screening: Option<ScreeningConfig>,
+
+    /// Route tool requests to models without native tool support
+    tool_emulation: bool,
+
+    /// Compiled PII detectors (None when PII detection is disabled)
+    pii_scanner: Option<Arc<PiiScanner>>,
+
+    /// Redact PII for cloud agents instead of restricting to local backends
+    pii_redact_cloud: bool,
+
     /// Budget configuration for cost enforcement
     budget_config: BudgetConfig,
 
@@ -92,12 +131,18 @@ pub struct Router {
     /// Tokenizer registry for accurate cost estimation (F14)
     tokenizer_registry: Arc<TokenizerRegistry>,
 
+    /// Per-model pricing for cost estimation, including provider catalogue prices
+    pricing: PricingTable,
+
     /// Shared quality metrics store for quality-aware routing
     quality_store: Arc<QualityMetricsStore>,
 
     /// Quality configuration for thresholds
     quality_config: QualityConfig,
 
+    /// Shared per-agent circuit breakers for fast failure isolation
+    circuit_breakers: Arc<CircuitBreakerStore>,
+
     /// Whether request queuing is enabled (T026)
     queue_enabled: bool,
 }
@@ -121,11 +166,20 @@ impl Router {
             fallbacks: HashMap::new(),
             round_robin_counter: Arc::new(AtomicU64::new(0)),
             policy_matcher: PolicyMatcher::default(),
+            content_rules: ContentRuleMatcher::default(),
+            semantic: None,
+            transforms: TransformChain::default(),
+            screening: None,
+            tool_emulation: false,
+            pii_scanner: None,
+            pii_redact_cloud: false,
             budget_config: BudgetConfig::default(),
             budget_state: Arc::new(DashMap::new()),
             tokenizer_registry,
+            pricing: PricingTable::new(),
             quality_store,
             quality_config,
+            circuit_breakers: Arc::new(CircuitBreakerStore::new(CircuitBreakerConfig::default())),
             queue_enabled: false,
         }
     }
@@ -150,11 +204,20 @@ impl Router {
             fallbacks,
             round_robin_counter: Arc::new(AtomicU64::new(0)),
             policy_matcher: PolicyMatcher::default(),
+            content_rules: ContentRuleMatcher::default(),
+            semantic: None,
+            transforms: TransformChain::default(),
+            screening: None,
+            tool_emulation: false,
+            pii_scanner: None,
+            pii_redact_cloud: false,
             budget_config: BudgetConfig::default(),
             budget_state: Arc::new(DashMap::new()),
             tokenizer_registry,
+            pricing: PricingTable::new(),
             quality_store,
             quality_config,
+            circuit_breakers: Arc::new(CircuitBreakerStore::new(CircuitBreakerConfig::default())),
             queue_enabled: false,
         }
     }
@@ -180,11 +243,20 @@ impl Router {
             fallbacks,
             round_robin_counter: Arc::new(AtomicU64::new(0)),
             policy_matcher,
+            content_rules: ContentRuleMatcher::default(),
+            semantic: None,
+            transforms: TransformChain::default(),
+            screening: None,
+            tool_emulation: false,
+            pii_scanner: None,
+            pii_redact_cloud: false,
             budget_config: BudgetConfig::default(),
             budget_state: Arc::new(DashMap::new()),
             tokenizer_registry,
+            pricing: PricingTable::new(),
             quality_store,
             quality_config,
+            circuit_breakers: Arc::new(CircuitBreakerStore::new(CircuitBreakerConfig::default())),
             queue_enabled: false,
         }
     }
@@ -213,11 +285,20 @@ impl Router {
             fallbacks,
             round_robin_counter: Arc::new(AtomicU64::new(0)),
             policy_matcher,
+            content_rules: ContentRuleMatcher::default(),
+            semantic: None,
+            transforms: TransformChain::default(),
+            screening: None,
+            tool_emulation: false,
+            pii_scanner: None,
+            pii_redact_cloud: false,
             budget_config,
             budget_state,
             tokenizer_registry,
+            pricing: PricingTable::new(),
             quality_store,
             quality_config,
+            circuit_breakers: Arc::new(CircuitBreakerStore::new(CircuitBreakerConfig::default())),
             queue_enabled: false,
         }
     }
@@ -262,8 +343,10 @@ impl Router {
     }
 
     /// Build a reconciler pipeline for the given model
-    /// Order: RequestAnalyzer → LifecycleReconciler → PrivacyReconciler → BudgetReconciler
-    ///        → TierReconciler → QualityReconciler → SchedulerReconciler
+    /// Order: RequestAnalyzer → LifecycleReconciler → [PiiReconciler] → PrivacyReconciler
+    ///        → BudgetReconciler
+    ///        → TierReconciler → CircuitBreakerReconciler → QualityReconciler
+    ///        → SchedulerReconciler
     fn build_pipeline(&self, model_aliases: HashMap<String, String>) -> ReconcilerPipeline {
         let analyzer = RequestAnalyzer::new(model_aliases, Arc::clone(&self.registry));
         let lifecycle = LifecycleReconciler::new(Arc::clone(&self.registry));
@@ -274,8 +357,10 @@ impl Router {
             self.budget_config.clone(),
             Arc::clone(&self.tokenizer_registry),
             Arc::clone(&self.budget_state),
-        );
+        )
+        .with_pricing(self.pricing.clone());
         let tier = TierReconciler::new(Arc::clone(&self.registry), self.policy_matcher.clone());
+        let circuit_breaker = CircuitBreakerReconciler::new(Arc::clone(&self.circuit_breakers));
         let quality =
             QualityReconciler::new(Arc::clone(&self.quality_store), self.quality_config.clone());
         let scheduler = SchedulerReconciler::new(
@@ -285,19 +370,33 @@ impl Router {
             Arc::clone(&self.round_robin_counter),
             Arc::clone(&self.quality_store),
             self.quality_config.clone(),
-        );
-        ReconcilerPipeline::with_queue(
-            vec![
-                Box::new(analyzer),
-                Box::new(lifecycle),
-                Box::new(privacy),
-                Box::new(budget),
-                Box::new(tier),
-                Box::new(quality),
-                Box::new(scheduler),
-            ],
-            self.queue_enabled,
         )
+        .with_tool_emulation(self.tool_emulation);
+        let mut reconcilers: Vec<Box<dyn reconciler::Reconciler>> =
+            vec![Box::new(analyzer), Box::new(lifecycle)];
+        if let Some(scanner) = &self.pii_scanner {
+            reconcilers.push(Box::new(PiiReconciler::new(
+                Arc::clone(&self.registry),
+                Arc::clone(scanner),
+                self.pii_redact_cloud,
+            )));
+        }
+        if let Some(screening) = &self.screening {
+            reconcilers.push(Box::new(InjectionReconciler::new(
+                self.policy_matcher.clone(),
+                screening.threshold,
+                screening.action,
+            )));
+        }
+        reconcilers.extend([
+            Box::new(privacy) as Box<dyn reconciler::Reconciler>,
+            Box::new(budget),
+            Box::new(tier),
+            Box::new(circuit_breaker),
+            Box::new(quality),
+            Box::new(scheduler),
+        ]);
+        ReconcilerPipeline::with_queue(reconcilers, self.queue_enabled)
     }
 
     /// Run the pipeline for a specific model (primary or fallback) and return the decision
@@ -477,9 +576,7 @@ impl Router {
             .iter()
             .max_by_key(|backend| {
                 let priority = backend.priority as u32;
-                let pending = backend
-                    .pending_requests
-                    .load(std::sync::atomic::Ordering::Relaxed);
+                let pending = backend.queue_depth();
                 let latency = backend
                     .avg_latency_ms
                     .load(std::sync::atomic::Ordering::Relaxed);
@@ -510,6 +607,10 @@ impl Router {
                 best.avg_latency_ms
                     .load(std::sync::atomic::Ordering::Relaxed),
             ),
+            reported_queue_depth: AtomicU32::new(
+                best.reported_queue_depth
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
             discovery_source: best.discovery_source,
             metadata: best.metadata.clone(),
             current_operation: best.current_operation.clone(),
@@ -547,6 +648,10 @@ impl Router {
                 best.avg_latency_ms
                     .load(std::sync::atomic::Ordering::Relaxed),
             ),
+            reported_queue_depth: AtomicU32::new(
+                best.reported_queue_depth
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
             discovery_source: best.discovery_source,
             metadata: best.metadata.clone(),
             current_operation: best.current_operation.clone(),
@@ -588,6 +693,10 @@ impl Router {
                 best.avg_latency_ms
                     .load(std::sync::atomic::Ordering::Relaxed),
             ),
+            reported_queue_depth: AtomicU32::new(
+                best.reported_queue_depth
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
             discovery_source: best.discovery_source,
             metadata: best.metadata.clone(),
             current_operation: best.current_operation.clone(),
@@ -622,8 +731,13 @@ impl Router {
                     return false;
                 }
 
-                // Check context length
-                if requirements.estimated_tokens > model_info.context_length {
+                // Check embeddings capability
+                if requirements.needs_embeddings && !model_info.supports_embeddings {
+                    return false;
+                }
+
+                // Check context length, reserving max_tokens for the output
+                if requirements.required_context() > model_info.context_length {
                     return false;
                 }
 
@@ -652,6 +766,88 @@ impl Router {
         &self.quality_store
     }
 
+    /// Get reference to the circuit breaker store.
+    pub fn circuit_breakers(&self) -> &Arc<CircuitBreakerStore> {
+        &self.circuit_breakers
+    }
+
+    /// Record a request outcome in the quality store and the agent's circuit breaker.
+    pub fn record_backend_outcome(&self, backend_id: &str, success: bool, ttft_ms: u32) {
+        self.quality_store
+            .record_outcome(backend_id, success, ttft_ms);
+        if success {
+            self.circuit_breakers.record_success(backend_id);
+        } else {
+            self.circuit_breakers.record_failure(backend_id);
+        }
+    }
+
+    /// Record a failed attempt.
+    ///
+    /// Client errors (4xx) count against quality but not toward the circuit
+    /// breaker: the backend answered, it was the request that was rejected.
+    pub fn record_backend_failure(&self, backend_id: &str, client_error: bool, ttft_ms: u32) {
+        if client_error {
+            self.quality_store
+                .record_outcome(backend_id, false, ttft_ms);
+        } else {
+            self.record_backend_outcome(backend_id, false, ttft_ms);
+        }
+    }
+
+    /// Replace the circuit breaker store with one using the given configuration.
+    pub fn set_circuit_breaker_config(&mut self, config: CircuitBreakerConfig) {
+        self.circuit_breakers = Arc::new(CircuitBreakerStore::new(config));
+    }
+
+    /// Enable PII detection with the given detectors (disabled if `config.enabled` is false).
+    pub fn set_pii_config(&mut self, config: &PiiConfig) -> Result<(), ConfigError> {
+        self.pii_scanner = if config.enabled {
+            Some(Arc::new(PiiScanner::new(config)?))
+        } else {
+            None
+        };
+        self.pii_redact_cloud = config.redact_cloud;
+        Ok(())
+    }
+
+    /// Response caching override from the traffic policy matching a model, if any.
+    pub fn cache_policy(&self, model: &str) -> Option<bool> {
+        self.policy_matcher
+            .find_policy(model)
+            .and_then(|policy| policy.cache)
+    }
+
+    /// PII scanner to redact requests dispatched to the given backend type.
+    ///
+    /// Returns `None` unless cloud redaction is enabled and the backend type
+    /// is one of the redacting cloud agents.
+    pub fn pii_redactor(&self, backend_type: BackendType) -> Option<&PiiScanner> {
+        if !self.pii_redact_cloud || !crate::pii::supports_redaction(backend_type) {
+            return None;
+        }
+        self.pii_scanner.as_deref()
+    }
+
+    /// Load Hugging Face tokenizer files for exact token counting.
+    pub fn set_tokenizer_files(&mut self, files: &[TokenizerFile]) -> Result<(), TokenizerError> {
+        if !files.is_empty() {
+            self.tokenizer_registry = Arc::new(TokenizerRegistry::with_files(files)?);
+        }
+        Ok(())
+    }
+
+    /// Replace the pricing table used for budget cost estimation.
+    pub fn set_pricing(&mut self, pricing: PricingTable) {
+        self.pricing = pricing;
+    }
+
+    /// Set whether tool requests may route to models without native tool
+    /// support, for the API layer to emulate tool calling.
+    pub fn set_tool_emulation(&mut self, enabled: bool) {
+        self.tool_emulation = enabled;
+    }
+
     /// Set whether request queuing is enabled (T026).
     pub fn set_queue_enabled(&mut self, enabled: bool) {
         self.queue_enabled = enabled;
@@ -793,6 +989,7 @@ mod filter_tests {
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -812,6 +1009,7 @@ mod filter_tests {
             supports_vision,
             supports_tools,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }
     }
@@ -849,7 +1047,15 @@ mod filter_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -881,7 +1087,15 @@ mod filter_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -913,7 +1127,15 @@ mod filter_tests {
             needs_vision: true,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -945,7 +1167,15 @@ mod filter_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -969,7 +1199,15 @@ mod filter_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("nonexistent", &requirements);
@@ -1000,7 +1238,15 @@ mod filter_tests {
             needs_vision: false,
             needs_tools: true,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -1017,6 +1263,7 @@ mod filter_tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
         let model_with_json = Model {
@@ -1026,6 +1273,7 @@ mod filter_tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
 
@@ -1051,7 +1299,15 @@ mod filter_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: true,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -1068,6 +1324,7 @@ mod filter_tests {
             supports_vision: true,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: None,
         };
 
@@ -1093,7 +1350,15 @@ mod filter_tests {
             needs_vision: true,
             needs_tools: true,
             needs_json_mode: true,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let candidates = router.filter_candidates("llama3:8b", &requirements);
@@ -1132,12 +1397,14 @@ mod smart_strategy_tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority,
             pending_requests: AtomicU32::new(pending_requests),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(avg_latency_ms),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -1169,7 +1436,15 @@ mod smart_strategy_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1191,7 +1466,15 @@ mod smart_strategy_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1213,7 +1496,15 @@ mod smart_strategy_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1237,7 +1528,15 @@ mod smart_strategy_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -1269,12 +1568,14 @@ mod other_strategies_tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -1308,7 +1609,15 @@ mod other_strategies_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // Should cycle through: A, B, C, A, B, C
@@ -1347,7 +1656,15 @@ mod other_strategies_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // Should always select Backend B (priority 1)
@@ -1372,7 +1689,15 @@ mod other_strategies_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // Should select from all three backends over many iterations
@@ -1413,12 +1738,14 @@ mod alias_and_fallback_tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -1455,7 +1782,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1495,7 +1830,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1535,7 +1878,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -1578,7 +1929,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1618,7 +1977,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // When resolving "gpt-4"
@@ -1660,7 +2027,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // When resolving "a"
@@ -1703,7 +2078,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // When resolving "a" (4-level chain)
@@ -1743,7 +2126,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None).unwrap();
@@ -1783,7 +2174,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // When select_backend("primary")
@@ -1828,7 +2227,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         // When select_backend("primary")
@@ -1881,7 +2288,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
         let result = router.select_backend(&requirements, None);
         assert!(
@@ -1928,7 +2343,15 @@ mod alias_and_fallback_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
         let result = router.select_backend(&requirements, None).unwrap();
         assert_eq!(result.backend.name, "Backend W");
@@ -1961,12 +2384,14 @@ mod constructor_tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -1980,7 +2405,15 @@ mod constructor_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         }
     }
 
@@ -2071,6 +2504,7 @@ mod select_backend_error_tests {
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -2084,7 +2518,15 @@ mod select_backend_error_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         }
     }
 
@@ -2103,6 +2545,7 @@ mod select_backend_error_tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             ))
@@ -2135,6 +2578,7 @@ mod select_backend_error_tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             ))
@@ -2168,6 +2612,7 @@ mod select_backend_error_tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 }],
             ))
@@ -2181,7 +2626,15 @@ mod select_backend_error_tests {
             needs_vision: true,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -2235,12 +2688,14 @@ mod budget_status_tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -2411,7 +2866,15 @@ mod budget_status_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -2441,6 +2904,7 @@ mod budget_status_tests {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         });
         registry.add_backend(backend).unwrap();
@@ -2472,7 +2936,15 @@ mod budget_status_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -2530,7 +3002,15 @@ mod budget_status_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -2580,7 +3060,15 @@ mod budget_status_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let result = router.select_backend(&requirements, None);
@@ -2617,7 +3105,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             None,
         );
@@ -2652,7 +3148,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             None,
         );
@@ -2684,7 +3188,15 @@ mod budget_status_tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
 
         let r1 = router.select_backend(&reqs, None).unwrap();
@@ -2709,7 +3221,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             None,
         );
@@ -2731,7 +3251,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             Some(crate::routing::reconciler::intent::TierEnforcementMode::Strict),
         );
@@ -2758,7 +3286,15 @@ mod budget_status_tests {
                 needs_vision: true,
                 needs_tools: true,
                 needs_json_mode: true,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert_eq!(candidates.len(), 1);
@@ -2781,7 +3317,15 @@ mod budget_status_tests {
                 needs_vision: true,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert!(candidates.is_empty());
@@ -2804,7 +3348,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: true,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert!(candidates.is_empty());
@@ -2827,7 +3379,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: true,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert!(candidates.is_empty());
@@ -2850,7 +3410,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert!(candidates.is_empty());
@@ -2880,7 +3448,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert!(candidates.is_empty());
@@ -2903,7 +3479,15 @@ mod budget_status_tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
         );
         assert!(candidates.is_empty());
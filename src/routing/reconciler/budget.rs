@@ -100,6 +100,12 @@ impl BudgetReconciler {
         }
     }
 
+    /// Use `pricing` instead of the built-in pricing table.
+    pub fn with_pricing(mut self, pricing: PricingTable) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
     /// Estimate cost for a request (FR-017, FR-018).
     ///
     /// Uses TokenizerRegistry when prompt text is available for accurate counting.
@@ -128,6 +134,17 @@ impl BudgetReconciler {
         }
     }
 
+    /// Estimate cost for a routing intent, reporting the exact tier when the
+    /// prompt was counted by the backend's own tokenizer.
+    fn estimate_intent_cost(&self, intent: &RoutingIntent) -> CostEstimate {
+        let mut estimate =
+            self.estimate_cost(&intent.resolved_model, intent.requirements.estimated_tokens);
+        if intent.requirements.prompt_tokens_exact {
+            estimate.token_count_tier = CostEstimate::TIER_EXACT;
+        }
+        estimate
+    }
+
     /// Calculate budget status based on current spending vs limits (FR-019).
     fn calculate_budget_status(&self) -> BudgetStatus {
         let monthly_limit = match self.config.monthly_limit_usd {
@@ -217,14 +234,12 @@ impl Reconciler for BudgetReconciler {
         // FR-016: No budget configured → pass through
         if self.config.monthly_limit_usd.is_none() {
             // Still populate cost estimate for informational purposes
-            intent.cost_estimate =
-                self.estimate_cost(&intent.resolved_model, intent.requirements.estimated_tokens);
+            intent.cost_estimate = self.estimate_intent_cost(intent);
             return Ok(());
         }
 
         // Step 1: Estimate cost for this request (FR-017, FR-018)
-        let cost_estimate =
-            self.estimate_cost(&intent.resolved_model, intent.requirements.estimated_tokens);
+        let cost_estimate = self.estimate_intent_cost(intent);
         intent.cost_estimate = cost_estimate.clone();
 
         // Record cost metric (US2: Precise Tracking)
@@ -462,12 +477,14 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -485,7 +502,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             candidates,
         )
@@ -566,6 +591,52 @@ mod tests {
         assert_eq!(estimate.cost_usd, 0.0); // Local models have no pricing
     }
 
+    #[test]
+    fn estimates_cost_from_custom_pricing() {
+        let registry = Arc::new(Registry::new());
+        let reconciler = BudgetReconciler::new(
+            Arc::clone(&registry),
+            budget_config(Some(100.0), HardLimitAction::Warn),
+            tokenizer_registry(),
+            Arc::new(DashMap::new()),
+        )
+        .with_pricing(PricingTable::new().with_models([(
+            "mistral-large-latest".to_string(),
+            crate::agent::pricing::ModelPricing {
+                input_price_per_1k: 0.002,
+                output_price_per_1k: 0.006,
+            },
+        )]));
+
+        let estimate = reconciler.estimate_cost("mistral-large-latest", 1000);
+        assert!((estimate.cost_usd - 0.005).abs() < 1e-9);
+    }
+
+    #[test]
+    fn backend_counted_prompt_reports_exact_tier() {
+        let registry = Arc::new(Registry::new());
+        let reconciler = BudgetReconciler::new(
+            Arc::clone(&registry),
+            budget_config(None, HardLimitAction::Warn),
+            tokenizer_registry(),
+            Arc::new(DashMap::new()),
+        );
+
+        let mut intent = create_intent("llama3:8b", vec![]);
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(
+            intent.cost_estimate.token_count_tier,
+            CostEstimate::TIER_HEURISTIC
+        );
+
+        intent.requirements.prompt_tokens_exact = true;
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(
+            intent.cost_estimate.token_count_tier,
+            CostEstimate::TIER_EXACT
+        );
+    }
+
     // === FR-019: Budget status transitions ===
 
     #[test]
@@ -0,0 +1,171 @@
+//! CircuitBreakerReconciler - excludes agents with open circuits
+//!
+//! Reads each candidate's circuit state from the CircuitBreakerStore and
+//! excludes agents whose circuit is open or whose half-open trial slots are
+//! all in use.
+
+use super::intent::RoutingIntent;
+use super::Reconciler;
+use crate::agent::circuit_breaker::{CircuitAdmission, CircuitBreakerStore};
+use crate::routing::error::RoutingError;
+use std::sync::Arc;
+
+/// CircuitBreakerReconciler filters agents by circuit breaker state.
+///
+/// # Pipeline Position
+/// RequestAnalyzer → PrivacyReconciler → BudgetReconciler → TierReconciler
+/// → **CircuitBreakerReconciler** → QualityReconciler → SchedulerReconciler
+///
+/// # Behavior
+/// - Closed circuits pass through
+/// - Open circuits are excluded with the remaining open time
+/// - Half-open circuits pass while trial slots remain
+pub struct CircuitBreakerReconciler {
+    store: Arc<CircuitBreakerStore>,
+}
+
+impl CircuitBreakerReconciler {
+    /// Create a new CircuitBreakerReconciler with the circuit breaker store.
+    pub fn new(store: Arc<CircuitBreakerStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl Reconciler for CircuitBreakerReconciler {
+    fn name(&self) -> &'static str {
+        "CircuitBreakerReconciler"
+    }
+
+    fn reconcile(&self, intent: &mut RoutingIntent) -> Result<(), RoutingError> {
+        let candidates: Vec<String> = intent.candidate_agents.clone();
+
+        for agent_id in &candidates {
+            match self.store.admission(agent_id) {
+                CircuitAdmission::Allowed => {}
+                CircuitAdmission::Open { retry_after_secs } => {
+                    intent.exclude_agent(
+                        agent_id.clone(),
+                        "CircuitBreakerReconciler",
+                        format!(
+                            "Circuit open after {} consecutive failures",
+                            self.store.config().failure_threshold
+                        ),
+                        format!(
+                            "Wait for the circuit to half-open (ETA: {}s)",
+                            retry_after_secs
+                        ),
+                    );
+                }
+                CircuitAdmission::HalfOpenSaturated => {
+                    intent.exclude_agent(
+                        agent_id.clone(),
+                        "CircuitBreakerReconciler",
+                        "Circuit half-open with all trial requests in flight".to_string(),
+                        "Wait for trial requests to complete".to_string(),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CircuitBreakerConfig;
+    use crate::routing::RequestRequirements;
+
+    fn create_intent(candidates: Vec<String>) -> RoutingIntent {
+        RoutingIntent::new(
+            "req-1".to_string(),
+            "llama3:8b".to_string(),
+            "llama3:8b".to_string(),
+            RequestRequirements {
+                model: "llama3:8b".to_string(),
+                estimated_tokens: 100,
+                needs_vision: false,
+                needs_tools: false,
+                needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
+                prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
+            },
+            candidates,
+        )
+    }
+
+    fn make_store(open_duration_seconds: u64) -> Arc<CircuitBreakerStore> {
+        Arc::new(CircuitBreakerStore::new(CircuitBreakerConfig {
+            failure_threshold: 2,
+            open_duration_seconds,
+            ..CircuitBreakerConfig::default()
+        }))
+    }
+
+    #[test]
+    fn closed_circuits_pass_through() {
+        let reconciler = CircuitBreakerReconciler::new(make_store(30));
+        let mut intent = create_intent(vec!["a".into(), "b".into()]);
+
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents.len(), 2);
+        assert!(intent.rejection_reasons.is_empty());
+    }
+
+    #[test]
+    fn open_circuit_excludes_agent_with_eta() {
+        let store = make_store(30);
+        store.record_failure("a");
+        store.record_failure("a");
+
+        let reconciler = CircuitBreakerReconciler::new(store);
+        let mut intent = create_intent(vec!["a".into(), "b".into()]);
+
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents, vec!["b"]);
+        let reason = &intent.rejection_reasons[0];
+        assert_eq!(reason.agent_id, "a");
+        assert_eq!(reason.reconciler, "CircuitBreakerReconciler");
+        assert!(reason.suggested_action.contains("ETA: "));
+    }
+
+    #[test]
+    fn half_open_saturated_excludes_agent() {
+        let store = make_store(0);
+        store.record_failure("a");
+        store.record_failure("a");
+        let _permit = store.on_dispatch("a");
+
+        let reconciler = CircuitBreakerReconciler::new(store);
+        let mut intent = create_intent(vec!["a".into()]);
+
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert!(intent.candidate_agents.is_empty());
+        assert!(intent.rejection_reasons[0].reason.contains("half-open"));
+    }
+
+    #[test]
+    fn half_open_with_free_slot_passes() {
+        let store = make_store(0);
+        store.record_failure("a");
+        store.record_failure("a");
+
+        let reconciler = CircuitBreakerReconciler::new(store);
+        let mut intent = create_intent(vec!["a".into()]);
+
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents, vec!["a"]);
+    }
+}
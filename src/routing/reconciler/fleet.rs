@@ -472,6 +472,7 @@ mod tests {
                     supports_vision: false,
                     supports_tools: false,
                     supports_json_mode: false,
+                    supports_embeddings: false,
                     max_output_tokens: None,
                 })
                 .collect(),
@@ -479,6 +480,7 @@ mod tests {
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
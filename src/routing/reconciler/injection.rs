@@ -0,0 +1,191 @@
+//! InjectionReconciler - applies prompt injection screening results
+//!
+//! Reads the risk score the screening stage annotated on the intent and
+//! applies the configured action for the resolved model: reject the request
+//! or log it. Tool downgrades rewrite the request, so the screening stage
+//! applies those before routing. Runs BEFORE PrivacyReconciler in the pipeline.
+
+use super::{intent::RoutingIntent, Reconciler};
+use crate::config::{InjectionAction, PolicyMatcher};
+use crate::routing::error::RoutingError;
+
+/// InjectionReconciler acts on requests flagged by injection screening.
+///
+/// # Pipeline Position
+/// RequestAnalyzer → Lifecycle → [Pii] → **InjectionReconciler** → PrivacyReconciler → ...
+///
+/// # Behavior
+/// 1. Skip requests that were not screened (`intent.injection_risk` is None)
+/// 2. Resolve the action: matching traffic policy, else the global action
+/// 3. Count the outcome in `nexus_reconciler_outcomes_total`
+/// 4. For flagged requests, log; with `reject`, exclude every candidate and
+///    reject the request without queueing or fallback
+pub struct InjectionReconciler {
+    policy_matcher: PolicyMatcher,
+    threshold: f32,
+    default_action: InjectionAction,
+}
+
+impl InjectionReconciler {
+    /// Create a new InjectionReconciler with the screening threshold and action.
+    pub fn new(policy_matcher: PolicyMatcher, threshold: f32, action: InjectionAction) -> Self {
+        Self {
+            policy_matcher,
+            threshold,
+            default_action: action,
+        }
+    }
+
+    fn action_for(&self, model: &str) -> InjectionAction {
+        self.policy_matcher
+            .find_policy(model)
+            .and_then(|policy| policy.injection_action)
+            .unwrap_or(self.default_action)
+    }
+}
+
+impl Reconciler for InjectionReconciler {
+    fn name(&self) -> &'static str {
+        "InjectionReconciler"
+    }
+
+    fn reconcile(&self, intent: &mut RoutingIntent) -> Result<(), RoutingError> {
+        let Some(risk) = intent.injection_risk else {
+            return Ok(());
+        };
+
+        let flagged = risk >= self.threshold;
+        let action = self.action_for(&intent.resolved_model);
+        let outcome = if flagged { action.as_str() } else { "passed" };
+        metrics::counter!(
+            "nexus_reconciler_outcomes_total",
+            "reconciler" => self.name().to_string(),
+            "outcome" => outcome,
+        )
+        .increment(1);
+
+        if !flagged {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            request_id = %intent.request_id,
+            model = %intent.resolved_model,
+            risk,
+            threshold = self.threshold,
+            action = action.as_str(),
+            "InjectionReconciler: request flagged as possible prompt injection"
+        );
+        if action != InjectionAction::Reject {
+            return Ok(());
+        }
+
+        let candidate_ids: Vec<String> = intent.candidate_agents.clone();
+        for agent_id in candidate_ids {
+            intent.exclude_agent(
+                agent_id,
+                "InjectionReconciler",
+                format!(
+                    "Prompt injection risk {:.2} reached the screening threshold {:.2}",
+                    risk, self.threshold
+                ),
+                "Remove instructions aimed at the model or its tools from the request".to_string(),
+            );
+        }
+        Err(RoutingError::Reject {
+            rejection_reasons: intent.rejection_reasons.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PrivacyConstraint, TrafficPolicy};
+    use crate::routing::RequestRequirements;
+
+    fn create_intent(model: &str, injection_risk: Option<f32>) -> RoutingIntent {
+        RoutingIntent::new(
+            "req-1".to_string(),
+            model.to_string(),
+            model.to_string(),
+            RequestRequirements {
+                model: model.to_string(),
+                estimated_tokens: 100,
+                needs_vision: false,
+                needs_tools: false,
+                needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
+                prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk,
+            },
+            vec!["a1".to_string(), "a2".to_string()],
+        )
+    }
+
+    fn reconciler(action: InjectionAction) -> InjectionReconciler {
+        let policy = TrafficPolicy {
+            model_pattern: "agent-*".to_string(),
+            privacy: PrivacyConstraint::Unrestricted,
+            max_cost_per_request: None,
+            min_tier: None,
+            fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: Some(InjectionAction::Reject),
+        };
+        InjectionReconciler::new(PolicyMatcher::compile(vec![policy]).unwrap(), 0.8, action)
+    }
+
+    #[test]
+    fn unscreened_and_low_risk_requests_pass() {
+        let reconciler = reconciler(InjectionAction::Reject);
+
+        let mut intent = create_intent("llama3", None);
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents.len(), 2);
+
+        let mut intent = create_intent("llama3", Some(0.3));
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents.len(), 2);
+    }
+
+    #[test]
+    fn reject_action_excludes_all_candidates() {
+        let reconciler = reconciler(InjectionAction::Reject);
+        let mut intent = create_intent("llama3", Some(0.95));
+
+        let err = reconciler.reconcile(&mut intent).unwrap_err();
+
+        assert!(intent.candidate_agents.is_empty());
+        match err {
+            RoutingError::Reject { rejection_reasons } => {
+                assert_eq!(rejection_reasons.len(), 2);
+                assert_eq!(rejection_reasons[0].reconciler, "InjectionReconciler");
+                assert!(rejection_reasons[0].reason.contains("0.95"));
+            }
+            other => panic!("expected Reject, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn log_action_keeps_candidates() {
+        let reconciler = reconciler(InjectionAction::Log);
+        let mut intent = create_intent("llama3", Some(0.95));
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents.len(), 2);
+    }
+
+    #[test]
+    fn policy_action_overrides_global_action() {
+        let reconciler = reconciler(InjectionAction::Log);
+        let mut intent = create_intent("agent-coder", Some(0.9));
+        assert!(reconciler.reconcile(&mut intent).is_err());
+    }
+}
@@ -104,12 +104,18 @@ pub struct RoutingIntent {
     pub requirements: RequestRequirements,
 
     // === Constraints from Policies ===
-    /// Privacy constraint from TrafficPolicy match (FR-011, FR-013)
+    /// Privacy constraint from TrafficPolicy match (FR-011, FR-013),
+    /// seeded from the request's content-derived constraint
     pub privacy_constraint: Option<PrivacyZone>,
 
-    /// Minimum capability tier from TrafficPolicy (FR-024)
+    /// Minimum capability tier from TrafficPolicy (FR-024),
+    /// seeded from the request's content-derived constraint
     pub min_capability_tier: Option<u8>,
 
+    /// Prompt injection risk score (0.0-1.0) from the screening stage,
+    /// seeded from the request requirements
+    pub injection_risk: Option<f32>,
+
     /// Tier enforcement mode from request headers (FR-027, FR-028)
     pub tier_enforcement_mode: TierEnforcementMode,
 
@@ -147,9 +153,10 @@ impl RoutingIntent {
             request_id,
             requested_model,
             resolved_model,
+            privacy_constraint: requirements.privacy_constraint,
+            min_capability_tier: requirements.min_capability_tier,
+            injection_risk: requirements.injection_risk,
             requirements,
-            privacy_constraint: None,
-            min_capability_tier: None,
             tier_enforcement_mode: TierEnforcementMode::default(),
             budget_status: BudgetStatus::Normal,
             cost_estimate: CostEstimate::default(),
@@ -191,7 +198,15 @@ mod tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         }
     }
 
@@ -168,12 +168,14 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation,
@@ -191,7 +193,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             candidates,
         )
@@ -4,10 +4,13 @@
 //! Each reconciler reads and annotates RoutingIntent without removing constraints.
 
 pub mod budget;
+pub mod circuit_breaker;
 pub mod decision;
 pub mod fleet;
+pub mod injection;
 pub mod intent;
 pub mod lifecycle;
+pub mod pii;
 pub mod privacy;
 pub mod quality;
 pub mod request_analyzer;
@@ -42,7 +45,8 @@ pub trait Reconciler: Send + Sync {
 }
 
 /// ReconcilerPipeline executes a sequence of reconcilers on routing intent.
-/// Order is fixed: RequestAnalyzer → Privacy → Budget → Tier → Quality → Scheduler
+/// Order is fixed: RequestAnalyzer → Privacy → Budget → Tier → CircuitBreaker → Quality
+/// → Scheduler
 pub struct ReconcilerPipeline {
     reconcilers: Vec<Box<dyn Reconciler>>,
     /// Whether request queuing is enabled (from QueueConfig)
@@ -243,7 +247,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             candidates.into_iter().map(|s| s.to_string()).collect(),
         )
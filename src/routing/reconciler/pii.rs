@@ -0,0 +1,277 @@
+//! PiiReconciler - restricts requests containing PII to local backends
+//!
+//! Scans message content with the configured PII detectors and, when any
+//! fire, sets the intent's privacy constraint to Restricted and excludes
+//! cloud agents. With cloud redaction enabled, agents that redact PII before
+//! dispatch stay eligible instead. Runs BEFORE PrivacyReconciler in the pipeline.
+
+use super::{intent::RoutingIntent, Reconciler};
+use crate::agent::PrivacyZone;
+use crate::pii::{supports_redaction, PiiScanner};
+use crate::registry::Registry;
+use crate::routing::error::RoutingError;
+use std::sync::Arc;
+
+/// PiiReconciler forces the Restricted privacy zone for requests containing PII.
+///
+/// # Pipeline Position
+/// RequestAnalyzer → Lifecycle → **PiiReconciler** → PrivacyReconciler → ...
+///
+/// # Behavior
+/// 1. Scan `requirements.prompt_text` with the configured detectors
+/// 2. If any detector fires, set `intent.privacy_constraint = Restricted`
+/// 3. Exclude agents outside the restricted zone, naming the detectors
+///
+/// When `redact_cloud` is set, step 2 is skipped and agents that support
+/// redaction (see [`crate::pii::supports_redaction`]) are kept.
+///
+/// Rejection reasons and logs name the detector only, never the matched content.
+pub struct PiiReconciler {
+    registry: Arc<Registry>,
+    scanner: Arc<PiiScanner>,
+    redact_cloud: bool,
+}
+
+impl PiiReconciler {
+    /// Create a new PiiReconciler with the given registry and compiled detectors.
+    pub fn new(registry: Arc<Registry>, scanner: Arc<PiiScanner>, redact_cloud: bool) -> Self {
+        Self {
+            registry,
+            scanner,
+            redact_cloud,
+        }
+    }
+
+    /// Whether PII is redacted before dispatch to this agent.
+    fn redacts_for(&self, agent_id: &str) -> bool {
+        self.redact_cloud
+            && self
+                .registry
+                .get_backend(agent_id)
+                .is_some_and(|backend| supports_redaction(backend.backend_type))
+    }
+
+    /// Determine the effective privacy zone for a backend.
+    ///
+    /// Agent profile first, then backend type default; unknown agents are
+    /// treated as Open (cloud) per FR-015.
+    fn get_backend_privacy_zone(&self, agent_id: &str) -> PrivacyZone {
+        if let Some(agent) = self.registry.get_agent(agent_id) {
+            return agent.profile().privacy_zone;
+        }
+        if let Some(backend) = self.registry.get_backend(agent_id) {
+            return backend.backend_type.default_privacy_zone();
+        }
+        PrivacyZone::Open
+    }
+}
+
+impl Reconciler for PiiReconciler {
+    fn name(&self) -> &'static str {
+        "PiiReconciler"
+    }
+
+    fn reconcile(&self, intent: &mut RoutingIntent) -> Result<(), RoutingError> {
+        let Some(text) = intent.requirements.prompt_text.clone() else {
+            return Ok(());
+        };
+
+        let detectors = self.scanner.detect(&text);
+        if detectors.is_empty() {
+            return Ok(());
+        }
+
+        let detector_list = detectors.join(", ");
+        tracing::info!(
+            model = %intent.resolved_model,
+            detectors = %detector_list,
+            redact_cloud = self.redact_cloud,
+            "PiiReconciler: PII detected, restricting to local or redacting backends"
+        );
+        for detector in &detectors {
+            metrics::counter!("nexus_pii_detections_total", "detector" => detector.clone())
+                .increment(1);
+        }
+
+        if !self.redact_cloud {
+            intent.privacy_constraint = Some(PrivacyZone::Restricted);
+        }
+
+        let candidate_ids: Vec<String> = intent.candidate_agents.clone();
+        for agent_id in &candidate_ids {
+            let zone = self.get_backend_privacy_zone(agent_id);
+            if zone != PrivacyZone::Restricted && !self.redacts_for(agent_id) {
+                intent.exclude_agent(
+                    agent_id.clone(),
+                    "PiiReconciler",
+                    format!(
+                        "Request content matched PII detector(s) [{}]; agent privacy zone \
+                         {:?} is not allowed",
+                        detector_list, zone
+                    ),
+                    "Remove the sensitive data from the prompt or use a local backend".to_string(),
+                );
+            }
+        }
+
+        tracing::debug!(
+            remaining = intent.candidate_agents.len(),
+            excluded = intent.excluded_agents.len(),
+            "PiiReconciler: filtering complete"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PiiConfig;
+    use crate::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Model};
+    use crate::routing::RequestRequirements;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, AtomicU64};
+
+    fn create_backend(id: &str, backend_type: BackendType) -> Backend {
+        Backend {
+            id: id.to_string(),
+            name: id.to_string(),
+            url: format!("http://{}", id),
+            backend_type,
+            status: BackendStatus::Healthy,
+            last_health_check: Utc::now(),
+            last_error: None,
+            models: vec![Model {
+                id: "llama3".to_string(),
+                name: "llama3".to_string(),
+                context_length: 4096,
+                supports_vision: false,
+                supports_tools: false,
+                supports_json_mode: false,
+                supports_embeddings: false,
+                max_output_tokens: None,
+            }],
+            priority: 1,
+            pending_requests: AtomicU32::new(0),
+            total_requests: AtomicU64::new(0),
+            avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
+            discovery_source: DiscoverySource::Static,
+            metadata: HashMap::new(),
+            current_operation: None,
+        }
+    }
+
+    fn create_intent(prompt_text: Option<&str>) -> RoutingIntent {
+        RoutingIntent::new(
+            "req-1".to_string(),
+            "llama3".to_string(),
+            "llama3".to_string(),
+            RequestRequirements {
+                model: "llama3".to_string(),
+                estimated_tokens: 100,
+                needs_vision: false,
+                needs_tools: false,
+                needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
+                prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: prompt_text.map(Arc::from),
+                injection_risk: None,
+            },
+            vec!["local".to_string(), "cloud".to_string()],
+        )
+    }
+
+    fn reconciler() -> PiiReconciler {
+        reconciler_with(false)
+    }
+
+    fn reconciler_with(redact_cloud: bool) -> PiiReconciler {
+        let registry = Arc::new(Registry::new());
+        registry
+            .add_backend(create_backend("local", BackendType::Ollama))
+            .unwrap();
+        registry
+            .add_backend(create_backend("cloud", BackendType::OpenAI))
+            .unwrap();
+        let scanner = PiiScanner::new(&PiiConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+        // Self-hosted but cloud-zoned: not a redacting agent
+        let edge = crate::agent::factory::create_agent(
+            "edge".to_string(),
+            "edge".to_string(),
+            "http://edge".to_string(),
+            BackendType::Generic,
+            Arc::new(reqwest::Client::new()),
+            HashMap::new(),
+            PrivacyZone::Open,
+            None,
+        )
+        .unwrap();
+        registry
+            .add_backend_with_agent(create_backend("edge", BackendType::Generic), edge)
+            .unwrap();
+        PiiReconciler::new(registry, Arc::new(scanner), redact_cloud)
+    }
+
+    #[test]
+    fn clean_prompt_passes_through() {
+        let mut intent = create_intent(Some("What is the capital of France?"));
+        reconciler().reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents.len(), 2);
+        assert_eq!(intent.privacy_constraint, None);
+    }
+
+    #[test]
+    fn missing_prompt_text_passes_through() {
+        let mut intent = create_intent(None);
+        reconciler().reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents.len(), 2);
+    }
+
+    #[test]
+    fn pii_restricts_to_local_agents() {
+        let mut intent = create_intent(Some("Refund card 4111 1111 1111 1111 please"));
+        reconciler().reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.privacy_constraint, Some(PrivacyZone::Restricted));
+        assert_eq!(intent.candidate_agents, vec!["local"]);
+        assert_eq!(intent.excluded_agents, vec!["cloud"]);
+    }
+
+    #[test]
+    fn rejection_names_detector_without_content() {
+        let email = "jane.doe@example.com";
+        let mut intent = create_intent(Some(&format!("Contact {} about it", email)));
+        reconciler().reconcile(&mut intent).unwrap();
+
+        let reason = &intent.rejection_reasons[0];
+        assert_eq!(reason.reconciler, "PiiReconciler");
+        assert!(reason.reason.contains("email"));
+        assert!(!reason.reason.contains(email));
+        assert!(!reason.suggested_action.contains(email));
+    }
+
+    #[test]
+    fn redact_cloud_keeps_redacting_agents() {
+        let mut intent = create_intent(Some("Contact jane.doe@example.com"));
+        intent.candidate_agents.push("edge".to_string());
+        reconciler_with(true).reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.privacy_constraint, None);
+        assert_eq!(intent.candidate_agents, vec!["local", "cloud"]);
+        assert_eq!(intent.excluded_agents, vec!["edge"]);
+    }
+}
@@ -5,7 +5,8 @@
 //! SchedulerReconciler in the pipeline.
 
 use super::{intent::RoutingIntent, Reconciler};
-use crate::config::PolicyMatcher;
+use crate::agent::PrivacyZone;
+use crate::config::{PolicyMatcher, PrivacyConstraint};
 use crate::registry::Registry;
 use crate::routing::error::RoutingError;
 use std::sync::Arc;
@@ -17,12 +18,14 @@ use std::sync::Arc;
 ///
 /// # Behavior
 /// 1. Look up the resolved model in the PolicyMatcher
-/// 2. If a policy matches, set `intent.privacy_constraint`
+/// 2. If a restricted policy matches, set `intent.privacy_constraint`
+///    (it may already be set by a content routing rule)
 /// 3. For each candidate, check its backend's privacy zone against the constraint
 /// 4. Exclude agents that violate the constraint with actionable rejection reasons
 ///
 /// # Zero-Config Default (FR-034)
-/// If no policies are configured, all agents pass through unchanged.
+/// If no policies or content rules impose a constraint, all agents pass
+/// through unchanged.
 pub struct PrivacyReconciler {
     registry: Arc<Registry>,
     policy_matcher: PolicyMatcher,
@@ -68,29 +71,31 @@ impl Reconciler for PrivacyReconciler {
     }
 
     fn reconcile(&self, intent: &mut RoutingIntent) -> Result<(), RoutingError> {
-        // FR-034: No policies configured → pass through (zero-config default)
-        if self.policy_matcher.is_empty() {
-            return Ok(());
+        // A matching Restricted policy tightens the constraint; an Unrestricted
+        // policy never loosens one already imposed by request content.
+        let policy_restricted = self
+            .policy_matcher
+            .find_policy(&intent.resolved_model)
+            .is_some_and(|p| p.privacy == PrivacyConstraint::Restricted);
+        if policy_restricted {
+            intent.privacy_constraint = Some(PrivacyZone::Restricted);
         }
 
-        // Find matching policy for the resolved model
-        let policy = match self.policy_matcher.find_policy(&intent.resolved_model) {
-            Some(p) => p,
-            None => return Ok(()), // No matching policy → unrestricted
+        // FR-034: No constraint from policies or content → pass through
+        if intent.privacy_constraint != Some(PrivacyZone::Restricted) {
+            return Ok(());
+        }
+        let constraint = PrivacyConstraint::Restricted;
+        let source = if policy_restricted {
+            "policy"
+        } else {
+            "request content"
         };
 
-        // Set the privacy constraint on the intent for downstream reconcilers
-        let constraint = policy.privacy;
-        intent.privacy_constraint = Some(match constraint {
-            crate::config::PrivacyConstraint::Restricted => crate::agent::PrivacyZone::Restricted,
-            crate::config::PrivacyConstraint::Unrestricted => {
-                return Ok(()); // Unrestricted → no filtering needed
-            }
-        });
-
         tracing::debug!(
             model = %intent.resolved_model,
             privacy = ?constraint,
+            source,
             candidates = intent.candidate_agents.len(),
             "PrivacyReconciler: applying privacy constraint"
         );
@@ -105,8 +110,8 @@ impl Reconciler for PrivacyReconciler {
                     agent_id.clone(),
                     "PrivacyReconciler",
                     format!(
-                        "Agent privacy zone {:?} violates {:?} policy for model '{}'",
-                        zone, constraint, intent.resolved_model
+                        "Agent privacy zone {:?} violates {:?} {} for model '{}'",
+                        zone, constraint, source, intent.resolved_model
                     ),
                     "Use a local backend or change the traffic policy to unrestricted".to_string(),
                 );
@@ -151,12 +156,14 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -174,7 +181,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             candidates,
         )
@@ -187,6 +202,9 @@ mod tests {
             max_cost_per_request: None,
             min_tier: None,
             fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
         }
     }
 
@@ -230,6 +248,47 @@ mod tests {
         assert_eq!(intent.excluded_agents, vec!["cloud"]);
     }
 
+    #[test]
+    fn content_constraint_excludes_cloud_without_policies() {
+        let registry = Arc::new(Registry::new());
+        registry
+            .add_backend(create_backend("local", "llama3:8b", BackendType::Ollama))
+            .unwrap();
+        registry
+            .add_backend(create_backend("cloud", "llama3:8b", BackendType::OpenAI))
+            .unwrap();
+
+        let reconciler = PrivacyReconciler::new(Arc::clone(&registry), PolicyMatcher::default());
+
+        let mut intent = create_intent("llama3:8b", vec!["local".into(), "cloud".into()]);
+        intent.privacy_constraint = Some(PrivacyZone::Restricted);
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents, vec!["local"]);
+        assert!(intent.rejection_reasons[0]
+            .reason
+            .contains("request content"));
+    }
+
+    #[test]
+    fn unrestricted_policy_does_not_loosen_content_constraint() {
+        let registry = Arc::new(Registry::new());
+        registry
+            .add_backend(create_backend("cloud", "gpt-4", BackendType::OpenAI))
+            .unwrap();
+
+        let mut policy = restricted_policy("gpt-4*");
+        policy.privacy = PrivacyConstraint::Unrestricted;
+        let matcher = PolicyMatcher::compile(vec![policy]).unwrap();
+        let reconciler = PrivacyReconciler::new(Arc::clone(&registry), matcher);
+
+        let mut intent = create_intent("gpt-4", vec!["cloud".into()]);
+        intent.privacy_constraint = Some(PrivacyZone::Restricted);
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert!(intent.candidate_agents.is_empty());
+    }
+
     #[test]
     fn restricted_policy_keeps_local_backends() {
         let registry = Arc::new(Registry::new());
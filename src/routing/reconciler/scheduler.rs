@@ -8,13 +8,38 @@ use super::Reconciler;
 use crate::agent::quality::QualityMetricsStore;
 use crate::agent::PrivacyZone;
 use crate::config::QualityConfig;
-use crate::registry::{Backend, BackendStatus, Registry};
+use crate::registry::{Backend, BackendStatus, Model, Registry};
 use crate::routing::error::RoutingError;
 use crate::routing::scoring::{score_backend, ScoringWeights};
 use crate::routing::strategies::RoutingStrategy;
+use crate::routing::RequestRequirements;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+/// Capabilities the request needs that the model lacks.
+pub(crate) fn missing_capabilities(
+    model: &Model,
+    requirements: &RequestRequirements,
+) -> Vec<&'static str> {
+    let mut missing = Vec::new();
+    if requirements.needs_vision && !model.supports_vision {
+        missing.push("vision");
+    }
+    if requirements.needs_tools && !model.supports_tools {
+        missing.push("tools");
+    }
+    if requirements.needs_json_mode && !model.supports_json_mode {
+        missing.push("json_mode");
+    }
+    if requirements.needs_embeddings && !model.supports_embeddings {
+        missing.push("embeddings");
+    }
+    if requirements.required_context() > model.context_length {
+        missing.push("context_length");
+    }
+    missing
+}
+
 /// SchedulerReconciler filters, scores, and selects the best candidate agent.
 /// It stores the selected agent_id and score in the intent for the pipeline
 /// to convert into a RoutingDecision.
@@ -36,6 +61,9 @@ pub struct SchedulerReconciler {
 
     /// Quality configuration thresholds
     quality_config: QualityConfig,
+
+    /// Keep tool requests eligible for models without native tool support
+    tool_emulation: bool,
 }
 
 impl SchedulerReconciler {
@@ -55,27 +83,31 @@ impl SchedulerReconciler {
             round_robin_counter,
             quality_store,
             quality_config,
+            tool_emulation: false,
         }
     }
 
+    /// Allow tool requests on models without native tool support, preferring
+    /// native ones when available.
+    pub fn with_tool_emulation(mut self, enabled: bool) -> Self {
+        self.tool_emulation = enabled;
+        self
+    }
+
     /// Check if a backend meets the request's capability requirements
     fn meets_requirements(backend: &Backend, model: &str, intent: &RoutingIntent) -> bool {
-        if let Some(model_info) = backend.models.iter().find(|m| m.id == model) {
-            if intent.requirements.needs_vision && !model_info.supports_vision {
-                return false;
-            }
-            if intent.requirements.needs_tools && !model_info.supports_tools {
-                return false;
-            }
-            if intent.requirements.needs_json_mode && !model_info.supports_json_mode {
-                return false;
-            }
-            if intent.requirements.estimated_tokens > model_info.context_length {
-                return false;
-            }
-            true
-        } else {
-            false
+        Self::missing_capabilities(backend, model, intent).is_empty()
+    }
+
+    /// Capabilities the request needs that the backend's model lacks.
+    fn missing_capabilities(
+        backend: &Backend,
+        model: &str,
+        intent: &RoutingIntent,
+    ) -> Vec<&'static str> {
+        match backend.models.iter().find(|m| m.id == model) {
+            Some(m) => missing_capabilities(m, &intent.requirements),
+            None => vec!["model_not_found"],
         }
     }
 
@@ -104,6 +136,42 @@ impl SchedulerReconciler {
         score
     }
 
+    /// Keep only backends that support `capability` natively, if any remain.
+    ///
+    /// Other backends stay eligible when none do; the gateway validates or
+    /// emulates the capability for them instead.
+    fn prefer_native(
+        &self,
+        intent: &mut RoutingIntent,
+        capability: &str,
+        supports: impl Fn(&Backend, &str) -> bool,
+    ) {
+        let model = intent.resolved_model.clone();
+        let (native, other): (Vec<String>, Vec<String>) =
+            intent.candidate_agents.iter().cloned().partition(|id| {
+                self.registry
+                    .get_backend(id)
+                    .is_some_and(|b| supports(&b, &model))
+            });
+        if native.is_empty() {
+            return;
+        }
+        for agent_id in other {
+            intent.exclude_agent(
+                agent_id,
+                "SchedulerReconciler",
+                format!(
+                    "No native {} support; a backend with native support is available",
+                    capability
+                ),
+                format!(
+                    "None needed; request routed to a backend with native {} support",
+                    capability
+                ),
+            );
+        }
+    }
+
     /// Apply TTFT penalty to score. Agents with avg_ttft_ms above the
     /// configured threshold get a proportional score reduction.
     fn apply_ttft_penalty(&self, score: u32, agent_id: &str) -> u32 {
@@ -144,31 +212,20 @@ impl Reconciler for SchedulerReconciler {
                     continue;
                 }
 
-                // Check capability requirements
+                // Check capability requirements; tools can be emulated
                 if !Self::meets_requirements(&backend, &model, intent) {
-                    let mut missing = Vec::new();
-                    if let Some(m) = backend.models.iter().find(|m| m.id == model) {
-                        if intent.requirements.needs_vision && !m.supports_vision {
-                            missing.push("vision");
-                        }
-                        if intent.requirements.needs_tools && !m.supports_tools {
-                            missing.push("tools");
-                        }
-                        if intent.requirements.needs_json_mode && !m.supports_json_mode {
-                            missing.push("json_mode");
-                        }
-                        if intent.requirements.estimated_tokens > m.context_length {
-                            missing.push("context_length");
-                        }
-                    } else {
-                        missing.push("model_not_found");
+                    let mut missing = Self::missing_capabilities(&backend, &model, intent);
+                    if self.tool_emulation {
+                        missing.retain(|capability| *capability != "tools");
+                    }
+                    if !missing.is_empty() {
+                        intent.exclude_agent(
+                            agent_id.clone(),
+                            "SchedulerReconciler",
+                            format!("Missing capabilities: {:?}", missing),
+                            "Use a backend that supports the required capabilities".to_string(),
+                        );
                     }
-                    intent.exclude_agent(
-                        agent_id.clone(),
-                        "SchedulerReconciler",
-                        format!("Missing capabilities: {:?}", missing),
-                        "Use a backend that supports the required capabilities".to_string(),
-                    );
                 }
             } else {
                 // Backend no longer in registry
@@ -181,6 +238,20 @@ impl Reconciler for SchedulerReconciler {
             }
         }
 
+        if intent.requirements.needs_json_schema {
+            self.prefer_native(intent, "JSON schema", |backend, _| {
+                backend.backend_type.supports_json_schema()
+            });
+        }
+        if intent.requirements.needs_tools && self.tool_emulation {
+            self.prefer_native(intent, "tool calling", |backend, model| {
+                backend
+                    .models
+                    .iter()
+                    .any(|m| m.id == model && m.supports_tools)
+            });
+        }
+
         // T026-T028: Score remaining candidates and select best using routing strategy
         // Scoring and selection happen here but the result is stored in intent
         // for the pipeline's execute() to read.
@@ -200,7 +271,7 @@ impl Reconciler for SchedulerReconciler {
                             .max_by_key(|b| {
                                 let raw_score = score_backend(
                                     b.priority as u32,
-                                    b.pending_requests.load(Ordering::Relaxed),
+                                    b.queue_depth(),
                                     b.avg_latency_ms.load(Ordering::Relaxed),
                                     &self.weights,
                                 );
@@ -211,7 +282,7 @@ impl Reconciler for SchedulerReconciler {
                             .expect("candidates verified non-empty");
                         let raw_score = score_backend(
                             best.priority as u32,
-                            best.pending_requests.load(Ordering::Relaxed),
+                            best.queue_depth(),
                             best.avg_latency_ms.load(Ordering::Relaxed),
                             &self.weights,
                         );
@@ -327,12 +398,14 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority,
             pending_requests: AtomicU32::new(pending),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(latency),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -346,7 +419,15 @@ mod tests {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         }
     }
 
@@ -428,6 +509,32 @@ mod tests {
         assert_eq!(intent.candidate_agents, vec!["b1"]);
     }
 
+    #[test]
+    fn reported_queue_depth_steers_smart_routing() {
+        let registry = Arc::new(Registry::new());
+        // Identical backends, but b1's metrics report a queue from other clients
+        for id in ["b1", "b2"] {
+            registry
+                .add_backend(create_test_backend(
+                    id,
+                    BackendStatus::Healthy,
+                    "llama3:8b",
+                    1,
+                    0,
+                    50,
+                ))
+                .unwrap();
+        }
+        registry.update_reported_queue_depth("b1", 40).unwrap();
+
+        let scheduler = make_scheduler(registry, RoutingStrategy::Smart);
+
+        let mut intent = create_intent("llama3:8b", vec!["b1".into(), "b2".into()]);
+        scheduler.reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents, vec!["b2"]);
+    }
+
     #[test]
     fn rejects_when_no_candidates_remain() {
         let registry = Arc::new(Registry::new());
@@ -477,7 +584,15 @@ mod tests {
                 needs_vision: true, // requires vision, but backend doesn't support it
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -572,7 +687,15 @@ mod tests {
                 needs_vision: true,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -596,7 +719,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -607,6 +738,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn meets_requirements_reserves_max_tokens_headroom() {
+        let backend = create_test_backend("b1", BackendStatus::Healthy, "llama3:8b", 1, 0, 50);
+        let mut requirements = RequestRequirements {
+            model: "llama3:8b".to_string(),
+            estimated_tokens: 3000, // Fits 4096 on its own
+            needs_vision: false,
+            needs_tools: false,
+            needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
+            prefers_streaming: false,
+            max_tokens: Some(2000),
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
+        };
+        let intent = RoutingIntent::new(
+            "req-1".to_string(),
+            "llama3:8b".to_string(),
+            "llama3:8b".to_string(),
+            requirements.clone(),
+            vec!["b1".into()],
+        );
+        assert!(!SchedulerReconciler::meets_requirements(
+            &backend,
+            "llama3:8b",
+            &intent
+        ));
+
+        requirements.max_tokens = Some(1000);
+        let intent = RoutingIntent::new(
+            "req-2".to_string(),
+            "llama3:8b".to_string(),
+            "llama3:8b".to_string(),
+            requirements,
+            vec!["b1".into()],
+        );
+        assert!(SchedulerReconciler::meets_requirements(
+            &backend,
+            "llama3:8b",
+            &intent
+        ));
+    }
+
     #[test]
     fn priority_only_strategy() {
         let registry = Arc::new(Registry::new());
@@ -866,7 +1044,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: true,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -902,7 +1088,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: true,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -912,6 +1106,95 @@ mod tests {
         assert!(intent.rejection_reasons[0].reason.contains("json_mode"));
     }
 
+    #[test]
+    fn json_schema_prefers_native_backends() {
+        let registry = Arc::new(Registry::new());
+        let mut native =
+            create_test_backend("vllm", BackendStatus::Healthy, "llama3:8b", 1, 9, 500);
+        native.backend_type = BackendType::VLLM;
+        registry.add_backend(native).unwrap();
+        registry
+            .add_backend(create_test_backend(
+                "ollama",
+                BackendStatus::Healthy,
+                "llama3:8b",
+                1,
+                0,
+                50,
+            ))
+            .unwrap();
+        let scheduler = make_scheduler(registry, RoutingStrategy::Smart);
+
+        let mut intent = create_intent("llama3:8b", vec!["vllm".into(), "ollama".into()]);
+        intent.requirements.needs_json_schema = true;
+        scheduler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents, vec!["vllm"]);
+
+        // Without a native backend, non-native ones stay eligible
+        let mut intent = create_intent("llama3:8b", vec!["ollama".into()]);
+        intent.requirements.needs_json_schema = true;
+        scheduler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents, vec!["ollama"]);
+    }
+
+    #[test]
+    fn tool_emulation_keeps_non_native_backends_eligible() {
+        let registry = Arc::new(Registry::new());
+        let mut native =
+            create_test_backend("native", BackendStatus::Healthy, "llama3:8b", 1, 9, 500);
+        native.models[0].supports_tools = true;
+        registry.add_backend(native).unwrap();
+        registry
+            .add_backend(create_test_backend(
+                "plain",
+                BackendStatus::Healthy,
+                "llama3:8b",
+                1,
+                0,
+                50,
+            ))
+            .unwrap();
+        let scheduler = make_scheduler(registry, RoutingStrategy::Smart).with_tool_emulation(true);
+
+        // Native tool support is preferred
+        let mut intent = create_intent("llama3:8b", vec!["native".into(), "plain".into()]);
+        intent.requirements.needs_tools = true;
+        scheduler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents, vec!["native"]);
+
+        // Without it, the model is still eligible for emulation
+        let mut intent = create_intent("llama3:8b", vec!["plain".into()]);
+        intent.requirements.needs_tools = true;
+        scheduler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents, vec!["plain"]);
+    }
+
+    #[test]
+    fn embedding_requests_only_reach_embedding_models() {
+        let registry = Arc::new(Registry::new());
+        let mut embedder =
+            create_test_backend("embedder", BackendStatus::Healthy, "bge-small", 1, 0, 50);
+        embedder.models[0].supports_embeddings = true;
+        registry.add_backend(embedder).unwrap();
+        registry
+            .add_backend(create_test_backend(
+                "chat",
+                BackendStatus::Healthy,
+                "bge-small",
+                1,
+                0,
+                10,
+            ))
+            .unwrap();
+        let scheduler = make_scheduler(registry, RoutingStrategy::Smart);
+
+        let mut intent = create_intent("bge-small", vec!["embedder".into(), "chat".into()]);
+        intent.requirements.needs_embeddings = true;
+        scheduler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.candidate_agents, vec!["embedder"]);
+        assert!(intent.rejection_reasons[0].reason.contains("embeddings"));
+    }
+
     #[test]
     fn meets_requirements_tools_required_not_supported() {
         let backend = create_test_backend("b1", BackendStatus::Healthy, "llama3:8b", 1, 0, 50);
@@ -925,7 +1208,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: true,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -949,7 +1240,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: true,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             vec!["b1".into()],
         );
@@ -24,7 +24,7 @@ pub struct AgentSchedulingProfile {
     /// Average latency (milliseconds)
     pub avg_latency_ms: u32,
 
-    /// Current pending requests
+    /// Current queue depth (see `Backend::queue_depth`)
     pub pending_requests: u32,
 
     /// Capability tier (if specified)
@@ -40,7 +40,7 @@ impl AgentSchedulingProfile {
     ) -> Self {
         let is_healthy = matches!(backend.status, BackendStatus::Healthy);
         let avg_latency_ms = backend.avg_latency_ms.load(Ordering::Relaxed);
-        let pending_requests = backend.pending_requests.load(Ordering::Relaxed);
+        let pending_requests = backend.queue_depth();
 
         Self {
             agent_id: backend.id.clone(),
@@ -104,12 +104,14 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(pending),
             total_requests: AtomicU64::new(100),
             avg_latency_ms: AtomicU32::new(latency),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
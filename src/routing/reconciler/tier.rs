@@ -19,12 +19,13 @@ use std::sync::Arc;
 /// # Behavior
 /// 1. Look up the resolved model in the PolicyMatcher
 /// 2. If a policy with `min_tier` matches, set `intent.min_capability_tier`
+///    (raising any minimum already imposed by a content routing rule)
 /// 3. Check enforcement mode (strict by default, or flexible via X-Nexus-Flexible)
 /// 4. In strict mode: exclude agents with capability_tier < min_tier
 /// 5. In flexible mode: exclude only if higher-tier agents remain after filtering
 ///
 /// # Zero-Config Default (FR-034)
-/// If no policies are configured or no policy has `min_tier`, all agents pass through.
+/// If neither a policy nor a content rule requires a tier, all agents pass through.
 pub struct TierReconciler {
     registry: Arc<Registry>,
     policy_matcher: PolicyMatcher,
@@ -57,21 +58,20 @@ impl Reconciler for TierReconciler {
     }
 
     fn reconcile(&self, intent: &mut RoutingIntent) -> Result<(), RoutingError> {
-        // FR-034: No policies configured → pass through
-        if self.policy_matcher.is_empty() {
-            return Ok(());
-        }
-
-        // Find matching policy for the resolved model
-        let policy = match self.policy_matcher.find_policy(&intent.resolved_model) {
-            Some(p) => p,
-            None => return Ok(()), // No matching policy → unrestricted
-        };
-
-        // FR-024: Check if policy specifies a minimum tier
-        let min_tier = match policy.min_tier {
+        // FR-024: Policy min_tier combines with any tier already required by
+        // request content; the stricter of the two wins.
+        let policy_tier = self
+            .policy_matcher
+            .find_policy(&intent.resolved_model)
+            .and_then(|p| p.min_tier);
+        let min_tier = match policy_tier.max(intent.min_capability_tier) {
             Some(t) => t,
-            None => return Ok(()), // No tier requirement in policy
+            None => return Ok(()), // FR-034: no tier requirement → pass through
+        };
+        let source = if policy_tier == Some(min_tier) {
+            "policy"
+        } else {
+            "request content"
         };
 
         // Set the tier constraint on the intent for downstream reconcilers
@@ -98,8 +98,8 @@ impl Reconciler for TierReconciler {
                             "TierReconciler",
                             format!(
                                 "Agent capability tier {} is below minimum tier {} \
-                                 required by policy for model '{}'",
-                                tier, min_tier, intent.resolved_model
+                                 required by {} for model '{}'",
+                                tier, min_tier, source, intent.resolved_model
                             ),
                             format!(
                                 "Use a backend with capability tier >= {} or set \
@@ -253,12 +253,14 @@ mod tests {
                 supports_vision: false,
                 supports_tools: false,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 1,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -276,7 +278,15 @@ mod tests {
                 needs_vision: false,
                 needs_tools: false,
                 needs_json_mode: false,
+                needs_json_schema: false,
+                needs_embeddings: false,
+                prompt_tokens_exact: false,
                 prefers_streaming: false,
+                max_tokens: None,
+                privacy_constraint: None,
+                min_capability_tier: None,
+                prompt_text: None,
+                injection_risk: None,
             },
             candidates,
         )
@@ -289,6 +299,9 @@ mod tests {
             max_cost_per_request: None,
             min_tier: Some(min_tier),
             fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
         }
     }
 
@@ -328,6 +341,9 @@ mod tests {
             max_cost_per_request: None,
             min_tier: None, // No tier requirement
             fallback_allowed: true,
+            context_overflow: None,
+            cache: None,
+            injection_action: None,
         };
         let matcher = PolicyMatcher::compile(vec![policy]).unwrap();
         let reconciler = TierReconciler::new(Arc::clone(&registry), matcher);
@@ -385,6 +401,49 @@ mod tests {
         assert_eq!(intent.min_capability_tier, Some(3));
     }
 
+    #[test]
+    fn content_min_tier_applies_without_policies() {
+        let registry = Arc::new(Registry::new());
+
+        let b1 = create_backend("high-tier", "auto-target");
+        let agent1 = Arc::new(MockTierAgent::new("high-tier", Some(3)));
+        registry.add_backend_with_agent(b1, agent1).unwrap();
+
+        let b2 = create_backend("low-tier", "auto-target");
+        let agent2 = Arc::new(MockTierAgent::new("low-tier", Some(1)));
+        registry.add_backend_with_agent(b2, agent2).unwrap();
+
+        let reconciler = TierReconciler::new(Arc::clone(&registry), PolicyMatcher::default());
+
+        let mut intent = create_intent("auto-target", vec!["high-tier".into(), "low-tier".into()]);
+        intent.min_capability_tier = Some(2);
+        reconciler.reconcile(&mut intent).unwrap();
+
+        assert_eq!(intent.candidate_agents, vec!["high-tier"]);
+        assert!(intent.rejection_reasons[0]
+            .reason
+            .contains("required by request content"));
+    }
+
+    #[test]
+    fn stricter_of_policy_and_content_tier_wins() {
+        let registry = Arc::new(Registry::new());
+        let reconciler = TierReconciler::new(
+            Arc::clone(&registry),
+            PolicyMatcher::compile(vec![tier_policy("gpt-4*", 2)]).unwrap(),
+        );
+
+        let mut intent = create_intent("gpt-4", vec![]);
+        intent.min_capability_tier = Some(4);
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.min_capability_tier, Some(4));
+
+        let mut intent = create_intent("gpt-4", vec![]);
+        intent.min_capability_tier = Some(1);
+        reconciler.reconcile(&mut intent).unwrap();
+        assert_eq!(intent.min_capability_tier, Some(2));
+    }
+
     #[test]
     fn strict_mode_rejects_all_when_none_meet_tier() {
         let registry = Arc::new(Registry::new());
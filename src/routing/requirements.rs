@@ -1,6 +1,15 @@
 //! Request requirements extraction
 
+use crate::agent::tokenizer::Tokenizer;
+use crate::agent::PrivacyZone;
 use crate::api::types::{ChatCompletionRequest, MessageContent};
+use std::sync::Arc;
+
+/// Tokens added per message for role and delimiters (OpenAI chat format).
+const TOKENS_PER_MESSAGE: u32 = 4;
+
+/// Tokens added once to prime the assistant reply.
+const TOKENS_PER_REPLY: u32 = 3;
 
 /// Requirements extracted from an incoming request
 #[derive(Debug, Clone, PartialEq)]
@@ -20,8 +29,36 @@ pub struct RequestRequirements {
     /// Whether the request requires JSON mode
     pub needs_json_mode: bool,
 
+    /// Whether the request asks for a `json_schema` response format.
+    /// Backends that enforce schemas natively are preferred; responses from
+    /// others are validated by the gateway.
+    pub needs_json_schema: bool,
+
+    /// Whether the request is for embeddings, which only embedding-capable
+    /// models can serve
+    pub needs_embeddings: bool,
+
+    /// Whether `estimated_tokens` is an exact count from the backend tokenizer
+    pub prompt_tokens_exact: bool,
+
     /// Whether the client prefers streaming responses (US4)
     pub prefers_streaming: bool,
+
+    /// Requested output budget, reserved as context window headroom
+    pub max_tokens: Option<u32>,
+
+    /// Privacy constraint imposed by request content (e.g. a content routing rule)
+    pub privacy_constraint: Option<PrivacyZone>,
+
+    /// Minimum capability tier imposed by request content
+    pub min_capability_tier: Option<u8>,
+
+    /// Concatenated message text for content-scanning reconcilers.
+    /// Only captured when a reconciler needs it (e.g. PII detection).
+    pub prompt_text: Option<Arc<str>>,
+
+    /// Prompt injection risk (0.0-1.0) from the screening stage, if it ran
+    pub injection_risk: Option<f32>,
 }
 
 impl RequestRequirements {
@@ -56,15 +93,15 @@ impl RequestRequirements {
         // Check for tools in extra fields
         let needs_tools = request.extra.contains_key("tools");
 
-        // Check for JSON mode in response_format
-        let needs_json_mode = request
+        // Check for JSON mode or a JSON schema in response_format
+        let response_format = request
             .extra
             .get("response_format")
             .and_then(|v: &serde_json::Value| v.as_object())
             .and_then(|obj: &serde_json::Map<String, serde_json::Value>| obj.get("type"))
-            .and_then(|v: &serde_json::Value| v.as_str())
-            .map(|t: &str| t == "json_object")
-            .unwrap_or(false);
+            .and_then(|v: &serde_json::Value| v.as_str());
+        let needs_json_mode = response_format == Some("json_object");
+        let needs_json_schema = response_format == Some("json_schema");
 
         // Check if client prefers streaming
         let prefers_streaming = request.stream;
@@ -75,9 +112,104 @@ impl RequestRequirements {
             needs_vision,
             needs_tools,
             needs_json_mode,
+            needs_json_schema,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming,
+            max_tokens: request.max_tokens,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         }
     }
+
+    /// Extract requirements, counting prompt tokens with the given tokenizer.
+    ///
+    /// Replaces the chars/4 estimate with the tokenizer's count, including
+    /// per-message framing overhead.
+    pub fn from_request_with_tokenizer(
+        request: &ChatCompletionRequest,
+        tokenizer: &dyn Tokenizer,
+    ) -> Self {
+        let mut requirements = Self::from_request(request);
+        requirements.estimated_tokens = count_prompt_tokens(request, tokenizer);
+        requirements
+    }
+
+    /// Context window needed to serve the request: prompt plus output headroom.
+    pub fn required_context(&self) -> u32 {
+        self.estimated_tokens
+            .saturating_add(self.max_tokens.unwrap_or(0))
+    }
+}
+
+/// Concatenate the text content and tool-call arguments of all messages, one
+/// part per line.
+pub fn prompt_text(request: &ChatCompletionRequest) -> String {
+    let mut text = String::new();
+    for message in &request.messages {
+        let mut parts: Vec<&str> = match &message.content {
+            MessageContent::Text { content } => vec![content.as_str()],
+            MessageContent::Parts { content } => content
+                .iter()
+                .filter_map(|part| part.text.as_deref())
+                .collect(),
+        };
+        parts.extend(
+            message
+                .tool_calls
+                .iter()
+                .flatten()
+                .map(|call| call.function.arguments.as_str()),
+        );
+        parts.extend(
+            message
+                .function_call
+                .iter()
+                .map(|call| call.arguments.as_str()),
+        );
+        for part in parts {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(part);
+        }
+    }
+    text
+}
+
+/// Count prompt tokens for a chat request using the given tokenizer.
+///
+/// Falls back to the chars/4 estimate for text the tokenizer cannot encode.
+pub fn count_prompt_tokens(request: &ChatCompletionRequest, tokenizer: &dyn Tokenizer) -> u32 {
+    if request.messages.is_empty() {
+        return 0;
+    }
+
+    let count = |text: &str| {
+        tokenizer
+            .count_tokens(text)
+            .unwrap_or(text.len() as u32 / 4)
+    };
+
+    let mut total = TOKENS_PER_REPLY;
+    for message in &request.messages {
+        total = total
+            .saturating_add(TOKENS_PER_MESSAGE)
+            .saturating_add(count(&message.role));
+        match &message.content {
+            MessageContent::Text { content } => {
+                total = total.saturating_add(count(content));
+            }
+            MessageContent::Parts { content } => {
+                for text in content.iter().filter_map(|part| part.text.as_deref()) {
+                    total = total.saturating_add(count(text));
+                }
+            }
+        }
+    }
+    total
 }
 
 #[cfg(test)]
@@ -96,6 +228,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -132,6 +266,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -161,6 +297,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -190,6 +328,8 @@ mod tests {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -237,6 +377,19 @@ mod tests {
         let request = create_json_mode_request("llama3:8b");
         let requirements = RequestRequirements::from_request(&request);
         assert!(requirements.needs_json_mode);
+        assert!(!requirements.needs_json_schema);
+    }
+
+    #[test]
+    fn detects_json_schema_requirement() {
+        let mut request = create_json_mode_request("llama3:8b");
+        request.extra.insert(
+            "response_format".to_string(),
+            serde_json::json!({"type": "json_schema", "json_schema": {"name": "answer"}}),
+        );
+        let requirements = RequestRequirements::from_request(&request);
+        assert!(requirements.needs_json_schema);
+        assert!(!requirements.needs_json_mode);
     }
 
     #[test]
@@ -271,6 +424,39 @@ mod tests {
         assert!(!requirements.prefers_streaming);
     }
 
+    #[test]
+    fn required_context_reserves_max_tokens() {
+        let mut request = create_simple_request("llama3:8b", &"a".repeat(400));
+        request.max_tokens = Some(500);
+        let requirements = RequestRequirements::from_request(&request);
+        assert_eq!(requirements.max_tokens, Some(500));
+        assert_eq!(requirements.required_context(), 600);
+    }
+
+    #[test]
+    fn tokenizer_count_includes_message_overhead() {
+        use crate::agent::tokenizer::HeuristicTokenizer;
+
+        let request = create_simple_request("llama3:8b", "Hello world");
+        let tokenizer = HeuristicTokenizer::new();
+        let requirements = RequestRequirements::from_request_with_tokenizer(&request, &tokenizer);
+        let content_tokens = tokenizer.count_tokens("Hello world").unwrap();
+        let role_tokens = tokenizer.count_tokens("user").unwrap();
+        assert_eq!(
+            requirements.estimated_tokens,
+            content_tokens + role_tokens + TOKENS_PER_MESSAGE + TOKENS_PER_REPLY
+        );
+    }
+
+    #[test]
+    fn tokenizer_count_empty_messages_is_zero() {
+        use crate::agent::tokenizer::HeuristicTokenizer;
+
+        let mut request = create_simple_request("llama3:8b", "Hello");
+        request.messages.clear();
+        assert_eq!(count_prompt_tokens(&request, &HeuristicTokenizer::new()), 0);
+    }
+
     #[test]
     fn detects_streaming_preference() {
         let mut request = create_simple_request("llama3:8b", "Hello");
@@ -278,4 +464,34 @@ mod tests {
         let requirements = RequestRequirements::from_request(&request);
         assert!(requirements.prefers_streaming);
     }
+
+    #[test]
+    fn prompt_text_joins_text_parts_and_skips_images() {
+        let request = create_vision_request("llava", "http://example.com/cat.png");
+        assert_eq!(prompt_text(&request), "What's in this image?");
+
+        let mut request = create_simple_request("llama3:8b", "first");
+        request.messages.push(request.messages[0].clone());
+        assert_eq!(prompt_text(&request), "first\nfirst");
+        assert_eq!(
+            RequestRequirements::from_request(&request).prompt_text,
+            None
+        );
+    }
+
+    #[test]
+    fn prompt_text_includes_tool_call_arguments() {
+        let request: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "gpt-4",
+            "messages": [
+                {"role": "user", "content": "Email my boss"},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1", "type": "function",
+                    "function": {"name": "send", "arguments": "{\"to\":\"boss@acme.io\"}"}
+                }]}
+            ]
+        }))
+        .unwrap();
+        assert!(prompt_text(&request).contains("boss@acme.io"));
+    }
 }
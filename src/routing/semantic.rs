@@ -0,0 +1,402 @@
+//! Semantic `nexus/auto` model selection
+//!
+//! Embeds the latest user message with the configured embedding model and
+//! picks the route whose example prompts are most similar. Example embeddings
+//! are computed once, on first use, through the same agent path as
+//! `/v1/embeddings`.
+
+use super::{RequestFeatures, RequestRequirements, Router, RoutingError};
+use crate::agent::PrivacyZone;
+use crate::api::types::ChatCompletionRequest;
+use crate::config::{ConfigError, SemanticRoutingConfig};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::OnceCell;
+
+/// Model name that opts a request into semantic routing.
+pub const SEMANTIC_AUTO_MODEL: &str = "nexus/auto";
+
+/// Semantic classifier with lazily computed example embeddings.
+#[derive(Debug)]
+pub struct SemanticClassifier {
+    config: SemanticRoutingConfig,
+    /// (route index, example embedding) pairs
+    examples: OnceCell<Vec<(usize, Vec<f32>)>>,
+}
+
+/// Outcome of semantic classification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticDecision {
+    /// Matched route, or None if the fallback model was used
+    pub route: Option<String>,
+    /// Model selected for the request
+    pub target_model: String,
+    /// Minimum capability tier required by the matched route
+    pub min_tier: Option<u8>,
+    /// Best cosine similarity, or None if embedding failed
+    pub score: Option<f32>,
+}
+
+impl SemanticDecision {
+    /// Detail reported alongside the route reason, e.g. `code:0.91`,
+    /// `0.42` (below threshold) or `embedding-unavailable`.
+    pub fn detail(&self) -> String {
+        match (&self.route, self.score) {
+            (Some(route), Some(score)) => format!("{}:{:.2}", route, score),
+            (_, Some(score)) => format!("{:.2}", score),
+            _ => "embedding-unavailable".to_string(),
+        }
+    }
+}
+
+/// Cosine similarity of two vectors (0.0 for mismatched or zero vectors).
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+impl SemanticClassifier {
+    /// Create a classifier from validated configuration.
+    pub fn new(config: SemanticRoutingConfig) -> Self {
+        Self {
+            config,
+            examples: OnceCell::new(),
+        }
+    }
+
+    /// Pick the route for a prompt embedding given the example embeddings.
+    fn decide(&self, prompt: &[f32], examples: &[(usize, Vec<f32>)]) -> SemanticDecision {
+        let best = examples
+            .iter()
+            .map(|(route, example)| (*route, cosine_similarity(prompt, example)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best {
+            Some((index, score)) if score >= self.config.threshold => {
+                let route = &self.config.routes[index];
+                SemanticDecision {
+                    route: Some(route.name.clone()),
+                    target_model: route.target_model.clone(),
+                    min_tier: route.min_tier,
+                    score: Some(score),
+                }
+            }
+            best => SemanticDecision {
+                route: None,
+                target_model: self.config.fallback_model.clone(),
+                min_tier: None,
+                score: Some(best.map(|(_, score)| score).unwrap_or(0.0)),
+            },
+        }
+    }
+
+    /// Decision used when the prompt cannot be embedded.
+    fn fallback(&self) -> SemanticDecision {
+        SemanticDecision {
+            route: None,
+            target_model: self.config.fallback_model.clone(),
+            min_tier: None,
+            score: None,
+        }
+    }
+}
+
+impl Router {
+    /// Enable semantic `nexus/auto` routing.
+    pub fn set_semantic_routing(
+        &mut self,
+        config: SemanticRoutingConfig,
+    ) -> Result<(), ConfigError> {
+        config.validate()?;
+        self.semantic = config.enabled.then(|| SemanticClassifier::new(config));
+        Ok(())
+    }
+
+    /// Classify a `nexus/auto` request and rewrite it to the selected model.
+    ///
+    /// Returns None if semantic routing is disabled or the request did not
+    /// ask for `nexus/auto`. Privacy constraints already on `requirements`
+    /// are kept; the route's tier raises any existing minimum.
+    pub async fn apply_semantic_routing(
+        &self,
+        request: &mut ChatCompletionRequest,
+        requirements: &mut RequestRequirements,
+    ) -> Option<SemanticDecision> {
+        let classifier = self.semantic.as_ref()?;
+        if request.model != SEMANTIC_AUTO_MODEL {
+            return None;
+        }
+
+        let prompt = RequestFeatures::extract(request, 0, None).latest_user_text;
+        let decision = match self
+            .classify(classifier, prompt, requirements.privacy_constraint)
+            .await
+        {
+            Ok(decision) => decision,
+            Err(e) => {
+                tracing::warn!(
+                    embedding_model = %classifier.config.embedding_model,
+                    error = %e,
+                    "Semantic routing unavailable, using fallback model"
+                );
+                classifier.fallback()
+            }
+        };
+
+        tracing::debug!(
+            route = ?decision.route,
+            target_model = %decision.target_model,
+            score = ?decision.score,
+            "Semantic routing decision"
+        );
+
+        let privacy_constraint = requirements.privacy_constraint;
+        let min_tier = requirements.min_capability_tier.max(decision.min_tier);
+        request.model = decision.target_model.clone();
+        *requirements = self.requirements_for(request);
+        requirements.privacy_constraint = privacy_constraint;
+        requirements.min_capability_tier = min_tier;
+
+        Some(decision)
+    }
+
+    async fn classify(
+        &self,
+        classifier: &SemanticClassifier,
+        prompt: String,
+        privacy_constraint: Option<PrivacyZone>,
+    ) -> Result<SemanticDecision, String> {
+        let examples = classifier
+            .examples
+            .get_or_try_init(|| async {
+                let (routes, texts): (Vec<usize>, Vec<String>) = classifier
+                    .config
+                    .routes
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, route)| route.examples.iter().map(move |e| (i, e.clone())))
+                    .unzip();
+                let vectors = self
+                    .embed(&classifier.config.embedding_model, texts, None)
+                    .await?;
+                Ok::<_, String>(routes.into_iter().zip(vectors).collect())
+            })
+            .await?;
+
+        let prompt = self
+            .embed(
+                &classifier.config.embedding_model,
+                vec![prompt],
+                privacy_constraint,
+            )
+            .await?
+            .pop()
+            .ok_or_else(|| "embedding backend returned no vectors".to_string())?;
+
+        Ok(classifier.decide(&prompt, examples))
+    }
+
+    /// Embed texts with the given model through the routed backend's agent.
+    ///
+    /// The texts are routed like a prompt: `privacy_constraint` is the
+    /// caller's constraint, and PII in the texts restricts the backend choice
+    /// when detection is enabled.
+    pub async fn embed(
+        &self,
+        model: &str,
+        texts: Vec<String>,
+        privacy_constraint: Option<PrivacyZone>,
+    ) -> Result<Vec<Vec<f32>>, String> {
+        let prompt_text: Option<Arc<str>> =
+            self.pii_scanner.is_some().then(|| texts.join("\n").into());
+        // Embedding inputs are never redacted, so cloud redaction cannot
+        // stand in for the restricted zone
+        let privacy_constraint = match (&self.pii_scanner, prompt_text.as_deref()) {
+            (Some(scanner), Some(text))
+                if self.pii_redact_cloud && !scanner.detect(text).is_empty() =>
+            {
+                Some(PrivacyZone::Restricted)
+            }
+            _ => privacy_constraint,
+        };
+        let requirements = RequestRequirements {
+            model: model.to_string(),
+            estimated_tokens: texts.iter().map(|s| s.len() as u32 / 4).sum(),
+            needs_vision: false,
+            needs_tools: false,
+            needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: true,
+            prompt_tokens_exact: false,
+            prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint,
+            min_capability_tier: None,
+            prompt_text,
+            injection_risk: None,
+        };
+        let routing_result = self
+            .select_backend(&requirements, None)
+            .map_err(|e: RoutingError| e.to_string())?;
+        let backend = &routing_result.backend;
+
+        let agent = self
+            .registry
+            .get_agent(&backend.id)
+            .filter(|agent| agent.profile().capabilities.embeddings)
+            .ok_or_else(|| format!("backend '{}' does not support embeddings", backend.id))?;
+
+        let _permit = self.circuit_breakers.on_dispatch(&backend.id);
+        let _ = self.registry.increment_pending(&backend.id);
+        let start = Instant::now();
+        let result = agent.embeddings(model, texts).await;
+        let latency_ms = start.elapsed().as_millis() as u32;
+        let _ = self.registry.decrement_pending(&backend.id);
+
+        match &result {
+            Ok(_) => self.record_backend_outcome(&backend.id, true, latency_ms),
+            Err(e) => self.record_backend_failure(&backend.id, e.is_client_error(), latency_ms),
+        }
+        result.map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SemanticRoute;
+
+    fn config() -> SemanticRoutingConfig {
+        SemanticRoutingConfig {
+            enabled: true,
+            embedding_model: "embed".to_string(),
+            threshold: 0.8,
+            fallback_model: "general".to_string(),
+            routes: vec![
+                SemanticRoute {
+                    name: "code".to_string(),
+                    target_model: "coder".to_string(),
+                    min_tier: Some(3),
+                    examples: vec!["write code".to_string()],
+                },
+                SemanticRoute {
+                    name: "math".to_string(),
+                    target_model: "mathstral".to_string(),
+                    min_tier: None,
+                    examples: vec!["solve equation".to_string()],
+                },
+            ],
+        }
+    }
+
+    fn examples() -> Vec<(usize, Vec<f32>)> {
+        vec![(0, vec![1.0, 0.0, 0.0]), (1, vec![0.0, 1.0, 0.0])]
+    }
+
+    #[test]
+    fn cosine_similarity_basics() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn nearest_route_above_threshold_wins() {
+        let classifier = SemanticClassifier::new(config());
+        let decision = classifier.decide(&[0.1, 0.9, 0.0], &examples());
+        assert_eq!(decision.route.as_deref(), Some("math"));
+        assert_eq!(decision.target_model, "mathstral");
+        assert!(decision.detail().starts_with("math:0.99"));
+
+        let decision = classifier.decide(&[0.9, 0.1, 0.0], &examples());
+        assert_eq!(decision.target_model, "coder");
+        assert_eq!(decision.min_tier, Some(3));
+    }
+
+    #[test]
+    fn below_threshold_uses_fallback() {
+        let classifier = SemanticClassifier::new(config());
+        let decision = classifier.decide(&[0.5, 0.5, 0.7], &examples());
+        assert_eq!(decision.route, None);
+        assert_eq!(decision.target_model, "general");
+        assert_eq!(decision.min_tier, None);
+        assert_eq!(decision.detail(), "0.50");
+    }
+
+    #[test]
+    fn fallback_reports_unavailable_embedding() {
+        let classifier = SemanticClassifier::new(config());
+        assert_eq!(classifier.fallback().detail(), "embedding-unavailable");
+    }
+    fn cloud_embedding_router(redact_cloud: bool) -> Router {
+        use crate::config::PiiConfig;
+        use crate::registry::{
+            Backend, BackendStatus, BackendType, DiscoverySource, Model, Registry,
+        };
+        use crate::routing::{RoutingStrategy, ScoringWeights};
+
+        let registry = Arc::new(Registry::new());
+        let model = Model {
+            id: "embed".to_string(),
+            name: "embed".to_string(),
+            context_length: 8192,
+            supports_vision: false,
+            supports_tools: false,
+            supports_json_mode: false,
+            supports_embeddings: true,
+            max_output_tokens: None,
+        };
+        let backend = Backend::new(
+            "cloud".to_string(),
+            "cloud".to_string(),
+            "https://api.openai.com".to_string(),
+            BackendType::OpenAI,
+            vec![model],
+            DiscoverySource::Static,
+            std::collections::HashMap::new(),
+        );
+        registry.add_backend(backend).unwrap();
+        registry
+            .update_status("cloud", BackendStatus::Healthy, None)
+            .unwrap();
+
+        let mut router = Router::new(registry, RoutingStrategy::Smart, ScoringWeights::default());
+        router
+            .set_pii_config(&PiiConfig {
+                enabled: true,
+                redact_cloud,
+                ..Default::default()
+            })
+            .unwrap();
+        router
+    }
+
+    #[tokio::test]
+    async fn embed_keeps_pii_off_cloud_backends() {
+        for redact_cloud in [false, true] {
+            let router = cloud_embedding_router(redact_cloud);
+
+            // Without PII the cloud backend is selected (and has no agent here)
+            let err = router
+                .embed("embed", vec!["hello there".to_string()], None)
+                .await
+                .unwrap_err();
+            assert!(err.contains("does not support embeddings"), "{}", err);
+
+            let err = router
+                .embed("embed", vec!["mail jane@example.com".to_string()], None)
+                .await
+                .unwrap_err();
+            assert!(!err.contains("does not support embeddings"), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Request transformation policies
+//!
+//! Applies the configured `[transforms]` chain to chat requests before
+//! routing requirements are computed: system prompt injection, parameter
+//! clamping, stripping of disallowed parameters and default stop sequences.
+//! Tenants identified by a request header may replace, skip or extend rules.
+
+use super::Router;
+use crate::api::types::{ChatCompletionRequest, ChatMessage, MessageContent};
+use crate::config::{ConfigError, TransformConfig, TransformRule};
+use std::collections::HashMap;
+
+/// A transform rule with its model glob pre-compiled.
+#[derive(Debug, Clone)]
+struct CompiledTransform {
+    rule: TransformRule,
+    model: Option<globset::GlobMatcher>,
+}
+
+impl CompiledTransform {
+    fn compile(rule: &TransformRule) -> Self {
+        Self {
+            model: rule
+                .model_pattern
+                .as_deref()
+                .and_then(|p| globset::Glob::new(p).ok())
+                .map(|glob| glob.compile_matcher()),
+            rule: rule.clone(),
+        }
+    }
+
+    fn matches(&self, model: &str) -> bool {
+        self.model.as_ref().is_none_or(|glob| glob.is_match(model))
+    }
+
+    /// Apply the rule's actions to a request.
+    fn apply(&self, request: &mut ChatCompletionRequest) {
+        let rule = &self.rule;
+
+        if let Some(prompt) = &rule.system_prompt {
+            prepend_system_prompt(request, prompt);
+        }
+        if let Some(range) = &rule.max_tokens {
+            match request.max_tokens {
+                Some(value) => {
+                    request.max_tokens = Some(range.clamp(f64::from(value)).round() as u32);
+                }
+                // An unbounded request would let the backend run past the cap
+                None => request.max_tokens = range.max.map(|max| max.round() as u32),
+            }
+        }
+        if let Some(range) = &rule.temperature {
+            if let Some(value) = request.temperature {
+                request.temperature = Some(range.clamp(f64::from(value)) as f32);
+            }
+        }
+        if let Some(range) = &rule.top_p {
+            if let Some(value) = request.top_p {
+                request.top_p = Some(range.clamp(f64::from(value)) as f32);
+            }
+        }
+        for param in &rule.strip_params {
+            request.extra.remove(param);
+        }
+        if request.stop.is_none() && !rule.default_stop.is_empty() {
+            request.stop = Some(rule.default_stop.clone());
+        }
+    }
+}
+
+/// Merge `prompt` into a leading plain-text system message, or insert one.
+pub(crate) fn prepend_system_prompt(request: &mut ChatCompletionRequest, prompt: &str) {
+    if let Some(first) = request.messages.first_mut() {
+        if first.role == "system" {
+            if let MessageContent::Text { content } = &mut first.content {
+                *content = format!("{}\n\n{}", prompt, content);
+                return;
+            }
+        }
+    }
+    request.messages.insert(
+        0,
+        ChatMessage {
+            role: "system".to_string(),
+            content: MessageContent::Text {
+                content: prompt.to_string(),
+            },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        },
+    );
+}
+
+/// Pre-compiled transform chains: the global chain plus one resolved chain
+/// per tenant override.
+#[derive(Debug, Clone, Default)]
+pub struct TransformChain {
+    global: Vec<CompiledTransform>,
+    tenants: HashMap<String, Vec<CompiledTransform>>,
+}
+
+impl TransformChain {
+    /// Compile the transform configuration.
+    ///
+    /// Returns an error if the configuration is invalid (see
+    /// `TransformConfig::validate`).
+    pub fn compile(config: &TransformConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+
+        let global: Vec<CompiledTransform> = config
+            .rules
+            .iter()
+            .map(CompiledTransform::compile)
+            .collect();
+
+        let tenants = config
+            .tenants
+            .iter()
+            .map(|overrides| {
+                let mut chain: Vec<CompiledTransform> = global
+                    .iter()
+                    .filter(|t| !overrides.skip.contains(&t.rule.name))
+                    .cloned()
+                    .collect();
+                for rule in &overrides.rules {
+                    let compiled = CompiledTransform::compile(rule);
+                    match chain.iter_mut().find(|t| t.rule.name == rule.name) {
+                        Some(existing) => *existing = compiled,
+                        None => chain.push(compiled),
+                    }
+                }
+                (overrides.tenant.clone(), chain)
+            })
+            .collect();
+
+        Ok(Self { global, tenants })
+    }
+
+    /// Returns true if no transforms are configured
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.tenants.is_empty()
+    }
+
+    /// Apply the chain for the authenticated `tenant` in order, returning the
+    /// applied rule names.
+    pub fn apply(&self, request: &mut ChatCompletionRequest, tenant: Option<&str>) -> Vec<String> {
+        let chain = tenant
+            .and_then(|tenant| self.tenants.get(tenant))
+            .unwrap_or(&self.global);
+
+        let mut applied = Vec::new();
+        for transform in chain {
+            if transform.matches(&request.model) {
+                transform.apply(request);
+                applied.push(transform.rule.name.clone());
+            }
+        }
+        applied
+    }
+}
+
+impl Router {
+    /// Replace the request transform chain.
+    pub fn set_transforms(&mut self, config: &TransformConfig) -> Result<(), ConfigError> {
+        self.transforms = TransformChain::compile(config)?;
+        Ok(())
+    }
+
+    /// Apply the transform chain to a request.
+    ///
+    /// Must run before requirements are computed so routing sees the
+    /// transformed request. Returns the names of the applied transforms.
+    pub fn apply_transforms(
+        &self,
+        request: &mut ChatCompletionRequest,
+        tenant: Option<&str>,
+    ) -> Vec<String> {
+        if self.transforms.is_empty() {
+            return Vec::new();
+        }
+        let applied = self.transforms.apply(request, tenant);
+        if !applied.is_empty() {
+            tracing::debug!(
+                model = %request.model,
+                tenant = ?tenant,
+                transforms = ?applied,
+                "Request transforms applied"
+            );
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{TenantTransforms, ValueRange};
+
+    fn request(model: &str, messages: Vec<(&str, &str)>) -> ChatCompletionRequest {
+        ChatCompletionRequest {
+            model: model.to_string(),
+            messages: messages
+                .into_iter()
+                .map(|(role, content)| ChatMessage {
+                    role: role.to_string(),
+                    content: MessageContent::Text {
+                        content: content.to_string(),
+                    },
+                    name: None,
+                    function_call: None,
+                    tool_calls: None,
+                    tool_call_id: None,
+                })
+                .collect(),
+            stream: false,
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            stop: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+            user: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    fn text(message: &ChatMessage) -> &str {
+        match &message.content {
+            MessageContent::Text { content } => content,
+            MessageContent::Parts { .. } => panic!("expected text content"),
+        }
+    }
+
+    fn prompt_rule(name: &str, prompt: &str) -> TransformRule {
+        TransformRule {
+            name: name.to_string(),
+            system_prompt: Some(prompt.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn limits_rule() -> TransformRule {
+        TransformRule {
+            name: "limits".to_string(),
+            max_tokens: Some(ValueRange {
+                min: None,
+                max: Some(1024.0),
+            }),
+            temperature: Some(ValueRange {
+                min: Some(0.0),
+                max: Some(1.0),
+            }),
+            strip_params: vec!["logit_bias".to_string()],
+            default_stop: vec!["</s>".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn chain(rules: Vec<TransformRule>, tenants: Vec<TenantTransforms>) -> TransformChain {
+        TransformChain::compile(&TransformConfig { rules, tenants }).unwrap()
+    }
+
+    #[test]
+    fn system_prompt_is_inserted_or_merged() {
+        let chain = chain(vec![prompt_rule("company", "Be concise.")], vec![]);
+
+        let mut req = request("llama3", vec![("user", "hi")]);
+        assert_eq!(chain.apply(&mut req, None), vec!["company"]);
+        assert_eq!(req.messages[0].role, "system");
+        assert_eq!(text(&req.messages[0]), "Be concise.");
+
+        let mut req = request("llama3", vec![("system", "You are Bob."), ("user", "hi")]);
+        chain.apply(&mut req, None);
+        assert_eq!(req.messages.len(), 2);
+        assert_eq!(text(&req.messages[0]), "Be concise.\n\nYou are Bob.");
+    }
+
+    #[test]
+    fn parameters_are_clamped_stripped_and_defaulted() {
+        let chain = chain(vec![limits_rule()], vec![]);
+        let mut req = request("llama3", vec![("user", "hi")]);
+        req.max_tokens = Some(8000);
+        req.temperature = Some(1.7);
+        req.extra
+            .insert("logit_bias".to_string(), serde_json::json!({"42": 10}));
+        req.extra.insert("seed".to_string(), serde_json::json!(7));
+
+        chain.apply(&mut req, None);
+
+        assert_eq!(req.max_tokens, Some(1024));
+        assert_eq!(req.temperature, Some(1.0));
+        assert!(!req.extra.contains_key("logit_bias"));
+        assert!(req.extra.contains_key("seed"));
+        assert_eq!(req.stop, Some(vec!["</s>".to_string()]));
+    }
+
+    #[test]
+    fn missing_max_tokens_defaults_to_range_max() {
+        let mut req = request("llama3", vec![("user", "hi")]);
+        chain(vec![limits_rule()], vec![]).apply(&mut req, None);
+        assert_eq!(req.max_tokens, Some(1024));
+
+        let mut min_only = limits_rule();
+        min_only.max_tokens = Some(ValueRange {
+            min: Some(16.0),
+            max: None,
+        });
+        let mut req = request("llama3", vec![("user", "hi")]);
+        chain(vec![min_only], vec![]).apply(&mut req, None);
+        assert_eq!(req.max_tokens, None);
+    }
+
+    #[test]
+    fn unset_parameters_and_existing_stop_are_preserved() {
+        let chain = chain(vec![limits_rule()], vec![]);
+        let mut req = request("llama3", vec![("user", "hi")]);
+        req.stop = Some(vec!["END".to_string()]);
+
+        chain.apply(&mut req, None);
+
+        assert_eq!(req.temperature, None);
+        assert_eq!(req.stop, Some(vec!["END".to_string()]));
+    }
+
+    #[test]
+    fn rules_only_apply_to_matching_models() {
+        let mut rule = prompt_rule("llama-only", "x");
+        rule.model_pattern = Some("llama*".to_string());
+        let chain = chain(vec![rule, limits_rule()], vec![]);
+
+        let mut req = request("mistral", vec![("user", "hi")]);
+        assert_eq!(chain.apply(&mut req, None), vec!["limits"]);
+        assert_eq!(req.messages.len(), 1);
+    }
+
+    #[test]
+    fn tenant_overrides_skip_replace_and_extend() {
+        let tenants = vec![TenantTransforms {
+            tenant: "research".to_string(),
+            skip: vec!["limits".to_string()],
+            rules: vec![
+                prompt_rule("company", "Research mode."),
+                prompt_rule("extra", "Cite sources."),
+            ],
+        }];
+        let mut company = prompt_rule("company", "Be concise.");
+        company.overridable = true;
+        let mut limits = limits_rule();
+        limits.overridable = true;
+        let chain = chain(vec![company, limits], tenants);
+
+        let mut req = request("llama3", vec![("user", "hi")]);
+        req.max_tokens = Some(8000);
+        let applied = chain.apply(&mut req, Some("research"));
+
+        assert_eq!(applied, vec!["company", "extra"]);
+        assert_eq!(req.max_tokens, Some(8000));
+        assert_eq!(text(&req.messages[0]), "Cite sources.\n\nResearch mode.");
+
+        let mut req = request("llama3", vec![("user", "hi")]);
+        assert_eq!(
+            chain.apply(&mut req, Some("unknown")),
+            vec!["company", "limits"]
+        );
+    }
+}
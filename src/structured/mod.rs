@@ -0,0 +1,285 @@
+//! Structured output enforcement
+//!
+//! Handles `response_format: {type: "json_schema"}` requests. Backends that
+//! enforce schemas natively receive the request unchanged; others get the
+//! schema as a system instruction. Non-streaming responses are validated in
+//! the gateway, and invalid ones can be sent back to the model together with
+//! the validation errors.
+
+mod schema;
+
+pub use schema::validate;
+
+use crate::api::types::{ChatCompletionRequest, ChatMessage, MessageContent};
+use crate::api::ChatCompletionResponse;
+use crate::routing::transform::prepend_system_prompt;
+use serde_json::Value;
+
+/// Validation errors quoted back to the model in a repair request.
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// The JSON schema a request asked for.
+#[derive(Debug, Clone)]
+pub struct StructuredOutput {
+    schema: Value,
+}
+
+impl StructuredOutput {
+    /// Extract the schema from `response_format`, if the request has one.
+    ///
+    /// A `json_schema` format without a schema only requires valid JSON.
+    pub fn from_request(request: &ChatCompletionRequest) -> Option<Self> {
+        let format = request.extra.get("response_format")?;
+        if format.get("type").and_then(Value::as_str) != Some("json_schema") {
+            return None;
+        }
+        let schema = format
+            .get("json_schema")
+            .and_then(|spec| spec.get("schema"))
+            .cloned()
+            .unwrap_or(Value::Bool(true));
+        Some(Self { schema })
+    }
+
+    /// Rewrite a request for a backend without native schema support.
+    ///
+    /// The schema moves into a system instruction; `response_format` becomes
+    /// plain JSON mode when the model supports it and is dropped otherwise.
+    pub fn adapt_request(&self, request: &mut ChatCompletionRequest, json_mode: bool) {
+        let instruction = format!(
+            "Respond only with JSON that conforms to this JSON schema:\n{}",
+            self.schema
+        );
+        prepend_system_prompt(request, &instruction);
+        if json_mode {
+            request.extra.insert(
+                "response_format".to_string(),
+                serde_json::json!({"type": "json_object"}),
+            );
+        } else {
+            request.extra.remove("response_format");
+        }
+    }
+
+    /// Validate a response against the schema, returning the violations.
+    ///
+    /// Markdown code fences around otherwise valid JSON are stripped from the
+    /// response content.
+    pub fn check(&self, response: &mut ChatCompletionResponse) -> Vec<String> {
+        let Some(choice) = response.choices.first_mut() else {
+            return vec!["response has no choices".to_string()];
+        };
+        let MessageContent::Text { content } = &mut choice.message.content else {
+            return vec!["response content is not text".to_string()];
+        };
+        let text = json_text(content);
+        let value: Value = match serde_json::from_str(text) {
+            Ok(value) => value,
+            Err(e) => return vec![format!("response is not valid JSON: {}", e)],
+        };
+        let errors = validate(&self.schema, &value);
+        if errors.is_empty() && text.len() != content.len() {
+            *content = text.to_string();
+        }
+        errors
+    }
+
+    /// Follow-up request asking the model to correct an invalid response.
+    ///
+    /// `request` is the request that produced `response`.
+    pub fn repair_request(
+        &self,
+        request: &ChatCompletionRequest,
+        response: &ChatCompletionResponse,
+        errors: &[String],
+    ) -> ChatCompletionRequest {
+        let answer = response
+            .choices
+            .first()
+            .map(|choice| choice.message.content.clone())
+            .unwrap_or(MessageContent::Text {
+                content: String::new(),
+            });
+        let mut feedback =
+            "Your previous response does not match the required JSON schema:".to_string();
+        for error in errors.iter().take(MAX_REPORTED_ERRORS) {
+            feedback.push_str("\n- ");
+            feedback.push_str(error);
+        }
+        feedback.push_str("\nReply with only the corrected JSON.");
+
+        let mut repair = request.clone();
+        repair.stream = false;
+        repair.messages.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: answer,
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        });
+        repair.messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: MessageContent::Text { content: feedback },
+            name: None,
+            function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
+        });
+        repair
+    }
+}
+
+/// Outcome of validating a response, including repair attempts.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaReport {
+    /// Model calls made, including the original request
+    pub attempts: u32,
+    /// Violations in the final response (empty when valid)
+    pub errors: Vec<String>,
+}
+
+impl SchemaReport {
+    /// Outcome name used in metrics and the `X-Nexus-Schema-Validation` header.
+    pub fn outcome(&self) -> &'static str {
+        if !self.errors.is_empty() {
+            "failed"
+        } else if self.attempts > 1 {
+            "repaired"
+        } else {
+            "passed"
+        }
+    }
+
+    /// Count the outcome in `nexus_structured_output_total`.
+    pub fn record(&self) {
+        metrics::counter!("nexus_structured_output_total", "outcome" => self.outcome())
+            .increment(1);
+    }
+}
+
+/// Response text with surrounding whitespace and Markdown code fences removed.
+fn json_text(content: &str) -> &str {
+    let trimmed = content.trim();
+    if let Some(fenced) = trimmed.strip_prefix("```") {
+        let body = fenced.strip_prefix("json").unwrap_or(fenced);
+        if let Some(body) = body.strip_suffix("```") {
+            return body.trim();
+        }
+    }
+    trimmed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "llama3",
+            "messages": [{"role": "user", "content": "Who?"}],
+            "response_format": {
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "person",
+                    "schema": {
+                        "type": "object",
+                        "properties": {"name": {"type": "string"}},
+                        "required": ["name"]
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn text(message: &ChatMessage) -> &str {
+        match &message.content {
+            MessageContent::Text { content } => content,
+            MessageContent::Parts { .. } => panic!("expected text content"),
+        }
+    }
+
+    #[test]
+    fn only_json_schema_formats_are_extracted() {
+        assert!(StructuredOutput::from_request(&request()).is_some());
+
+        let mut req = request();
+        req.extra.insert(
+            "response_format".to_string(),
+            serde_json::json!({"type": "json_object"}),
+        );
+        assert!(StructuredOutput::from_request(&req).is_none());
+    }
+
+    #[test]
+    fn adapt_moves_schema_into_system_prompt() {
+        let structured = StructuredOutput::from_request(&request()).unwrap();
+
+        let mut req = request();
+        structured.adapt_request(&mut req, true);
+        assert_eq!(req.messages[0].role, "system");
+        assert!(text(&req.messages[0]).contains("\"required\":[\"name\"]"));
+        assert_eq!(req.extra["response_format"]["type"], "json_object");
+
+        let mut req = request();
+        structured.adapt_request(&mut req, false);
+        assert!(!req.extra.contains_key("response_format"));
+    }
+
+    #[test]
+    fn check_strips_code_fences_from_valid_json() {
+        let structured = StructuredOutput::from_request(&request()).unwrap();
+        let mut resp = response("```json\n{\"name\": \"Ada\"}\n```");
+        assert!(structured.check(&mut resp).is_empty());
+        assert_eq!(text(&resp.choices[0].message), "{\"name\": \"Ada\"}");
+
+        let mut resp = response("{\"age\": 3}");
+        assert_eq!(
+            structured.check(&mut resp),
+            vec!["$: missing required property 'name'"]
+        );
+        let mut resp = response("Sure! Here you go.");
+        assert!(structured.check(&mut resp)[0].starts_with("response is not valid JSON"));
+    }
+
+    #[test]
+    fn repair_request_quotes_answer_and_errors() {
+        let structured = StructuredOutput::from_request(&request()).unwrap();
+        let errors = vec!["$: missing required property 'name'".to_string()];
+        let repair = structured.repair_request(&request(), &response("{}"), &errors);
+
+        assert_eq!(repair.messages.len(), 3);
+        assert_eq!(repair.messages[1].role, "assistant");
+        assert_eq!(text(&repair.messages[1]), "{}");
+        assert!(text(&repair.messages[2]).contains("missing required property 'name'"));
+    }
+
+    #[test]
+    fn report_outcomes() {
+        let mut report = SchemaReport {
+            attempts: 1,
+            errors: vec![],
+        };
+        assert_eq!(report.outcome(), "passed");
+        report.attempts = 2;
+        assert_eq!(report.outcome(), "repaired");
+        report.errors.push("bad".to_string());
+        assert_eq!(report.outcome(), "failed");
+    }
+}
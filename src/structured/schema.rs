@@ -0,0 +1,369 @@
+//! JSON Schema validation
+//!
+//! Validates response JSON against the subset of JSON Schema that model
+//! providers accept for structured output: `type`, `enum`, `const`, object
+//! `properties` / `required` / `additionalProperties`, array `items` /
+//! `prefixItems`, string and numeric bounds, `pattern`, the `allOf` /
+//! `anyOf` / `oneOf` / `not` combinators and local `$ref`s (`#/$defs/...`).
+//! Unknown keywords (e.g. `format`) are ignored.
+
+use serde_json::{Map, Value};
+
+/// Maximum `$ref` / nesting depth, guarding against recursive schemas.
+const MAX_DEPTH: usize = 64;
+
+/// Validate `instance` against `schema`, returning one message per violation.
+///
+/// Messages start with the offending location (`$`, `$.name`, `$.items[2]`).
+pub fn validate(schema: &Value, instance: &Value) -> Vec<String> {
+    let mut validator = Validator {
+        root: schema,
+        errors: Vec::new(),
+    };
+    validator.check(schema, instance, "$", 0);
+    validator.errors
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    errors: Vec<String>,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, path: &str, message: impl std::fmt::Display) {
+        self.errors.push(format!("{}: {}", path, message));
+    }
+
+    /// Whether `instance` satisfies `schema`, without recording errors.
+    fn matches(&self, schema: &Value, instance: &Value, depth: usize) -> bool {
+        let mut trial = Validator {
+            root: self.root,
+            errors: Vec::new(),
+        };
+        trial.check(schema, instance, "$", depth);
+        trial.errors.is_empty()
+    }
+
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        let pointer = reference.strip_prefix('#')?;
+        if pointer.is_empty() {
+            return Some(self.root);
+        }
+        self.root.pointer(pointer)
+    }
+
+    fn check(&mut self, schema: &Value, instance: &Value, path: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            self.error(path, "schema nesting too deep");
+            return;
+        }
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                self.error(path, "no value is allowed here");
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match self.resolve(reference) {
+                Some(target) => self.check(target, instance, path, depth + 1),
+                None => self.error(path, format!("unresolvable $ref '{}'", reference)),
+            }
+        }
+
+        if let Some(expected) = schema.get("type") {
+            let types: Vec<&str> = match expected {
+                Value::String(t) => vec![t.as_str()],
+                Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.iter().any(|t| type_matches(t, instance)) {
+                self.error(
+                    path,
+                    format!(
+                        "expected {}, got {}",
+                        types.join(" or "),
+                        type_name(instance)
+                    ),
+                );
+                return;
+            }
+        }
+        if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+            if !options.contains(instance) {
+                self.error(
+                    path,
+                    format!("must be one of {}", Value::from(options.clone())),
+                );
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != instance {
+                self.error(path, format!("must equal {}", expected));
+            }
+        }
+
+        match instance {
+            Value::Object(object) => self.check_object(schema, object, path, depth),
+            Value::Array(items) => self.check_array(schema, items, path, depth),
+            Value::String(text) => self.check_string(schema, text, path),
+            Value::Number(number) => {
+                if let Some(number) = number.as_f64() {
+                    self.check_number(schema, number, path);
+                }
+            }
+            _ => {}
+        }
+
+        self.check_combinators(schema, instance, path, depth);
+    }
+
+    fn check_object(
+        &mut self,
+        schema: &Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+        depth: usize,
+    ) {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    self.error(path, format!("missing required property '{}'", name));
+                }
+            }
+        }
+        for (name, value) in object {
+            let child = format!("{}.{}", path, name);
+            match properties.and_then(|p| p.get(name)) {
+                Some(property) => self.check(property, value, &child, depth + 1),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        self.error(path, format!("unexpected property '{}'", name))
+                    }
+                    Some(additional) => self.check(additional, value, &child, depth + 1),
+                    None => {}
+                },
+            }
+        }
+    }
+
+    fn check_array(
+        &mut self,
+        schema: &Map<String, Value>,
+        items: &[Value],
+        path: &str,
+        depth: usize,
+    ) {
+        let prefix = schema
+            .get("prefixItems")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for (i, item) in items.iter().enumerate() {
+            let child = format!("{}[{}]", path, i);
+            if let Some(item_schema) = prefix.get(i).or_else(|| schema.get("items")) {
+                self.check(item_schema, item, &child, depth + 1);
+            }
+        }
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                self.error(path, format!("expected at least {} items", min));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if items.len() as u64 > max {
+                self.error(path, format!("expected at most {} items", max));
+            }
+        }
+        if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+            let duplicate = items
+                .iter()
+                .enumerate()
+                .any(|(i, item)| items[..i].contains(item));
+            if duplicate {
+                self.error(path, "items must be unique");
+            }
+        }
+    }
+
+    fn check_string(&mut self, schema: &Map<String, Value>, text: &str, path: &str) {
+        let length = text.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+            if length < min {
+                self.error(path, format!("expected at least {} characters", min));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+            if length > max {
+                self.error(path, format!("expected at most {} characters", max));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            // Patterns the regex engine cannot compile are not enforced
+            if let Ok(regex) = regex::Regex::new(pattern) {
+                if !regex.is_match(text) {
+                    self.error(path, format!("does not match pattern '{}'", pattern));
+                }
+            }
+        }
+    }
+
+    fn check_number(&mut self, schema: &Map<String, Value>, number: f64, path: &str) {
+        let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+        if let Some(min) = bound("minimum") {
+            if number < min {
+                self.error(path, format!("must be >= {}", min));
+            }
+        }
+        if let Some(max) = bound("maximum") {
+            if number > max {
+                self.error(path, format!("must be <= {}", max));
+            }
+        }
+        if let Some(min) = bound("exclusiveMinimum") {
+            if number <= min {
+                self.error(path, format!("must be > {}", min));
+            }
+        }
+        if let Some(max) = bound("exclusiveMaximum") {
+            if number >= max {
+                self.error(path, format!("must be < {}", max));
+            }
+        }
+        if let Some(step) = bound("multipleOf").filter(|step| *step > 0.0) {
+            let quotient = number / step;
+            if (quotient - quotient.round()).abs() > 1e-9 {
+                self.error(path, format!("must be a multiple of {}", step));
+            }
+        }
+    }
+
+    fn check_combinators(
+        &mut self,
+        schema: &Map<String, Value>,
+        instance: &Value,
+        path: &str,
+        depth: usize,
+    ) {
+        if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+            for sub in all {
+                self.check(sub, instance, path, depth + 1);
+            }
+        }
+        if let Some(any) = schema.get("anyOf").and_then(Value::as_array) {
+            if !any.iter().any(|sub| self.matches(sub, instance, depth + 1)) {
+                self.error(path, "does not match any of the allowed schemas (anyOf)");
+            }
+        }
+        if let Some(one) = schema.get("oneOf").and_then(Value::as_array) {
+            let matched = one
+                .iter()
+                .filter(|sub| self.matches(sub, instance, depth + 1))
+                .count();
+            if matched != 1 {
+                self.error(
+                    path,
+                    format!("must match exactly one schema (oneOf), matched {}", matched),
+                );
+            }
+        }
+        if let Some(not) = schema.get("not") {
+            if self.matches(not, instance, depth + 1) {
+                self.error(path, "must not match the schema in 'not'");
+            }
+        }
+    }
+}
+
+fn type_matches(expected: &str, instance: &Value) -> bool {
+    match expected {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "string" => instance.is_string(),
+        "array" => instance.is_array(),
+        "object" => instance.is_object(),
+        "number" => instance.is_number(),
+        "integer" => match instance {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn person() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "age": {"type": "integer", "minimum": 0},
+                "role": {"enum": ["admin", "user"]},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 2}
+            },
+            "required": ["name", "age"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn valid_instance_has_no_errors() {
+        let instance = json!({"name": "Ada", "age": 36, "role": "admin", "tags": ["x"]});
+        assert!(validate(&person(), &instance).is_empty());
+    }
+
+    #[test]
+    fn reports_each_violation_with_its_path() {
+        let instance = json!({"age": -1.5, "role": "root", "tags": ["a", 2, "c"], "extra": 1});
+        let errors = validate(&person(), &instance);
+        assert!(errors.contains(&"$: missing required property 'name'".to_string()));
+        assert!(errors.contains(&"$.age: expected integer, got number".to_string()));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("$.role: must be one of")));
+        assert!(errors.contains(&"$.tags[1]: expected string, got number".to_string()));
+        assert!(errors.contains(&"$.tags: expected at most 2 items".to_string()));
+        assert!(errors.contains(&"$: unexpected property 'extra'".to_string()));
+    }
+
+    #[test]
+    fn resolves_local_refs_and_combinators() {
+        let schema = json!({
+            "$defs": {"id": {"anyOf": [{"type": "integer"}, {"type": "string", "pattern": "^id-"}]}},
+            "type": "array",
+            "items": {"$ref": "#/$defs/id"}
+        });
+        assert!(validate(&schema, &json!([1, "id-2"])).is_empty());
+        let errors = validate(&schema, &json!([1, "x"]));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("$[1]: does not match any"));
+    }
+
+    #[test]
+    fn recursive_refs_terminate() {
+        let schema = json!({"$ref": "#"});
+        let errors = validate(&schema, &json!(1));
+        assert_eq!(errors, vec!["$: schema nesting too deep"]);
+    }
+}
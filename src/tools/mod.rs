@@ -0,0 +1,443 @@
+//! Tool-calling emulation
+//!
+//! Lets models without native function calling serve requests with `tools`.
+//! The tool definitions are rendered into a system prompt asking for a
+//! constrained tool-call format, and the model's output is parsed back into
+//! OpenAI `tool_calls`. Earlier tool calls and tool results in the
+//! conversation are rewritten as plain text the model can follow.
+
+mod stream;
+
+pub use stream::ToolCallStream;
+
+use crate::api::types::{ChatCompletionRequest, FunctionCall, MessageContent, ToolCall};
+use crate::api::ChatCompletionResponse;
+use crate::config::ToolCallFormat;
+use crate::routing::transform::prepend_system_prompt;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Opening tag of an XML-format tool call.
+const TOOL_CALL_OPEN: &str = "<tool_call>";
+
+/// Closing tag of an XML-format tool call.
+const TOOL_CALL_CLOSE: &str = "</tool_call>";
+
+/// Request fields that only native tool calling understands.
+const TOOL_FIELDS: [&str; 3] = ["tools", "tool_choice", "parallel_tool_calls"];
+
+/// Tool calls parsed from model output.
+#[derive(Debug, Clone, PartialEq)]
+struct ParsedCalls {
+    /// (function name, JSON-encoded arguments) per call
+    calls: Vec<(String, String)>,
+    /// Output text outside the tool calls
+    text: String,
+}
+
+/// The tools a request declared, for emulating calls to them.
+#[derive(Debug, Clone)]
+pub struct ToolEmulation {
+    /// Function definitions (`name`, `description`, `parameters`)
+    functions: Vec<Value>,
+    format: ToolCallFormat,
+}
+
+impl ToolEmulation {
+    /// Extract the function tools from a request, if it declares any.
+    pub fn from_request(request: &ChatCompletionRequest, format: ToolCallFormat) -> Option<Self> {
+        let functions: Vec<Value> = request
+            .extra
+            .get("tools")?
+            .as_array()?
+            .iter()
+            .filter_map(|tool| tool.get("function"))
+            .filter(|function| function.get("name").and_then(Value::as_str).is_some())
+            .cloned()
+            .collect();
+        if functions.is_empty() {
+            return None;
+        }
+        Some(Self { functions, format })
+    }
+
+    /// Rewrite a request for a model without native tool calling.
+    ///
+    /// Removes the tool fields, renders the tools into a system prompt
+    /// (unless `tool_choice` is `"none"`) and turns earlier tool calls and
+    /// `tool` messages into text.
+    pub fn adapt_request(&self, request: &mut ChatCompletionRequest) {
+        let choice = request.extra.get("tool_choice").cloned();
+        for field in TOOL_FIELDS {
+            request.extra.remove(field);
+        }
+
+        let mut call_names = HashMap::new();
+        for message in &mut request.messages {
+            if let Some(calls) = message.tool_calls.take() {
+                let mut text = message_text(&message.content);
+                for call in &calls {
+                    call_names.insert(call.id.clone(), call.function.name.clone());
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(&self.render_call(&call.function));
+                }
+                message.content = MessageContent::Text { content: text };
+            }
+            if message.role == "tool" {
+                let id = message.tool_call_id.take().unwrap_or_default();
+                let name = message
+                    .name
+                    .take()
+                    .or_else(|| call_names.get(&id).cloned())
+                    .unwrap_or_else(|| "tool".to_string());
+                message.role = "user".to_string();
+                message.content = MessageContent::Text {
+                    content: format!(
+                        "Result of {} (call {}):\n{}",
+                        name,
+                        id,
+                        message_text(&message.content)
+                    ),
+                };
+            }
+        }
+
+        if choice.as_ref().and_then(Value::as_str) == Some("none") {
+            return;
+        }
+        prepend_system_prompt(request, &self.instruction(choice.as_ref()));
+    }
+
+    /// Convert tool calls in a response's text into `tool_calls`.
+    ///
+    /// Returns whether any choice called a tool.
+    pub fn convert_response(&self, response: &mut ChatCompletionResponse) -> bool {
+        let mut called = false;
+        for choice in &mut response.choices {
+            let MessageContent::Text { content } = &choice.message.content else {
+                continue;
+            };
+            let Some(parsed) = self.parse(content) else {
+                continue;
+            };
+            choice.message.content = MessageContent::Text {
+                content: parsed.text,
+            };
+            choice.message.tool_calls = Some(
+                parsed
+                    .calls
+                    .into_iter()
+                    .map(|(name, arguments)| ToolCall {
+                        id: call_id(),
+                        call_type: "function".to_string(),
+                        function: FunctionCall { name, arguments },
+                    })
+                    .collect(),
+            );
+            choice.finish_reason = Some("tool_calls".to_string());
+            called = true;
+        }
+        record(called);
+        called
+    }
+
+    /// Streaming counterpart of [`convert_response`](Self::convert_response).
+    pub fn stream(self) -> ToolCallStream {
+        ToolCallStream::new(self)
+    }
+
+    /// System prompt describing the tools and the expected call format.
+    fn instruction(&self, choice: Option<&Value>) -> String {
+        let mut prompt = "You have access to the following tools:\n".to_string();
+        for function in &self.functions {
+            prompt.push_str(&function.to_string());
+            prompt.push('\n');
+        }
+        prompt.push('\n');
+        prompt.push_str(match self.format {
+            ToolCallFormat::Xml => {
+                "To call a tool, reply with one block per call and nothing else:\n\
+                 <tool_call>{\"name\": \"<tool name>\", \"arguments\": {<arguments as JSON>}}</tool_call>"
+            }
+            ToolCallFormat::Json => {
+                "To call tools, reply with only this JSON object:\n\
+                 {\"tool_calls\": [{\"name\": \"<tool name>\", \"arguments\": {<arguments as JSON>}}]}"
+            }
+        });
+        prompt.push('\n');
+
+        let forced = choice
+            .and_then(|c| c.get("function"))
+            .and_then(|f| f.get("name"))
+            .and_then(Value::as_str);
+        match (forced, choice.and_then(Value::as_str)) {
+            (Some(name), _) => prompt.push_str(&format!("You must call the `{}` tool.", name)),
+            (None, Some("required")) => prompt.push_str("You must call at least one tool."),
+            _ => prompt.push_str("If no tool is needed, answer the user directly."),
+        }
+        prompt
+    }
+
+    /// Render an earlier tool call in the format the model is asked to use.
+    fn render_call(&self, function: &FunctionCall) -> String {
+        let arguments: Value = serde_json::from_str(&function.arguments)
+            .unwrap_or_else(|_| Value::String(function.arguments.clone()));
+        let call = serde_json::json!({"name": function.name, "arguments": arguments});
+        match self.format {
+            ToolCallFormat::Xml => format!("{}{}{}", TOOL_CALL_OPEN, call, TOOL_CALL_CLOSE),
+            ToolCallFormat::Json => serde_json::json!({"tool_calls": [call]}).to_string(),
+        }
+    }
+
+    /// Parse tool calls from model output, in either format.
+    ///
+    /// Returns None when the output is not a tool call, or names a tool the
+    /// request did not declare; it is then passed through as text.
+    fn parse(&self, output: &str) -> Option<ParsedCalls> {
+        if output.contains(TOOL_CALL_OPEN) {
+            return self.parse_xml(output);
+        }
+        let value: Value = serde_json::from_str(strip_fences(output)).ok()?;
+        let calls = match &value {
+            Value::Array(calls) => calls.iter().collect(),
+            Value::Object(object) => match object.get("tool_calls").and_then(Value::as_array) {
+                Some(calls) => calls.iter().collect(),
+                None => vec![&value],
+            },
+            _ => return None,
+        };
+        Some(ParsedCalls {
+            calls: self.calls(calls)?,
+            text: String::new(),
+        })
+    }
+
+    fn parse_xml(&self, output: &str) -> Option<ParsedCalls> {
+        let mut text = String::new();
+        let mut bodies = Vec::new();
+        let mut rest = output;
+        while let Some(start) = rest.find(TOOL_CALL_OPEN) {
+            text.push_str(&rest[..start]);
+            let after = &rest[start + TOOL_CALL_OPEN.len()..];
+            // A missing closing tag at the end of the output is tolerated
+            let (body, next) = match after.find(TOOL_CALL_CLOSE) {
+                Some(end) => (&after[..end], &after[end + TOOL_CALL_CLOSE.len()..]),
+                None => (after, ""),
+            };
+            bodies.push(serde_json::from_str::<Value>(strip_fences(body)).ok()?);
+            rest = next;
+        }
+        text.push_str(rest);
+        Some(ParsedCalls {
+            calls: self.calls(bodies.iter().collect())?,
+            text: text.trim().to_string(),
+        })
+    }
+
+    /// Validate `{"name", "arguments"}` call objects against the declared tools.
+    fn calls(&self, values: Vec<&Value>) -> Option<Vec<(String, String)>> {
+        if values.is_empty() {
+            return None;
+        }
+        values
+            .into_iter()
+            .map(|value| {
+                let name = value.get("name").and_then(Value::as_str)?;
+                if !self.declares(name) {
+                    return None;
+                }
+                let arguments = match value.get("arguments").or_else(|| value.get("parameters")) {
+                    Some(Value::String(arguments)) => arguments.clone(),
+                    Some(arguments) => arguments.to_string(),
+                    None => "{}".to_string(),
+                };
+                Some((name.to_string(), arguments))
+            })
+            .collect()
+    }
+
+    fn declares(&self, name: &str) -> bool {
+        self.functions
+            .iter()
+            .any(|function| function.get("name").and_then(Value::as_str) == Some(name))
+    }
+}
+
+/// Count an emulated response in `nexus_tool_emulation_total`.
+fn record(called: bool) {
+    let outcome = if called { "tool_calls" } else { "text" };
+    metrics::counter!("nexus_tool_emulation_total", "outcome" => outcome).increment(1);
+}
+
+/// New OpenAI-style tool call ID.
+fn call_id() -> String {
+    format!("call_{}", &uuid::Uuid::new_v4().simple().to_string()[..24])
+}
+
+/// Plain text of a message, joining the text parts of multimodal content.
+fn message_text(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text { content } => content.clone(),
+        MessageContent::Parts { content } => content
+            .iter()
+            .filter_map(|part| part.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Text with surrounding whitespace and Markdown code fences removed.
+fn strip_fences(text: &str) -> &str {
+    let trimmed = text.trim();
+    if let Some(fenced) = trimmed.strip_prefix("```") {
+        let body = fenced.strip_prefix("json").unwrap_or(fenced);
+        if let Some(body) = body.strip_suffix("```") {
+            return body.trim();
+        }
+    }
+    trimmed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::ChatMessage;
+
+    fn request() -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "llama3",
+            "messages": [
+                {"role": "user", "content": "Weather in Paris?"},
+                {"role": "assistant", "content": null, "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                }]},
+                {"role": "tool", "tool_call_id": "call_1", "content": "18C"}
+            ],
+            "tools": [{
+                "type": "function",
+                "function": {
+                    "name": "get_weather",
+                    "description": "Current weather",
+                    "parameters": {"type": "object", "properties": {"city": {"type": "string"}}}
+                }
+            }],
+            "tool_choice": "auto"
+        }))
+        .unwrap()
+    }
+
+    fn response(content: &str) -> ChatCompletionResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": content},
+                "finish_reason": "stop"
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn text(message: &ChatMessage) -> &str {
+        match &message.content {
+            MessageContent::Text { content } => content,
+            MessageContent::Parts { .. } => panic!("expected text content"),
+        }
+    }
+
+    fn emulation(format: ToolCallFormat) -> ToolEmulation {
+        ToolEmulation::from_request(&request(), format).unwrap()
+    }
+
+    #[test]
+    fn adapt_renders_tools_and_rewrites_history() {
+        let mut req = request();
+        emulation(ToolCallFormat::Xml).adapt_request(&mut req);
+
+        assert!(!req.extra.contains_key("tools"));
+        assert!(!req.extra.contains_key("tool_choice"));
+        assert_eq!(req.messages[0].role, "system");
+        assert!(text(&req.messages[0]).contains("\"name\":\"get_weather\""));
+        assert!(text(&req.messages[0]).contains(TOOL_CALL_OPEN));
+
+        assert!(req.messages[2].tool_calls.is_none());
+        assert_eq!(
+            text(&req.messages[2]),
+            "<tool_call>{\"arguments\":{\"city\":\"Paris\"},\"name\":\"get_weather\"}</tool_call>"
+        );
+        assert_eq!(req.messages[3].role, "user");
+        assert_eq!(
+            text(&req.messages[3]),
+            "Result of get_weather (call call_1):\n18C"
+        );
+    }
+
+    #[test]
+    fn tool_choice_shapes_the_instruction() {
+        let mut req = request();
+        req.extra.insert(
+            "tool_choice".to_string(),
+            serde_json::json!({"type": "function", "function": {"name": "get_weather"}}),
+        );
+        emulation(ToolCallFormat::Json).adapt_request(&mut req);
+        assert!(text(&req.messages[0]).contains("You must call the `get_weather` tool."));
+        assert!(text(&req.messages[0]).contains("{\"tool_calls\": ["));
+
+        let mut req = request();
+        req.extra
+            .insert("tool_choice".to_string(), serde_json::json!("none"));
+        emulation(ToolCallFormat::Xml).adapt_request(&mut req);
+        assert_eq!(req.messages[0].role, "user");
+    }
+
+    #[test]
+    fn parses_xml_and_json_calls() {
+        let emulation = emulation(ToolCallFormat::Xml);
+        let parsed = emulation
+            .parse("Checking.\n<tool_call>{\"name\": \"get_weather\", \"arguments\": {\"city\": \"Oslo\"}}</tool_call>")
+            .unwrap();
+        assert_eq!(parsed.text, "Checking.");
+        assert_eq!(
+            parsed.calls,
+            vec![("get_weather".to_string(), "{\"city\":\"Oslo\"}".to_string())]
+        );
+
+        let parsed = emulation
+            .parse("```json\n{\"tool_calls\": [{\"name\": \"get_weather\", \"arguments\": \"{}\"}]}\n```")
+            .unwrap();
+        assert_eq!(parsed.calls[0].1, "{}");
+
+        assert!(emulation.parse("It is sunny.").is_none());
+        assert!(emulation.parse("{\"temperature\": 18}").is_none());
+        assert!(emulation
+            .parse("<tool_call>{\"name\": \"rm_rf\", \"arguments\": {}}</tool_call>")
+            .is_none());
+    }
+
+    #[test]
+    fn convert_response_sets_tool_calls() {
+        let emulation = emulation(ToolCallFormat::Xml);
+        let mut resp = response(
+            "<tool_call>{\"name\": \"get_weather\", \"arguments\": {\"city\": \"Oslo\"}}</tool_call>",
+        );
+        assert!(emulation.convert_response(&mut resp));
+
+        let choice = &resp.choices[0];
+        assert_eq!(choice.finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(text(&choice.message), "");
+        let calls = choice.message.tool_calls.as_ref().unwrap();
+        assert!(calls[0].id.starts_with("call_"));
+        assert_eq!(calls[0].function.name, "get_weather");
+
+        let mut resp = response("It is sunny.");
+        assert!(!emulation.convert_response(&mut resp));
+        assert!(resp.choices[0].message.tool_calls.is_none());
+    }
+}
@@ -0,0 +1,334 @@
+//! Streaming tool-call conversion
+//!
+//! Converts emulated tool calls in streamed `chat.completion.chunk` payloads
+//! into `tool_calls` deltas. Text before a tool call streams through; once a
+//! choice starts a call (a `<tool_call>` tag, or output opening with JSON)
+//! the rest is held back and parsed when the choice finishes. Output that
+//! turns out not to be a tool call is released as text.
+
+use super::{call_id, record, ToolEmulation, TOOL_CALL_OPEN};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Output state of one choice.
+#[derive(Default)]
+struct PendingChoice {
+    /// Text not yet released
+    text: String,
+    /// Whether the held text is a tool call in progress
+    capturing: bool,
+    /// Whether any text has been released
+    released: bool,
+}
+
+impl PendingChoice {
+    /// Add a delta and return the text that can be released now.
+    fn push(&mut self, delta: &str) -> String {
+        self.text.push_str(delta);
+        if self.capturing {
+            return String::new();
+        }
+        if !self.released {
+            let start = self.text.trim_start();
+            if start.is_empty() {
+                return String::new();
+            }
+            if start.starts_with('{') || start.starts_with("```") {
+                self.capturing = true;
+                return String::new();
+            }
+        }
+        // Hold back a possible partial opening tag at the end
+        let boundary = match self.text.find(TOOL_CALL_OPEN) {
+            Some(start) => {
+                self.capturing = true;
+                start
+            }
+            None => self.text.len() - partial_tag_len(&self.text),
+        };
+        let released: String = self.text.drain(..boundary).collect();
+        self.released |= !released.is_empty();
+        released
+    }
+}
+
+/// Converts one streamed response. A default stream passes data through
+/// unchanged.
+///
+/// Accepts either bare JSON payloads or raw SSE text (`data: {...}` lines,
+/// possibly split mid-line).
+#[derive(Default)]
+pub struct ToolCallStream {
+    emulation: Option<ToolEmulation>,
+    /// Pending output per choice index
+    choices: HashMap<u64, PendingChoice>,
+    /// Last chunk seen, used to emit held-back output at end of stream
+    template: Option<Value>,
+    /// Whether input arrives as raw SSE text rather than bare payloads
+    sse_framed: bool,
+    /// Incomplete trailing SSE line
+    line_buffer: String,
+}
+
+impl ToolCallStream {
+    pub(super) fn new(emulation: ToolEmulation) -> Self {
+        Self {
+            emulation: Some(emulation),
+            ..Default::default()
+        }
+    }
+
+    /// Convert one streamed chunk. Non-JSON payloads pass through unchanged.
+    ///
+    /// The result may be empty while output is held back or a line is incomplete.
+    pub fn filter_chunk(&mut self, data: &str) -> String {
+        if self.emulation.is_none() {
+            return data.to_string();
+        }
+        if self.sse_framed || data.trim_start().starts_with("data:") {
+            self.sse_framed = true;
+            return self.filter_sse(data);
+        }
+        self.filter_payload(data)
+    }
+
+    /// Convert the final data before `[DONE]` and release all held output.
+    pub fn finish(&mut self, tail: &str) -> String {
+        let mut out = self.filter_chunk(tail);
+        if !self.line_buffer.is_empty() {
+            let rest = std::mem::take(&mut self.line_buffer);
+            out.push_str(&self.filter_sse(&format!("{}\n", rest)));
+        }
+        if let Some(payload) = self.flush_payload() {
+            if self.sse_framed {
+                out.push_str(&format!("data: {}\n\n", payload));
+            } else {
+                out.push_str(&payload);
+            }
+        }
+        out
+    }
+
+    fn filter_sse(&mut self, text: &str) -> String {
+        self.line_buffer.push_str(text);
+        let mut out = String::new();
+        while let Some(pos) = self.line_buffer.find('\n') {
+            let line: String = self.line_buffer.drain(..=pos).collect();
+            let payload = line
+                .strip_prefix("data:")
+                .map(|rest| rest.trim_start_matches(' ').trim_end_matches(['\r', '\n']));
+            match payload {
+                Some("[DONE]") => {
+                    if let Some(payload) = self.flush_payload() {
+                        out.push_str(&format!("data: {}\n\n", payload));
+                    }
+                    out.push_str(&line);
+                }
+                Some(payload) => {
+                    out.push_str("data: ");
+                    out.push_str(&self.filter_payload(payload));
+                    out.push('\n');
+                }
+                None => out.push_str(&line),
+            }
+        }
+        out
+    }
+
+    fn filter_payload(&mut self, data: &str) -> String {
+        let Ok(mut chunk) = serde_json::from_str::<Value>(data) else {
+            return data.to_string();
+        };
+        let Some(choices) = chunk.get_mut("choices").and_then(|c| c.as_array_mut()) else {
+            return data.to_string();
+        };
+        for choice in choices.iter_mut() {
+            let index = choice.get("index").and_then(|i| i.as_u64()).unwrap_or(0);
+            let finished = choice
+                .get("finish_reason")
+                .is_some_and(|reason| !reason.is_null());
+            let delta = choice
+                .get("delta")
+                .and_then(|d| d.get("content"))
+                .and_then(|c| c.as_str())
+                .unwrap_or("");
+            if delta.is_empty() && !finished && !self.choices.contains_key(&index) {
+                continue;
+            }
+            let pending = self.choices.entry(index).or_default();
+            let released = pending.push(delta);
+            if finished {
+                let pending = self.choices.remove(&index).unwrap_or_default();
+                self.finish_choice(choice, released, pending);
+            } else if let Some(delta) = choice.get_mut("delta").and_then(|d| d.as_object_mut()) {
+                delta.insert("content".to_string(), Value::String(released));
+            }
+        }
+        self.template = Some(chunk.clone());
+        serde_json::to_string(&chunk).unwrap_or_else(|_| data.to_string())
+    }
+
+    fn flush_payload(&mut self) -> Option<String> {
+        let mut chunk = self.template.take()?;
+        let mut choices = Vec::new();
+        let mut pending: Vec<_> = self.choices.drain().collect();
+        pending.sort_by_key(|(index, _)| *index);
+        for (index, pending) in pending {
+            if pending.text.is_empty() {
+                continue;
+            }
+            let mut choice = json!({"index": index, "delta": {}, "finish_reason": null});
+            self.finish_choice(&mut choice, String::new(), pending);
+            choices.push(choice);
+        }
+        if choices.is_empty() {
+            return None;
+        }
+        chunk["choices"] = Value::Array(choices);
+        serde_json::to_string(&chunk).ok()
+    }
+
+    /// Resolve a finished choice's held output into text or `tool_calls`.
+    fn finish_choice(&self, choice: &mut Value, mut released: String, pending: PendingChoice) {
+        let parsed = match (&self.emulation, pending.capturing) {
+            (Some(emulation), true) => emulation.parse(&pending.text),
+            _ => None,
+        };
+        record(parsed.is_some());
+        let Some(parsed) = parsed else {
+            released.push_str(&pending.text);
+            choice["delta"]["content"] = Value::String(released);
+            return;
+        };
+        released.push_str(&parsed.text);
+        let calls: Vec<Value> = parsed
+            .calls
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, arguments))| {
+                json!({
+                    "index": i,
+                    "id": call_id(),
+                    "type": "function",
+                    "function": {"name": name, "arguments": arguments},
+                })
+            })
+            .collect();
+        choice["delta"]["content"] = if released.is_empty() {
+            Value::Null
+        } else {
+            Value::String(released)
+        };
+        choice["delta"]["tool_calls"] = Value::Array(calls);
+        choice["finish_reason"] = Value::String("tool_calls".to_string());
+    }
+}
+
+/// Length of the longest suffix of `text` that starts the opening tag.
+fn partial_tag_len(text: &str) -> usize {
+    (1..TOOL_CALL_OPEN.len())
+        .rev()
+        .find(|&len| text.ends_with(&TOOL_CALL_OPEN[..len]))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ToolCallFormat;
+
+    fn stream() -> ToolCallStream {
+        let request = serde_json::from_value(json!({
+            "model": "llama3",
+            "messages": [{"role": "user", "content": "Weather?"}],
+            "tools": [{"type": "function", "function": {"name": "get_weather"}}]
+        }))
+        .unwrap();
+        ToolEmulation::from_request(&request, ToolCallFormat::Xml)
+            .unwrap()
+            .stream()
+    }
+
+    fn chunk(content: &str, finish: Option<&str>) -> String {
+        json!({
+            "id": "c1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": finish}]
+        })
+        .to_string()
+    }
+
+    fn delta(payload: &str) -> Value {
+        serde_json::from_str::<Value>(payload).unwrap()["choices"][0].clone()
+    }
+
+    #[test]
+    fn default_stream_passes_through() {
+        let mut stream = ToolCallStream::default();
+        assert_eq!(stream.filter_chunk("data: x\n\n"), "data: x\n\n");
+    }
+
+    #[test]
+    fn text_streams_with_partial_tag_held_back() {
+        let mut stream = stream();
+        let out = delta(&stream.filter_chunk(&chunk("Sunny <tool", None)));
+        assert_eq!(out["delta"]["content"], "Sunny ");
+        let out = delta(&stream.filter_chunk(&chunk("s are fun", Some("stop"))));
+        assert_eq!(out["delta"]["content"], "<tools are fun");
+        assert_eq!(out["finish_reason"], "stop");
+    }
+
+    #[test]
+    fn tool_call_split_across_deltas_becomes_tool_call_delta() {
+        let mut stream = stream();
+        let first = delta(&stream.filter_chunk(&chunk("Let me check. <tool_", None)));
+        assert_eq!(first["delta"]["content"], "Let me check. ");
+        let held = delta(&stream.filter_chunk(&chunk(
+            "call>{\"name\": \"get_weather\", \"arguments\": {\"city\": \"Oslo\"}}",
+            None,
+        )));
+        assert_eq!(held["delta"]["content"], "");
+
+        let last = delta(&stream.filter_chunk(&chunk("</tool_call>", Some("stop"))));
+        assert_eq!(last["finish_reason"], "tool_calls");
+        assert!(last["delta"]["content"].is_null());
+        let call = &last["delta"]["tool_calls"][0];
+        assert_eq!(call["index"], 0);
+        assert_eq!(call["type"], "function");
+        assert_eq!(call["function"]["name"], "get_weather");
+        assert_eq!(call["function"]["arguments"], "{\"city\":\"Oslo\"}");
+    }
+
+    #[test]
+    fn sse_framed_json_call_is_flushed_at_done() {
+        let mut stream = stream();
+        let sse = format!(
+            "data: {}\n\ndata: {}\n\n",
+            chunk("{\"name\": \"get_weather\", ", None),
+            chunk("\"arguments\": {}}", None)
+        );
+        let out = stream.filter_chunk(&sse);
+        assert!(!out.contains("get_weather"));
+
+        let out = stream.filter_chunk("data: [DONE]\n\n");
+        let (payload, done) = out.split_once("\n\n").unwrap();
+        let choice = delta(payload.strip_prefix("data: ").unwrap());
+        assert_eq!(choice["finish_reason"], "tool_calls");
+        assert_eq!(
+            choice["delta"]["tool_calls"][0]["function"]["arguments"],
+            "{}"
+        );
+        assert_eq!(done, "data: [DONE]\n\n");
+    }
+
+    #[test]
+    fn json_text_that_is_not_a_call_is_released() {
+        let mut stream = stream();
+        stream.filter_chunk(&chunk("{\"temperature\"", None));
+        let out = delta(&stream.filter_chunk(&chunk(": 18}", Some("stop"))));
+        assert_eq!(out["delta"]["content"], "{\"temperature\": 18}");
+        assert_eq!(out["finish_reason"], "stop");
+    }
+}
@@ -43,6 +43,7 @@ fn create_backend(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         priority: 50,
@@ -51,6 +52,7 @@ fn create_backend(
         pending_requests: std::sync::atomic::AtomicU32::new(0),
         total_requests: std::sync::atomic::AtomicU64::new(0),
         avg_latency_ms: std::sync::atomic::AtomicU32::new(0),
+        reported_queue_depth: std::sync::atomic::AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
@@ -94,6 +96,9 @@ fn test_privacy_rejection_produces_reasons() {
         max_cost_per_request: None,
         min_tier: None,
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
 
@@ -119,7 +124,15 @@ fn test_privacy_rejection_produces_reasons() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -179,6 +192,9 @@ fn test_tier_rejection_produces_reasons_with_tier_info() {
         max_cost_per_request: None,
         min_tier: Some(4),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
 
@@ -204,7 +220,15 @@ fn test_tier_rejection_produces_reasons_with_tier_info() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -257,6 +281,9 @@ fn test_combined_privacy_and_tier_rejection() {
         max_cost_per_request: None,
         min_tier: Some(4),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
 
@@ -284,7 +311,15 @@ fn test_combined_privacy_and_tier_rejection() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -338,6 +373,9 @@ fn test_rejection_reasons_include_suggested_actions() {
         max_cost_per_request: None,
         min_tier: None,
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
 
@@ -363,7 +401,15 @@ fn test_rejection_reasons_include_suggested_actions() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -458,7 +504,15 @@ fn test_budget_hard_limit_block_all_produces_rejection() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -47,6 +47,7 @@ async fn create_test_app_with_two_backends(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );
@@ -72,6 +73,7 @@ async fn create_test_app_with_two_backends(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );
@@ -300,6 +302,7 @@ async fn test_completions_long_model_name() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );
@@ -0,0 +1,139 @@
+//! Integration tests for the Azure OpenAI backend type
+//!
+//! Verifies that chat requests reach the model's deployment with the
+//! `api-version` parameter and `api-key` header, and that Azure content
+//! filter rejections surface as `content_filter` errors.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DEPLOYMENT_PATH: &str = "/openai/deployments/prod-gpt4o/chat/completions";
+
+fn app(mock_server: &MockServer) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "azure-1".to_string(),
+        "azure".to_string(),
+        mock_server.uri(),
+        BackendType::AzureOpenAI,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let metadata = HashMap::from([
+        ("api_key".to_string(), "azure-key".to_string()),
+        ("api_version".to_string(), "2024-10-21".to_string()),
+        ("deployment:gpt-4o".to_string(), "prod-gpt4o".to_string()),
+    ]);
+    let agent = create_agent(
+        "azure-1".to_string(),
+        "azure".to_string(),
+        mock_server.uri(),
+        BackendType::AzureOpenAI,
+        Arc::new(reqwest::Client::new()),
+        metadata,
+        PrivacyZone::Open,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("azure-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models("azure-1", vec![common::make_model("gpt-4o")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn chat_request() -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "gpt-4o",
+        "messages": [{"role": "user", "content": "Hello"}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn chat_request_reaches_deployment() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(DEPLOYMENT_PATH))
+        .and(query_param("api-version", "2024-10-21"))
+        .and(header("api-key", "azure-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server);
+    let response = app.call(chat_request()).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-nexus-backend-type"], "cloud");
+}
+
+#[tokio::test]
+async fn content_filter_rejection_is_reported() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(DEPLOYMENT_PATH))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "message": "The response was filtered due to the prompt triggering Azure OpenAI's content management policy.",
+                "type": null,
+                "param": "prompt",
+                "code": "content_filter",
+                "status": 400,
+                "innererror": {
+                    "code": "ResponsibleAIPolicyViolation",
+                    "content_filter_result": {
+                        "hate": {"filtered": false, "severity": "safe"},
+                        "violence": {"filtered": true, "severity": "high"}
+                    }
+                }
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server);
+    let response = app.call(chat_request()).await.unwrap();
+
+    assert_eq!(response.status(), 422);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "content_filter");
+    assert!(json["error"]["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("(filtered: violence)"));
+}
@@ -43,6 +43,7 @@ fn test_backend(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         priority: 50,
@@ -51,6 +52,7 @@ fn test_backend(
         pending_requests: std::sync::atomic::AtomicU32::new(0),
         total_requests: std::sync::atomic::AtomicU64::new(0),
         avg_latency_ms: std::sync::atomic::AtomicU32::new(0),
+        reported_queue_depth: std::sync::atomic::AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
@@ -107,7 +109,15 @@ fn test_no_policies_no_filtering() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -149,7 +159,15 @@ fn test_empty_policy_matcher_passes_all() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -216,7 +234,15 @@ fn test_routing_without_f13_headers() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     // None = no tier enforcement header (backward compatible)
@@ -250,7 +276,15 @@ fn test_mixed_zone_configuration() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -290,7 +324,15 @@ fn test_select_backend_none_tier_mode() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     // Passing None should work exactly like before F13
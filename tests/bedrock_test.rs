@@ -0,0 +1,276 @@
+//! Integration tests for the AWS Bedrock backend type
+//!
+//! A local stub stands in for the Bedrock runtime endpoint and recomputes the
+//! SigV4 signature of every request, so chat and streaming requests only
+//! succeed when they are signed with the configured credentials.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use chrono::NaiveDateTime;
+use nexus::agent::factory::create_agent;
+use nexus::agent::sigv4::{self, AwsCredentials};
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Match, Mock, MockServer, ResponseTemplate};
+
+const REGION: &str = "eu-central-1";
+const ACCESS_KEY_ID: &str = "AKIDEXAMPLE";
+const SECRET_ACCESS_KEY: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+const MODEL_PATH: &str = "/model/anthropic.claude-3-haiku-20240307-v1%3A0";
+
+/// Matches requests whose `authorization` header is the signature the stub
+/// computes itself for the request's method, URL, body and `x-amz-date`.
+struct ValidSignature;
+
+impl Match for ValidSignature {
+    fn matches(&self, request: &wiremock::Request) -> bool {
+        let header = |name: &str| {
+            request
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let (Some(date), Some(authorization)) = (header("x-amz-date"), header("authorization"))
+        else {
+            return false;
+        };
+        let Ok(time) = NaiveDateTime::parse_from_str(date, "%Y%m%dT%H%M%SZ") else {
+            return false;
+        };
+        // wiremock reports origin-form requests against "localhost"; sign
+        // against the host the client actually sent
+        let mut url = request.url.clone();
+        let Some(Ok(())) = header("host").map(|host| url.set_host(host.split(':').next())) else {
+            return false;
+        };
+        let port = header("host")
+            .and_then(|host| host.split(':').nth(1))
+            .and_then(|port| port.parse().ok());
+        if url.set_port(port).is_err() {
+            return false;
+        }
+
+        let signed: Vec<(&str, &str)> = header("content-type")
+            .map(|value| ("content-type", value))
+            .into_iter()
+            .collect();
+        let credentials = AwsCredentials {
+            access_key_id: ACCESS_KEY_ID.to_string(),
+            secret_access_key: SECRET_ACCESS_KEY.to_string(),
+            session_token: None,
+        };
+        let expected = sigv4::sign(
+            request.method.as_str(),
+            &url,
+            &signed,
+            &request.body,
+            &credentials,
+            REGION,
+            "bedrock",
+            time.and_utc(),
+        );
+
+        expected
+            .iter()
+            .any(|(name, value)| name == "authorization" && value == authorization)
+    }
+}
+
+/// Encode one event-stream frame with string headers.
+fn event_frame(event_type: &str, payload: serde_json::Value) -> Vec<u8> {
+    let mut headers = Vec::new();
+    for (name, value) in [
+        (":event-type", event_type),
+        (":content-type", "application/json"),
+        (":message-type", "event"),
+    ] {
+        headers.push(name.len() as u8);
+        headers.extend_from_slice(name.as_bytes());
+        headers.push(7);
+        headers.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        headers.extend_from_slice(value.as_bytes());
+    }
+    let payload = payload.to_string().into_bytes();
+
+    let total_len = 16 + headers.len() + payload.len();
+    let mut frame = Vec::with_capacity(total_len);
+    frame.extend_from_slice(&(total_len as u32).to_be_bytes());
+    frame.extend_from_slice(&(headers.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&crc32fast::hash(&frame).to_be_bytes());
+    frame.extend_from_slice(&headers);
+    frame.extend_from_slice(&payload);
+    frame.extend_from_slice(&crc32fast::hash(&frame).to_be_bytes());
+    frame
+}
+
+fn app(mock_server: &MockServer, secret_access_key: &str) -> axum::Router {
+    // Variable names are unique per secret so parallel tests don't race
+    let secret_var = format!("BEDROCK_TEST_SECRET_{}", secret_access_key.len());
+    std::env::set_var("BEDROCK_TEST_ACCESS_KEY_ID", ACCESS_KEY_ID);
+    std::env::set_var(&secret_var, secret_access_key);
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "bedrock-1".to_string(),
+        "bedrock".to_string(),
+        mock_server.uri(),
+        BackendType::Bedrock,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let metadata = HashMap::from([
+        ("region".to_string(), REGION.to_string()),
+        (
+            "aws_access_key_id_env".to_string(),
+            "BEDROCK_TEST_ACCESS_KEY_ID".to_string(),
+        ),
+        ("aws_secret_access_key_env".to_string(), secret_var),
+        (
+            "deployment:claude-3-haiku".to_string(),
+            "anthropic.claude-3-haiku-20240307-v1:0".to_string(),
+        ),
+    ]);
+    let agent = create_agent(
+        "bedrock-1".to_string(),
+        "bedrock".to_string(),
+        mock_server.uri(),
+        BackendType::Bedrock,
+        Arc::new(reqwest::Client::new()),
+        metadata,
+        PrivacyZone::Open,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("bedrock-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models("bedrock-1", vec![common::make_model("claude-3-haiku")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn chat_request(stream: bool) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "claude-3-haiku",
+        "messages": [
+            {"role": "system", "content": "Be brief."},
+            {"role": "user", "content": "Hello"}
+        ],
+        "stream": stream
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn mount_converse(mock_server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path(format!("{}/converse", MODEL_PATH)))
+        .and(ValidSignature)
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "output": {"message": {"role": "assistant", "content": [{"text": "Hi there"}]}},
+            "stopReason": "end_turn",
+            "usage": {"inputTokens": 9, "outputTokens": 2, "totalTokens": 11}
+        })))
+        .mount(mock_server)
+        .await;
+}
+
+#[tokio::test]
+async fn signed_chat_request_is_translated() {
+    let mock_server = MockServer::start().await;
+    mount_converse(&mock_server).await;
+
+    let mut app = app(&mock_server, SECRET_ACCESS_KEY);
+    let response = app.call(chat_request(false)).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-nexus-backend-type"], "cloud");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["model"], "claude-3-haiku");
+    assert_eq!(json["choices"][0]["message"]["content"], "Hi there");
+    assert_eq!(json["choices"][0]["finish_reason"], "stop");
+    assert_eq!(json["usage"]["total_tokens"], 11);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let sent: serde_json::Value = requests[0].body_json().unwrap();
+    assert_eq!(sent["system"][0]["text"], "Be brief.");
+    assert_eq!(sent["messages"][0]["content"][0]["text"], "Hello");
+}
+
+#[tokio::test]
+async fn wrongly_signed_request_is_rejected() {
+    let mock_server = MockServer::start().await;
+    mount_converse(&mock_server).await;
+
+    let mut app = app(&mock_server, "not-the-secret");
+    let response = app.call(chat_request(false)).await.unwrap();
+
+    assert!(response.status().is_client_error() || response.status().is_server_error());
+}
+
+#[tokio::test]
+async fn converse_stream_is_translated_to_sse() {
+    let mock_server = MockServer::start().await;
+    let mut events = event_frame("messageStart", serde_json::json!({"role": "assistant"}));
+    for text in ["Hi", " there"] {
+        events.extend(event_frame(
+            "contentBlockDelta",
+            serde_json::json!({"contentBlockIndex": 0, "delta": {"text": text}}),
+        ));
+    }
+    events.extend(event_frame(
+        "messageStop",
+        serde_json::json!({"stopReason": "end_turn"}),
+    ));
+    Mock::given(method("POST"))
+        .and(path(format!("{}/converse-stream", MODEL_PATH)))
+        .and(ValidSignature)
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "application/vnd.amazon.eventstream")
+                .set_body_bytes(events),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server, SECRET_ACCESS_KEY);
+    let response = app.call(chat_request(true)).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    let content: String = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter_map(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+        .filter_map(|chunk| {
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(String::from)
+        })
+        .collect();
+    assert_eq!(content, "Hi there");
+    assert!(body.contains(r#""finish_reason":"stop""#));
+    assert!(body.contains("data: [DONE]"));
+}
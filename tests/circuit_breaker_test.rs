@@ -0,0 +1,170 @@
+//! Integration tests for per-backend circuit breakers
+//!
+//! Verifies that consecutive backend failures open the circuit, that open
+//! circuits stop traffic from reaching the backend, and that circuit state is
+//! reported by /v1/stats.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn make_app(mock_server: &MockServer) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let mut config = NexusConfig::default();
+    config.routing.max_retries = 0;
+    config.queue.enabled = false;
+    config.circuit_breaker.failure_threshold = 2;
+    config.circuit_breaker.open_duration_seconds = 60;
+
+    let backend = Backend::new(
+        "flaky-backend".to_string(),
+        "Flaky Backend".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("flaky-backend", BackendStatus::Healthy, None);
+    let _ = registry.update_models("flaky-backend", vec![common::make_model("test-model")]);
+
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    create_router(state)
+}
+
+fn chat_request() -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "test-model",
+        "messages": [{"role": "user", "content": "Hello"}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn body_json(response: axum::response::Response) -> Value {
+    let bytes: Vec<u8> = response
+        .into_body()
+        .into_data_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .filter_map(|chunk| chunk.ok())
+        .flat_map(|chunk| chunk.to_vec())
+        .collect();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn consecutive_failures_open_circuit_and_reject() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(500).set_body_json(serde_json::json!({
+            "error": {"message": "boom", "type": "server_error", "param": null, "code": null}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut app = make_app(&mock_server).await;
+
+    // Two failures reach the backend
+    for _ in 0..2 {
+        let response = app.call(chat_request()).await.unwrap();
+        assert_ne!(response.status(), 503);
+    }
+
+    // Third request is rejected without reaching the backend
+    let response = app.call(chat_request()).await.unwrap();
+    assert_eq!(response.status(), 503);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+
+    // Circuit state is visible in /v1/stats
+    let stats_request = Request::builder()
+        .uri("/v1/stats")
+        .body(Body::empty())
+        .unwrap();
+    let stats = body_json(app.call(stats_request).await.unwrap()).await;
+    let backend = &stats["backends"][0];
+    assert_eq!(backend["circuit"]["state"], "open");
+    assert_eq!(backend["circuit"]["consecutive_failures"], 2);
+}
+
+#[tokio::test]
+async fn successes_keep_circuit_closed() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "test-model",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut app = make_app(&mock_server).await;
+    for _ in 0..3 {
+        let response = app.call(chat_request()).await.unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    let stats_request = Request::builder()
+        .uri("/v1/stats")
+        .body(Body::empty())
+        .unwrap();
+    let stats = body_json(app.call(stats_request).await.unwrap()).await;
+    assert_eq!(stats["backends"][0]["circuit"]["state"], "closed");
+}
+
+#[tokio::test]
+async fn client_errors_do_not_open_circuit() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "message": "messages must not be empty",
+                "type": "invalid_request_error",
+                "param": "messages",
+                "code": null
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let mut app = make_app(&mock_server).await;
+    for _ in 0..3 {
+        let response = app.call(chat_request()).await.unwrap();
+        assert_ne!(response.status(), 503);
+    }
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 3);
+
+    let stats_request = Request::builder()
+        .uri("/v1/stats")
+        .body(Body::empty())
+        .unwrap();
+    let stats = body_json(app.call(stats_request).await.unwrap()).await;
+    assert_eq!(stats["backends"][0]["circuit"]["state"], "closed");
+}
@@ -46,6 +46,7 @@ async fn create_app_with_type(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );
@@ -0,0 +1,300 @@
+//! Integration tests for the Cohere backend type and the provider catalogue
+//!
+//! A wiremock server stands in for Cohere's v2 API and for an
+//! OpenAI-compatible provider declared under `[providers.*]`.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn app(mock_server: &MockServer, model: nexus::registry::Model) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "cohere-1".to_string(),
+        "cohere".to_string(),
+        mock_server.uri(),
+        BackendType::Cohere,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "cohere-1".to_string(),
+        "cohere".to_string(),
+        mock_server.uri(),
+        BackendType::Cohere,
+        Arc::new(reqwest::Client::new()),
+        HashMap::from([("api_key".to_string(), "co-test".to_string())]),
+        PrivacyZone::Open,
+        Some(4),
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("cohere-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models("cohere-1", vec![model]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn post(uri: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn body_to_string(response: axum::response::Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn chat_is_translated_to_v2_chat() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v2/chat"))
+        .and(header("authorization", "Bearer co-test"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "command-r",
+            "messages": [
+                {"role": "system", "content": "Be brief."},
+                {"role": "user", "content": "Hello"}
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "c1",
+            "finish_reason": "COMPLETE",
+            "message": {"role": "assistant", "content": [{"type": "text", "text": "Hi there"}]},
+            "usage": {"tokens": {"input_tokens": 9, "output_tokens": 2}}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server, common::make_model("command-r"));
+    let response = app
+        .call(post(
+            "/v1/chat/completions",
+            serde_json::json!({
+                "model": "command-r",
+                "messages": [
+                    {"role": "system", "content": "Be brief."},
+                    {"role": "user", "content": "Hello"}
+                ]
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-nexus-backend-type"], "cloud");
+    let json: serde_json::Value = serde_json::from_str(&body_to_string(response).await).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Hi there");
+    assert_eq!(json["choices"][0]["finish_reason"], "stop");
+    assert_eq!(json["usage"]["total_tokens"], 11);
+}
+
+#[tokio::test]
+async fn chat_stream_is_translated_to_sse() {
+    let mock_server = MockServer::start().await;
+    let mut events = String::new();
+    for event in [
+        serde_json::json!({"type": "message-start", "id": "c1", "delta": {"message": {"role": "assistant"}}}),
+        serde_json::json!({"type": "content-delta", "index": 0, "delta": {"message": {"content": {"text": "Hi"}}}}),
+        serde_json::json!({"type": "content-delta", "index": 0, "delta": {"message": {"content": {"text": " there"}}}}),
+        serde_json::json!({"type": "message-end", "delta": {"finish_reason": "COMPLETE"}}),
+    ] {
+        events.push_str(&format!("event: {}\ndata: {}\n\n", event["type"], event));
+    }
+    Mock::given(method("POST"))
+        .and(path("/v2/chat"))
+        .and(body_partial_json(serde_json::json!({"stream": true})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(events),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server, common::make_model("command-r"));
+    let response = app
+        .call(post(
+            "/v1/chat/completions",
+            serde_json::json!({
+                "model": "command-r",
+                "messages": [{"role": "user", "content": "Hello"}],
+                "stream": true
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = body_to_string(response).await;
+    let content: String = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter_map(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+        .filter_map(|chunk| {
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(String::from)
+        })
+        .collect();
+    assert_eq!(content, "Hi there");
+    assert!(body.contains(r#""finish_reason":"stop""#));
+    assert!(body.contains("data: [DONE]"));
+}
+
+#[tokio::test]
+async fn embeddings_are_served_from_v2_embed() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v2/embed"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "embed-english-v3.0",
+            "texts": ["hello", "world"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "e1",
+            "embeddings": {"float": [[0.1, 0.2], [0.3, 0.4]]}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let model = nexus::registry::Model {
+        supports_embeddings: true,
+        ..common::make_model("embed-english-v3.0")
+    };
+    let mut app = app(&mock_server, model);
+    let response = app
+        .call(post(
+            "/v1/embeddings",
+            serde_json::json!({"model": "embed-english-v3.0", "input": ["hello", "world"]}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body_to_string(response).await).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+    assert_eq!(json["data"][1]["index"], 1);
+}
+
+#[tokio::test]
+async fn rerank_is_served_from_v2_rerank() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v2/rerank"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "r1",
+            "results": [
+                {"index": 1, "relevance_score": 0.98},
+                {"index": 0, "relevance_score": 0.01}
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server, common::make_model("rerank-v3.5"));
+    let response = app
+        .call(post(
+            "/v1/rerank",
+            serde_json::json!({
+                "model": "rerank-v3.5",
+                "query": "What is Deep Learning?",
+                "documents": ["Cheese is tasty.", "Deep Learning is a subset of ML."]
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_str(&body_to_string(response).await).unwrap();
+    assert_eq!(json["results"][0]["index"], 1);
+    assert_eq!(json["results"][1]["index"], 0);
+}
+
+#[tokio::test]
+async fn custom_provider_auth_and_pricing_are_applied() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(header("x-api-key", "acme-secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-acme",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "acme-large",
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hi"}, "finish_reason": "stop"}],
+            "usage": {"prompt_tokens": 1000, "completion_tokens": 1000, "total_tokens": 2000}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    std::env::set_var("COHERE_TEST_ACME_KEY", "acme-secret");
+    let mut config: NexusConfig = toml::from_str(&format!(
+        r#"
+        [[backends]]
+        name = "acme"
+        type = "openai"
+        provider = "acme"
+
+        [providers.acme]
+        base_url = "{}"
+        api_key_env = "COHERE_TEST_ACME_KEY"
+        auth_header = "x-api-key"
+        auth_scheme = ""
+
+        [providers.acme.pricing]
+        "acme-large" = {{ input_price_per_1k = 0.002, output_price_per_1k = 0.006 }}
+        "#,
+        mock_server.uri()
+    ))
+    .unwrap();
+    config.resolve_providers().unwrap();
+    config.queue.enabled = false;
+
+    let registry = Arc::new(Registry::new());
+    nexus::cli::serve::load_backends_from_config(&config, &registry).unwrap();
+    let backend_id = registry.get_all_backends()[0].id.clone();
+    let _ = registry.update_status(&backend_id, BackendStatus::Healthy, None);
+    let _ = registry.update_models(&backend_id, vec![common::make_model("acme-large")]);
+
+    let mut app = create_router(Arc::new(AppState::new(registry, Arc::new(config))));
+    let response = app
+        .call(post(
+            "/v1/chat/completions",
+            serde_json::json!({
+                "model": "acme-large",
+                "messages": [{"role": "user", "content": "Hello"}]
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-nexus-cost-estimated"], "0.0080");
+}
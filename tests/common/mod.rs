@@ -38,6 +38,7 @@ pub fn make_model(id: &str) -> Model {
         supports_vision: false,
         supports_tools: false,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }
 }
@@ -51,6 +52,7 @@ pub fn make_vision_model(id: &str, context_length: u32) -> Model {
         supports_vision: true,
         supports_tools: false,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }
 }
@@ -64,10 +66,19 @@ pub fn make_tools_model(id: &str, context_length: u32) -> Model {
         supports_vision: false,
         supports_tools: true,
         supports_json_mode: false,
+        supports_embeddings: false,
         max_output_tokens: None,
     }
 }
 
+/// Create a test embedding model.
+pub fn make_embedding_model(id: &str) -> Model {
+    Model {
+        supports_embeddings: true,
+        ..make_model(id)
+    }
+}
+
 // =============================================================================
 // Backend Builders
 // =============================================================================
@@ -90,6 +101,7 @@ pub fn make_backend(id: &str, name: &str, model_id: &str, priority: i32) -> Back
         pending_requests: AtomicU32::new(0),
         total_requests: AtomicU64::new(0),
         avg_latency_ms: AtomicU32::new(50),
+        reported_queue_depth: AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
@@ -154,6 +166,7 @@ pub async fn make_app_with_mock(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: true,
             max_output_tokens: None,
         }],
     );
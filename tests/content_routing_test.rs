@@ -0,0 +1,180 @@
+//! Integration tests for content-based routing rules
+//!
+//! Verifies that `[[routing.rules]]` rewrite the target model based on
+//! request content and the X-Nexus-Route-Tag header, report the matched
+//! rule in response headers, and apply privacy constraints.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::api::{create_router, AppState};
+use nexus::config::{ContentRule, NexusConfig, PrivacyConstraint};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn rules() -> Vec<ContentRule> {
+    vec![
+        ContentRule {
+            name: "sensitive".to_string(),
+            keywords: vec!["confidential".to_string()],
+            target_model: Some("small".to_string()),
+            privacy: Some(PrivacyConstraint::Restricted),
+            ..Default::default()
+        },
+        ContentRule {
+            name: "batch".to_string(),
+            model_pattern: Some("auto".to_string()),
+            route_tag: Some("batch".to_string()),
+            target_model: Some("small".to_string()),
+            ..Default::default()
+        },
+        ContentRule {
+            name: "code".to_string(),
+            model_pattern: Some("auto".to_string()),
+            has_code: Some(true),
+            target_model: Some("large".to_string()),
+            ..Default::default()
+        },
+        ContentRule {
+            name: "chat".to_string(),
+            model_pattern: Some("auto".to_string()),
+            target_model: Some("small".to_string()),
+            ..Default::default()
+        },
+    ]
+}
+
+async fn setup(backend_type: BackendType) -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "small",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Done"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "backend-1".to_string(),
+        "Backend 1".to_string(),
+        mock_server.uri(),
+        backend_type,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("backend-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "backend-1",
+        vec![common::make_model("small"), common::make_model("large")],
+    );
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.routing.rules = rules();
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(content: &str, route_tag: Option<&str>) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "auto",
+        "messages": [{"role": "user", "content": content}]
+    });
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(tag) = route_tag {
+        builder = builder.header("x-nexus-route-tag", tag);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn forwarded_model(mock_server: &MockServer) -> Value {
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    let body: Value = serde_json::from_slice(&requests[0].body).unwrap();
+    body["model"].clone()
+}
+
+#[tokio::test]
+async fn code_prompt_routes_to_large_model() {
+    let (mock_server, mut app) = setup(BackendType::Generic).await;
+
+    let response = app
+        .call(chat_request("Review this:\n```\nfn main() {}\n```", None))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-route-rule").unwrap(),
+        "code"
+    );
+    assert_eq!(
+        response.headers().get("x-nexus-route-reason").unwrap(),
+        "content-rule"
+    );
+    assert_eq!(forwarded_model(&mock_server).await, "large");
+}
+
+#[tokio::test]
+async fn chit_chat_routes_to_small_model() {
+    let (mock_server, mut app) = setup(BackendType::Generic).await;
+
+    let response = app.call(chat_request("Hi there!", None)).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-route-rule").unwrap(),
+        "chat"
+    );
+    assert_eq!(forwarded_model(&mock_server).await, "small");
+}
+
+#[tokio::test]
+async fn route_tag_header_selects_rule() {
+    let (mock_server, mut app) = setup(BackendType::Generic).await;
+
+    let response = app
+        .call(chat_request("```\nlet x = 1;\n```", Some("batch")))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-route-rule").unwrap(),
+        "batch"
+    );
+    assert_eq!(forwarded_model(&mock_server).await, "small");
+}
+
+#[tokio::test]
+async fn privacy_rule_keeps_request_off_cloud_backends() {
+    let (mock_server, mut app) = setup(BackendType::OpenAI).await;
+
+    let response = app
+        .call(chat_request("This is confidential", None))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 503);
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
@@ -0,0 +1,193 @@
+//! Integration tests for context-window-aware routing
+//!
+//! Verifies that max_tokens is reserved as context headroom and that the
+//! X-Nexus-Context-Overflow header selects upgrade, truncate or reject
+//! handling for oversized requests.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Model, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn model(id: &str, context_length: u32) -> Model {
+    Model {
+        context_length,
+        ..common::make_model(id)
+    }
+}
+
+async fn setup() -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "small",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Done"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "backend-1".to_string(),
+        "Backend 1".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("backend-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "backend-1",
+        vec![model("small", 1000), model("large", 32768)],
+    );
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(body: Value, overflow: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(mode) = overflow {
+        builder = builder.header("x-nexus-context-overflow", mode);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+fn long_conversation() -> Value {
+    let filler = "word ".repeat(350);
+    serde_json::json!({
+        "model": "small",
+        "max_tokens": 100,
+        "messages": [
+            {"role": "system", "content": "You are helpful."},
+            {"role": "user", "content": filler},
+            {"role": "assistant", "content": filler},
+            {"role": "user", "content": filler},
+            {"role": "user", "content": "Summarize."}
+        ]
+    })
+}
+
+async fn body_json(response: axum::response::Response) -> Value {
+    let bytes: Vec<u8> = response
+        .into_body()
+        .into_data_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .filter_map(|chunk| chunk.ok())
+        .flat_map(|chunk| chunk.to_vec())
+        .collect();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+async fn forwarded_body(mock_server: &MockServer) -> Value {
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    serde_json::from_slice(&requests[0].body).unwrap()
+}
+
+#[tokio::test]
+async fn max_tokens_is_reserved_as_headroom() {
+    let (mock_server, mut app) = setup().await;
+    let body = serde_json::json!({
+        "model": "small",
+        "max_tokens": 2000,
+        "messages": [{"role": "user", "content": "Hi"}]
+    });
+
+    let response = app.call(chat_request(body, None)).await.unwrap();
+
+    assert_eq!(response.status(), 503);
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn reject_mode_returns_actionable_400() {
+    let (mock_server, mut app) = setup().await;
+    let body = serde_json::json!({
+        "model": "small",
+        "max_tokens": 2000,
+        "messages": [{"role": "user", "content": "Hi"}]
+    });
+
+    let response = app.call(chat_request(body, Some("reject"))).await.unwrap();
+
+    assert_eq!(response.status(), 400);
+    let json = body_json(response).await;
+    assert_eq!(json["error"]["code"], "context_length_exceeded");
+    let message = json["error"]["message"].as_str().unwrap();
+    assert!(message.contains("1000"));
+    assert!(message.contains("max_tokens"));
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn upgrade_mode_routes_to_larger_context_model() {
+    let (mock_server, mut app) = setup().await;
+    let body = serde_json::json!({
+        "model": "small",
+        "max_tokens": 2000,
+        "messages": [{"role": "user", "content": "Hi"}]
+    });
+
+    let response = app.call(chat_request(body, Some("upgrade"))).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-context-overflow").unwrap(),
+        "upgraded:large"
+    );
+    assert_eq!(forwarded_body(&mock_server).await["model"], "large");
+}
+
+#[tokio::test]
+async fn truncate_mode_drops_oldest_messages() {
+    let (mock_server, mut app) = setup().await;
+
+    let response = app
+        .call(chat_request(long_conversation(), Some("truncate")))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let header = response
+        .headers()
+        .get("x-nexus-context-overflow")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(header.starts_with("truncated:"));
+
+    let forwarded = forwarded_body(&mock_server).await;
+    let messages = forwarded["messages"].as_array().unwrap();
+    assert!(messages.len() < 5);
+    assert_eq!(messages[0]["role"], "system");
+    assert_eq!(messages.last().unwrap()["content"], "Summarize.");
+}
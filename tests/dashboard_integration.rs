@@ -93,6 +93,7 @@ async fn test_websocket_sends_backend_status_update() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -203,6 +204,7 @@ async fn test_model_matrix_reflects_model_removal_when_backend_offline() {
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: None,
         },
         Model {
@@ -212,6 +214,7 @@ async fn test_model_matrix_reflects_model_removal_when_backend_offline() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         },
     ];
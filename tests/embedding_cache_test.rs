@@ -0,0 +1,145 @@
+//! Integration tests for the `/v1/embeddings` vector cache
+//!
+//! Verifies that only uncached inputs are forwarded, that cached and fresh
+//! vectors come back in the original order, and that concurrent identical
+//! inputs share one backend call.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::Service;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_embedding(mock_server: &MockServer, input: &str, value: f32) {
+    Mock::given(method("POST"))
+        .and(path("/api/embed"))
+        .and(body_partial_json(serde_json::json!({ "input": input })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "embeddings": [[value, 0.0]] }))
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+async fn setup() -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    mount_embedding(&mock_server, "alpha", 1.0).await;
+    mount_embedding(&mock_server, "beta", 2.0).await;
+    mount_embedding(&mock_server, "gamma", 3.0).await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "ollama-1".to_string(),
+        "Ollama 1".to_string(),
+        mock_server.uri(),
+        BackendType::Ollama,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "ollama-1".to_string(),
+        "Ollama 1".to_string(),
+        mock_server.uri(),
+        BackendType::Ollama,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("ollama-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models("ollama-1", vec![common::make_embedding_model("embed")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.cache.embeddings.enabled = true;
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn embedding_request(input: &[&str]) -> Request<Body> {
+    let body = serde_json::json!({ "model": "embed", "input": input });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/embeddings")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn first_values(response: axum::response::Response) -> Vec<f64> {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["embedding"][0].as_f64().unwrap())
+        .collect()
+}
+
+async fn forwarded_inputs(mock_server: &MockServer) -> Vec<String> {
+    mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| {
+            let body: Value = serde_json::from_slice(&r.body).unwrap();
+            body["input"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn only_uncached_inputs_are_forwarded_in_order() {
+    let (mock_server, mut app) = setup().await;
+
+    let first = app.call(embedding_request(&["beta"])).await.unwrap();
+    assert_eq!(first.status(), 200);
+
+    let response = app
+        .call(embedding_request(&["alpha", "beta", "gamma"]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(first_values(response).await, vec![1.0, 2.0, 3.0]);
+
+    assert_eq!(
+        forwarded_inputs(&mock_server).await,
+        vec!["beta", "alpha", "gamma"]
+    );
+}
+
+#[tokio::test]
+async fn concurrent_identical_inputs_share_one_backend_call() {
+    let (mock_server, app) = setup().await;
+
+    let requests = (0..3).map(|_| {
+        let mut app = app.clone();
+        async move { app.call(embedding_request(&["alpha"])).await.unwrap() }
+    });
+    for response in futures::future::join_all(requests).await {
+        assert_eq!(response.status(), 200);
+        assert_eq!(first_values(response).await, vec![1.0]);
+    }
+
+    assert_eq!(forwarded_inputs(&mock_server).await, vec!["alpha"]);
+}
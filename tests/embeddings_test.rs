@@ -5,6 +5,13 @@ mod common;
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use common::make_app_with_mock;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
 use tower::Service;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, ResponseTemplate};
@@ -144,3 +151,88 @@ async fn embeddings_invalid_json_returns_422() {
     // Axum returns 400 for JSON parse errors in this project
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
+
+async fn generic_embedding_app(mock_server: &wiremock::MockServer) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "vllm-1".to_string(),
+        "vLLM 1".to_string(),
+        mock_server.uri(),
+        BackendType::VLLM,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "vllm-1".to_string(),
+        "vLLM 1".to_string(),
+        mock_server.uri(),
+        BackendType::VLLM,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("vllm-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "vllm-1",
+        vec![
+            common::make_embedding_model("bge-small"),
+            common::make_model("llama3"),
+        ],
+    );
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn embedding_request(model: &str) -> Request<Body> {
+    let body = serde_json::json!({"model": model, "input": ["hello", "world"]});
+    Request::builder()
+        .method("POST")
+        .uri("/v1/embeddings")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn generic_backend_serves_embedding_models() {
+    let mock_server = wiremock::MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/embeddings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [
+                {"object": "embedding", "embedding": [0.1, 0.2], "index": 0},
+                {"object": "embedding", "embedding": [0.3, 0.4], "index": 1}
+            ],
+            "model": "bge-small"
+        })))
+        .mount(&mock_server)
+        .await;
+    let mut app = generic_embedding_app(&mock_server).await;
+
+    let response = app.call(embedding_request("bge-small")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+    let value = json["data"][1]["embedding"][0].as_f64().unwrap();
+    assert!((value - 0.3).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn chat_models_are_not_routed_embedding_requests() {
+    let mock_server = wiremock::MockServer::start().await;
+    let mut app = generic_embedding_app(&mock_server).await;
+
+    let response = app.call(embedding_request("llama3")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
@@ -49,6 +49,7 @@ async fn create_test_app_with_fallback(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );
@@ -74,6 +75,7 @@ async fn create_test_app_with_fallback(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );
@@ -277,3 +277,61 @@ async fn test_latency_tracking() {
     // Just verify it's been set (can be 0 for sub-millisecond responses)
     assert!(latency < 1000, "Latency should be reasonable");
 }
+
+#[tokio::test]
+async fn test_health_check_records_vllm_queue_depth() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [{"id": "llama3"}]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/metrics"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "vllm:num_requests_running{model_name=\"llama3\"} 3.0\n\
+             vllm:num_requests_waiting{model_name=\"llama3\"} 4.0\n",
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "vllm-1".to_string(),
+        "vLLM".to_string(),
+        mock_server.uri(),
+        BackendType::VLLM,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = nexus::agent::factory::create_agent(
+        "vllm-1".to_string(),
+        "vLLM".to_string(),
+        mock_server.uri(),
+        BackendType::VLLM,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        nexus::agent::PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+
+    let checker = HealthChecker::new(registry.clone(), HealthCheckConfig::default());
+    let results = checker.check_all_backends().await;
+    assert!(matches!(results[0].1, HealthCheckResult::Success { .. }));
+
+    // Nexus has nothing in flight, but the backend is busy with other clients
+    let backend = registry.get_backend("vllm-1").unwrap();
+    assert_eq!(
+        backend
+            .reported_queue_depth
+            .load(std::sync::atomic::Ordering::SeqCst),
+        7
+    );
+    assert_eq!(backend.queue_depth(), 7);
+}
@@ -272,6 +272,7 @@ async fn test_loading_backend_blocked_from_routing() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -324,6 +325,7 @@ async fn test_loading_backend_blocked_from_routing() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -363,7 +365,15 @@ async fn test_loading_backend_blocked_from_routing() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let result = router.select_backend(&requirements, None);
@@ -441,6 +451,7 @@ async fn test_migrate_model_initiates_coordination() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -556,6 +567,7 @@ async fn test_migration_source_continues_serving() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -647,7 +659,15 @@ async fn test_migration_source_continues_serving() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let result = router.select_backend(&requirements, None);
@@ -716,6 +736,7 @@ async fn test_traffic_shifts_after_target_loads() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -754,7 +775,15 @@ async fn test_traffic_shifts_after_target_loads() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let result = router.select_backend(&requirements, None);
@@ -829,6 +858,7 @@ async fn test_migration_rollback_on_failure() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -966,6 +996,7 @@ async fn test_unload_model_success_returns_200() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
@@ -1047,6 +1078,7 @@ async fn test_unload_model_with_active_requests_returns_409() {
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         DiscoverySource::Static,
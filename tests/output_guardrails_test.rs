@@ -0,0 +1,267 @@
+//! Integration tests for output guardrails
+//!
+//! Verifies that response rules redact, flag and block non-streaming
+//! responses, that blocked streams end with an error chunk and `[DONE]`,
+//! and that the moderation model is consulted through Nexus routing without
+//! leaving the answering backend's privacy zone.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::api::{create_router, AppState};
+use nexus::config::{GuardrailAction, GuardrailRule, NexusConfig};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn completion(model: &str, content: &str) -> Value {
+    serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop"
+        }]
+    })
+}
+
+fn sse_body(deltas: &[&str]) -> String {
+    let mut body = String::new();
+    for delta in deltas {
+        let chunk = serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{"index": 0, "delta": {"content": delta}, "finish_reason": null}]
+        });
+        body.push_str(&format!("data: {}\n\n", chunk));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+async fn setup(
+    template: ResponseTemplate,
+    configure: impl FnOnce(&mut NexusConfig),
+) -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(body_partial_json(serde_json::json!({"model": "guard"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(completion("guard", "unsafe\nS1")))
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(template)
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "local".to_string(),
+        "local".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("local", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "local",
+        vec![common::make_model("llama3"), common::make_model("guard")],
+    );
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.guardrails.enabled = true;
+    config.guardrails.rules = vec![
+        GuardrailRule {
+            name: "api_key".to_string(),
+            pattern: Some("sk-[a-z0-9]{8,}".to_string()),
+            keywords: vec![],
+            action: GuardrailAction::Redact,
+        },
+        GuardrailRule {
+            name: "codenames".to_string(),
+            pattern: None,
+            keywords: vec!["bluebird".to_string()],
+            action: GuardrailAction::Block,
+        },
+    ];
+    configure(&mut config);
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(stream: bool) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "stream": stream,
+        "messages": [{"role": "user", "content": "hello"}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn body_text(response: axum::response::Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn redacts_response_and_reports_rule() {
+    let template = ResponseTemplate::new(200)
+        .set_body_json(completion("llama3", "Your key is sk-abcdef123456."));
+    let (_mock_server, mut app) = setup(template, |_| {}).await;
+
+    let response = app.call(chat_request(false)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-guardrails").unwrap(),
+        "redact:api_key"
+    );
+    let json: Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(
+        json["choices"][0]["message"]["content"],
+        "Your key is [REDACTED]."
+    );
+}
+
+#[tokio::test]
+async fn blocks_response_with_banned_term() {
+    let template = ResponseTemplate::new(200)
+        .set_body_json(completion("llama3", "Project Bluebird launches soon."));
+    let (_mock_server, mut app) = setup(template, |_| {}).await;
+
+    let response = app.call(chat_request(false)).await.unwrap();
+    assert_eq!(response.status(), 422);
+    let json: Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(json["error"]["code"], "content_filter");
+    assert!(!json.to_string().contains("Bluebird"));
+}
+
+#[tokio::test]
+async fn blocked_stream_ends_with_error_chunk() {
+    let template = ResponseTemplate::new(200).set_body_raw(
+        sse_body(&["Project Blue", "bird launches", " soon."]),
+        "text/event-stream",
+    );
+    let (_mock_server, mut app) = setup(template, |_| {}).await;
+
+    let response = app.call(chat_request(true)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body = body_text(response).await;
+
+    assert!(!body.to_lowercase().contains("bluebird"));
+    assert!(!body.contains("launches"));
+    assert!(body.contains("blocked by guardrail 'codenames'"));
+    assert!(body.trim_end().ends_with("data: [DONE]"));
+}
+
+#[tokio::test]
+async fn stream_redacts_match_split_across_chunks() {
+    let template = ResponseTemplate::new(200).set_body_raw(
+        sse_body(&["key: sk-abc", "def123456", " ok"]),
+        "text/event-stream",
+    );
+    let (_mock_server, mut app) = setup(template, |_| {}).await;
+
+    let response = app.call(chat_request(true)).await.unwrap();
+    let body = body_text(response).await;
+
+    let text: String = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter_map(|payload| serde_json::from_str::<Value>(payload).ok())
+        .filter_map(|chunk| {
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(String::from)
+        })
+        .collect();
+    assert_eq!(text, "key: [REDACTED] ok");
+}
+
+#[tokio::test]
+async fn moderation_model_blocks_unsafe_response() {
+    let template =
+        ResponseTemplate::new(200).set_body_json(completion("llama3", "Harmless looking text."));
+    let (mock_server, mut app) = setup(template, |config| {
+        config.guardrails.moderation.enabled = true;
+        config.guardrails.moderation.model = "guard".to_string();
+    })
+    .await;
+
+    let response = app.call(chat_request(false)).await.unwrap();
+    assert_eq!(response.status(), 422);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let moderation: Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(moderation["model"], "guard");
+    assert_eq!(
+        moderation["messages"][1]["content"],
+        "Harmless looking text."
+    );
+}
+
+#[tokio::test]
+async fn moderation_stays_in_the_answering_backends_zone() {
+    let template =
+        ResponseTemplate::new(200).set_body_json(completion("llama3", "Harmless looking text."));
+    let (local_server, _) = setup(template.clone(), |_| {}).await;
+    let cloud_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(completion("guard", "safe")))
+        .mount(&cloud_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    for (id, uri, backend_type, model) in [
+        ("local", local_server.uri(), BackendType::Generic, "llama3"),
+        ("cloud", cloud_server.uri(), BackendType::OpenAI, "guard"),
+    ] {
+        let backend = Backend::new(
+            id.to_string(),
+            id.to_string(),
+            uri,
+            backend_type,
+            vec![],
+            DiscoverySource::Static,
+            HashMap::new(),
+        );
+        registry.add_backend(backend).unwrap();
+        let _ = registry.update_status(id, BackendStatus::Healthy, None);
+        let _ = registry.update_models(id, vec![common::make_model(model)]);
+    }
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.guardrails.enabled = true;
+    config.guardrails.moderation.enabled = true;
+    config.guardrails.moderation.model = "guard".to_string();
+    let mut app = create_router(Arc::new(AppState::new(registry, Arc::new(config))));
+
+    // No restricted backend serves the moderation model, so the check fails
+    // open instead of sending a local answer to the cloud
+    let response = app.call(chat_request(false)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(cloud_server.received_requests().await.unwrap().is_empty());
+}
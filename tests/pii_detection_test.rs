@@ -0,0 +1,148 @@
+//! Integration tests for PII detection
+//!
+//! Verifies that prompts containing PII are restricted to local backends
+//! when `[pii]` is enabled, and that rejections never echo the matched
+//! content.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const EMAIL: &str = "jane.doe@example.com";
+
+async fn mock_backend() -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Done"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn add_backend(registry: &Registry, id: &str, url: String, backend_type: BackendType) {
+    let backend = Backend::new(
+        id.to_string(),
+        id.to_string(),
+        url,
+        backend_type,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status(id, BackendStatus::Healthy, None);
+    let _ = registry.update_models(id, vec![common::make_model("llama3")]);
+}
+
+fn app(registry: Arc<Registry>, pii_enabled: bool) -> axum::Router {
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.pii.enabled = pii_enabled;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn chat_request(content: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "messages": [{"role": "user", "content": content}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn body_string(response: axum::response::Response) -> String {
+    let bytes: Vec<u8> = response
+        .into_body()
+        .into_data_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .filter_map(|chunk| chunk.ok())
+        .flat_map(|chunk| chunk.to_vec())
+        .collect();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[tokio::test]
+async fn pii_prompt_routes_to_local_backend() {
+    let local = mock_backend().await;
+    let cloud = mock_backend().await;
+    let registry = Arc::new(Registry::new());
+    add_backend(&registry, "local", local.uri(), BackendType::Generic);
+    add_backend(&registry, "cloud", cloud.uri(), BackendType::OpenAI);
+    let mut app = app(registry, true);
+
+    for _ in 0..3 {
+        let response = app
+            .call(chat_request(&format!("Email {} the invoice", EMAIL)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get("x-nexus-privacy-zone").unwrap(),
+            "restricted"
+        );
+    }
+
+    assert_eq!(local.received_requests().await.unwrap().len(), 3);
+    assert!(cloud.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn pii_prompt_rejected_without_local_backend() {
+    let cloud = mock_backend().await;
+    let registry = Arc::new(Registry::new());
+    add_backend(&registry, "cloud", cloud.uri(), BackendType::OpenAI);
+    let mut app = app(registry, true);
+
+    let response = app
+        .call(chat_request(&format!("Email {} the invoice", EMAIL)))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 503);
+    assert!(!format!("{:?}", response.headers()).contains(EMAIL));
+    let body = body_string(response).await;
+    assert!(!body.contains(EMAIL));
+    assert!(cloud.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn pii_detection_disabled_by_default() {
+    let cloud = mock_backend().await;
+    let registry = Arc::new(Registry::new());
+    add_backend(&registry, "cloud", cloud.uri(), BackendType::OpenAI);
+    let mut app = app(registry, false);
+
+    let response = app
+        .call(chat_request(&format!("Email {} the invoice", EMAIL)))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+}
@@ -0,0 +1,173 @@
+//! Integration tests for reversible PII redaction
+//!
+//! Verifies that with `pii.redact_cloud` enabled, requests dispatched to the
+//! OpenAI agent carry placeholders instead of the original entities, and that
+//! the originals are restored in both buffered and streamed responses.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const EMAIL: &str = "jane.doe@example.com";
+
+async fn setup(response: ResponseTemplate) -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(response)
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let metadata = HashMap::from([("api_key".to_string(), "sk-test".to_string())]);
+    let backend = Backend::new(
+        "openai".to_string(),
+        "OpenAI".to_string(),
+        mock_server.uri(),
+        BackendType::OpenAI,
+        vec![],
+        DiscoverySource::Static,
+        metadata.clone(),
+    );
+    let agent = create_agent(
+        "openai".to_string(),
+        "OpenAI".to_string(),
+        mock_server.uri(),
+        BackendType::OpenAI,
+        Arc::new(reqwest::Client::new()),
+        metadata,
+        PrivacyZone::Open,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("openai", BackendStatus::Healthy, None);
+    let _ = registry.update_models("openai", vec![common::make_model("gpt-4")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.pii.enabled = true;
+    config.pii.redact_cloud = true;
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(stream: bool) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "gpt-4",
+        "stream": stream,
+        "messages": [{"role": "user", "content": format!("Draft a reply to {}", EMAIL)}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn body_string(response: axum::response::Response) -> String {
+    let bytes: Vec<u8> = response
+        .into_body()
+        .into_data_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .filter_map(|chunk| chunk.ok())
+        .flat_map(|chunk| chunk.to_vec())
+        .collect();
+    String::from_utf8(bytes).unwrap()
+}
+
+async fn forwarded_body(mock_server: &MockServer) -> String {
+    let requests = mock_server.received_requests().await.unwrap();
+    String::from_utf8(requests[0].body.clone()).unwrap()
+}
+
+fn sse_chunk(content: &str, finish_reason: Option<&str>) -> String {
+    let chunk = serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 0,
+        "model": "gpt-4",
+        "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": finish_reason}]
+    });
+    format!("data: {}\n\n", chunk)
+}
+
+#[tokio::test]
+async fn buffered_response_restores_placeholders() {
+    let (mock_server, mut app) =
+        setup(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi [EMAIL_1], thanks!"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .await;
+
+    let response = app.call(chat_request(false)).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    let forwarded = forwarded_body(&mock_server).await;
+    assert!(forwarded.contains("[EMAIL_1]"));
+    assert!(!forwarded.contains(EMAIL));
+    let body: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+    assert_eq!(
+        body["choices"][0]["message"]["content"],
+        format!("Hi {}, thanks!", EMAIL)
+    );
+}
+
+#[tokio::test]
+async fn streamed_response_restores_split_placeholders() {
+    let sse = [
+        sse_chunk("Hi [EM", None),
+        sse_chunk("AIL_", None),
+        sse_chunk("1], thanks!", None),
+        sse_chunk("", Some("stop")),
+        "data: [DONE]\n\n".to_string(),
+    ]
+    .concat();
+    let (mock_server, mut app) = setup(
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/event-stream")
+            .set_body_string(sse),
+    )
+    .await;
+
+    let response = app.call(chat_request(true)).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    let body = body_string(response).await;
+    assert!(!forwarded_body(&mock_server).await.contains(EMAIL));
+    let content: String = body
+        .lines()
+        .map(|line| line.trim_start_matches("data: "))
+        .filter(|data| *data != "[DONE]")
+        .filter_map(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+        .filter_map(|chunk| {
+            chunk["choices"][0]["delta"]["content"]
+                .as_str()
+                .map(String::from)
+        })
+        .collect();
+    assert_eq!(content, format!("Hi {}, thanks!", EMAIL));
+}
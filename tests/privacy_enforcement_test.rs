@@ -50,6 +50,7 @@ fn create_test_backend(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         priority: 50,
@@ -58,6 +59,7 @@ fn create_test_backend(
         pending_requests: std::sync::atomic::AtomicU32::new(0),
         total_requests: std::sync::atomic::AtomicU64::new(0),
         avg_latency_ms: std::sync::atomic::AtomicU32::new(0),
+        reported_queue_depth: std::sync::atomic::AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: metadata.clone(),
         current_operation: None,
@@ -103,6 +105,9 @@ fn test_restricted_backend_available_routes_correctly() {
         max_cost_per_request: None,
         min_tier: None,
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -135,7 +140,15 @@ fn test_restricted_backend_available_routes_correctly() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -201,6 +214,9 @@ fn test_restricted_backend_offline_open_available_returns_503() {
         max_cost_per_request: None,
         min_tier: None,
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -233,7 +249,15 @@ fn test_restricted_backend_offline_open_available_returns_503() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -297,6 +321,9 @@ fn test_cross_zone_failover_never_happens() {
         max_cost_per_request: None,
         min_tier: None,
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -329,7 +356,15 @@ fn test_cross_zone_failover_never_happens() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
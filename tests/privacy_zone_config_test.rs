@@ -9,6 +9,7 @@
 use nexus::agent::PrivacyZone;
 use nexus::config::backend::BackendConfig;
 use nexus::registry::{BackendType, Registry};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[test]
@@ -211,6 +212,11 @@ fn test_zone_tier_flow_to_agent_profile() {
         api_key_env: None,
         zone: Some(PrivacyZone::Restricted),
         tier: Some(4),
+        api_version: None,
+        deployments: HashMap::new(),
+        region: None,
+        aws_credentials_env: None,
+        provider: None,
     };
 
     // Create agent using the factory (simulating serve.rs behavior)
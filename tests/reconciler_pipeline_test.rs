@@ -50,12 +50,14 @@ fn create_backend(
             supports_vision: true,
             supports_tools: true,
             supports_json_mode: true,
+            supports_embeddings: false,
             max_output_tokens: Some(4096),
         }],
         priority,
         pending_requests: AtomicU32::new(0),
         total_requests: AtomicU64::new(0),
         avg_latency_ms: AtomicU32::new(50),
+        reported_queue_depth: AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
@@ -73,7 +75,15 @@ fn create_intent(model: &str, candidates: Vec<&str>) -> RoutingIntent {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         },
         candidates.into_iter().map(|s| s.to_string()).collect(),
     )
@@ -112,6 +122,9 @@ fn privacy_excludes_cloud_agents_with_restricted_constraint() {
         max_cost_per_request: None,
         min_tier: None,
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     }];
     let matcher = PolicyMatcher::compile(policies).unwrap();
 
@@ -0,0 +1,174 @@
+//! Integration tests for request transformation policies
+//!
+//! Verifies that the `[transforms]` chain rewrites the request forwarded to
+//! the backend, honours per-tenant overrides keyed on the authenticated
+//! tenant, and reports the applied rules in `x-nexus-transforms`.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderValue, Request};
+use nexus::api::{create_router, AppState};
+use nexus::config::{NexusConfig, TenantTransforms, TransformRule, ValueRange};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const RESEARCH_KEY: &str = "Bearer research-key";
+
+/// Authenticated tenant id for an Authorization header value.
+fn tenant_id(authorization: &str) -> String {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "authorization",
+        HeaderValue::from_str(authorization).unwrap(),
+    );
+    nexus::cache::authenticated_tenant(&headers).unwrap()
+}
+
+async fn setup() -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "ok"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "local".to_string(),
+        "local".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("local", BackendStatus::Healthy, None);
+    let _ = registry.update_models("local", vec![common::make_model("llama3")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.transforms.rules = vec![
+        TransformRule {
+            name: "company-prompt".to_string(),
+            model_pattern: Some("llama*".to_string()),
+            system_prompt: Some("You are Acme's assistant.".to_string()),
+            overridable: true,
+            ..Default::default()
+        },
+        TransformRule {
+            name: "limits".to_string(),
+            max_tokens: Some(ValueRange {
+                min: None,
+                max: Some(512.0),
+            }),
+            strip_params: vec!["logit_bias".to_string()],
+            default_stop: vec!["</s>".to_string()],
+            ..Default::default()
+        },
+    ];
+    config.transforms.tenants = vec![TenantTransforms {
+        tenant: tenant_id(RESEARCH_KEY),
+        skip: vec!["company-prompt".to_string()],
+        rules: vec![],
+    }];
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(headers: &[(&str, &str)]) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "max_tokens": 4096,
+        "logit_bias": {"42": 10},
+        "messages": [{"role": "user", "content": "hello"}]
+    });
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn forwarded_body(mock_server: &MockServer) -> Value {
+    let requests = mock_server.received_requests().await.unwrap();
+    serde_json::from_slice(&requests.last().unwrap().body).unwrap()
+}
+
+#[tokio::test]
+async fn transforms_rewrite_forwarded_request() {
+    let (mock_server, mut app) = setup().await;
+
+    let response = app.call(chat_request(&[])).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-transforms").unwrap(),
+        "company-prompt,limits"
+    );
+
+    let body = forwarded_body(&mock_server).await;
+    assert_eq!(body["messages"][0]["role"], "system");
+    assert_eq!(body["messages"][0]["content"], "You are Acme's assistant.");
+    assert_eq!(body["max_tokens"], 512);
+    assert_eq!(body["stop"], serde_json::json!(["</s>"]));
+    assert!(body.get("logit_bias").is_none());
+}
+
+#[tokio::test]
+async fn tenant_override_skips_rules() {
+    let (mock_server, mut app) = setup().await;
+
+    let response = app
+        .call(chat_request(&[("authorization", RESEARCH_KEY)]))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-transforms").unwrap(),
+        "limits"
+    );
+
+    let body = forwarded_body(&mock_server).await;
+    assert_eq!(body["messages"][0]["role"], "user");
+    assert_eq!(body["max_tokens"], 512);
+}
+
+#[tokio::test]
+async fn tenant_header_cannot_select_overrides() {
+    let (mock_server, mut app) = setup().await;
+    let research = tenant_id(RESEARCH_KEY);
+
+    let response = app
+        .call(chat_request(&[
+            ("authorization", "Bearer other-key"),
+            ("x-nexus-tenant", research.as_str()),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers().get("x-nexus-transforms").unwrap(),
+        "company-prompt,limits"
+    );
+
+    let body = forwarded_body(&mock_server).await;
+    assert_eq!(body["messages"][0]["role"], "system");
+}
@@ -10,6 +10,7 @@ fn test_vram_free_bytes_calculation() {
         vram_total_bytes: Some(24_000_000_000), // 24GB
         vram_used_bytes: Some(8_000_000_000),   // 8GB
         pending_requests: None,
+        kv_cache_usage: None,
         avg_latency_ms: None,
         loaded_models: vec![],
     };
@@ -23,6 +24,7 @@ fn test_vram_free_bytes_with_no_total() {
         vram_total_bytes: None,
         vram_used_bytes: Some(8_000_000_000),
         pending_requests: None,
+        kv_cache_usage: None,
         avg_latency_ms: None,
         loaded_models: vec![],
     };
@@ -36,6 +38,7 @@ fn test_vram_free_bytes_with_no_used() {
         vram_total_bytes: Some(24_000_000_000),
         vram_used_bytes: None,
         pending_requests: None,
+        kv_cache_usage: None,
         avg_latency_ms: None,
         loaded_models: vec![],
     };
@@ -56,6 +59,7 @@ fn test_vram_free_bytes_saturating_sub() {
         vram_total_bytes: Some(8_000_000_000),
         vram_used_bytes: Some(24_000_000_000),
         pending_requests: None,
+        kv_cache_usage: None,
         avg_latency_ms: None,
         loaded_models: vec![],
     };
@@ -69,6 +73,7 @@ fn test_vram_free_bytes_zero_used() {
         vram_total_bytes: Some(24_000_000_000),
         vram_used_bytes: Some(0),
         pending_requests: None,
+        kv_cache_usage: None,
         avg_latency_ms: None,
         loaded_models: vec![],
     };
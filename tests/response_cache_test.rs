@@ -0,0 +1,179 @@
+//! Integration tests for the exact-match response cache
+//!
+//! Verifies that identical deterministic requests are answered from the cache
+//! with `x-nexus-cache: hit`, replayed as SSE for streaming clients, and kept
+//! apart per tenant and per traffic policy.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use futures::StreamExt;
+use nexus::api::{create_router, AppState};
+use nexus::config::{NexusConfig, PrivacyConstraint, TrafficPolicy};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn setup(policies: Vec<TrafficPolicy>) -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Paris"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "local".to_string(),
+        "local".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("local", BackendStatus::Healthy, None);
+    let _ = registry.update_models("local", vec![common::make_model("llama3")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.cache.enabled = true;
+    config.routing.policies = policies;
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(temperature: f32, stream: bool, auth: Option<&str>) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "temperature": temperature,
+        "stream": stream,
+        "messages": [{"role": "user", "content": "Capital of France?"}]
+    });
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(auth) = auth {
+        builder = builder.header("authorization", auth);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+fn cache_header(response: &axum::response::Response) -> Option<String> {
+    response
+        .headers()
+        .get("x-nexus-cache")
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+async fn forwarded(mock_server: &MockServer) -> usize {
+    mock_server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn identical_deterministic_request_is_served_from_cache() {
+    let (mock_server, mut app) = setup(vec![]).await;
+
+    let first = app.call(chat_request(0.0, false, None)).await.unwrap();
+    assert_eq!(first.status(), 200);
+    assert_eq!(cache_header(&first).as_deref(), Some("miss"));
+
+    let second = app.call(chat_request(0.0, false, None)).await.unwrap();
+    assert_eq!(second.status(), 200);
+    assert_eq!(cache_header(&second).as_deref(), Some("hit"));
+    let body = axum::body::to_bytes(second.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["choices"][0]["message"]["content"], "Paris");
+
+    assert_eq!(forwarded(&mock_server).await, 1);
+}
+
+#[tokio::test]
+async fn streaming_client_replays_cached_response() {
+    let (mock_server, mut app) = setup(vec![]).await;
+    app.call(chat_request(0.0, false, None)).await.unwrap();
+
+    let response = app.call(chat_request(0.0, true, None)).await.unwrap();
+
+    assert_eq!(cache_header(&response).as_deref(), Some("hit"));
+    let bytes: Vec<u8> = response
+        .into_body()
+        .into_data_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .filter_map(|chunk| chunk.ok())
+        .flat_map(|chunk| chunk.to_vec())
+        .collect();
+    let body = String::from_utf8(bytes).unwrap();
+    assert!(body.contains("chat.completion.chunk"));
+    assert!(body.contains("Paris"));
+    assert!(body.contains("data: [DONE]"));
+    assert_eq!(forwarded(&mock_server).await, 1);
+}
+
+#[tokio::test]
+async fn non_deterministic_requests_bypass_cache() {
+    let (mock_server, mut app) = setup(vec![]).await;
+
+    for _ in 0..2 {
+        let response = app.call(chat_request(0.7, false, None)).await.unwrap();
+        assert_eq!(cache_header(&response), None);
+    }
+    assert_eq!(forwarded(&mock_server).await, 2);
+}
+
+#[tokio::test]
+async fn tenants_do_not_share_entries() {
+    let (mock_server, mut app) = setup(vec![]).await;
+
+    app.call(chat_request(0.0, false, Some("Bearer team-a")))
+        .await
+        .unwrap();
+    let other = app
+        .call(chat_request(0.0, false, Some("Bearer team-b")))
+        .await
+        .unwrap();
+
+    assert_eq!(cache_header(&other).as_deref(), Some("miss"));
+    assert_eq!(forwarded(&mock_server).await, 2);
+}
+
+#[tokio::test]
+async fn policy_can_disable_caching() {
+    let (mock_server, mut app) = setup(vec![TrafficPolicy {
+        model_pattern: "llama*".to_string(),
+        privacy: PrivacyConstraint::Unrestricted,
+        max_cost_per_request: None,
+        min_tier: None,
+        fallback_allowed: true,
+        context_overflow: None,
+        cache: Some(false),
+        injection_action: None,
+    }])
+    .await;
+
+    for _ in 0..2 {
+        let response = app.call(chat_request(0.0, false, None)).await.unwrap();
+        assert_eq!(cache_header(&response), None);
+    }
+    assert_eq!(forwarded(&mock_server).await, 2);
+}
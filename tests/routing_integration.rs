@@ -53,6 +53,8 @@ fn test_routing_with_multiple_backends() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -108,6 +110,8 @@ fn test_routing_with_aliases() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -163,6 +167,8 @@ fn test_routing_with_fallbacks() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -211,6 +217,8 @@ fn test_routing_performance() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -276,6 +284,8 @@ fn test_routing_with_chained_aliases() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -362,6 +372,8 @@ fn test_routing_with_max_depth_chain() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -421,6 +433,8 @@ fn test_routing_result_with_alias_and_fallback() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -464,6 +478,8 @@ fn test_routing_result_no_fallback_info_when_no_fallback_configured() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -523,6 +539,8 @@ fn test_round_robin_routing_with_fallback() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -582,6 +600,8 @@ fn test_priority_routing_with_fallback() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -633,6 +653,8 @@ fn test_random_routing_with_fallback() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -677,6 +699,8 @@ fn test_round_robin_route_reason_multiple_backends() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -720,6 +744,8 @@ fn test_priority_route_reason_multiple_backends() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -763,6 +789,8 @@ fn test_random_route_reason_multiple_backends() {
             },
             name: None,
             function_call: None,
+            tool_calls: None,
+            tool_call_id: None,
         }],
         stream: false,
         temperature: None,
@@ -0,0 +1,225 @@
+//! Integration tests for the semantic response cache
+//!
+//! Verifies that near-identical prompts are answered from the cache after the
+//! final user message is embedded through the embeddings agent path, that
+//! dissimilar prompts and other tenants miss, and that entries can be
+//! invalidated through `DELETE /v1/cache/semantic`.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::{NexusConfig, SemanticCacheConfig};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_embedding(mock_server: &MockServer, input: &str, vector: [f32; 3]) {
+    Mock::given(method("POST"))
+        .and(path("/api/embed"))
+        .and(body_partial_json(serde_json::json!({ "input": input })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "embeddings": [vector] })),
+        )
+        .mount(mock_server)
+        .await;
+}
+
+async fn setup() -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "model": "llama3",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {"role": "assistant", "content": "Use the reset link"},
+            "done": true,
+            "done_reason": "stop"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    mount_embedding(&mock_server, "How do I reset my password?", [1.0, 0.0, 0.0]).await;
+    mount_embedding(
+        &mock_server,
+        "how can I reset my password",
+        [0.99, 0.05, 0.0],
+    )
+    .await;
+    mount_embedding(
+        &mock_server,
+        "What are your opening hours?",
+        [0.0, 1.0, 0.0],
+    )
+    .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "ollama-1".to_string(),
+        "Ollama 1".to_string(),
+        mock_server.uri(),
+        BackendType::Ollama,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "ollama-1".to_string(),
+        "Ollama 1".to_string(),
+        mock_server.uri(),
+        BackendType::Ollama,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("ollama-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "ollama-1",
+        vec![
+            common::make_embedding_model("embed"),
+            common::make_model("llama3"),
+        ],
+    );
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.cache.enabled = true;
+    config.cache.semantic = SemanticCacheConfig {
+        enabled: true,
+        embedding_model: "embed".to_string(),
+        threshold: 0.95,
+        max_entries: 100,
+    };
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(content: &str, auth: Option<&str>) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "temperature": 0.0,
+        "messages": [{"role": "user", "content": content}]
+    });
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json");
+    if let Some(auth) = auth {
+        builder = builder.header("authorization", auth);
+    }
+    builder.body(Body::from(body.to_string())).unwrap()
+}
+
+fn header(response: &axum::response::Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+async fn chat_calls(mock_server: &MockServer) -> usize {
+    mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/api/chat")
+        .count()
+}
+
+#[tokio::test]
+async fn similar_prompt_is_served_from_semantic_cache() {
+    let (mock_server, mut app) = setup().await;
+
+    let first = app
+        .call(chat_request("How do I reset my password?", None))
+        .await
+        .unwrap();
+    assert_eq!(header(&first, "x-nexus-cache").as_deref(), Some("miss"));
+
+    let similar = app
+        .call(chat_request("how can I reset my password", None))
+        .await
+        .unwrap();
+    assert_eq!(similar.status(), 200);
+    assert_eq!(header(&similar, "x-nexus-cache").as_deref(), Some("hit"));
+    let similarity: f32 = header(&similar, "x-nexus-cache-similarity")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(similarity >= 0.95);
+
+    assert_eq!(chat_calls(&mock_server).await, 1);
+}
+
+#[tokio::test]
+async fn dissimilar_prompt_and_other_tenant_miss() {
+    let (mock_server, mut app) = setup().await;
+
+    app.call(chat_request("How do I reset my password?", None))
+        .await
+        .unwrap();
+    let other_question = app
+        .call(chat_request("What are your opening hours?", None))
+        .await
+        .unwrap();
+    assert_eq!(
+        header(&other_question, "x-nexus-cache").as_deref(),
+        Some("miss")
+    );
+
+    let other_tenant = app
+        .call(chat_request(
+            "how can I reset my password",
+            Some("Bearer team-b"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        header(&other_tenant, "x-nexus-cache").as_deref(),
+        Some("miss")
+    );
+
+    assert_eq!(chat_calls(&mock_server).await, 3);
+}
+
+#[tokio::test]
+async fn invalidation_api_drops_tenant_entries() {
+    let (mock_server, mut app) = setup().await;
+    app.call(chat_request("How do I reset my password?", None))
+        .await
+        .unwrap();
+
+    let response = app
+        .call(
+            Request::builder()
+                .method("DELETE")
+                .uri("/v1/cache/semantic?model=llama3")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["invalidated"], 1);
+
+    let after = app
+        .call(chat_request("how can I reset my password", None))
+        .await
+        .unwrap();
+    assert_eq!(header(&after, "x-nexus-cache").as_deref(), Some("miss"));
+    assert_eq!(chat_calls(&mock_server).await, 2);
+}
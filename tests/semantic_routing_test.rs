@@ -0,0 +1,202 @@
+//! Integration tests for semantic `nexus/auto` model selection
+//!
+//! Verifies that `nexus/auto` requests are classified by embedding the prompt
+//! through the embeddings agent path, routed to the nearest example's model
+//! (or the fallback model), and that the decision is reported in the
+//! X-Nexus-Route-Reason header.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::{NexusConfig, SemanticRoute, SemanticRoutingConfig};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn embedding(vector: [f32; 3]) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({ "embeddings": [vector] }))
+}
+
+async fn mount_embedding(mock_server: &MockServer, input: &str, vector: [f32; 3]) {
+    Mock::given(method("POST"))
+        .and(path("/api/embed"))
+        .and(body_partial_json(serde_json::json!({ "input": input })))
+        .respond_with(embedding(vector))
+        .with_priority(1)
+        .mount(mock_server)
+        .await;
+}
+
+async fn setup(embeddings_available: bool) -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "model": "coder",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {"role": "assistant", "content": "Done"},
+            "done": true,
+            "done_reason": "stop"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    if embeddings_available {
+        mount_embedding(&mock_server, "write code", [1.0, 0.0, 0.0]).await;
+        mount_embedding(&mock_server, "solve equation", [0.0, 1.0, 0.0]).await;
+        mount_embedding(&mock_server, "Please write code for me", [0.9, 0.1, 0.0]).await;
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(embedding([0.0, 0.0, 1.0]))
+            .with_priority(10)
+            .mount(&mock_server)
+            .await;
+    } else {
+        Mock::given(method("POST"))
+            .and(path("/api/embed"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+    }
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "ollama-1".to_string(),
+        "Ollama 1".to_string(),
+        mock_server.uri(),
+        BackendType::Ollama,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "ollama-1".to_string(),
+        "Ollama 1".to_string(),
+        mock_server.uri(),
+        BackendType::Ollama,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("ollama-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "ollama-1",
+        vec![
+            common::make_embedding_model("embed"),
+            common::make_model("coder"),
+            common::make_model("mathstral"),
+            common::make_model("general"),
+        ],
+    );
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.routing.semantic = SemanticRoutingConfig {
+        enabled: true,
+        embedding_model: "embed".to_string(),
+        threshold: 0.8,
+        fallback_model: "general".to_string(),
+        routes: vec![
+            SemanticRoute {
+                name: "code".to_string(),
+                target_model: "coder".to_string(),
+                min_tier: None,
+                examples: vec!["write code".to_string()],
+            },
+            SemanticRoute {
+                name: "math".to_string(),
+                target_model: "mathstral".to_string(),
+                min_tier: None,
+                examples: vec!["solve equation".to_string()],
+            },
+        ],
+    };
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn auto_request(content: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "nexus/auto",
+        "messages": [{"role": "user", "content": content}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn forwarded_model(mock_server: &MockServer) -> Value {
+    let requests = mock_server.received_requests().await.unwrap();
+    let chat = requests
+        .iter()
+        .find(|r| r.url.path() == "/api/chat")
+        .expect("chat request forwarded");
+    let body: Value = serde_json::from_slice(&chat.body).unwrap();
+    body["model"].clone()
+}
+
+fn route_reason(response: &axum::response::Response) -> String {
+    response
+        .headers()
+        .get("x-nexus-route-reason")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn nearest_example_selects_route() {
+    let (mock_server, mut app) = setup(true).await;
+
+    let response = app
+        .call(auto_request("Please write code for me"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(route_reason(&response), "semantic-match:code:0.99");
+    assert_eq!(forwarded_model(&mock_server).await, "coder");
+}
+
+#[tokio::test]
+async fn low_confidence_uses_fallback_model() {
+    let (mock_server, mut app) = setup(true).await;
+
+    let response = app.call(auto_request("Tell me a story")).await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(route_reason(&response), "semantic-fallback:0.00");
+    assert_eq!(forwarded_model(&mock_server).await, "general");
+}
+
+#[tokio::test]
+async fn embedding_failure_uses_fallback_model() {
+    let (mock_server, mut app) = setup(false).await;
+
+    let response = app
+        .call(auto_request("Please write code for me"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        route_reason(&response),
+        "semantic-fallback:embedding-unavailable"
+    );
+    assert_eq!(forwarded_model(&mock_server).await, "general");
+}
@@ -0,0 +1,192 @@
+//! Integration tests for single-flight deduplication of chat requests
+//!
+//! Verifies that identical concurrent requests reach the backend once, with
+//! followers receiving the leader's response and routing headers (or a
+//! fan-out of its SSE stream) marked by `x-nexus-single-flight: follower`.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{HeaderMap, Request};
+use futures::StreamExt;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SSE_BODY: &str = concat!(
+    "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":0,",
+    "\"model\":\"llama3\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Paris\"},",
+    "\"finish_reason\":null}]}\n\n",
+    "data: [DONE]\n\n"
+);
+
+async fn setup(
+    stream: bool,
+    configure: impl FnOnce(&mut NexusConfig),
+) -> (MockServer, axum::Router) {
+    let mock_server = MockServer::start().await;
+    let template = if stream {
+        ResponseTemplate::new(200).set_body_raw(SSE_BODY, "text/event-stream")
+    } else {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Paris"},
+                "finish_reason": "stop"
+            }]
+        }))
+    };
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(template.set_delay(Duration::from_millis(300)))
+        .mount(&mock_server)
+        .await;
+
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "local".to_string(),
+        "local".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("local", BackendStatus::Healthy, None);
+    let _ = registry.update_models("local", vec![common::make_model("llama3")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    configure(&mut config);
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    (mock_server, create_router(state))
+}
+
+fn chat_request(stream: bool) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "temperature": 0.0,
+        "stream": stream,
+        "messages": [{"role": "user", "content": "Capital of France?"}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// Fire `count` identical requests concurrently and collect (headers, body).
+async fn fire(app: &axum::Router, stream: bool, count: usize) -> Vec<(HeaderMap, String)> {
+    let requests = (0..count).map(|_| {
+        let mut app = app.clone();
+        async move {
+            let response = app.call(chat_request(stream)).await.unwrap();
+            assert_eq!(response.status(), 200);
+            let headers = response.headers().clone();
+            let bytes: Vec<u8> = response
+                .into_body()
+                .into_data_stream()
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .filter_map(|chunk| chunk.ok())
+                .flat_map(|chunk| chunk.to_vec())
+                .collect();
+            (headers, String::from_utf8(bytes).unwrap())
+        }
+    });
+    futures::future::join_all(requests).await
+}
+
+fn is_follower(headers: &HeaderMap) -> bool {
+    headers.contains_key("x-nexus-single-flight")
+}
+
+async fn forwarded(mock_server: &MockServer) -> usize {
+    mock_server.received_requests().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn identical_concurrent_requests_share_one_backend_call() {
+    let (mock_server, app) = setup(false, |config| {
+        config.cache.single_flight.enabled = true;
+    })
+    .await;
+
+    let results = fire(&app, false, 3).await;
+
+    assert_eq!(forwarded(&mock_server).await, 1);
+    assert_eq!(
+        results
+            .iter()
+            .filter(|(headers, _)| is_follower(headers))
+            .count(),
+        2
+    );
+    for (headers, body) in results {
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["choices"][0]["message"]["content"], "Paris");
+        assert_eq!(headers["x-nexus-backend"], "local");
+        assert_eq!(headers["x-nexus-route-reason"], "capability-match");
+        assert_eq!(headers["x-nexus-privacy-zone"], "restricted");
+    }
+}
+
+#[tokio::test]
+async fn disabled_by_default() {
+    let (mock_server, app) = setup(false, |_| {}).await;
+
+    let results = fire(&app, false, 3).await;
+
+    assert_eq!(forwarded(&mock_server).await, 3);
+    assert!(results.iter().all(|(headers, _)| !is_follower(headers)));
+}
+
+#[tokio::test]
+async fn identical_streams_fan_out_from_one_backend_stream() {
+    let (mock_server, app) = setup(true, |config| {
+        config.cache.single_flight.enabled = true;
+        config.cache.single_flight.streaming = true;
+    })
+    .await;
+
+    let results = fire(&app, true, 3).await;
+
+    assert_eq!(forwarded(&mock_server).await, 1);
+    assert_eq!(
+        results
+            .iter()
+            .filter(|(headers, _)| is_follower(headers))
+            .count(),
+        2
+    );
+    for (_, body) in results {
+        assert!(body.contains("Paris"));
+        assert!(body.contains("data: [DONE]"));
+    }
+}
+
+#[tokio::test]
+async fn streams_are_not_shared_unless_enabled() {
+    let (mock_server, app) = setup(true, |config| {
+        config.cache.single_flight.enabled = true;
+    })
+    .await;
+
+    fire(&app, true, 2).await;
+
+    assert_eq!(forwarded(&mock_server).await, 2);
+}
@@ -25,6 +25,7 @@ mod us1_basic_logging {
                 total_tokens: 150,
             }),
             extra: std::collections::HashMap::new(),
+            timings: None,
         };
 
         // Verify token extraction produces all required fields
@@ -244,12 +245,14 @@ mod us3_routing_visibility {
                 supports_vision: false,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 5,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -271,12 +274,14 @@ mod us3_routing_visibility {
                 supports_vision: false,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 3,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -295,7 +300,15 @@ mod us3_routing_visibility {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
         let result = router.select_backend(&requirements, None).unwrap();
 
@@ -334,12 +347,14 @@ mod us3_routing_visibility {
                 supports_vision: false,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 5,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -363,7 +378,15 @@ mod us3_routing_visibility {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
         let result = router.select_backend(&requirements, None).unwrap();
 
@@ -403,12 +426,14 @@ mod us3_routing_visibility {
                 supports_vision: false,
                 supports_tools: true,
                 supports_json_mode: false,
+                supports_embeddings: false,
                 max_output_tokens: None,
             }],
             priority: 5,
             pending_requests: AtomicU32::new(0),
             total_requests: AtomicU64::new(0),
             avg_latency_ms: AtomicU32::new(50),
+            reported_queue_depth: AtomicU32::new(0),
             discovery_source: DiscoverySource::Static,
             metadata: HashMap::new(),
             current_operation: None,
@@ -427,7 +452,15 @@ mod us3_routing_visibility {
             needs_vision: false,
             needs_tools: false,
             needs_json_mode: false,
+            needs_json_schema: false,
+            needs_embeddings: false,
+            prompt_tokens_exact: false,
             prefers_streaming: false,
+            max_tokens: None,
+            privacy_constraint: None,
+            min_capability_tier: None,
+            prompt_text: None,
+            injection_risk: None,
         };
         let result = router.select_backend(&requirements, None).unwrap();
 
@@ -453,6 +486,8 @@ mod us4_privacy_safe_logging {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -501,6 +536,8 @@ mod us4_privacy_safe_logging {
                 },
                 name: None,
                 function_call: None,
+                tool_calls: None,
+                tool_call_id: None,
             }],
             stream: false,
             temperature: None,
@@ -602,6 +639,7 @@ mod us6_aggregator_compatibility {
                 total_tokens: 150,
             }),
             extra: std::collections::HashMap::new(),
+            timings: None,
         };
 
         let (prompt, completion, total) = extract_tokens(&response);
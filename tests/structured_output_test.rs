@@ -0,0 +1,196 @@
+//! Integration tests for JSON schema structured output
+//!
+//! Verifies that schema requests prefer natively enforcing backends, that
+//! other backends get the schema as an instruction and have their responses
+//! validated, and that invalid output is repaired or rejected with 422.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn completion(content: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "llama3",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 20, "completion_tokens": 5, "total_tokens": 25}
+    }))
+}
+
+/// Backend whose first `invalid` responses fail the schema.
+async fn mock_backend(invalid: u64) -> MockServer {
+    let mock_server = MockServer::start().await;
+    if invalid > 0 {
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(completion("{\"age\": 36}"))
+            .up_to_n_times(invalid)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(completion("{\"name\": \"Ada\"}"))
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn add_backend(registry: &Registry, id: &str, url: String, backend_type: BackendType) {
+    let backend = Backend::new(
+        id.to_string(),
+        id.to_string(),
+        url,
+        backend_type,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status(id, BackendStatus::Healthy, None);
+    let _ = registry.update_models(id, vec![common::make_model("llama3")]);
+}
+
+fn app(registry: Arc<Registry>) -> axum::Router {
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    create_router(state)
+}
+
+fn schema_request() -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "messages": [{"role": "user", "content": "Name a mathematician."}],
+        "response_format": {
+            "type": "json_schema",
+            "json_schema": {
+                "name": "person",
+                "schema": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}},
+                    "required": ["name"]
+                }
+            }
+        }
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn forwarded_bodies(mock_server: &MockServer) -> Vec<Value> {
+    mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| serde_json::from_slice(&request.body).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn invalid_response_is_repaired() {
+    let mock_server = mock_backend(1).await;
+    let registry = Arc::new(Registry::new());
+    add_backend(&registry, "local", mock_server.uri(), BackendType::Generic);
+    let mut app = app(registry);
+
+    let response = app.call(schema_request()).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-schema-validation").unwrap(),
+        "repaired"
+    );
+    assert_eq!(
+        response.headers().get("x-nexus-schema-attempts").unwrap(),
+        "2"
+    );
+    // Usage covers the original attempt and the repair
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(
+        body["usage"],
+        serde_json::json!({"prompt_tokens": 40, "completion_tokens": 10, "total_tokens": 50})
+    );
+
+    let bodies = forwarded_bodies(&mock_server).await;
+    assert_eq!(bodies.len(), 2);
+    // The schema travels as an instruction; the backend never sees json_schema
+    assert_eq!(bodies[0]["messages"][0]["role"], "system");
+    assert!(bodies[0]["messages"][0]["content"]
+        .as_str()
+        .unwrap()
+        .contains("\"required\":[\"name\"]"));
+    assert!(bodies[0].get("response_format").is_none());
+    let feedback = bodies[1]["messages"][3]["content"].as_str().unwrap();
+    assert!(feedback.contains("missing required property 'name'"));
+}
+
+#[tokio::test]
+async fn exhausted_repairs_fail_with_422() {
+    let mock_server = mock_backend(10).await;
+    let registry = Arc::new(Registry::new());
+    add_backend(&registry, "local", mock_server.uri(), BackendType::Generic);
+    let mut app = app(registry);
+
+    let response = app.call(schema_request()).await.unwrap();
+    assert_eq!(response.status(), 422);
+    assert_eq!(
+        response.headers().get("x-nexus-schema-validation").unwrap(),
+        "failed"
+    );
+    assert_eq!(
+        response.headers().get("x-nexus-schema-attempts").unwrap(),
+        "3"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "schema_validation_failed");
+}
+
+#[tokio::test]
+async fn native_backend_is_preferred_and_receives_schema() {
+    let generic = mock_backend(0).await;
+    let native = mock_backend(0).await;
+    let registry = Arc::new(Registry::new());
+    add_backend(&registry, "generic", generic.uri(), BackendType::Generic);
+    add_backend(&registry, "vllm", native.uri(), BackendType::VLLM);
+    let mut app = app(registry);
+
+    let response = app.call(schema_request()).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-schema-validation").unwrap(),
+        "passed"
+    );
+
+    assert!(forwarded_bodies(&generic).await.is_empty());
+    let bodies = forwarded_bodies(&native).await;
+    assert_eq!(bodies[0]["response_format"]["type"], "json_schema");
+    assert_eq!(bodies[0]["messages"].as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,176 @@
+//! Integration tests for the Hugging Face TEI backend type
+//!
+//! A wiremock server stands in for TEI's native API, checking that
+//! `/v1/embeddings` is served from `/embed` and `/v1/rerank` from `/rerank`.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn app(mock_server: &MockServer, backend_type: BackendType, model_id: &str) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "tei-1".to_string(),
+        "tei".to_string(),
+        mock_server.uri(),
+        backend_type,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "tei-1".to_string(),
+        "tei".to_string(),
+        mock_server.uri(),
+        backend_type,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("tei-1", BackendStatus::Healthy, None);
+    let model = nexus::registry::Model {
+        supports_embeddings: true,
+        ..common::make_model(model_id)
+    };
+    let _ = registry.update_models("tei-1", vec![model]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn post(uri: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn body_to_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn embeddings_are_served_from_embed() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/embed"))
+        .and(body_json(serde_json::json!({"inputs": ["hello", "world"]})))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([[0.1, 0.2], [0.3, 0.4]])),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server, BackendType::TEI, "bge-base");
+    let response = app
+        .call(post(
+            "/v1/embeddings",
+            serde_json::json!({"model": "bge-base", "input": ["hello", "world"]}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-nexus-backend-type"], "local");
+    let json = body_to_json(response).await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+    assert_eq!(json["data"][1]["index"], 1);
+}
+
+#[tokio::test]
+async fn rerank_is_served_from_rerank() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/rerank"))
+        .and(body_json(serde_json::json!({
+            "query": "What is Deep Learning?",
+            "texts": ["Cheese is tasty.", "Deep Learning is a subset of ML.", "Tea."]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"index": 1, "score": 0.98},
+            {"index": 2, "score": 0.05},
+            {"index": 0, "score": 0.01}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut app = app(&mock_server, BackendType::TEI, "bge-reranker");
+    let response = app
+        .call(post(
+            "/v1/rerank",
+            serde_json::json!({
+                "model": "bge-reranker",
+                "query": "What is Deep Learning?",
+                "documents": ["Cheese is tasty.", "Deep Learning is a subset of ML.", "Tea."],
+                "top_n": 2,
+                "return_documents": true
+            }),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let json = body_to_json(response).await;
+    let results = json["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["index"], 1);
+    assert_eq!(
+        results[0]["document"]["text"],
+        "Deep Learning is a subset of ML."
+    );
+    assert_eq!(results[1]["index"], 2);
+}
+
+#[tokio::test]
+async fn rerank_rejects_backends_without_support() {
+    let mock_server = MockServer::start().await;
+
+    let mut app = app(&mock_server, BackendType::TGI, "llama");
+    let response = app
+        .call(post(
+            "/v1/rerank",
+            serde_json::json!({"model": "llama", "query": "q", "documents": ["a"]}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn rerank_rejects_empty_documents() {
+    let mock_server = MockServer::start().await;
+
+    let mut app = app(&mock_server, BackendType::TEI, "bge-reranker");
+    let response = app
+        .call(post(
+            "/v1/rerank",
+            serde_json::json!({"model": "bge-reranker", "query": "q", "documents": []}),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
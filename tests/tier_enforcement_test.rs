@@ -44,6 +44,7 @@ fn create_test_backend_with_tier(
             supports_vision: false,
             supports_tools: false,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
         priority: 50,
@@ -52,6 +53,7 @@ fn create_test_backend_with_tier(
         pending_requests: std::sync::atomic::AtomicU32::new(0),
         total_requests: std::sync::atomic::AtomicU64::new(0),
         avg_latency_ms: std::sync::atomic::AtomicU32::new(0),
+        reported_queue_depth: std::sync::atomic::AtomicU32::new(0),
         discovery_source: DiscoverySource::Static,
         metadata: HashMap::new(),
         current_operation: None,
@@ -98,6 +100,9 @@ fn test_no_headers_defaults_to_strict_mode() {
         max_cost_per_request: None,
         min_tier: Some(3),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -129,7 +134,15 @@ fn test_no_headers_defaults_to_strict_mode() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -178,6 +191,9 @@ fn test_strict_header_enforces_exact_tier_matching() {
         max_cost_per_request: None,
         min_tier: Some(3),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -207,7 +223,15 @@ fn test_strict_header_enforces_exact_tier_matching() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -261,6 +285,9 @@ fn test_flexible_header_allows_higher_tier_substitution() {
         max_cost_per_request: None,
         min_tier: Some(3),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -290,7 +317,15 @@ fn test_flexible_header_allows_higher_tier_substitution() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -342,6 +377,9 @@ fn test_flexible_mode_never_downgrades() {
         max_cost_per_request: None,
         min_tier: Some(3),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -371,7 +409,15 @@ fn test_flexible_mode_never_downgrades() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -437,6 +483,9 @@ fn test_flexible_mode_upgrades_to_available_higher_tier() {
         max_cost_per_request: None,
         min_tier: Some(3),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -466,7 +515,15 @@ fn test_flexible_mode_upgrades_to_available_higher_tier() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -521,6 +578,9 @@ fn test_conflicting_headers_strict_wins() {
         max_cost_per_request: None,
         min_tier: Some(3),
         fallback_allowed: true,
+        context_overflow: None,
+        cache: None,
+        injection_action: None,
     };
 
     let policy_matcher = PolicyMatcher::compile(vec![policy]).unwrap();
@@ -550,7 +610,15 @@ fn test_conflicting_headers_strict_wins() {
         needs_vision: false,
         needs_tools: false,
         needs_json_mode: false,
+        needs_json_schema: false,
+        needs_embeddings: false,
+        prompt_tokens_exact: false,
         prefers_streaming: false,
+        max_tokens: None,
+        privacy_constraint: None,
+        min_capability_tier: None,
+        prompt_text: None,
+        injection_risk: None,
     };
 
     let mut intent = RoutingIntent::new(
@@ -0,0 +1,191 @@
+//! Integration tests for backend token counting
+//!
+//! Verifies that prompts for models served by llama.cpp are counted with the
+//! backend's `/tokenize` endpoint before routing, so context-window checks use
+//! the exact count, that backends without the endpoint fall back to the
+//! estimate, and that configured `tokenizer.json` files count offline.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::agent::factory::create_agent;
+use nexus::agent::PrivacyZone;
+use nexus::api::{create_router, AppState};
+use nexus::config::{NexusConfig, TokenizerFile};
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Model, Registry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mock_backend(tokens_per_text: Option<usize>) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hi"},
+                "finish_reason": "stop"
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+    if let Some(count) = tokens_per_text {
+        Mock::given(method("POST"))
+            .and(path("/tokenize"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "tokens": vec![1; count] })),
+            )
+            .mount(&mock_server)
+            .await;
+    }
+    mock_server
+}
+
+fn app(mock_server: &MockServer) -> axum::Router {
+    app_with_config(mock_server, NexusConfig::default())
+}
+
+fn app_with_config(mock_server: &MockServer, mut config: NexusConfig) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "llamacpp-1".to_string(),
+        "llama.cpp".to_string(),
+        mock_server.uri(),
+        BackendType::LlamaCpp,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    let agent = create_agent(
+        "llamacpp-1".to_string(),
+        "llama.cpp".to_string(),
+        mock_server.uri(),
+        BackendType::LlamaCpp,
+        Arc::new(reqwest::Client::new()),
+        HashMap::new(),
+        PrivacyZone::Restricted,
+        None,
+    )
+    .unwrap();
+    registry.add_backend_with_agent(backend, agent).unwrap();
+    let _ = registry.update_status("llamacpp-1", BackendStatus::Healthy, None);
+    let _ = registry.update_models(
+        "llamacpp-1",
+        vec![Model {
+            context_length: 64,
+            ..common::make_model("llama3")
+        }],
+    );
+
+    config.queue.enabled = false;
+    create_router(Arc::new(AppState::new(registry, Arc::new(config))))
+}
+
+fn chat_request() -> Request<Body> {
+    chat_request_with("Summarise this.")
+}
+
+fn chat_request_with(content: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "messages": [{"role": "user", "content": content}]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .header("x-nexus-context-overflow", "reject")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn exact_backend_count_drives_context_check() {
+    // "Summarise this." is ~4 tokens by estimate, but the backend says 100
+    let mock_server = mock_backend(Some(100)).await;
+    let mut app = app(&mock_server);
+
+    let response = app.call(chat_request()).await.unwrap();
+    assert_eq!(response.status(), 400);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("largest context window"));
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(requests.iter().any(|r| r.url.path() == "/tokenize"));
+    assert!(requests
+        .iter()
+        .all(|r| r.url.path() != "/v1/chat/completions"));
+}
+
+#[tokio::test]
+async fn small_exact_count_is_routed() {
+    let mock_server = mock_backend(Some(2)).await;
+    let mut app = app(&mock_server);
+
+    let response = app.call(chat_request()).await.unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn missing_tokenize_endpoint_falls_back_to_estimate() {
+    let mock_server = mock_backend(None).await;
+    let mut app = app(&mock_server);
+
+    let response = app.call(chat_request()).await.unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn configured_tokenizer_file_counts_offline() {
+    // Word-level tokenizer: one token per word
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tokenizer.json");
+    let tokenizer = serde_json::json!({
+        "version": "1.0",
+        "truncation": null,
+        "padding": null,
+        "added_tokens": [],
+        "normalizer": null,
+        "pre_tokenizer": {"type": "Whitespace"},
+        "post_processor": null,
+        "decoder": null,
+        "model": {
+            "type": "WordLevel",
+            "vocab": {"hello": 0, "[UNK]": 1},
+            "unk_token": "[UNK]"
+        }
+    });
+    std::fs::write(&path, tokenizer.to_string()).unwrap();
+
+    // 40 words in 240 chars: ~69 tokens by estimate, over the 64-token window
+    let content = "hello ".repeat(40);
+
+    let mock_server = mock_backend(None).await;
+    let mut app = app(&mock_server);
+    let response = app.call(chat_request_with(&content)).await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    let mut config = NexusConfig::default();
+    config.routing.tokenizers = vec![TokenizerFile {
+        model_pattern: "llama3*".to_string(),
+        path,
+    }];
+    let mut app = app_with_config(&mock_server, config);
+    let response = app.call(chat_request_with(&content)).await.unwrap();
+    assert_eq!(response.status(), 200);
+}
@@ -0,0 +1,207 @@
+//! Integration tests for tool-calling emulation
+//!
+//! Verifies that tool requests reach models without native function calling
+//! when emulation is enabled, with the tools rendered into a system prompt and
+//! the model's tool-call text converted into OpenAI `tool_calls`, for both
+//! complete and streamed responses.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::Request;
+use nexus::api::{create_router, AppState};
+use nexus::config::NexusConfig;
+use nexus::registry::{Backend, BackendStatus, BackendType, DiscoverySource, Registry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::Service;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TOOL_CALL: &str =
+    "<tool_call>{\"name\": \"get_weather\", \"arguments\": {\"city\": \"Oslo\"}}</tool_call>";
+
+fn completion(content: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "llama3",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop"
+        }]
+    }))
+}
+
+fn sse(deltas: &[&str]) -> ResponseTemplate {
+    let mut body = String::new();
+    for (i, delta) in deltas.iter().enumerate() {
+        let finish = (i + 1 == deltas.len()).then_some("stop");
+        let chunk = serde_json::json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "llama3",
+            "choices": [{"index": 0, "delta": {"content": delta}, "finish_reason": finish}]
+        });
+        body.push_str(&format!("data: {}\n\n", chunk));
+    }
+    body.push_str("data: [DONE]\n\n");
+    ResponseTemplate::new(200)
+        .set_body_string(body)
+        .insert_header("content-type", "text/event-stream")
+}
+
+async fn mock_backend(response: ResponseTemplate) -> MockServer {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(response)
+        .mount(&mock_server)
+        .await;
+    mock_server
+}
+
+fn app(mock_server: &MockServer, emulation: bool) -> axum::Router {
+    let registry = Arc::new(Registry::new());
+    let backend = Backend::new(
+        "local".to_string(),
+        "local".to_string(),
+        mock_server.uri(),
+        BackendType::Generic,
+        vec![],
+        DiscoverySource::Static,
+        HashMap::new(),
+    );
+    registry.add_backend(backend).unwrap();
+    let _ = registry.update_status("local", BackendStatus::Healthy, None);
+    let _ = registry.update_models("local", vec![common::make_model("llama3")]);
+
+    let mut config = NexusConfig::default();
+    config.queue.enabled = false;
+    config.tool_emulation.enabled = emulation;
+    let state = Arc::new(AppState::new(registry, Arc::new(config)));
+    create_router(state)
+}
+
+fn tool_request(stream: bool) -> Request<Body> {
+    let body = serde_json::json!({
+        "model": "llama3",
+        "messages": [{"role": "user", "content": "Weather in Oslo?"}],
+        "stream": stream,
+        "tools": [{
+            "type": "function",
+            "function": {
+                "name": "get_weather",
+                "parameters": {"type": "object", "properties": {"city": {"type": "string"}}}
+            }
+        }]
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat/completions")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn forwarded_body(mock_server: &MockServer) -> Value {
+    let requests = mock_server.received_requests().await.unwrap();
+    serde_json::from_slice(&requests[0].body).unwrap()
+}
+
+#[tokio::test]
+async fn tool_call_text_becomes_tool_calls() {
+    let mock_server = mock_backend(completion(TOOL_CALL)).await;
+    let mut app = app(&mock_server, true);
+
+    let response = app.call(tool_request(false)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-tool-emulation").unwrap(),
+        "true"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let choice = &json["choices"][0];
+    assert_eq!(choice["finish_reason"], "tool_calls");
+    assert!(choice["message"]["content"].is_null());
+    let call = &choice["message"]["tool_calls"][0];
+    assert_eq!(call["type"], "function");
+    assert_eq!(call["function"]["name"], "get_weather");
+    assert_eq!(call["function"]["arguments"], "{\"city\":\"Oslo\"}");
+
+    // The backend sees the tools as a system prompt, not as `tools`
+    let forwarded = forwarded_body(&mock_server).await;
+    assert!(forwarded.get("tools").is_none());
+    assert_eq!(forwarded["messages"][0]["role"], "system");
+    assert!(forwarded["messages"][0]["content"]
+        .as_str()
+        .unwrap()
+        .contains("get_weather"));
+}
+
+#[tokio::test]
+async fn streamed_tool_call_becomes_tool_call_delta() {
+    let mock_server = mock_backend(sse(&["<tool_", &TOOL_CALL[6..]])).await;
+    let mut app = app(&mock_server, true);
+
+    let response = app.call(tool_request(true)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("x-nexus-tool-emulation").unwrap(),
+        "true"
+    );
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8_lossy(&body);
+    assert!(!body.contains("<tool_call>"));
+
+    let chunks: Vec<Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .filter(|data| *data != "[DONE]")
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect();
+    let last = &chunks.last().unwrap()["choices"][0];
+    assert_eq!(last["finish_reason"], "tool_calls");
+    assert_eq!(
+        last["delta"]["tool_calls"][0]["function"]["name"],
+        "get_weather"
+    );
+    assert!(body.trim_end().ends_with("data: [DONE]"));
+}
+
+#[tokio::test]
+async fn plain_answers_pass_through() {
+    let mock_server = mock_backend(completion("It is sunny in Oslo.")).await;
+    let mut app = app(&mock_server, true);
+
+    let response = app.call(tool_request(false)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json["choices"][0]["message"]["content"],
+        "It is sunny in Oslo."
+    );
+    assert!(json["choices"][0]["message"].get("tool_calls").is_none());
+}
+
+#[tokio::test]
+async fn disabled_emulation_rejects_models_without_tools() {
+    let mock_server = mock_backend(completion(TOOL_CALL)).await;
+    let mut app = app(&mock_server, false);
+
+    let response = app.call(tool_request(false)).await.unwrap();
+    assert_ne!(response.status(), 200);
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
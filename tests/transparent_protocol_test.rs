@@ -48,6 +48,7 @@ async fn create_test_app_with_backend(
             supports_vision: false,
             supports_tools: true,
             supports_json_mode: false,
+            supports_embeddings: false,
             max_output_tokens: None,
         }],
     );